@@ -0,0 +1,401 @@
+//! Incremental batch calculation: extend a previous result with new data.
+//!
+//! Recomputing a long history every time a few bars arrive is wasteful. Batch
+//! calculators implementing [`Appendable`] can return the matching stream
+//! alongside their output; [`Appendable::append`] then feeds that stream only
+//! the new data and returns the values for it.
+//!
+//! The state is the indicator's own `*Stream` type, so history followed by any
+//! number of appends produces exactly the output of
+//! [`calculate_resumable`](Appendable::calculate_resumable) over the whole
+//! series, and matches [`Indicator::calculate`] to floating point precision.
+//! With the `serde` feature the state can be persisted like any stream (see
+//! [`Snapshot`](crate::snapshot::Snapshot)), and it can also be kept running
+//! with [`StreamingIndicator::next`] directly.
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::Sma;
+//! use ta_core::Appendable;
+//!
+//! let sma = Sma::new(3).unwrap();
+//! let (mut values, mut state) = sma.calculate_resumable(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+//!
+//! // Later, only the new bars are processed
+//! values.extend(sma.append(&mut state, &[5.0, 6.0]).unwrap());
+//! assert_eq!(values[5], 5.0);
+//! ```
+
+use crate::indicators::{
+    Adx, AdxOutput, AdxStream, Atr, AtrStream, BBands, BBandsOutput, BBandsStream, Cvd, CvdBar,
+    CvdOhlcv, CvdOhlcvStream, CvdStream, Ema, EmaStream, Hma, HmaStream, Ichimoku, IchimokuOutput,
+    IchimokuStream, LinReg, LinRegOutput, LinRegStream, Macd, MacdOutput, MacdStream, Mfi,
+    MfiStream, RollingVwap, RollingVwapStream, Rsi, RsiStream, SessionVwap, SessionVwapStream, Sma,
+    SmaStream, Stoch, StochOutput, StochRsi, StochRsiOutput, StochRsiStream, StochStream, Wma,
+    WmaStream,
+};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorResult, OHLCV};
+
+/// Batch calculator whose results can be extended without recomputation.
+pub trait Appendable<Input, Output>: Indicator<Input, Output> {
+    /// Stream type carrying the resumable state.
+    type Stream;
+
+    /// Create a fresh stream configured like this calculator.
+    ///
+    /// # Errors
+    /// Returns an error if the stream rejects the calculator's parameters.
+    fn stream(&self) -> IndicatorResult<Self::Stream>;
+
+    /// Calculate values for `data` following everything `state` has already
+    /// processed, advancing `state` past it.
+    ///
+    /// # Errors
+    /// Returns an error if `data` is malformed (e.g. mismatched array lengths).
+    fn append(&self, state: &mut Self::Stream, data: Input) -> IndicatorResult<Output>;
+
+    /// Calculate values for `data` and return them with the state needed to
+    /// [`append`](Self::append) more data later.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`append`](Self::append).
+    fn calculate_resumable(&self, data: Input) -> IndicatorResult<(Output, Self::Stream)> {
+        let mut state = self.stream()?;
+        let output = self.append(&mut state, data)?;
+        Ok((output, state))
+    }
+}
+
+/// Feed each item to `stream`, with `NaN` where it has no value yet.
+fn feed<S, I, O>(stream: &mut S, items: impl IntoIterator<Item = I>) -> Vec<O>
+where
+    S: StreamingIndicator<I, O>,
+    O: IndicatorOutput,
+{
+    items
+        .into_iter()
+        .map(|item| stream.next(item).unwrap_or_else(O::nan))
+        .collect()
+}
+
+/// Zip (highs, lows, closes) into bars, rejecting mismatched lengths.
+fn hlc_bars<'a>(
+    &(highs, lows, closes): &(&'a [f64], &'a [f64], &'a [f64]),
+) -> IndicatorResult<impl Iterator<Item = (f64, f64, f64)> + 'a> {
+    check_lengths(&[
+        ("highs", highs.len()),
+        ("lows", lows.len()),
+        ("closes", closes.len()),
+    ])?;
+    Ok(highs
+        .iter()
+        .zip(lows)
+        .zip(closes)
+        .map(|((&h, &l), &c)| (h, l, c)))
+}
+
+// ============================================================================
+// Price Series
+// ============================================================================
+
+macro_rules! impl_appendable_prices {
+    ($($batch:ty => $stream:ty, $output:ty, |$this:ident| $make:expr;)*) => {
+        $(
+            impl Appendable<&[f64], Vec<$output>> for $batch {
+                type Stream = $stream;
+
+                fn stream(&self) -> IndicatorResult<$stream> {
+                    let $this = self;
+                    $make
+                }
+
+                fn append(&self, state: &mut $stream, data: &[f64]) -> IndicatorResult<Vec<$output>> {
+                    Ok(feed(state, data.iter().copied()))
+                }
+            }
+        )*
+    };
+}
+
+impl_appendable_prices! {
+    Sma => SmaStream, f64, |s| SmaStream::new(s.period());
+    Ema => EmaStream, f64, |s| EmaStream::with_multiplier(s.period(), s.multiplier());
+    Wma => WmaStream, f64, |s| WmaStream::new(s.period());
+    Hma => HmaStream, f64, |s| HmaStream::new(s.period());
+    Rsi => RsiStream, f64, |s| RsiStream::new(s.period());
+    Cvd => CvdStream, f64, |_s| Ok(CvdStream::new());
+    Macd => MacdStream, MacdOutput, |s| MacdStream::with_signal_type(
+        s.fast_period(),
+        s.slow_period(),
+        s.signal_period(),
+        s.signal_type(),
+    );
+    BBands => BBandsStream, BBandsOutput, |s| BBandsStream::new(s.period(), s.k());
+    LinReg => LinRegStream, LinRegOutput, |s| LinRegStream::new(s.period(), s.num_std_dev());
+    StochRsi => StochRsiStream, StochRsiOutput, |s| StochRsiStream::new(
+        s.rsi_period(),
+        s.stoch_period(),
+        s.k_smooth(),
+        s.d_period(),
+    );
+}
+
+// ============================================================================
+// Bar Series
+// ============================================================================
+
+macro_rules! impl_appendable_hlc {
+    ($($batch:ty => $stream:ty, $output:ty, |$this:ident| $make:expr;)*) => {
+        $(
+            impl<'a> Appendable<&(&'a [f64], &'a [f64], &'a [f64]), Vec<$output>> for $batch {
+                type Stream = $stream;
+
+                fn stream(&self) -> IndicatorResult<$stream> {
+                    let $this = self;
+                    $make
+                }
+
+                fn append(
+                    &self,
+                    state: &mut $stream,
+                    data: &(&'a [f64], &'a [f64], &'a [f64]),
+                ) -> IndicatorResult<Vec<$output>> {
+                    Ok(feed(state, hlc_bars(data)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_appendable_hlc! {
+    Atr => AtrStream, f64, |s| AtrStream::new(s.period());
+    Adx => AdxStream, AdxOutput, |s| AdxStream::new(s.period());
+    Stoch => StochStream, StochOutput, |s| StochStream::new_with_slowing(
+        s.k_period(),
+        s.d_period(),
+        s.slowing(),
+        s.stoch_type(),
+    );
+    Ichimoku => IchimokuStream, IchimokuOutput, |s| IchimokuStream::new(
+        s.tenkan_period(),
+        s.kijun_period(),
+        s.senkou_b_period(),
+    );
+}
+
+impl<'a> Appendable<&(&'a [f64], &'a [f64], &'a [f64], &'a [f64]), Vec<f64>> for Mfi {
+    type Stream = MfiStream;
+
+    fn stream(&self) -> IndicatorResult<MfiStream> {
+        MfiStream::new(self.period())
+    }
+
+    fn append(
+        &self,
+        state: &mut MfiStream,
+        &(highs, lows, closes, volumes): &(&'a [f64], &'a [f64], &'a [f64], &'a [f64]),
+    ) -> IndicatorResult<Vec<f64>> {
+        let len = highs.len();
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+            ("volumes", volumes.len()),
+        ])?;
+        let bars = (0..len).map(|i| (highs[i], lows[i], closes[i], volumes[i]));
+        Ok(feed(state, bars))
+    }
+}
+
+impl Appendable<&[CvdBar], Vec<f64>> for CvdOhlcv {
+    type Stream = CvdOhlcvStream;
+
+    fn stream(&self) -> IndicatorResult<CvdOhlcvStream> {
+        Ok(CvdOhlcvStream::new())
+    }
+
+    fn append(&self, state: &mut CvdOhlcvStream, data: &[CvdBar]) -> IndicatorResult<Vec<f64>> {
+        Ok(feed(state, data.iter().copied()))
+    }
+}
+
+// ============================================================================
+// Candles
+// ============================================================================
+
+impl Appendable<&[OHLCV], Vec<f64>> for SessionVwap {
+    type Stream = SessionVwapStream;
+
+    fn stream(&self) -> IndicatorResult<SessionVwapStream> {
+        Ok(SessionVwapStream::with_session(self.session().clone()))
+    }
+
+    fn append(&self, state: &mut SessionVwapStream, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
+        Ok(feed(state, data.iter().copied()))
+    }
+}
+
+impl Appendable<&[OHLCV], Vec<f64>> for RollingVwap {
+    type Stream = RollingVwapStream;
+
+    fn stream(&self) -> IndicatorResult<RollingVwapStream> {
+        RollingVwapStream::new(self.period())
+    }
+
+    fn append(&self, state: &mut RollingVwapStream, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
+        Ok(feed(state, data.iter().copied()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{SignalType, StochType};
+    use crate::types::IndicatorError;
+    use std::fmt::Debug;
+    use std::ops::Range;
+
+    fn candles(n: i32) -> Vec<OHLCV> {
+        (0..n)
+            .map(|i| {
+                let x = f64::from(i);
+                let close = 100.0 + (x * 0.37).sin() * 5.0 + x * 0.05;
+                let open = close - (x * 0.91).cos();
+                let high = open.max(close) + 0.5 + (x * 0.13).sin().abs();
+                let low = open.min(close) - 0.5 - (x * 0.29).cos().abs();
+                let volume = 1000.0 + (x * 0.53).sin() * 400.0;
+                OHLCV::new(i64::from(i) * 3_600_000, open, high, low, close, volume)
+            })
+            .collect()
+    }
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a.is_nan() && b.is_nan()) || (a - b).abs() < 1e-9
+    }
+
+    /// Calculate the first bar of `0..len`, append the rest in uneven chunks
+    /// and compare against a single resumable calculation over everything.
+    fn assert_appends<S, O: Debug>(
+        len: usize,
+        resumable: impl Fn(Range<usize>) -> (Vec<O>, S),
+        append: impl Fn(&mut S, Range<usize>) -> Vec<O>,
+    ) {
+        let (full, _) = resumable(0..len);
+
+        let (mut output, mut state) = resumable(0..1);
+        for chunk in [1..7, 7..40, 40..41, 41..90, 90..len] {
+            output.extend(append(&mut state, chunk));
+        }
+        // Debug formatting of f64 round-trips exactly
+        assert_eq!(format!("{output:?}"), format!("{full:?}"));
+    }
+
+    macro_rules! assert_price_appends {
+        ($indicator:expr, $data:expr) => {{
+            let indicator = $indicator;
+            assert_appends(
+                $data.len(),
+                |r| indicator.calculate_resumable(&$data[r]).unwrap(),
+                |state, r| indicator.append(state, &$data[r]).unwrap(),
+            );
+        }};
+    }
+
+    macro_rules! assert_hlc_appends {
+        ($indicator:expr, $h:expr, $l:expr, $c:expr) => {{
+            let indicator = $indicator;
+            assert_appends(
+                $h.len(),
+                |r| {
+                    let input = (&$h[r.clone()], &$l[r.clone()], &$c[r]);
+                    indicator.calculate_resumable(&input).unwrap()
+                },
+                |state, r| {
+                    let input = (&$h[r.clone()], &$l[r.clone()], &$c[r]);
+                    indicator.append(state, &input).unwrap()
+                },
+            );
+        }};
+    }
+
+    #[test]
+    fn test_append_matches_full_price_calculation() {
+        let closes: Vec<f64> = candles(150).iter().map(|c| c.close).collect();
+
+        assert_price_appends!(Sma::new(20).unwrap(), closes);
+        assert_price_appends!(Ema::new(10).unwrap(), closes);
+        assert_price_appends!(Rsi::new(14).unwrap(), closes);
+        assert_price_appends!(Hma::new(9).unwrap(), closes);
+        assert_price_appends!(
+            Macd::with_signal_type(12, 26, 9, SignalType::Sma).unwrap(),
+            closes
+        );
+        assert_price_appends!(StochRsi::new(14, 14, 3, 3).unwrap(), closes);
+
+        // The resumable output agrees with the plain batch calculation
+        let macd = Macd::new(12, 26, 9).unwrap();
+        let (resumable, _) = macd.calculate_resumable(&closes).unwrap();
+        let batch = macd.calculate(&closes).unwrap();
+        assert!(resumable
+            .iter()
+            .zip(&batch)
+            .all(|(a, b)| approx_eq(a.histogram, b.histogram)));
+    }
+
+    #[test]
+    fn test_append_matches_full_bar_calculation() {
+        let data = candles(150);
+        let h: Vec<f64> = data.iter().map(|c| c.high).collect();
+        let l: Vec<f64> = data.iter().map(|c| c.low).collect();
+        let c: Vec<f64> = data.iter().map(|c| c.close).collect();
+
+        assert_hlc_appends!(Adx::new(14).unwrap(), h, l, c);
+        assert_hlc_appends!(
+            Stoch::new_with_slowing(14, 3, 5, StochType::Slow).unwrap(),
+            h,
+            l,
+            c
+        );
+        assert_hlc_appends!(Ichimoku::new(9, 26, 52).unwrap(), h, l, c);
+
+        let adx = Adx::new(14).unwrap();
+        let (resumable, _) = adx.calculate_resumable(&(&h, &l, &c)).unwrap();
+        let batch = adx.calculate(&(&h, &l, &c)).unwrap();
+        assert!(resumable
+            .iter()
+            .zip(&batch)
+            .all(|(a, b)| approx_eq(a.adx, b.adx)));
+    }
+
+    #[test]
+    fn test_append_candles_and_state_continues_as_stream() {
+        let data = candles(100);
+        let vwap = RollingVwap::new(20).unwrap();
+
+        let (mut values, mut state) = vwap.calculate_resumable(&data[..60]).unwrap();
+        values.extend(vwap.append(&mut state, &data[60..99]).unwrap());
+        let last = state.next(data[99]).unwrap();
+
+        let expected = vwap.calculate(&data).unwrap();
+        assert!(values.iter().zip(&expected).all(|(a, b)| approx_eq(*a, *b)));
+        assert!(approx_eq(last, expected[99]));
+    }
+
+    #[test]
+    fn test_append_rejects_mismatched_lengths() {
+        let atr = Atr::new(3).unwrap();
+        let (_, mut state) = atr
+            .calculate_resumable(&(&[3.0, 4.0][..], &[1.0, 2.0][..], &[2.0, 3.0][..]))
+            .unwrap();
+        let result = atr.append(&mut state, &(&[5.0][..], &[][..], &[4.0][..]));
+        assert!(matches!(
+            result,
+            Err(IndicatorError::LengthMismatch {
+                name: "lows",
+                len: 0,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,667 @@
+//! Aggregation of raw trades into time bars.
+//!
+//! [`BarAggregator`] turns a trade feed into [`OHLCV`] candles of any interval,
+//! aligned to UTC (optionally shifted by an offset). Every accepted trade
+//! updates the forming bar; a trade from a later interval, or
+//! [`BarAggregator::advance`] once the interval is over, closes it.
+//!
+//! Bars can be fed straight into any `StreamingIndicator<OHLCV, _>` with
+//! [`BarAggregator::push_into`], which calls `next` when a bar opens and
+//! `update` while it forms, so the indicator always reflects the live bar.
+//!
+//! # Example
+//! ```
+//! use ta_core::bars::{BarAggregator, Trade};
+//! use ta_core::indicators::SessionVwapStream;
+//!
+//! // One-minute bars
+//! let mut bars = BarAggregator::new(60_000).unwrap();
+//! let mut vwap = SessionVwapStream::new();
+//!
+//! bars.push_into(Trade::new(1_000, 100.0, 2.0), &mut vwap);
+//! bars.push_into(Trade::new(30_000, 102.0, 1.0), &mut vwap);
+//!
+//! // A trade in the next minute closes the first bar
+//! let update = bars.push(Trade::new(61_000, 101.0, 1.0)).unwrap();
+//! let closed = update.closed.unwrap();
+//! assert_eq!((closed.candle.open, closed.candle.high, closed.candle.volume), (100.0, 102.0, 3.0));
+//! assert_eq!(update.current.candle.timestamp, 60_000);
+//! ```
+
+use crate::traits::StreamingIndicator;
+use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+
+/// Aggressor side of a trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    /// Buyer lifted the offer
+    Buy,
+    /// Seller hit the bid
+    Sell,
+}
+
+/// A single executed trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trade {
+    /// Unix timestamp in milliseconds (UTC)
+    pub timestamp: i64,
+    /// Execution price
+    pub price: f64,
+    /// Traded quantity
+    pub size: f64,
+    /// Aggressor side, if the feed reports it
+    pub side: Option<Side>,
+}
+
+impl Trade {
+    /// Creates a trade without side information.
+    #[must_use]
+    pub const fn new(timestamp: i64, price: f64, size: f64) -> Self {
+        Self {
+            timestamp,
+            price,
+            size,
+            side: None,
+        }
+    }
+
+    /// Creates a trade with a known aggressor side.
+    #[must_use]
+    pub const fn with_side(timestamp: i64, price: f64, size: f64, side: Side) -> Self {
+        Self {
+            timestamp,
+            price,
+            size,
+            side: Some(side),
+        }
+    }
+}
+
+/// Best bid and offer prevailing when a trade executed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quote {
+    /// Best bid price
+    pub bid: f64,
+    /// Best offer price
+    pub ask: f64,
+}
+
+impl Quote {
+    /// Creates a quote.
+    #[must_use]
+    pub const fn new(bid: f64, ask: f64) -> Self {
+        Self { bid, ask }
+    }
+
+    /// Midpoint of the quote, or `None` if it is crossed or not finite.
+    #[must_use]
+    pub fn midpoint(&self) -> Option<f64> {
+        (self.bid.is_finite() && self.ask.is_finite() && self.bid <= self.ask)
+            .then(|| (self.bid + self.ask) / 2.0)
+    }
+}
+
+/// How a [`TradeClassifier`] signs trades.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Classification {
+    /// The aggressor side reported by the feed; trades without one are
+    /// unsigned
+    #[default]
+    Aggressor,
+    /// Tick rule: a trade above the previous trade's price is a buy, below
+    /// it a sell, and at the same price it takes the previous trade's side
+    TickRule,
+    /// Lee-Ready: a trade above the prevailing quote midpoint is a buy,
+    /// below it a sell; at the midpoint, or without a valid quote, the tick
+    /// rule decides
+    LeeReady,
+}
+
+/// Signs trades as buyer- or seller-initiated.
+///
+/// The tick rule looks at every trade classified so far, so trades must be
+/// classified in execution order.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeClassifier {
+    rule: Classification,
+    quote: Option<Quote>,
+    last_price: Option<f64>,
+    last_side: Option<Side>,
+}
+
+impl TradeClassifier {
+    /// Creates a classifier applying `rule`.
+    #[must_use]
+    pub const fn new(rule: Classification) -> Self {
+        Self {
+            rule,
+            quote: None,
+            last_price: None,
+            last_side: None,
+        }
+    }
+
+    /// Classification rule.
+    #[must_use]
+    pub const fn rule(&self) -> Classification {
+        self.rule
+    }
+
+    /// Set the quote prevailing from now on, used by [`Classification::LeeReady`].
+    pub fn set_quote(&mut self, quote: Quote) {
+        self.quote = Some(quote);
+    }
+
+    /// Side of `trade`, or `None` if it cannot be determined (no reported
+    /// side, or no price change since the first trade).
+    pub fn classify(&mut self, trade: &Trade) -> Option<Side> {
+        let tick = self.tick(trade.price);
+        match self.rule {
+            Classification::Aggressor => trade.side,
+            Classification::TickRule => tick,
+            Classification::LeeReady => self
+                .quote
+                .and_then(|quote| quote.midpoint())
+                .and_then(|mid| side_of_move(mid, trade.price))
+                .or(tick),
+        }
+    }
+
+    /// Forget the quote and the previous trade.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.rule);
+    }
+
+    fn tick(&mut self, price: f64) -> Option<Side> {
+        let side = self
+            .last_price
+            .and_then(|last| side_of_move(last, price))
+            .or(self.last_side);
+        self.last_price = Some(price);
+        self.last_side = side;
+        side
+    }
+}
+
+/// Buy if `price` is above `reference`, sell if below.
+fn side_of_move(reference: f64, price: f64) -> Option<Side> {
+    if price > reference {
+        Some(Side::Buy)
+    } else if price < reference {
+        Some(Side::Sell)
+    } else {
+        None
+    }
+}
+
+/// A time bar built from trades.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bar {
+    /// The candle; `timestamp` is the bar's open time
+    pub candle: OHLCV,
+    /// Volume of trades with [`Side::Buy`]
+    pub buy_volume: f64,
+    /// Volume of trades with [`Side::Sell`]
+    pub sell_volume: f64,
+    /// Number of trades in the bar
+    pub trade_count: usize,
+}
+
+impl Bar {
+    fn open(start: i64, trade: &Trade) -> Self {
+        let mut bar = Self {
+            candle: OHLCV::new(
+                start,
+                trade.price,
+                trade.price,
+                trade.price,
+                trade.price,
+                0.0,
+            ),
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            trade_count: 0,
+        };
+        bar.add(trade);
+        bar
+    }
+
+    fn add(&mut self, trade: &Trade) {
+        let candle = &mut self.candle;
+        candle.high = candle.high.max(trade.price);
+        candle.low = candle.low.min(trade.price);
+        candle.close = trade.price;
+        candle.volume += trade.size;
+
+        match trade.side {
+            Some(Side::Buy) => self.buy_volume += trade.size,
+            Some(Side::Sell) => self.sell_volume += trade.size,
+            None => {}
+        }
+        self.trade_count += 1;
+    }
+
+    /// Buy volume minus sell volume.
+    #[must_use]
+    pub fn delta(&self) -> f64 {
+        self.buy_volume - self.sell_volume
+    }
+}
+
+/// What a trade did to the bars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarUpdate {
+    /// The bar this trade closed, if it started a new interval (bar-close event)
+    pub closed: Option<Bar>,
+    /// The forming bar including this trade (bar-update event)
+    pub current: Bar,
+    /// Whether this trade opened `current`
+    pub opened: bool,
+}
+
+/// Streaming trade-to-bar aggregator.
+///
+/// Bars cover `[start, start + interval)` where `start` is aligned to the Unix
+/// epoch plus `offset` (UTC), so daily bars open at midnight UTC and 4-hour
+/// bars at 00:00, 04:00, ... Intervals without trades produce no bar.
+///
+/// Trades belonging to an interval before the forming bar's, or one that
+/// is already closed, are dropped. Trades with a non-finite price or a negative or non-finite size are
+/// dropped too.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarAggregator {
+    interval: i64,
+    offset: i64,
+    current: Option<Bar>,
+    // Trades before this time belong to closed bars
+    closed_until: i64,
+}
+
+impl BarAggregator {
+    /// Creates an aggregator for bars of `interval_ms` milliseconds aligned
+    /// to UTC.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` is not positive.
+    pub fn new(interval_ms: i64) -> IndicatorResult<Self> {
+        Self::with_offset(interval_ms, 0)
+    }
+
+    /// Creates an aggregator whose bars open `offset_ms` after the UTC-aligned
+    /// boundaries, e.g. weekly bars opening on Monday are
+    /// `with_offset(WEEK, 4 * DAY)` since the epoch was a Thursday.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` is not positive.
+    pub fn with_offset(interval_ms: i64, offset_ms: i64) -> IndicatorResult<Self> {
+        if interval_ms <= 0 {
+            return Err(IndicatorError::InvalidParameter(
+                "interval must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self {
+            interval: interval_ms,
+            offset: offset_ms.rem_euclid(interval_ms),
+            current: None,
+            closed_until: i64::MIN,
+        })
+    }
+
+    /// Aggregate a batch of trades into bars, including the last, possibly
+    /// incomplete one.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` is not positive.
+    pub fn aggregate(interval_ms: i64, trades: &[Trade]) -> IndicatorResult<Vec<Bar>> {
+        let mut aggregator = Self::new(interval_ms)?;
+        let mut bars: Vec<Bar> = trades
+            .iter()
+            .filter_map(|&trade| aggregator.push(trade)?.closed)
+            .collect();
+        bars.extend(aggregator.flush());
+        Ok(bars)
+    }
+
+    /// Bar interval in milliseconds.
+    #[must_use]
+    pub const fn interval(&self) -> i64 {
+        self.interval
+    }
+
+    /// Alignment offset in milliseconds.
+    #[must_use]
+    pub const fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// The forming bar, if any trade has arrived since the last close.
+    #[must_use]
+    pub const fn current(&self) -> Option<&Bar> {
+        self.current.as_ref()
+    }
+
+    /// Open time of the bar containing `timestamp`.
+    #[must_use]
+    pub const fn bar_start(&self, timestamp: i64) -> i64 {
+        timestamp - (timestamp - self.offset).rem_euclid(self.interval)
+    }
+
+    /// Add a trade.
+    ///
+    /// Returns `None` if the trade was dropped (see [`BarAggregator`]).
+    pub fn push(&mut self, trade: Trade) -> Option<BarUpdate> {
+        if !trade.price.is_finite() || !trade.size.is_finite() || trade.size < 0.0 {
+            return None;
+        }
+        if trade.timestamp < self.closed_until {
+            return None;
+        }
+
+        let start = self.bar_start(trade.timestamp);
+        let mut closed = None;
+        if let Some(bar) = &mut self.current {
+            if start < bar.candle.timestamp {
+                // An earlier interval, possibly one that never had a bar
+                return None;
+            }
+            if start == bar.candle.timestamp {
+                bar.add(&trade);
+                return Some(BarUpdate {
+                    closed: None,
+                    current: *bar,
+                    opened: false,
+                });
+            }
+            closed = self.close();
+        }
+
+        let bar = Bar::open(start, &trade);
+        self.current = Some(bar);
+        Some(BarUpdate {
+            closed,
+            current: bar,
+            opened: true,
+        })
+    }
+
+    /// Add a trade and keep `stream` in sync with the forming bar: `next`
+    /// when the trade opens a bar, `update` otherwise.
+    ///
+    /// Returns the stream's value for the forming bar, or `None` if the trade
+    /// was dropped or the stream has no value yet.
+    pub fn push_into<S, O>(&mut self, trade: Trade, stream: &mut S) -> Option<O>
+    where
+        S: StreamingIndicator<OHLCV, O>,
+    {
+        let update = self.push(trade)?;
+        if update.opened {
+            stream.next(update.current.candle)
+        } else {
+            stream.update(update.current.candle)
+        }
+    }
+
+    /// Close the forming bar if its interval has ended by `now`.
+    ///
+    /// Call this from a timer to get bar-close events for quiet markets,
+    /// where no trade from the next interval arrives to close the bar.
+    pub fn advance(&mut self, now: i64) -> Option<Bar> {
+        let start = self.current?.candle.timestamp;
+        if now >= start.saturating_add(self.interval) {
+            self.close()
+        } else {
+            None
+        }
+    }
+
+    /// Close the forming bar regardless of time, e.g. at the end of a batch.
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.close()
+    }
+
+    /// Drop the forming bar and forget all history.
+    pub fn reset(&mut self) {
+        self.current = None;
+        self.closed_until = i64::MIN;
+    }
+
+    fn close(&mut self) -> Option<Bar> {
+        let bar = self.current.take()?;
+        self.closed_until = bar.candle.timestamp.saturating_add(self.interval);
+        Some(bar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{FrvpStream, SessionVwap, SessionVwapStream};
+    use crate::traits::Indicator;
+
+    const MINUTE: i64 = 60_000;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+
+    fn trades(n: i32) -> Vec<Trade> {
+        (0..n)
+            .map(|i| {
+                let price = 100.0 + (f64::from(i) * 0.7).sin() * 3.0;
+                let side = if i % 3 == 0 { Side::Sell } else { Side::Buy };
+                let size = 1.0 + f64::from(i % 4);
+                Trade::with_side(i64::from(i) * 7_000 + 500, price, size, side)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new_validation() {
+        assert!(BarAggregator::new(0).is_err());
+        assert!(BarAggregator::new(-MINUTE).is_err());
+        assert_eq!(
+            BarAggregator::with_offset(DAY, -HOUR).unwrap().offset(),
+            23 * HOUR
+        );
+    }
+
+    #[test]
+    fn test_utc_alignment() {
+        let four_hours = BarAggregator::new(4 * HOUR).unwrap();
+        // 2024-03-01 13:37:00 UTC opens the 12:00 bar
+        let ts = 1_709_300_220_000;
+        assert_eq!(four_hours.bar_start(ts), 1_709_294_400_000);
+
+        let daily = BarAggregator::new(DAY).unwrap();
+        assert_eq!(daily.bar_start(ts), 1_709_251_200_000);
+        assert_eq!(daily.bar_start(-1), -DAY);
+
+        // Weekly bars opening Monday 00:00 UTC (2024-02-26)
+        let weekly = BarAggregator::with_offset(7 * DAY, 4 * DAY).unwrap();
+        assert_eq!(weekly.bar_start(ts), 1_708_905_600_000);
+    }
+
+    #[test]
+    fn test_bar_fields_and_events() {
+        let mut bars = BarAggregator::new(MINUTE).unwrap();
+
+        let first = bars
+            .push(Trade::with_side(1_000, 10.0, 1.0, Side::Buy))
+            .unwrap();
+        assert!(first.opened && first.closed.is_none());
+
+        bars.push(Trade::with_side(2_000, 12.0, 2.0, Side::Sell));
+        bars.push(Trade::new(3_000, 9.0, 0.5));
+        let update = bars.push(Trade::new(4_000, 11.0, 1.0)).unwrap();
+        assert!(!update.opened && update.closed.is_none());
+
+        let bar = update.current;
+        assert_eq!(bar.candle, OHLCV::new(0, 10.0, 12.0, 9.0, 11.0, 4.5));
+        assert_eq!(
+            (bar.buy_volume, bar.sell_volume, bar.trade_count),
+            (1.0, 2.0, 4)
+        );
+        assert!((bar.delta() + 1.0).abs() < 1e-12);
+
+        // Skipping quiet minutes closes the bar and opens one at the new minute
+        let next = bars.push(Trade::new(3 * MINUTE + 10, 11.5, 1.0)).unwrap();
+        assert_eq!(next.closed, Some(bar));
+        assert!(next.opened);
+        assert_eq!(next.current.candle.timestamp, 3 * MINUTE);
+    }
+
+    #[test]
+    fn test_drops_late_and_invalid_trades() {
+        let mut bars = BarAggregator::new(MINUTE).unwrap();
+        bars.push(Trade::new(1_000, 10.0, 1.0));
+        bars.push(Trade::new(MINUTE + 1_000, 11.0, 1.0));
+
+        // Out of order within the forming bar is fine; the closed bar is final
+        assert!(bars.push(Trade::new(MINUTE, 10.5, 1.0)).is_some());
+        assert!(bars.push(Trade::new(59_999, 9.0, 1.0)).is_none());
+
+        assert!(bars
+            .push(Trade::new(MINUTE + 2_000, f64::NAN, 1.0))
+            .is_none());
+        assert!(bars.push(Trade::new(MINUTE + 2_000, 10.0, -1.0)).is_none());
+        assert_eq!(bars.current().unwrap().trade_count, 2);
+    }
+
+    #[test]
+    fn test_drops_trades_before_first_bar() {
+        let mut bars = BarAggregator::new(MINUTE).unwrap();
+        bars.push(Trade::new(61_000, 10.0, 1.0));
+
+        // No bar has closed yet, but the interval is before the forming one
+        assert!(bars.push(Trade::new(59_000, 9.0, 1.0)).is_none());
+        let update = bars.push(Trade::new(62_000, 11.0, 1.0)).unwrap();
+        assert_eq!(update.closed, None);
+        assert_eq!(update.current.candle.timestamp, MINUTE);
+        assert!((update.current.candle.low - 10.0).abs() < 1e-12);
+        assert_eq!(update.current.trade_count, 2);
+    }
+
+    #[test]
+    fn test_trade_classification() {
+        let prices = [10.0, 10.5, 10.5, 10.25, 10.25, 10.5];
+
+        let mut tick = TradeClassifier::new(Classification::TickRule);
+        let sides: Vec<_> = prices
+            .iter()
+            .map(|&p| tick.classify(&Trade::new(0, p, 1.0)))
+            .collect();
+        assert_eq!(
+            sides,
+            [
+                None,
+                Some(Side::Buy),
+                Some(Side::Buy),
+                Some(Side::Sell),
+                Some(Side::Sell),
+                Some(Side::Buy)
+            ]
+        );
+
+        // Quote midpoint 10.375 decides, the tick rule breaks ties
+        let mut lee_ready = TradeClassifier::new(Classification::LeeReady);
+        lee_ready.set_quote(Quote::new(10.25, 10.5));
+        let sides: Vec<_> = [10.25, 10.375, 10.5]
+            .iter()
+            .map(|&p| lee_ready.classify(&Trade::new(0, p, 1.0)))
+            .collect();
+        assert_eq!(sides, [Some(Side::Sell), Some(Side::Buy), Some(Side::Buy)]);
+
+        // A crossed quote falls back to the tick rule
+        lee_ready.set_quote(Quote::new(11.0, 10.0));
+        assert_eq!(
+            lee_ready.classify(&Trade::new(0, 10.4, 1.0)),
+            Some(Side::Sell)
+        );
+
+        let mut aggressor = TradeClassifier::new(Classification::Aggressor);
+        assert_eq!(aggressor.classify(&Trade::new(0, 10.0, 1.0)), None);
+        assert_eq!(
+            aggressor.classify(&Trade::with_side(0, 9.0, 1.0, Side::Buy)),
+            Some(Side::Buy)
+        );
+    }
+
+    #[test]
+    fn test_advance_closes_quiet_bar() {
+        let mut bars = BarAggregator::new(MINUTE).unwrap();
+        bars.push(Trade::new(1_000, 10.0, 1.0));
+
+        assert!(bars.advance(MINUTE - 1).is_none());
+        let closed = bars.advance(MINUTE).unwrap();
+        assert_eq!(closed.candle, OHLCV::new(0, 10.0, 10.0, 10.0, 10.0, 1.0));
+        assert!(bars.current().is_none());
+        assert!(bars.advance(2 * MINUTE).is_none());
+
+        // The closed interval no longer accepts trades
+        assert!(bars.push(Trade::new(30_000, 10.0, 1.0)).is_none());
+        assert!(bars.push(Trade::new(MINUTE, 10.0, 1.0)).unwrap().opened);
+    }
+
+    #[test]
+    fn test_aggregate_matches_streaming() {
+        let data = trades(200);
+        let bars = BarAggregator::aggregate(MINUTE, &data).unwrap();
+
+        let mut aggregator = BarAggregator::new(MINUTE).unwrap();
+        let mut streamed: Vec<Bar> = data
+            .iter()
+            .filter_map(|&t| aggregator.push(t)?.closed)
+            .collect();
+        streamed.extend(aggregator.flush());
+
+        assert_eq!(bars, streamed);
+        assert_eq!(bars.iter().map(|b| b.trade_count).sum::<usize>(), 200);
+        let volume: f64 = data.iter().map(|t| t.size).sum();
+        assert!((bars.iter().map(|b| b.candle.volume).sum::<f64>() - volume).abs() < 1e-9);
+        assert!(bars
+            .windows(2)
+            .all(|w| w[1].candle.timestamp - w[0].candle.timestamp == MINUTE));
+    }
+
+    #[test]
+    fn test_push_into_tracks_forming_bar() {
+        let data = trades(200);
+        let candles: Vec<OHLCV> = BarAggregator::aggregate(MINUTE, &data)
+            .unwrap()
+            .iter()
+            .map(|b| b.candle)
+            .collect();
+        let expected = SessionVwap::new().calculate(&candles).unwrap();
+
+        let mut aggregator = BarAggregator::new(MINUTE).unwrap();
+        let mut vwap = SessionVwapStream::new();
+        let mut live = Vec::new();
+        for &trade in &data {
+            let value = aggregator.push_into(trade, &mut vwap).unwrap();
+            live.push((aggregator.current().unwrap().candle.timestamp, value));
+        }
+
+        // The last value seen for each bar matches the batch result
+        for (candle, expected) in candles.iter().zip(&expected) {
+            let last = live
+                .iter()
+                .rev()
+                .find(|(ts, _)| *ts == candle.timestamp)
+                .unwrap();
+            assert!((last.1 - expected).abs() < 1e-9);
+        }
+
+        // FRVP over the live bars sees one candle per bar
+        let mut frvp = FrvpStream::new(10).unwrap();
+        let mut aggregator = BarAggregator::new(MINUTE).unwrap();
+        for &trade in &data {
+            aggregator.push_into(trade, &mut frvp);
+        }
+        assert_eq!(frvp.candle_count(), candles.len());
+    }
+}
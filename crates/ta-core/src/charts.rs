@@ -0,0 +1,804 @@
+//! Alternative bar types: Heikin-Ashi, Renko, range, volume and tick bars.
+//!
+//! Every transformer emits plain [`OHLCV`] candles, so all indicators run on
+//! them unchanged:
+//! - [`HeikinAshi`] / [`HeikinAshiStream`] smooth time candles one-to-one
+//! - [`Renko`] builds fixed-size or ATR-sized bricks from candle closes
+//! - [`RangeBars`], [`VolumeBars`] and [`TickBars`] cut a trade feed into bars
+//!   of equal price range, volume or trade count
+//!
+//! Renko, range, volume and tick bars are not aligned with their input, so
+//! they implement [`BarBuilder`]: `push` returns the bars an input completed
+//! (none, one or several) and `build` runs over a whole history.
+//!
+//! # Example
+//! ```
+//! use ta_core::bars::Trade;
+//! use ta_core::charts::{BarBuilder, Renko, TickBars};
+//! use ta_core::indicators::Ema;
+//! use ta_core::{Indicator, OHLCV};
+//!
+//! let candles: Vec<OHLCV> = (0..50)
+//!     .map(|i| {
+//!         let c = 100.0 + f64::from(i) * 0.3;
+//!         OHLCV::new(i64::from(i) * 60_000, c, c + 0.2, c - 0.2, c, 5.0)
+//!     })
+//!     .collect();
+//!
+//! // One-point bricks: 14 up bricks from 100.0 to 114.0
+//! let bricks = Renko::new(1.0).unwrap().build(&candles);
+//! assert_eq!(bricks.len(), 14);
+//! assert_eq!((bricks[0].open, bricks[0].close), (100.0, 101.0));
+//!
+//! // EMA of brick closes
+//! let closes: Vec<f64> = bricks.iter().map(|b| b.close).collect();
+//! let ema = Ema::new(3).unwrap().calculate(&closes).unwrap();
+//! assert!(!ema[2].is_nan());
+//!
+//! // Bars of 10 trades
+//! let trades: Vec<Trade> = (0..25).map(|i| Trade::new(i, 100.0, 1.0)).collect();
+//! let mut ticks = TickBars::new(10).unwrap();
+//! let closed: Vec<OHLCV> = trades.iter().flat_map(|&t| ticks.push(t)).collect();
+//! assert_eq!(closed.len(), 2);
+//! assert_eq!(ticks.current().unwrap().timestamp, 20);
+//! ```
+
+use crate::bars::Trade;
+use crate::indicators::AtrStream;
+use crate::traits::{Indicator, StreamingIndicator};
+use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+
+/// Builds bars that are not aligned with their input.
+pub trait BarBuilder<Input> {
+    /// Feed one input. Returns the bars it completed, oldest first.
+    fn push(&mut self, input: Input) -> Vec<OHLCV>;
+
+    /// The forming bar, if any.
+    fn current(&self) -> Option<OHLCV>;
+
+    /// Drop all state.
+    fn reset(&mut self);
+
+    /// Build the completed bars of a whole history. The forming bar at the
+    /// end is left out. Does not touch this builder's streaming state.
+    fn build(&self, data: &[Input]) -> Vec<OHLCV>
+    where
+        Self: Clone,
+        Input: Copy,
+    {
+        let mut builder = self.clone();
+        builder.reset();
+        data.iter().flat_map(|&input| builder.push(input)).collect()
+    }
+}
+
+/// Most bars a single input completes. A larger jump (a bad print, or a
+/// size far below the price) emits this many and carries the rest over;
+/// see [`Renko`] and [`RangeBars`].
+pub const MAX_BARS_PER_INPUT: usize = 10_000;
+
+fn require_positive(value: f64, name: &str) -> IndicatorResult<()> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(IndicatorError::InvalidParameter(format!(
+            "{name} must be greater than 0"
+        )))
+    }
+}
+
+/// A bar opened by `trade`.
+fn open_bar(trade: &Trade) -> OHLCV {
+    OHLCV::new(
+        trade.timestamp,
+        trade.price,
+        trade.price,
+        trade.price,
+        trade.price,
+        trade.size,
+    )
+}
+
+/// `bar` extended by `trade`.
+fn extend_bar(bar: &mut OHLCV, trade: &Trade) {
+    bar.high = bar.high.max(trade.price);
+    bar.low = bar.low.min(trade.price);
+    bar.close = trade.price;
+    bar.volume += trade.size;
+}
+
+/// Whether a trade can go into a bar.
+fn is_valid(trade: &Trade) -> bool {
+    trade.price.is_finite() && trade.size.is_finite() && trade.size >= 0.0
+}
+
+// ============================================================================
+// Heikin-Ashi
+// ============================================================================
+
+/// Heikin-Ashi candle following a previous Heikin-Ashi (open, close).
+fn heikin_ashi(candle: &OHLCV, prev: Option<(f64, f64)>) -> OHLCV {
+    let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+    let open = prev.map_or((candle.open + candle.close) / 2.0, |(o, c)| (o + c) / 2.0);
+    OHLCV {
+        open,
+        high: candle.high.max(open).max(close),
+        low: candle.low.min(open).min(close),
+        close,
+        ..*candle
+    }
+}
+
+/// Heikin-Ashi candles (batch).
+///
+/// - Close: (open + high + low + close) / 4
+/// - Open: midpoint of the previous Heikin-Ashi open and close; the first
+///   one is the midpoint of the candle's own open and close
+/// - High/Low: candle extremes including the Heikin-Ashi open and close
+///
+/// Timestamp and volume are kept.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeikinAshi;
+
+impl HeikinAshi {
+    /// Creates a Heikin-Ashi transformer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Indicator<&[OHLCV], Vec<OHLCV>> for HeikinAshi {
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<OHLCV>> {
+        let mut prev = None;
+        Ok(data
+            .iter()
+            .map(|candle| {
+                let ha = heikin_ashi(candle, prev);
+                prev = Some((ha.open, ha.close));
+                ha
+            })
+            .collect())
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+/// Heikin-Ashi candles (streaming).
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeikinAshiStream {
+    // Heikin-Ashi (open, close) of the last candle
+    prev: Option<(f64, f64)>,
+    // `prev` before the last candle, restored by `update`
+    before: Option<(f64, f64)>,
+}
+
+impl HeikinAshiStream {
+    /// Creates a streaming Heikin-Ashi transformer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            prev: None,
+            before: None,
+        }
+    }
+}
+
+impl StreamingIndicator<OHLCV, OHLCV> for HeikinAshiStream {
+    fn init(&mut self, data: &[OHLCV]) -> IndicatorResult<Vec<OHLCV>> {
+        self.reset();
+        Ok(data
+            .iter()
+            .filter_map(|&candle| self.next(candle))
+            .collect())
+    }
+
+    fn next(&mut self, candle: OHLCV) -> Option<OHLCV> {
+        let ha = heikin_ashi(&candle, self.prev);
+        self.before = self.prev;
+        self.prev = Some((ha.open, ha.close));
+        Some(ha)
+    }
+
+    fn update(&mut self, candle: OHLCV) -> Option<OHLCV> {
+        if self.prev.is_none() {
+            return self.next(candle);
+        }
+        let ha = heikin_ashi(&candle, self.before);
+        self.prev = Some((ha.open, ha.close));
+        Some(ha)
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<OHLCV> {
+        Some(heikin_ashi(&candle, self.prev))
+    }
+
+    fn reset(&mut self) {
+        self.prev = None;
+        self.before = None;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prev.is_some()
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+// ============================================================================
+// Renko
+// ============================================================================
+
+/// How Renko sizes its bricks.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BrickSize {
+    /// Every brick is this many price units
+    Fixed(f64),
+    /// Bricks are the current ATR of the input candles
+    Atr(AtrStream),
+}
+
+/// Renko bricks built from candle closes.
+///
+/// A brick forms when the close moves one box beyond the top of the last
+/// brick (up) or its bottom (down), so a reversal needs two boxes from the
+/// last close. A large move forms several bricks at once. Bricks are labelled
+/// with the timestamp of the candle that completed them; the first brick of a
+/// candle carries the volume traded since the previous brick.
+///
+/// With ATR-sized bricks, no bricks form until the ATR is ready, and each
+/// candle's bricks use the ATR including that candle.
+///
+/// A candle forms at most [`MAX_BARS_PER_INPUT`] bricks; the rest of a larger
+/// move forms with the next candles. Boxes too small to change the price at
+/// its floating-point precision form no bricks.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Renko {
+    size: BrickSize,
+    // (bottom, top) of the last brick, or the anchor price before the first
+    last: Option<(f64, f64)>,
+    volume: f64,
+}
+
+impl Renko {
+    /// Creates Renko with bricks of `box_size` price units.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `box_size` is not a positive number.
+    pub fn new(box_size: f64) -> IndicatorResult<Self> {
+        require_positive(box_size, "box size")?;
+        Ok(Self::with_size(BrickSize::Fixed(box_size)))
+    }
+
+    /// Creates Renko with bricks sized by the ATR over `period` candles.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `period` is 0.
+    pub fn atr(period: usize) -> IndicatorResult<Self> {
+        Ok(Self::with_size(BrickSize::Atr(AtrStream::new(period)?)))
+    }
+
+    const fn with_size(size: BrickSize) -> Self {
+        Self {
+            size,
+            last: None,
+            volume: 0.0,
+        }
+    }
+
+    /// Returns how bricks are sized.
+    #[must_use]
+    pub const fn brick_size(&self) -> &BrickSize {
+        &self.size
+    }
+}
+
+impl BarBuilder<OHLCV> for Renko {
+    fn push(&mut self, candle: OHLCV) -> Vec<OHLCV> {
+        let box_size = match &mut self.size {
+            BrickSize::Fixed(size) => Some(*size),
+            BrickSize::Atr(atr) => atr.next((candle.high, candle.low, candle.close)),
+        };
+        let price = candle.close;
+        if !price.is_finite() {
+            return Vec::new();
+        }
+        self.volume += candle.volume;
+
+        let (mut bottom, mut top) = *self.last.get_or_insert((price, price));
+        let Some(size) = box_size.filter(|s| s.is_finite() && *s > 0.0) else {
+            return Vec::new();
+        };
+
+        let mut bricks = Vec::new();
+        let brick = |open: f64, close: f64| OHLCV {
+            timestamp: candle.timestamp,
+            open,
+            high: open.max(close),
+            low: open.min(close),
+            close,
+            volume: 0.0,
+        };
+        // A box below the price's precision would never move it
+        while bricks.len() < MAX_BARS_PER_INPUT && price >= top + size && top + size > top {
+            bricks.push(brick(top, top + size));
+            (bottom, top) = (top, top + size);
+        }
+        while bricks.len() < MAX_BARS_PER_INPUT && price <= bottom - size && bottom - size < bottom
+        {
+            bricks.push(brick(bottom, bottom - size));
+            (bottom, top) = (bottom - size, bottom);
+        }
+
+        if let Some(first) = bricks.first_mut() {
+            first.volume = std::mem::take(&mut self.volume);
+            self.last = Some((bottom, top));
+        }
+        bricks
+    }
+
+    fn current(&self) -> Option<OHLCV> {
+        // Bricks only exist once complete
+        None
+    }
+
+    fn reset(&mut self) {
+        if let BrickSize::Atr(atr) = &mut self.size {
+            atr.reset();
+        }
+        self.last = None;
+        self.volume = 0.0;
+    }
+}
+
+// ============================================================================
+// Range, Volume and Tick Bars
+// ============================================================================
+
+/// Bars of equal price range built from trades.
+///
+/// A bar closes as soon as its high-low range would exceed `range`: it is
+/// capped at exactly `range` and closes at the boundary, where the next bar
+/// opens. A trade gapping across several ranges closes several bars; the
+/// trade's volume goes to the bar it finally lands in. Bars are labelled with
+/// the timestamp of their first trade.
+///
+/// A trade closes at most [`MAX_BARS_PER_INPUT`] bars, and none once the
+/// range is too small to change the price at its floating-point precision;
+/// the rest of the move then goes into the forming bar, whose range exceeds
+/// `range`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeBars {
+    range: f64,
+    current: Option<OHLCV>,
+}
+
+impl RangeBars {
+    /// Creates range bars spanning `range` price units.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `range` is not a positive number.
+    pub fn new(range: f64) -> IndicatorResult<Self> {
+        require_positive(range, "range")?;
+        Ok(Self {
+            range,
+            current: None,
+        })
+    }
+
+    /// Returns the bar range.
+    #[must_use]
+    pub const fn range(&self) -> f64 {
+        self.range
+    }
+}
+
+impl BarBuilder<Trade> for RangeBars {
+    fn push(&mut self, trade: Trade) -> Vec<OHLCV> {
+        if !is_valid(&trade) {
+            return Vec::new();
+        }
+        let Some(bar) = &mut self.current else {
+            self.current = Some(open_bar(&trade));
+            return Vec::new();
+        };
+
+        let mut closed = Vec::new();
+        loop {
+            let boundary = if trade.price > bar.low + self.range {
+                bar.low + self.range
+            } else if trade.price < bar.high - self.range {
+                bar.high - self.range
+            } else {
+                extend_bar(bar, &trade);
+                return closed;
+            };
+            // A flat bar at the boundary would repeat forever
+            let stuck = bar.high == boundary && bar.low == boundary;
+            if stuck || closed.len() >= MAX_BARS_PER_INPUT {
+                extend_bar(bar, &trade);
+                return closed;
+            }
+
+            bar.high = bar.high.max(boundary);
+            bar.low = bar.low.min(boundary);
+            bar.close = boundary;
+            closed.push(*bar);
+            *bar = OHLCV::new(trade.timestamp, boundary, boundary, boundary, boundary, 0.0);
+        }
+    }
+
+    fn current(&self) -> Option<OHLCV> {
+        self.current
+    }
+
+    fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+/// Bars of equal traded volume built from trades.
+///
+/// A bar closes with the trade that brings its volume to `volume` or more.
+/// Trades are not split, so a bar can hold more than `volume`. Bars are
+/// labelled with the timestamp of their first trade.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeBars {
+    volume: f64,
+    current: Option<OHLCV>,
+}
+
+impl VolumeBars {
+    /// Creates bars of `volume` traded units.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `volume` is not a positive number.
+    pub fn new(volume: f64) -> IndicatorResult<Self> {
+        require_positive(volume, "volume")?;
+        Ok(Self {
+            volume,
+            current: None,
+        })
+    }
+
+    /// Returns the volume per bar.
+    #[must_use]
+    pub const fn volume(&self) -> f64 {
+        self.volume
+    }
+}
+
+impl BarBuilder<Trade> for VolumeBars {
+    fn push(&mut self, trade: Trade) -> Vec<OHLCV> {
+        if !is_valid(&trade) {
+            return Vec::new();
+        }
+        let bar = match &mut self.current {
+            Some(bar) => {
+                extend_bar(bar, &trade);
+                *bar
+            }
+            None => *self.current.insert(open_bar(&trade)),
+        };
+
+        if bar.volume >= self.volume {
+            self.current = None;
+            vec![bar]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn current(&self) -> Option<OHLCV> {
+        self.current
+    }
+
+    fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+/// Bars of an equal number of trades.
+///
+/// Bars are labelled with the timestamp of their first trade.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TickBars {
+    ticks: usize,
+    count: usize,
+    current: Option<OHLCV>,
+}
+
+impl TickBars {
+    /// Creates bars of `ticks` trades.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `ticks` is 0.
+    pub fn new(ticks: usize) -> IndicatorResult<Self> {
+        if ticks == 0 {
+            return Err(IndicatorError::InvalidParameter(
+                "ticks must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self {
+            ticks,
+            count: 0,
+            current: None,
+        })
+    }
+
+    /// Returns the number of trades per bar.
+    #[must_use]
+    pub const fn ticks(&self) -> usize {
+        self.ticks
+    }
+}
+
+impl BarBuilder<Trade> for TickBars {
+    fn push(&mut self, trade: Trade) -> Vec<OHLCV> {
+        if !is_valid(&trade) {
+            return Vec::new();
+        }
+        let bar = match &mut self.current {
+            Some(bar) => {
+                extend_bar(bar, &trade);
+                *bar
+            }
+            None => *self.current.insert(open_bar(&trade)),
+        };
+
+        self.count += 1;
+        if self.count == self.ticks {
+            self.count = 0;
+            self.current = None;
+            vec![bar]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn current(&self) -> Option<OHLCV> {
+        self.current
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.current = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Atr;
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-10
+    }
+
+    fn candle(i: i64, close: f64) -> OHLCV {
+        OHLCV::new(i, close, close + 0.25, close - 0.25, close, 1.0)
+    }
+
+    fn trades(prices: &[f64]) -> Vec<Trade> {
+        prices
+            .iter()
+            .zip(0..)
+            .map(|(&price, i)| Trade::new(i, price, 1.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_heikin_ashi() {
+        let candles = [
+            OHLCV::new(0, 10.0, 12.0, 9.0, 11.0, 5.0),
+            OHLCV::new(1, 11.0, 13.0, 10.0, 12.0, 6.0),
+            OHLCV::new(2, 12.0, 12.5, 8.0, 9.0, 7.0),
+        ];
+        let ha = HeikinAshi::new().calculate(&candles).unwrap();
+
+        assert!(approx_eq(ha[0].open, 10.5));
+        assert!(approx_eq(ha[0].close, 10.5));
+        assert!(approx_eq(ha[1].open, 10.5));
+        assert!(approx_eq(ha[1].close, 11.5));
+        assert!(approx_eq(ha[1].high, 13.0));
+        assert!(approx_eq(ha[1].low, 10.0));
+        assert!(approx_eq(ha[2].open, 11.0));
+        assert!(approx_eq(ha[2].close, 10.375));
+        assert!(approx_eq(ha[2].high, 12.5));
+        assert_eq!(
+            (ha[2].timestamp, ha[2].volume.to_bits()),
+            (2, 7.0_f64.to_bits())
+        );
+    }
+
+    #[test]
+    fn test_heikin_ashi_stream_matches_batch() {
+        let candles: Vec<OHLCV> = (0..40)
+            .map(|i| candle(i64::from(i), 100.0 + (f64::from(i) * 0.4).sin() * 3.0))
+            .collect();
+        let expected = HeikinAshi::new().calculate(&candles).unwrap();
+
+        let mut stream = HeikinAshiStream::new();
+        assert_eq!(stream.init(&candles).unwrap(), expected);
+
+        // Amending the live candle
+        let mut live = HeikinAshiStream::new();
+        for (c, e) in candles.iter().zip(&expected) {
+            let spike = OHLCV {
+                close: c.close + 5.0,
+                high: c.high + 5.0,
+                ..*c
+            };
+            assert_eq!(live.peek(spike), live.next(spike));
+            assert_eq!(live.update(*c), Some(*e));
+        }
+    }
+
+    #[test]
+    fn test_renko_fixed() {
+        let closes = [100.0, 100.6, 101.2, 103.1, 102.4, 100.9, 99.8, 99.9];
+        let candles: Vec<OHLCV> = closes.iter().zip(0..).map(|(&c, i)| candle(i, c)).collect();
+        let bricks = Renko::new(1.0).unwrap().build(&candles);
+
+        let shape: Vec<(i64, f64, f64)> = bricks
+            .iter()
+            .map(|b| (b.timestamp, b.open, b.close))
+            .collect();
+        assert_eq!(
+            shape,
+            [
+                (2, 100.0, 101.0),
+                (3, 101.0, 102.0),
+                (3, 102.0, 103.0),
+                // Reversal needs two boxes: from 102.0 (bottom of last brick)
+                (5, 102.0, 101.0),
+                (6, 101.0, 100.0),
+            ]
+        );
+        // Volume since the previous brick goes to the first brick of a candle
+        assert!(approx_eq(bricks[0].volume, 3.0));
+        assert!(approx_eq(bricks[1].volume, 1.0));
+        assert!(approx_eq(bricks[2].volume, 0.0));
+        assert!(approx_eq(bricks[3].volume, 2.0));
+        assert!(bricks
+            .iter()
+            .all(|b| approx_eq(b.high, b.open.max(b.close))));
+    }
+
+    #[test]
+    fn test_renko_atr() {
+        let candles: Vec<OHLCV> = (0..60)
+            .map(|i| candle(i64::from(i), 100.0 + f64::from(i) * 0.4))
+            .collect();
+        let mut renko = Renko::atr(14).unwrap();
+        let streamed: Vec<OHLCV> = candles.iter().flat_map(|&c| renko.push(c)).collect();
+        assert_eq!(streamed, renko.build(&candles));
+
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let atr = Atr::new(14)
+            .unwrap()
+            .calculate(&(&highs, &lows, &closes))
+            .unwrap();
+
+        // No bricks while ATR warms up; then each brick is one ATR tall
+        assert!(streamed.iter().all(|b| b.timestamp >= 13));
+        for brick in &streamed {
+            let size = atr[usize::try_from(brick.timestamp).unwrap()];
+            assert!(approx_eq((brick.close - brick.open).abs(), size));
+        }
+        assert!(!streamed.is_empty());
+        assert!(Renko::new(0.0).is_err() && Renko::atr(0).is_err());
+    }
+
+    #[test]
+    fn test_range_bars() {
+        let mut bars = RangeBars::new(1.0).unwrap();
+        let prices = [100.0, 100.4, 99.8, 100.7, 101.0, 103.5, 103.2];
+        let closed: Vec<OHLCV> = trades(&prices)
+            .into_iter()
+            .flat_map(|t| bars.push(t))
+            .collect();
+
+        let shape: Vec<(f64, f64, f64, f64)> = closed
+            .iter()
+            .map(|b| (b.open, b.high, b.low, b.close))
+            .collect();
+        assert_eq!(
+            shape,
+            [
+                (100.0, 100.8, 99.8, 100.8),
+                // The gap to 103.5 passes through two full ranges
+                (100.8, 101.8, 100.8, 101.8),
+                (101.8, 102.8, 101.8, 102.8),
+            ]
+        );
+        for b in &closed {
+            assert!((b.high - b.low - 1.0).abs() < 1e-9);
+        }
+
+        let current = bars.current().unwrap();
+        assert!(approx_eq(current.open, 102.8));
+        assert!(approx_eq(current.high, 103.5));
+        assert!(approx_eq(current.close, 103.2));
+        // The gapping trade's volume lands in the bar it finally reached
+        assert!(approx_eq(current.volume, 2.0));
+    }
+
+    #[test]
+    fn test_tiny_sizes_and_large_jumps_terminate() {
+        // Boxes and ranges below the price's precision never move it
+        let mut renko = Renko::new(1e-15).unwrap();
+        assert!(renko.push(candle(0, 100.0)).is_empty());
+        assert!(renko.push(candle(1, 100.5)).is_empty());
+        let mut range = RangeBars::new(1e-15).unwrap();
+        let closed: Vec<OHLCV> = trades(&[100.0, 100.5])
+            .into_iter()
+            .flat_map(|t| range.push(t))
+            .collect();
+        assert!(closed.is_empty());
+        assert!(approx_eq(range.current().unwrap().high, 100.5));
+
+        // A jump of a million boxes emits the cap and carries the rest over
+        let mut renko = Renko::new(1.0).unwrap();
+        renko.push(candle(0, 0.0));
+        assert_eq!(renko.push(candle(1, 1e6)).len(), MAX_BARS_PER_INPUT);
+        assert_eq!(renko.push(candle(2, 1e6)).len(), MAX_BARS_PER_INPUT);
+        let mut range = RangeBars::new(1.0).unwrap();
+        range.push(Trade::new(0, 0.0, 1.0));
+        assert_eq!(
+            range.push(Trade::new(1, 1e6, 1.0)).len(),
+            MAX_BARS_PER_INPUT
+        );
+        let current = range.current().unwrap();
+        assert!(approx_eq(current.low, 1e4) && approx_eq(current.high, 1e6));
+    }
+
+    #[test]
+    fn test_volume_and_tick_bars() {
+        let feed = [
+            Trade::new(0, 10.0, 3.0),
+            Trade::new(1, 11.0, 4.0),
+            Trade::new(2, 9.0, 5.0),
+            Trade::new(3, 10.0, 1.0),
+            Trade::new(4, f64::NAN, 1.0),
+            Trade::new(5, 12.0, 20.0),
+            Trade::new(6, 12.5, 1.0),
+        ];
+
+        let volume = VolumeBars::new(10.0).unwrap().build(&feed);
+        assert_eq!(volume.len(), 2);
+        assert_eq!(volume[0], OHLCV::new(0, 10.0, 11.0, 9.0, 9.0, 12.0));
+        assert_eq!(volume[1], OHLCV::new(3, 10.0, 12.0, 10.0, 12.0, 21.0));
+
+        let mut ticks = TickBars::new(3).unwrap();
+        let closed: Vec<OHLCV> = feed.iter().flat_map(|&t| ticks.push(t)).collect();
+        assert_eq!(
+            closed,
+            [
+                OHLCV::new(0, 10.0, 11.0, 9.0, 9.0, 12.0),
+                OHLCV::new(3, 10.0, 12.5, 10.0, 12.5, 22.0)
+            ]
+        );
+        assert!(ticks.current().is_none());
+
+        ticks.reset();
+        assert!(ticks.push(feed[0]).is_empty());
+        assert_eq!(
+            ticks.current(),
+            Some(OHLCV::new(0, 10.0, 10.0, 10.0, 10.0, 3.0))
+        );
+        assert!(VolumeBars::new(-1.0).is_err() && TickBars::new(0).is_err());
+    }
+}
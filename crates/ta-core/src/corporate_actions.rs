@@ -0,0 +1,552 @@
+//! Split and dividend adjustment of OHLCV histories.
+//!
+//! Raw equity prices jump at every split and drop by the cash amount at
+//! every ex-dividend date. Indicators read those jumps as real moves: `Atr`
+//! and `BBands` widen, pivot levels land far from price. Back-adjusting
+//! rescales every bar before an event so the series is continuous in
+//! today's share basis, leaving the most recent prices untouched.
+//!
+//! An [`Adjuster`] holds the [`CorporateAction`]s and an [`AdjustMode`]:
+//! - [`AdjustMode::PriceOnly`] applies splits only;
+//! - [`AdjustMode::TotalReturn`] also applies dividends, scaling earlier bars
+//!   by `1 - dividend / previous close` as if the cash were reinvested.
+//!
+//! Prices are divided by the split ratio and volume multiplied by it;
+//! dividends leave volume unchanged. For live data, [`AdjustedStream`]
+//! re-bases a stream's state when an event occurs.
+//!
+//! # Example
+//! ```
+//! use ta_core::corporate_actions::{AdjustMode, Adjuster, CorporateAction};
+//! use ta_core::OHLCV;
+//!
+//! let candles = [
+//!     OHLCV::new(0, 200.0, 204.0, 198.0, 202.0, 1_000.0),
+//!     OHLCV::new(1, 101.0, 103.0, 100.0, 102.0, 2_200.0), // 2-for-1 split
+//! ];
+//!
+//! let adjuster = Adjuster::new(AdjustMode::PriceOnly)
+//!     .with_action(CorporateAction::Split { ex_date: 1, ratio: 2.0 })
+//!     .unwrap();
+//! let adjusted = adjuster.adjust(&candles).unwrap();
+//! assert_eq!(adjusted[0].close, 101.0);
+//! assert_eq!(adjusted[0].volume, 2_000.0);
+//! assert_eq!(adjusted[1], candles[1]);
+//! ```
+
+use crate::traits::{IndicatorOutput, StreamingIndicator};
+use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+
+/// A corporate action affecting every bar before its ex-date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CorporateAction {
+    /// Stock split: `ratio` new shares per old share (2.0 for a 2-for-1
+    /// split, 0.1 for a 1-for-10 reverse split)
+    Split { ex_date: i64, ratio: f64 },
+    /// Cash dividend of `amount` per share
+    Dividend { ex_date: i64, amount: f64 },
+}
+
+impl CorporateAction {
+    /// Timestamp of the first bar trading in the new basis.
+    #[must_use]
+    pub const fn ex_date(&self) -> i64 {
+        match self {
+            Self::Split { ex_date, .. } | Self::Dividend { ex_date, .. } => *ex_date,
+        }
+    }
+
+    fn validate(&self) -> IndicatorResult<()> {
+        match *self {
+            Self::Split { ratio, .. } if !(ratio.is_finite() && ratio > 0.0) => {
+                Err(IndicatorError::InvalidParameter(format!(
+                    "split ratio must be positive, got {ratio}"
+                )))
+            }
+            Self::Dividend { amount, .. } if !(amount.is_finite() && amount >= 0.0) => {
+                Err(IndicatorError::InvalidParameter(format!(
+                    "dividend amount must be non-negative, got {amount}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Which corporate actions an [`Adjuster`] applies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AdjustMode {
+    /// Splits only: prices stay comparable to quoted prices
+    #[default]
+    PriceOnly,
+    /// Splits and dividends: returns include reinvested dividends
+    TotalReturn,
+}
+
+/// Multipliers applied to one bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdjustmentFactor {
+    /// Multiplier of open, high, low and close
+    pub price: f64,
+    /// Multiplier of volume
+    pub volume: f64,
+}
+
+impl AdjustmentFactor {
+    /// Leaves the bar unchanged.
+    pub const IDENTITY: Self = Self {
+        price: 1.0,
+        volume: 1.0,
+    };
+
+    /// Applies the factor to a candle.
+    #[must_use]
+    pub fn apply(&self, candle: OHLCV) -> OHLCV {
+        OHLCV {
+            timestamp: candle.timestamp,
+            open: candle.open * self.price,
+            high: candle.high * self.price,
+            low: candle.low * self.price,
+            close: candle.close * self.price,
+            volume: candle.volume * self.volume,
+        }
+    }
+}
+
+/// Back-adjusts OHLCV series for splits and dividends.
+///
+/// Input candles must be in ascending timestamp order (see
+/// [`sort_dedupe`](crate::validate::sort_dedupe)).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Adjuster {
+    mode: AdjustMode,
+    /// Sorted by ex-date
+    actions: Vec<CorporateAction>,
+}
+
+impl Adjuster {
+    /// Creates an adjuster with no actions.
+    #[must_use]
+    pub const fn new(mode: AdjustMode) -> Self {
+        Self {
+            mode,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Adds an action.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` for a split ratio that is not positive or
+    /// a negative dividend.
+    pub fn with_action(mut self, action: CorporateAction) -> IndicatorResult<Self> {
+        self.push(action)?;
+        Ok(self)
+    }
+
+    /// Adds an action.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` for a split ratio that is not positive or
+    /// a negative dividend.
+    pub fn push(&mut self, action: CorporateAction) -> IndicatorResult<()> {
+        action.validate()?;
+        let at = self
+            .actions
+            .partition_point(|a| a.ex_date() <= action.ex_date());
+        self.actions.insert(at, action);
+        Ok(())
+    }
+
+    /// Adjustment mode.
+    #[must_use]
+    pub const fn mode(&self) -> AdjustMode {
+        self.mode
+    }
+
+    /// Actions, ordered by ex-date.
+    #[must_use]
+    pub fn actions(&self) -> &[CorporateAction] {
+        &self.actions
+    }
+
+    /// Cumulative factor of every bar: the product over the actions whose
+    /// ex-date is after the bar.
+    ///
+    /// A dividend factor uses the raw close of the last bar before its
+    /// ex-date; a dividend with no earlier bar has no effect.
+    ///
+    /// # Errors
+    /// Returns `NonFiniteInput` if that close is not finite, and
+    /// `InvalidData` if the dividend is not below it.
+    pub fn factors(&self, candles: &[OHLCV]) -> IndicatorResult<Vec<AdjustmentFactor>> {
+        let mut factor = AdjustmentFactor::IDENTITY;
+        let mut factors = vec![AdjustmentFactor::IDENTITY; candles.len()];
+        let mut pending = self.actions.iter().rev().peekable();
+
+        for (index, candle) in candles.iter().enumerate().rev() {
+            while let Some(action) = pending.next_if(|a| a.ex_date() > candle.timestamp) {
+                match *action {
+                    CorporateAction::Split { ratio, .. } => {
+                        factor.price /= ratio;
+                        factor.volume *= ratio;
+                    }
+                    CorporateAction::Dividend { amount, .. } => {
+                        if self.mode == AdjustMode::TotalReturn {
+                            factor.price *= dividend_factor(amount, candle.close, index)?;
+                        }
+                    }
+                }
+            }
+            factors[index] = factor;
+        }
+        Ok(factors)
+    }
+
+    /// Back-adjusted copy of `candles`.
+    ///
+    /// # Errors
+    /// See [`factors`](Self::factors).
+    pub fn adjust(&self, candles: &[OHLCV]) -> IndicatorResult<Vec<OHLCV>> {
+        let factors = self.factors(candles)?;
+        Ok(candles
+            .iter()
+            .zip(&factors)
+            .map(|(&candle, factor)| factor.apply(candle))
+            .collect())
+    }
+}
+
+/// Price multiplier of a dividend paid after a bar closing at `close`.
+fn dividend_factor(amount: f64, close: f64, index: usize) -> IndicatorResult<f64> {
+    if !close.is_finite() {
+        return Err(IndicatorError::NonFiniteInput { index });
+    }
+    if amount >= close {
+        return Err(IndicatorError::InvalidData {
+            index,
+            reason: format!("dividend {amount} is not below the previous close {close}"),
+        });
+    }
+    Ok(1.0 - amount / close)
+}
+
+/// A candle stream (such as a [`Sourced`](crate::Sourced) single-series
+/// stream or a VWAP) fed with raw candles, that re-bases its state on
+/// corporate actions.
+///
+/// Raw candles are kept; [`apply`](Self::apply) adds an action, then resets
+/// the inner stream and replays the adjusted history, so the state matches
+/// a stream run over back-adjusted data from the start. Call it once the
+/// action's ex-date bar has arrived: that bar and later ones already trade
+/// in the new basis and pass through unchanged, while an earlier bar fed
+/// after the action would miss its factor, so `apply` rejects actions whose
+/// ex-date is after the last candle.
+///
+/// Replaying costs O(n) in the history length; every other call is that of
+/// the inner stream. The history grows by one candle per bar and is never
+/// trimmed; to bound it, re-create the stream and [`init`] it with the
+/// recent candles when convenient.
+///
+/// [`init`]: StreamingIndicator::init
+#[derive(Debug, Clone)]
+pub struct AdjustedStream<S> {
+    inner: S,
+    adjuster: Adjuster,
+    history: Vec<OHLCV>,
+}
+
+impl<S> AdjustedStream<S> {
+    /// Wraps `inner`, adjusting in `mode`.
+    #[must_use]
+    pub const fn new(inner: S, mode: AdjustMode) -> Self {
+        Self {
+            inner,
+            adjuster: Adjuster::new(mode),
+            history: Vec::new(),
+        }
+    }
+
+    /// Returns the wrapped stream.
+    #[must_use]
+    pub const fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Actions applied so far.
+    #[must_use]
+    pub const fn adjuster(&self) -> &Adjuster {
+        &self.adjuster
+    }
+
+    /// Raw candles received so far.
+    #[must_use]
+    pub fn history(&self) -> &[OHLCV] {
+        &self.history
+    }
+
+    /// Back-adjusted candles received so far.
+    ///
+    /// # Errors
+    /// See [`Adjuster::factors`].
+    pub fn adjusted_history(&self) -> IndicatorResult<Vec<OHLCV>> {
+        self.adjuster.adjust(&self.history)
+    }
+
+    /// Adds `action` and re-bases the inner stream, returning its outputs
+    /// over the adjusted history.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the ex-date is after the last candle
+    /// received, and the errors of [`Adjuster::push`] and
+    /// [`Adjuster::factors`]; the action is not kept and the stream is
+    /// unchanged.
+    pub fn apply<O>(&mut self, action: CorporateAction) -> IndicatorResult<Vec<O>>
+    where
+        S: StreamingIndicator<OHLCV, O>,
+    {
+        let ex_date = action.ex_date();
+        if self
+            .history
+            .last()
+            .is_none_or(|last| last.timestamp < ex_date)
+        {
+            return Err(IndicatorError::InvalidParameter(format!(
+                "ex-date {ex_date} has not been reached: apply the action once its bar has arrived"
+            )));
+        }
+        let mut adjuster = self.adjuster.clone();
+        adjuster.push(action)?;
+        let history = adjuster.adjust(&self.history)?;
+
+        self.adjuster = adjuster;
+        self.inner.reset();
+        self.inner.init(&history)
+    }
+}
+
+impl<S, O> StreamingIndicator<OHLCV, O> for AdjustedStream<S>
+where
+    S: StreamingIndicator<OHLCV, O>,
+    O: IndicatorOutput,
+{
+    fn init(&mut self, data: &[OHLCV]) -> IndicatorResult<Vec<O>> {
+        let adjusted = self.adjuster.adjust(data)?;
+        self.history = data.to_vec();
+        self.inner.reset();
+        self.inner.init(&adjusted)
+    }
+
+    fn next(&mut self, candle: OHLCV) -> Option<O> {
+        self.history.push(candle);
+        self.inner.next(candle)
+    }
+
+    fn update(&mut self, candle: OHLCV) -> Option<O> {
+        match self.history.last_mut() {
+            Some(last) => *last = candle,
+            None => self.history.push(candle),
+        }
+        self.inner.update(candle)
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<O> {
+        self.inner.peek(candle)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.history.clear();
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Ema, EmaStream};
+    use crate::source::{PriceSource, Sourced};
+    use crate::traits::Indicator;
+
+    fn daily(closes: &[f64]) -> Vec<OHLCV> {
+        closes
+            .iter()
+            .zip(0..)
+            .map(|(&close, day)| {
+                OHLCV::new(day, close - 0.5, close + 1.0, close - 1.0, close, 1_000.0)
+            })
+            .collect()
+    }
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a.is_nan() && b.is_nan()) || (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_split_scales_prices_and_volume() {
+        let candles = daily(&[100.0, 102.0, 51.0, 52.0]);
+        let adjuster = Adjuster::new(AdjustMode::PriceOnly)
+            .with_action(CorporateAction::Split {
+                ex_date: 2,
+                ratio: 2.0,
+            })
+            .unwrap();
+
+        let result = adjuster.adjust(&candles).unwrap();
+        assert!(approx_eq(result[0].close, 50.0));
+        assert!(approx_eq(result[1].high, 51.5));
+        assert!(approx_eq(result[1].volume, 2_000.0));
+        assert_eq!(result[2..], candles[2..]);
+    }
+
+    #[test]
+    fn test_dividends_only_in_total_return_mode() {
+        let candles = daily(&[100.0, 98.0, 99.0]);
+        let dividend = CorporateAction::Dividend {
+            ex_date: 1,
+            amount: 2.0,
+        };
+
+        let price_only = Adjuster::new(AdjustMode::PriceOnly)
+            .with_action(dividend)
+            .unwrap();
+        assert_eq!(price_only.adjust(&candles).unwrap(), candles);
+
+        let total_return = Adjuster::new(AdjustMode::TotalReturn)
+            .with_action(dividend)
+            .unwrap();
+        let factors = total_return.factors(&candles).unwrap();
+        assert!(approx_eq(factors[0].price, 0.98));
+        assert!(approx_eq(factors[0].volume, 1.0));
+        assert_eq!(factors[1], AdjustmentFactor::IDENTITY);
+
+        let adjusted = total_return.adjust(&candles).unwrap();
+        assert!(approx_eq(adjusted[0].close, 98.0));
+    }
+
+    #[test]
+    fn test_actions_compound() {
+        let candles = daily(&[100.0, 50.0, 49.0, 49.5]);
+        let adjuster = Adjuster::new(AdjustMode::TotalReturn)
+            .with_action(CorporateAction::Dividend {
+                ex_date: 2,
+                amount: 0.5,
+            })
+            .unwrap()
+            .with_action(CorporateAction::Split {
+                ex_date: 1,
+                ratio: 2.0,
+            })
+            .unwrap();
+        assert_eq!(adjuster.actions()[0].ex_date(), 1);
+
+        let factors = adjuster.factors(&candles).unwrap();
+        assert!(approx_eq(factors[1].price, 0.99));
+        assert!(approx_eq(factors[0].price, 0.495));
+        assert!(approx_eq(factors[0].volume, 2.0));
+        assert_eq!(factors[3], AdjustmentFactor::IDENTITY);
+
+        // Events before the first bar change nothing
+        let early = Adjuster::new(AdjustMode::TotalReturn)
+            .with_action(CorporateAction::Dividend {
+                ex_date: 0,
+                amount: 1.0,
+            })
+            .unwrap();
+        assert_eq!(early.adjust(&candles).unwrap(), candles);
+    }
+
+    #[test]
+    fn test_invalid_actions() {
+        let adjuster = Adjuster::new(AdjustMode::TotalReturn);
+        for action in [
+            CorporateAction::Split {
+                ex_date: 1,
+                ratio: 0.0,
+            },
+            CorporateAction::Split {
+                ex_date: 1,
+                ratio: f64::NAN,
+            },
+            CorporateAction::Dividend {
+                ex_date: 1,
+                amount: -1.0,
+            },
+        ] {
+            assert!(matches!(
+                adjuster.clone().with_action(action),
+                Err(IndicatorError::InvalidParameter(_))
+            ));
+        }
+
+        let candles = daily(&[1.0, 1.0]);
+        let too_large = adjuster
+            .with_action(CorporateAction::Dividend {
+                ex_date: 1,
+                amount: 1.0,
+            })
+            .unwrap();
+        assert!(matches!(
+            too_large.adjust(&candles),
+            Err(IndicatorError::InvalidData { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_adjusted_stream_rebases_on_split() {
+        let raw = daily(&[
+            100.0, 101.0, 99.0, 102.0, 104.0, 103.0, 52.0, 51.5, 53.0, 52.5, 54.0,
+        ]);
+        let split = CorporateAction::Split {
+            ex_date: 6,
+            ratio: 2.0,
+        };
+        let adjusted = Adjuster::new(AdjustMode::PriceOnly)
+            .with_action(split)
+            .unwrap()
+            .adjust(&raw)
+            .unwrap();
+        let expected = Sourced::new(Ema::new(3).unwrap(), PriceSource::Close)
+            .calculate(adjusted.as_slice())
+            .unwrap();
+
+        let ema = Sourced::new(EmaStream::new(3).unwrap(), PriceSource::Close);
+        let mut stream = AdjustedStream::new(ema, AdjustMode::PriceOnly);
+        stream.init(&raw[..5]).unwrap();
+        // Bars before the ex-date would arrive unadjusted after the split
+        assert!(matches!(
+            stream.apply::<f64>(split),
+            Err(IndicatorError::InvalidParameter(_))
+        ));
+        assert!(stream.adjuster().actions().is_empty());
+
+        stream.next(raw[5]);
+        stream.next(raw[6]);
+        let rebased = stream.apply(split).unwrap();
+        assert_eq!(rebased.len(), 7);
+        for (a, b) in rebased.iter().zip(&expected) {
+            assert!(approx_eq(*a, *b));
+        }
+        for (i, &candle) in raw.iter().enumerate().skip(7) {
+            assert!(approx_eq(stream.next(candle).unwrap(), expected[i]));
+        }
+        assert_eq!(stream.history(), raw.as_slice());
+        assert_eq!(stream.adjusted_history().unwrap(), adjusted);
+
+        // Rejected actions leave the stream as it was
+        assert!(stream
+            .apply::<f64>(CorporateAction::Split {
+                ex_date: 3,
+                ratio: -2.0,
+            })
+            .is_err());
+        assert_eq!(stream.adjuster().actions(), [split]);
+    }
+}
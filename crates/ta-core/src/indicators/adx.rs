@@ -32,8 +32,11 @@
 //! # Default Period
 //! - 14 periods (standard)
 
-use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::{IndicatorError, IndicatorResult};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::{check_ring, ensure};
 
 /// ADX output structure containing ADX, +DI, and -DI values.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -58,6 +61,12 @@ impl AdxOutput {
     }
 }
 
+impl IndicatorOutput for AdxOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
 /// ADX calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Adx {
@@ -125,11 +134,11 @@ impl Indicator<&AdxInput<'_>, Vec<AdxOutput>> for Adx {
         let (highs, lows, closes) = *data;
         let len = highs.len();
 
-        if lows.len() != len || closes.len() != len {
-            return Err(IndicatorError::InvalidParameter(
-                "highs, lows, and closes must have the same length".to_string(),
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
 
         let mut result = vec![AdxOutput::nan(); len];
 
@@ -214,6 +223,21 @@ impl Indicator<&AdxInput<'_>, Vec<AdxOutput>> for Adx {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        // DI needs `period` bars, ADX smooths `period` DX values on top
+        2 * self.period - 1
+    }
+}
+
+impl Adx {
+    /// Calculates ADX from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<AdxOutput>> {
+        self.calculate(&(data.high(), data.low(), data.close()))
+    }
 }
 
 /// Calculate +DI, -DI, and DX from smoothed values.
@@ -244,7 +268,8 @@ fn calculate_di_and_dx(
 pub type AdxBar = (f64, f64, f64);
 
 /// Streaming ADX calculator for real-time updates.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdxStream {
     period: usize,
     count: usize,
@@ -267,6 +292,48 @@ pub struct AdxStream {
     // ADX state
     current_adx: Option<f64>,
     adx_initialized: bool,
+    // State before the most recent `next`, restored by `update`
+    checkpoint: AdxCheckpoint,
+}
+
+/// Scalar state of an [`AdxStream`] captured before each bar.
+///
+/// DX values are only ever written at `dx_count`, so restoring the count is
+/// enough to roll the DX buffer back as well.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct AdxCheckpoint {
+    prev_high: f64,
+    prev_low: f64,
+    prev_close: f64,
+    smoothed_tr: f64,
+    smoothed_plus_dm: f64,
+    smoothed_minus_dm: f64,
+    initial_tr_sum: f64,
+    initial_plus_dm_sum: f64,
+    initial_minus_dm_sum: f64,
+    dx_count: usize,
+    current_adx: Option<f64>,
+    adx_initialized: bool,
+}
+
+impl AdxCheckpoint {
+    const fn empty() -> Self {
+        Self {
+            prev_high: f64::NAN,
+            prev_low: f64::NAN,
+            prev_close: f64::NAN,
+            smoothed_tr: 0.0,
+            smoothed_plus_dm: 0.0,
+            smoothed_minus_dm: 0.0,
+            initial_tr_sum: 0.0,
+            initial_plus_dm_sum: 0.0,
+            initial_minus_dm_sum: 0.0,
+            dx_count: 0,
+            current_adx: None,
+            adx_initialized: false,
+        }
+    }
 }
 
 impl AdxStream {
@@ -297,6 +364,7 @@ impl AdxStream {
             dx_count: 0,
             current_adx: None,
             adx_initialized: false,
+            checkpoint: AdxCheckpoint::empty(),
         })
     }
 
@@ -324,6 +392,49 @@ impl AdxStream {
             None
         }
     }
+
+    fn save_checkpoint(&mut self) {
+        self.checkpoint = AdxCheckpoint {
+            prev_high: self.prev_high,
+            prev_low: self.prev_low,
+            prev_close: self.prev_close,
+            smoothed_tr: self.smoothed_tr,
+            smoothed_plus_dm: self.smoothed_plus_dm,
+            smoothed_minus_dm: self.smoothed_minus_dm,
+            initial_tr_sum: self.initial_tr_sum,
+            initial_plus_dm_sum: self.initial_plus_dm_sum,
+            initial_minus_dm_sum: self.initial_minus_dm_sum,
+            dx_count: self.dx_count,
+            current_adx: self.current_adx,
+            adx_initialized: self.adx_initialized,
+        };
+    }
+
+    fn restore_checkpoint(&mut self) {
+        let cp = self.checkpoint;
+        self.prev_high = cp.prev_high;
+        self.prev_low = cp.prev_low;
+        self.prev_close = cp.prev_close;
+        self.smoothed_tr = cp.smoothed_tr;
+        self.smoothed_plus_dm = cp.smoothed_plus_dm;
+        self.smoothed_minus_dm = cp.smoothed_minus_dm;
+        self.initial_tr_sum = cp.initial_tr_sum;
+        self.initial_plus_dm_sum = cp.initial_plus_dm_sum;
+        self.initial_minus_dm_sum = cp.initial_minus_dm_sum;
+        self.dx_count = cp.dx_count;
+        self.current_adx = cp.current_adx;
+        self.adx_initialized = cp.adx_initialized;
+    }
+
+    /// Checks the DX buffer and counters of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        check_ring(self.period, self.dx_buffer.len(), self.dx_head)?;
+        ensure(
+            self.dx_count <= self.period && self.checkpoint.dx_count <= self.period,
+            "DX count must not exceed the period",
+        )
+    }
 }
 
 impl StreamingIndicator<AdxBar, AdxOutput> for AdxStream {
@@ -339,6 +450,7 @@ impl StreamingIndicator<AdxBar, AdxOutput> for AdxStream {
 
     fn next(&mut self, bar: AdxBar) -> Option<AdxOutput> {
         let (high, low, close) = bar;
+        self.save_checkpoint();
         self.count += 1;
 
         // First bar: just store values
@@ -437,6 +549,71 @@ impl StreamingIndicator<AdxBar, AdxOutput> for AdxStream {
         })
     }
 
+    fn update(&mut self, bar: AdxBar) -> Option<AdxOutput> {
+        if self.count == 0 {
+            return self.next(bar);
+        }
+
+        self.restore_checkpoint();
+        self.count -= 1;
+        self.next(bar)
+    }
+
+    fn peek(&self, bar: AdxBar) -> Option<AdxOutput> {
+        let (high, low, _) = bar;
+        let count = self.count + 1;
+        if count == 1 {
+            return None;
+        }
+
+        let tr = Adx::true_range(high, low, self.prev_close);
+        let (plus_dm, minus_dm) =
+            Adx::directional_movement(high, low, self.prev_high, self.prev_low);
+        let n = self.period as f64;
+
+        // Accumulation phase: only the seeding bar produces DI values
+        if count <= self.period + 1 {
+            if count < self.period + 1 {
+                return None;
+            }
+            let (plus_di, minus_di, _) = calculate_di_and_dx(
+                self.initial_plus_dm_sum + plus_dm,
+                self.initial_minus_dm_sum + minus_dm,
+                self.initial_tr_sum + tr,
+            );
+            return Some(AdxOutput {
+                adx: f64::NAN,
+                plus_di,
+                minus_di,
+            });
+        }
+
+        let (plus_di, minus_di, dx) = calculate_di_and_dx(
+            self.smoothed_plus_dm - (self.smoothed_plus_dm / n) + plus_dm,
+            self.smoothed_minus_dm - (self.smoothed_minus_dm / n) + minus_dm,
+            self.smoothed_tr - (self.smoothed_tr / n) + tr,
+        );
+
+        let adx = if self.adx_initialized {
+            let prev_adx = self.current_adx.unwrap_or(0.0);
+            ((prev_adx * (n - 1.0)) + dx) / n
+        } else if self.dx_count < self.period && self.dx_count + 1 == self.period {
+            self.dx_buffer[..self.dx_count]
+                .iter()
+                .chain([&dx])
+                .sum::<f64>()
+                / n
+        } else {
+            f64::NAN
+        };
+
+        Some(AdxOutput {
+            adx,
+            plus_di,
+            minus_di,
+        })
+    }
+
     fn reset(&mut self) {
         self.count = 0;
         self.prev_high = f64::NAN;
@@ -453,11 +630,16 @@ impl StreamingIndicator<AdxBar, AdxOutput> for AdxStream {
         self.dx_count = 0;
         self.current_adx = None;
         self.adx_initialized = false;
+        self.checkpoint = AdxCheckpoint::empty();
     }
 
     fn is_ready(&self) -> bool {
         self.adx_initialized
     }
+
+    fn lookback(&self) -> usize {
+        2 * self.period - 1
+    }
 }
 
 #[cfg(test)]
@@ -573,6 +755,38 @@ mod tests {
         assert!(!result.minus_di.is_nan());
     }
 
+    #[test]
+    fn test_adx_stream_update_matches_next() {
+        let bars: Vec<AdxBar> = (0..30)
+            .map(|i| {
+                let base = 100.0 + (f64::from(i) * 0.7).sin() * 5.0 + f64::from(i) * 0.2;
+                (base + 1.0, base - 1.0, base + 0.3)
+            })
+            .collect();
+        let mut live = AdxStream::new(5).unwrap();
+        let mut reference = AdxStream::new(5).unwrap();
+
+        for &(high, low, close) in &bars {
+            live.next((high - 0.5, low + 0.5, close));
+            live.update((high + 2.0, low - 2.0, low - 1.0));
+            let amended = live.update((high, low, close));
+            let expected = reference.next((high, low, close));
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => {
+                    assert_eq!(a.adx.is_nan(), e.adx.is_nan());
+                    if !e.adx.is_nan() {
+                        assert_approx_eq(a.adx, e.adx);
+                    }
+                    assert_approx_eq(a.plus_di, e.plus_di);
+                    assert_approx_eq(a.minus_di, e.minus_di);
+                }
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_adx_empty_data() {
         let adx = Adx::new(14).unwrap();
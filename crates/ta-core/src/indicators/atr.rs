@@ -26,7 +26,10 @@
 //! ```
 
 use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::{IndicatorError, IndicatorResult};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
 
 /// ATR calculator for batch operations.
 #[derive(Debug, Clone)]
@@ -81,11 +84,11 @@ impl Indicator<&AtrInput<'_>, Vec<f64>> for Atr {
         let len = highs.len();
 
         // Validate input lengths match
-        if lows.len() != len || closes.len() != len {
-            return Err(IndicatorError::InvalidParameter(
-                "highs, lows, and closes must have the same length".to_string(),
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
 
         let mut result = vec![f64::NAN; len];
 
@@ -122,6 +125,20 @@ impl Indicator<&AtrInput<'_>, Vec<f64>> for Atr {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
+}
+
+impl Atr {
+    /// Calculates ATR from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<f64>> {
+        self.calculate(&(data.high(), data.low(), data.close()))
+    }
 }
 
 /// Streaming ATR calculator for real-time O(1) updates.
@@ -129,7 +146,8 @@ impl Indicator<&AtrInput<'_>, Vec<f64>> for Atr {
 /// After initialization, each `next()` call is O(1) as it only needs:
 /// - Previous ATR value
 /// - Previous close (for True Range calculation)
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AtrStream {
     period: usize,
     prev_close: Option<f64>,
@@ -137,6 +155,8 @@ pub struct AtrStream {
     tr_buffer: Vec<f64>,
     count: usize,
     initialized: bool,
+    // (prev_close, prev_atr, initialized) before the most recent `next`, restored by `update`
+    prev_state: (Option<f64>, Option<f64>, bool),
 }
 
 impl AtrStream {
@@ -154,6 +174,7 @@ impl AtrStream {
             tr_buffer: Vec::with_capacity(period),
             count: 0,
             initialized: false,
+            prev_state: (None, None, false),
         })
     }
 
@@ -171,7 +192,7 @@ impl AtrStream {
 
     /// Calculate True Range.
     #[inline]
-    fn true_range(high: f64, low: f64, prev_close: Option<f64>) -> f64 {
+    pub(crate) fn true_range(high: f64, low: f64, prev_close: Option<f64>) -> f64 {
         match prev_close {
             Some(pc) => {
                 let hl = high - low;
@@ -182,6 +203,21 @@ impl AtrStream {
             None => high - low,
         }
     }
+
+    /// Checks that a restored stream has an ATR whenever it is initialized.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(self.period > 0, "period must be greater than 0")?;
+        ensure(
+            self.initialized == self.prev_atr.is_some()
+                && (!self.prev_state.2 || self.prev_state.1.is_some()),
+            "an initialized stream must have an ATR",
+        )?;
+        ensure(
+            self.initialized || self.tr_buffer.len() < self.period,
+            "seeding buffer must be shorter than the period",
+        )
+    }
 }
 
 /// Input for streaming: (high, low, close) tuple
@@ -200,6 +236,7 @@ impl StreamingIndicator<AtrBar, f64> for AtrStream {
 
     fn next(&mut self, value: AtrBar) -> Option<f64> {
         let (high, low, close) = value;
+        self.prev_state = (self.prev_close, self.prev_atr, self.initialized);
         self.count += 1;
 
         // Calculate True Range
@@ -215,12 +252,16 @@ impl StreamingIndicator<AtrBar, f64> for AtrStream {
                 let first_atr = self.tr_buffer.iter().sum::<f64>() / self.period as f64;
                 self.prev_atr = Some(first_atr);
                 self.initialized = true;
-                self.tr_buffer.clear(); // No longer needed
                 return Some(first_atr);
             }
             return None;
         }
 
+        // No longer needed once the seeding bar can no longer be amended
+        if !self.tr_buffer.is_empty() {
+            self.tr_buffer.clear();
+        }
+
         // Wilder's smoothing for subsequent values
         let prev = self.prev_atr.unwrap();
         let n = self.period as f64;
@@ -229,17 +270,56 @@ impl StreamingIndicator<AtrBar, f64> for AtrStream {
         Some(atr)
     }
 
+    fn update(&mut self, value: AtrBar) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        let (prev_close, prev_atr, initialized) = self.prev_state;
+        if !initialized {
+            // Last bar was still accumulating (or seeding): drop its TR
+            self.tr_buffer.pop();
+        }
+        self.prev_close = prev_close;
+        self.prev_atr = prev_atr;
+        self.initialized = initialized;
+        self.count -= 1;
+
+        self.next(value)
+    }
+
+    fn peek(&self, value: AtrBar) -> Option<f64> {
+        let (high, low, _) = value;
+        let tr = Self::true_range(high, low, self.prev_close);
+
+        if !self.initialized {
+            if self.tr_buffer.len() + 1 < self.period {
+                return None;
+            }
+            return Some(self.tr_buffer.iter().chain([&tr]).sum::<f64>() / self.period as f64);
+        }
+
+        let prev = self.prev_atr?;
+        let n = self.period as f64;
+        Some(((prev * (n - 1.0)) + tr) / n)
+    }
+
     fn reset(&mut self) {
         self.prev_close = None;
         self.prev_atr = None;
         self.tr_buffer.clear();
         self.count = 0;
         self.initialized = false;
+        self.prev_state = (None, None, false);
     }
 
     fn is_ready(&self) -> bool {
         self.initialized
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 #[cfg(test)]
@@ -396,6 +476,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_atr_stream_update_matches_next() {
+        let bars: Vec<AtrBar> = vec![
+            (48.70, 47.79, 48.16),
+            (48.72, 48.14, 48.61),
+            (48.90, 48.39, 48.75),
+            (48.87, 48.37, 48.63),
+            (48.82, 48.24, 48.74),
+            (49.05, 48.64, 49.03),
+            (49.20, 48.94, 49.07),
+            (49.35, 48.86, 49.32),
+        ];
+        let mut live = AtrStream::new(3).unwrap();
+        let mut reference = AtrStream::new(3).unwrap();
+
+        for &(high, low, close) in &bars {
+            // Bar opens narrow, spikes, then settles on its final range
+            live.next((close + 0.01, close - 0.01, close));
+            live.update((high + 1.0, low, close));
+            let amended = live.update((high, low, close));
+            let expected = reference.next((high, low, close));
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => assert_approx_eq(a, e),
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_atr_stream_reset() {
         let mut stream = AtrStream::new(5).unwrap();
@@ -413,4 +523,21 @@ mod tests {
         assert!(!stream.is_ready());
         assert!(stream.current().is_none());
     }
+
+    #[test]
+    fn test_atr_columns_match_tuple() {
+        let (highs, lows, closes) = sample_data();
+        let timestamps = vec![0; highs.len()];
+        let volumes = vec![1.0; highs.len()];
+        let columns =
+            OhlcvColumns::new(&timestamps, &closes, &highs, &lows, &closes, &volumes).unwrap();
+
+        let atr = Atr::new(14).unwrap();
+        let expected = atr.calculate(&(&highs, &lows, &closes)).unwrap();
+        let result = atr.calculate_columns(&columns).unwrap();
+        for (a, b) in result.iter().skip(13).zip(expected.iter().skip(13)) {
+            assert_approx_eq(*a, *b);
+        }
+        assert!(result[12].is_nan());
+    }
 }
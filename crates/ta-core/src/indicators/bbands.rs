@@ -24,9 +24,12 @@
 //! let result = bbands.calculate(&prices).unwrap();
 //! ```
 
-use crate::traits::{Indicator, StreamingIndicator};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::{check_ring, ensure};
+
 /// Bollinger Bands output containing all components.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BBandsOutput {
@@ -74,6 +77,12 @@ impl BBandsOutput {
     }
 }
 
+impl IndicatorOutput for BBandsOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
 /// Bollinger Bands calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct BBands {
@@ -181,6 +190,10 @@ impl Indicator<&[f64], Vec<BBandsOutput>> for BBands {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 /// Streaming Bollinger Bands calculator using Welford's online algorithm.
@@ -189,7 +202,8 @@ impl Indicator<&[f64], Vec<BBandsOutput>> for BBands {
 /// - Ring buffer for the window
 /// - Running sum for mean calculation
 /// - Running M2 (sum of squared differences) for variance
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BBandsStream {
     period: usize,
     k: f64,
@@ -198,6 +212,10 @@ pub struct BBandsStream {
     count: usize,
     sum: f64,
     sum_sq: f64, // Sum of squares for variance calculation
+    // State before the most recent `next`, restored by `update`
+    prev_sums: (f64, f64),
+    prev_slot: f64,
+    prev_count: usize,
 }
 
 impl BBandsStream {
@@ -221,6 +239,9 @@ impl BBandsStream {
             count: 0,
             sum: 0.0,
             sum_sq: 0.0,
+            prev_sums: (0.0, 0.0),
+            prev_slot: 0.0,
+            prev_count: 0,
         })
     }
 
@@ -241,14 +262,14 @@ impl BBandsStream {
         self.k
     }
 
-    /// Calculate output from current state.
+    /// Calculate output from the window sums.
     #[inline]
-    fn calculate_output(&self, price: f64) -> BBandsOutput {
+    fn calculate_output(&self, sum: f64, sum_sq: f64, price: f64) -> BBandsOutput {
         let n = self.period as f64;
-        let mean = self.sum / n;
+        let mean = sum / n;
 
         // Variance = E[X²] - E[X]² (using sum of squares method)
-        let variance = (self.sum_sq / n) - (mean * mean);
+        let variance = (sum_sq / n) - (mean * mean);
         // Handle potential floating point errors making variance slightly negative
         let stddev = if variance > 0.0 { variance.sqrt() } else { 0.0 };
 
@@ -265,6 +286,17 @@ impl BBandsStream {
 
         BBandsOutput::new(upper, mean, lower, percent_b, bandwidth)
     }
+
+    /// Checks the ring buffer and counters of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        check_ring(self.period, self.buffer.len(), self.head)?;
+        // `next` caps the count one past the period
+        ensure(
+            self.count <= self.period + 1 && self.prev_count <= self.period + 1,
+            "count must not exceed the period",
+        )
+    }
 }
 
 impl StreamingIndicator<f64, BBandsOutput> for BBandsStream {
@@ -280,6 +312,10 @@ impl StreamingIndicator<f64, BBandsOutput> for BBandsStream {
 
     #[inline]
     fn next(&mut self, value: f64) -> Option<BBandsOutput> {
+        self.prev_sums = (self.sum, self.sum_sq);
+        self.prev_slot = self.buffer[self.head];
+        self.prev_count = self.count;
+
         // Remove old value from sums if buffer is full
         if self.count >= self.period {
             let old_value = self.buffer[self.head];
@@ -301,7 +337,38 @@ impl StreamingIndicator<f64, BBandsOutput> for BBandsStream {
             return None;
         }
 
-        Some(self.calculate_output(value))
+        Some(self.calculate_output(self.sum, self.sum_sq, value))
+    }
+
+    fn update(&mut self, value: f64) -> Option<BBandsOutput> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        // Rewind the head and restore the slot the last value overwrote
+        self.head = (self.head + self.period - 1) % self.period;
+        self.buffer[self.head] = self.prev_slot;
+        (self.sum, self.sum_sq) = self.prev_sums;
+        self.count = self.prev_count;
+
+        self.next(value)
+    }
+
+    fn peek(&self, value: f64) -> Option<BBandsOutput> {
+        if self.count + 1 < self.period {
+            return None;
+        }
+
+        let (mut sum, mut sum_sq) = (self.sum, self.sum_sq);
+        if self.count >= self.period {
+            let old_value = self.buffer[self.head];
+            sum -= old_value;
+            sum_sq -= old_value * old_value;
+        }
+        sum += value;
+        sum_sq += value * value;
+
+        Some(self.calculate_output(sum, sum_sq, value))
     }
 
     fn reset(&mut self) {
@@ -310,11 +377,18 @@ impl StreamingIndicator<f64, BBandsOutput> for BBandsStream {
         self.count = 0;
         self.sum = 0.0;
         self.sum_sq = 0.0;
+        self.prev_sums = (0.0, 0.0);
+        self.prev_slot = 0.0;
+        self.prev_count = 0;
     }
 
     fn is_ready(&self) -> bool {
         self.count >= self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 #[cfg(test)]
@@ -449,6 +523,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bbands_stream_update_matches_next() {
+        let data: Vec<f64> = vec![10.0, 11.0, 12.0, 11.0, 10.0, 11.0, 12.0, 13.0, 12.0, 11.0];
+        let mut live = BBandsStream::new(5, 2.0).unwrap();
+        let mut reference = BBandsStream::new(5, 2.0).unwrap();
+
+        for &value in &data {
+            live.next(value + 3.0);
+            live.update(value - 1.5);
+            let amended = live.update(value);
+            let expected = reference.next(value);
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => {
+                    assert_approx_eq(a.upper, e.upper);
+                    assert_approx_eq(a.middle, e.middle);
+                    assert_approx_eq(a.lower, e.lower);
+                    assert_approx_eq(a.percent_b, e.percent_b);
+                }
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_bbands_stream_reset() {
         let mut stream = BBandsStream::new(5, 2.0).unwrap();
@@ -3,17 +3,20 @@
 //! CVD tracks the cumulative difference between buying and selling pressure.
 //! It's useful for identifying divergences between price and volume flow.
 //!
-//! The indicator supports two input modes:
+//! The indicator supports three input modes:
 //! 1. **Direct delta**: User provides pre-calculated buy-sell delta per bar
 //! 2. **OHLCV approximation**: Estimate delta from candle data
+//! 3. **Trades**: Sign each trade (reported aggressor side, tick rule or
+//!    Lee-Ready) and aggregate into time bars with a delta candle per bar
 //!
 //! OHLCV approximation formula:
 //! - `buyVolume ≈ volume * (close - low) / (high - low)`
 //! - `sellVolume ≈ volume * (high - close) / (high - low)`
 //! - `delta = buyVolume - sellVolume`
 
+use crate::bars::{Bar, BarAggregator, Classification, Quote, Side, Trade, TradeClassifier};
 use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::IndicatorResult;
+use crate::types::{check_lengths, IndicatorResult, OhlcvColumns, OHLCV};
 
 /// Input for CVD calculation from HLC+Volume data.
 /// Tuple: (high, low, close, volume)
@@ -67,6 +70,10 @@ impl Indicator<&[f64], Vec<f64>> for Cvd {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
 }
 
 /// Batch CVD calculator using OHLCV data.
@@ -143,6 +150,31 @@ impl Indicator<&[CvdBar], Vec<f64>> for CvdOhlcv {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+impl CvdOhlcv {
+    /// Calculates CVD from the high, low, close and volume columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<f64>> {
+        let mut cumulative = 0.0;
+        Ok(data
+            .high()
+            .iter()
+            .zip(data.low())
+            .zip(data.close())
+            .zip(data.volume())
+            .map(|(((&high, &low), &close), &volume)| {
+                cumulative += Self::calculate_delta(high, low, close, volume);
+                cumulative
+            })
+            .collect())
+    }
 }
 
 /// Streaming CVD calculator for direct delta input.
@@ -159,9 +191,13 @@ impl Indicator<&[CvdBar], Vec<f64>> for CvdOhlcv {
 /// // O(1) updates
 /// let cvd = stream.next(200.0); // Returns Some(325.0)
 /// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CvdStream {
     cumulative: f64,
     ready: bool,
+    // State before the most recent bar, restored by `update`
+    prev_state: (f64, bool),
 }
 
 impl CvdStream {
@@ -171,6 +207,7 @@ impl CvdStream {
         Self {
             cumulative: 0.0,
             ready: false,
+            prev_state: (0.0, false),
         }
     }
 
@@ -202,6 +239,7 @@ impl StreamingIndicator<f64, f64> for CvdStream {
         let mut result = Vec::with_capacity(deltas.len());
 
         for &delta in deltas {
+            self.prev_state = (self.cumulative, self.ready);
             if delta.is_nan() {
                 result.push(f64::NAN);
             } else {
@@ -215,6 +253,7 @@ impl StreamingIndicator<f64, f64> for CvdStream {
     }
 
     fn next(&mut self, delta: f64) -> Option<f64> {
+        self.prev_state = (self.cumulative, self.ready);
         if delta.is_nan() {
             return if self.ready {
                 Some(self.cumulative)
@@ -228,14 +267,31 @@ impl StreamingIndicator<f64, f64> for CvdStream {
         Some(self.cumulative)
     }
 
+    fn update(&mut self, delta: f64) -> Option<f64> {
+        (self.cumulative, self.ready) = self.prev_state;
+        self.next(delta)
+    }
+
+    fn peek(&self, delta: f64) -> Option<f64> {
+        if delta.is_nan() {
+            return self.ready.then_some(self.cumulative);
+        }
+        Some(self.cumulative + delta)
+    }
+
     fn reset(&mut self) {
         self.cumulative = 0.0;
         self.ready = false;
+        self.prev_state = (0.0, false);
     }
 
     fn is_ready(&self) -> bool {
         self.ready
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
 }
 
 /// Streaming CVD calculator for OHLCV input.
@@ -256,9 +312,13 @@ impl StreamingIndicator<f64, f64> for CvdStream {
 /// // O(1) updates
 /// let cvd = stream.next((108.0, 102.0, 107.0, 1200.0));
 /// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CvdOhlcvStream {
     cumulative: f64,
     ready: bool,
+    // State before the most recent bar, restored by `update`
+    prev_state: (f64, bool),
 }
 
 impl CvdOhlcvStream {
@@ -268,6 +328,7 @@ impl CvdOhlcvStream {
         Self {
             cumulative: 0.0,
             ready: false,
+            prev_state: (0.0, false),
         }
     }
 
@@ -299,6 +360,7 @@ impl StreamingIndicator<CvdBar, f64> for CvdOhlcvStream {
         let mut result = Vec::with_capacity(bars.len());
 
         for &(high, low, close, volume) in bars {
+            self.prev_state = (self.cumulative, self.ready);
             let delta = CvdOhlcv::calculate_delta(high, low, close, volume);
             self.cumulative += delta;
             self.ready = true;
@@ -311,19 +373,304 @@ impl StreamingIndicator<CvdBar, f64> for CvdOhlcvStream {
     fn next(&mut self, bar: CvdBar) -> Option<f64> {
         let (high, low, close, volume) = bar;
         let delta = CvdOhlcv::calculate_delta(high, low, close, volume);
+        self.prev_state = (self.cumulative, self.ready);
         self.cumulative += delta;
         self.ready = true;
         Some(self.cumulative)
     }
 
+    fn update(&mut self, bar: CvdBar) -> Option<f64> {
+        (self.cumulative, self.ready) = self.prev_state;
+        self.next(bar)
+    }
+
+    fn peek(&self, bar: CvdBar) -> Option<f64> {
+        let (high, low, close, volume) = bar;
+        Some(self.cumulative + CvdOhlcv::calculate_delta(high, low, close, volume))
+    }
+
     fn reset(&mut self) {
         self.cumulative = 0.0;
         self.ready = false;
+        self.prev_state = (0.0, false);
     }
 
     fn is_ready(&self) -> bool {
         self.ready
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+/// Trades paired with the quote prevailing at each one.
+pub(crate) type QuotedTrades<'a> = (&'a [Trade], &'a [Quote]);
+
+/// A time bar of signed trades with its cumulative volume delta candle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaBar {
+    /// Price bar; its buy and sell volumes follow the classification
+    pub bar: Bar,
+    /// CVD over the bar: open is the previous bar's close, high/low/close
+    /// track the running CVD after each trade; volume is the bar's volume
+    pub cvd: OHLCV,
+}
+
+/// What a trade did to the delta bars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaUpdate {
+    /// The bar this trade closed, if it started a new interval
+    pub closed: Option<DeltaBar>,
+    /// The forming bar including this trade
+    pub current: DeltaBar,
+    /// Whether this trade opened `current`
+    pub opened: bool,
+}
+
+/// Batch CVD calculator over trades.
+///
+/// Signs each trade with a [`TradeClassifier`] and aggregates the trades
+/// into UTC-aligned time bars (see [`BarAggregator`]), each with a candle of
+/// the running CVD. Unsigned trades add volume but no delta. The last,
+/// possibly incomplete, bar is included.
+///
+/// [`Classification::LeeReady`] needs quotes: calculate over
+/// `&(&trades[..], &quotes[..])`; over trades alone it falls back to the
+/// tick rule.
+///
+/// # Example
+/// ```
+/// use ta_core::bars::{Classification, Trade};
+/// use ta_core::indicators::TradeCvd;
+/// use ta_core::traits::Indicator;
+///
+/// let trades = [
+///     Trade::new(1_000, 100.0, 2.0),
+///     Trade::new(2_000, 100.5, 3.0), // uptick: buy
+///     Trade::new(61_000, 100.25, 1.0), // downtick: sell
+/// ];
+/// let cvd = TradeCvd::new(60_000, Classification::TickRule).unwrap();
+/// let bars = cvd.calculate(&trades[..]).unwrap();
+/// assert_eq!(bars.len(), 2);
+/// assert_eq!((bars[0].cvd.open, bars[0].cvd.close), (0.0, 3.0));
+/// assert_eq!((bars[1].cvd.open, bars[1].cvd.close), (3.0, 2.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TradeCvd {
+    bars: BarAggregator,
+    rule: Classification,
+}
+
+impl TradeCvd {
+    /// Create a calculator for bars of `interval_ms` milliseconds aligned to
+    /// UTC.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` is not positive.
+    pub fn new(interval_ms: i64, rule: Classification) -> IndicatorResult<Self> {
+        Self::with_offset(interval_ms, 0, rule)
+    }
+
+    /// Create a calculator whose bars open `offset_ms` after the UTC-aligned
+    /// boundaries (see [`BarAggregator::with_offset`]).
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` is not positive.
+    pub fn with_offset(
+        interval_ms: i64,
+        offset_ms: i64,
+        rule: Classification,
+    ) -> IndicatorResult<Self> {
+        Ok(Self {
+            bars: BarAggregator::with_offset(interval_ms, offset_ms)?,
+            rule,
+        })
+    }
+
+    fn stream(&self) -> TradeCvdStream {
+        TradeCvdStream {
+            bars: self.bars.clone(),
+            classifier: TradeClassifier::new(self.rule),
+            cumulative: 0.0,
+            cvd: None,
+        }
+    }
+}
+
+impl Indicator<&[Trade], Vec<DeltaBar>> for TradeCvd {
+    fn calculate(&self, trades: &[Trade]) -> IndicatorResult<Vec<DeltaBar>> {
+        let mut stream = self.stream();
+        let mut bars: Vec<DeltaBar> = trades
+            .iter()
+            .filter_map(|&trade| stream.push(trade)?.closed)
+            .collect();
+        bars.extend(stream.flush());
+        Ok(bars)
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+impl Indicator<&QuotedTrades<'_>, Vec<DeltaBar>> for TradeCvd {
+    fn calculate(&self, data: &QuotedTrades<'_>) -> IndicatorResult<Vec<DeltaBar>> {
+        let (trades, quotes) = *data;
+        check_lengths(&[("trades", trades.len()), ("quotes", quotes.len())])?;
+
+        let mut stream = self.stream();
+        let mut bars = Vec::new();
+        for (&trade, &quote) in trades.iter().zip(quotes) {
+            stream.set_quote(quote);
+            bars.extend(stream.push(trade).and_then(|update| update.closed));
+        }
+        bars.extend(stream.flush());
+        Ok(bars)
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+/// Streaming CVD over trades, bar by bar.
+///
+/// Every accepted trade updates the forming [`DeltaBar`]; a trade from a
+/// later interval closes it. Trades the [`BarAggregator`] drops (late or
+/// invalid) leave the classifier and the CVD unchanged.
+///
+/// # Example
+/// ```
+/// use ta_core::bars::{Classification, Quote, Trade};
+/// use ta_core::indicators::TradeCvdStream;
+///
+/// let mut cvd = TradeCvdStream::new(60_000, Classification::LeeReady).unwrap();
+/// cvd.set_quote(Quote::new(99.5, 100.5));
+/// let update = cvd.push(Trade::new(1_000, 100.5, 2.0)).unwrap(); // at the ask
+/// assert_eq!(update.current.cvd.close, 2.0);
+///
+/// let update = cvd.push(Trade::new(61_000, 99.5, 5.0)).unwrap(); // at the bid
+/// assert_eq!(update.closed.unwrap().cvd.close, 2.0);
+/// assert_eq!((update.current.cvd.open, update.current.cvd.low), (2.0, -3.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeCvdStream {
+    bars: BarAggregator,
+    classifier: TradeClassifier,
+    cumulative: f64,
+    // Delta candle of the forming bar
+    cvd: Option<OHLCV>,
+}
+
+impl TradeCvdStream {
+    /// Create a stream for bars of `interval_ms` milliseconds aligned to UTC.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` is not positive.
+    pub fn new(interval_ms: i64, rule: Classification) -> IndicatorResult<Self> {
+        Ok(TradeCvd::new(interval_ms, rule)?.stream())
+    }
+
+    /// Create a stream whose bars open `offset_ms` after the UTC-aligned
+    /// boundaries.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` is not positive.
+    pub fn with_offset(
+        interval_ms: i64,
+        offset_ms: i64,
+        rule: Classification,
+    ) -> IndicatorResult<Self> {
+        Ok(TradeCvd::with_offset(interval_ms, offset_ms, rule)?.stream())
+    }
+
+    /// Set the quote prevailing from now on, used by [`Classification::LeeReady`].
+    pub fn set_quote(&mut self, quote: Quote) {
+        self.classifier.set_quote(quote);
+    }
+
+    /// CVD after the last accepted trade.
+    #[must_use]
+    pub const fn cumulative(&self) -> f64 {
+        self.cumulative
+    }
+
+    /// The forming bar, if any trade has arrived since the last close.
+    #[must_use]
+    pub fn current(&self) -> Option<DeltaBar> {
+        Some(DeltaBar {
+            bar: *self.bars.current()?,
+            cvd: self.cvd?,
+        })
+    }
+
+    /// Add a trade.
+    ///
+    /// Returns `None` if the trade was dropped.
+    pub fn push(&mut self, trade: Trade) -> Option<DeltaUpdate> {
+        // Classify a copy so a dropped trade leaves the tick state alone
+        let mut classifier = self.classifier;
+        let side = classifier.classify(&trade);
+        let update = self.bars.push(Trade { side, ..trade })?;
+        self.classifier = classifier;
+
+        let closed = update.closed.and_then(|bar| self.close(bar));
+        let open = self.cumulative;
+        self.cumulative += match side {
+            Some(Side::Buy) => trade.size,
+            Some(Side::Sell) => -trade.size,
+            None => 0.0,
+        };
+
+        let start = update.current.candle.timestamp;
+        let cvd = match &mut self.cvd {
+            Some(cvd) if !update.opened => cvd,
+            slot => slot.insert(OHLCV::new(start, open, open, open, open, 0.0)),
+        };
+        cvd.high = cvd.high.max(self.cumulative);
+        cvd.low = cvd.low.min(self.cumulative);
+        cvd.close = self.cumulative;
+        cvd.volume = update.current.candle.volume;
+
+        Some(DeltaUpdate {
+            closed,
+            current: DeltaBar {
+                bar: update.current,
+                cvd: *cvd,
+            },
+            opened: update.opened,
+        })
+    }
+
+    /// Close the forming bar if its interval has ended by `now`.
+    pub fn advance(&mut self, now: i64) -> Option<DeltaBar> {
+        let bar = self.bars.advance(now)?;
+        self.close(bar)
+    }
+
+    /// Close the forming bar regardless of time.
+    pub fn flush(&mut self) -> Option<DeltaBar> {
+        let bar = self.bars.flush()?;
+        self.close(bar)
+    }
+
+    /// Drop the forming bar, the classifier state and the CVD.
+    pub fn reset(&mut self) {
+        self.bars.reset();
+        self.classifier.reset();
+        self.cumulative = 0.0;
+        self.cvd = None;
+    }
+
+    fn close(&mut self, bar: Bar) -> Option<DeltaBar> {
+        Some(DeltaBar {
+            bar,
+            cvd: self.cvd.take()?,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -458,6 +805,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cvd_stream_update_replaces_last_delta() {
+        let mut stream = CvdStream::new();
+        let _ = stream.init(&[100.0, 50.0]).unwrap();
+
+        // Amend the last historical delta, then a live one
+        assert!((stream.update(-50.0).unwrap() - 50.0).abs() < 1e-10);
+        assert!((stream.next(30.0).unwrap() - 80.0).abs() < 1e-10);
+        assert!((stream.update(10.0).unwrap() - 60.0).abs() < 1e-10);
+        assert!((stream.update(f64::NAN).unwrap() - 50.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cvd_ohlcv_stream_update_matches_next() {
+        let mut live = CvdOhlcvStream::new();
+        let mut reference = CvdOhlcvStream::new();
+        let bars = [
+            (105.0, 99.0, 104.0, 1000.0),
+            (106.0, 102.0, 103.0, 800.0),
+            (108.0, 102.0, 107.0, 1200.0),
+        ];
+
+        for &(high, low, close, volume) in &bars {
+            live.next((high, low, low, volume / 2.0));
+            let amended = live.update((high, low, close, volume)).unwrap();
+            let expected = reference.next((high, low, close, volume)).unwrap();
+            assert!((amended - expected).abs() < 1e-10);
+        }
+    }
+
     #[test]
     fn test_cvd_reset() {
         let mut stream = CvdStream::new();
@@ -470,4 +847,118 @@ mod tests {
         let result = stream.init(&[100.0, 50.0]).unwrap();
         assert!((result[1] - 150.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_cvd_ohlcv_columns_match_bars() {
+        let bars = [
+            (105.0, 99.0, 104.0, 1000.0),
+            (106.0, 102.0, 103.0, 800.0),
+            (104.0, 100.0, 102.0, 500.0),
+        ];
+        let timestamps = [0, 1, 2];
+        let opens = [0.0; 3];
+        let highs = bars.map(|b| b.0);
+        let lows = bars.map(|b| b.1);
+        let closes = bars.map(|b| b.2);
+        let volumes = bars.map(|b| b.3);
+        let columns =
+            OhlcvColumns::new(&timestamps, &opens, &highs, &lows, &closes, &volumes).unwrap();
+
+        let cvd = CvdOhlcv::new();
+        let expected = cvd.calculate(&bars[..]).unwrap();
+        let result = cvd.calculate_columns(&columns).unwrap();
+        assert_eq!(result.len(), 3);
+        for (a, b) in result.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_trade_cvd_delta_candles() {
+        let trades = [
+            Trade::with_side(1_000, 10.0, 2.0, Side::Buy),
+            Trade::with_side(2_000, 10.1, 5.0, Side::Sell),
+            Trade::new(3_000, 10.2, 1.0),
+            Trade::with_side(4_000, 10.3, 1.0, Side::Buy),
+            Trade::with_side(61_000, 10.2, 4.0, Side::Buy),
+        ];
+        let cvd = TradeCvd::new(60_000, Classification::Aggressor).unwrap();
+        let bars = cvd.calculate(&trades[..]).unwrap();
+
+        assert_eq!(bars.len(), 2);
+        // Running CVD: 2, -3, -3, -2
+        assert_eq!(bars[0].cvd, OHLCV::new(0, 0.0, 2.0, -3.0, -2.0, 9.0));
+        assert_eq!(
+            (bars[0].bar.buy_volume, bars[0].bar.sell_volume),
+            (3.0, 5.0)
+        );
+        assert_eq!(bars[1].cvd, OHLCV::new(60_000, -2.0, 2.0, -2.0, 2.0, 4.0));
+
+        // The tick rule overrides the reported sides
+        let tick = TradeCvd::new(60_000, Classification::TickRule).unwrap();
+        let bars = tick.calculate(&trades[..]).unwrap();
+        assert!((bars[0].cvd.close - 7.0).abs() < 1e-12);
+        assert!((bars[1].bar.sell_volume - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trade_cvd_stream_matches_batch() {
+        let trades: Vec<Trade> = (0..50)
+            .map(|i| {
+                let price = 100.0 + (f64::from(i) * 0.9).sin();
+                Trade::new(i64::from(i) * 7_000, price, 1.0 + f64::from(i % 3))
+            })
+            .collect();
+        let quotes: Vec<Quote> = trades
+            .iter()
+            .map(|t| Quote::new(t.price - 0.1, t.price + 0.05))
+            .collect();
+        let cvd = TradeCvd::new(60_000, Classification::LeeReady).unwrap();
+        let expected = cvd.calculate(&(&trades[..], &quotes[..])).unwrap();
+
+        let mut stream = TradeCvdStream::new(60_000, Classification::LeeReady).unwrap();
+        let mut closed = Vec::new();
+        for (&trade, &quote) in trades.iter().zip(&quotes) {
+            stream.set_quote(quote);
+            let update = stream.push(trade).unwrap();
+            closed.extend(update.closed);
+            assert_eq!(stream.current(), Some(update.current));
+        }
+        closed.extend(stream.flush());
+        assert_eq!(closed, expected);
+
+        // Every trade is above the midpoint
+        let volume: f64 = trades.iter().map(|t| t.size).sum();
+        assert!((expected.last().unwrap().cvd.close - volume).abs() < 1e-9);
+
+        assert!(matches!(
+            cvd.calculate(&(&trades[..], &quotes[1..])),
+            Err(crate::types::IndicatorError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_trade_cvd_stream_ignores_dropped_trades() {
+        let mut stream = TradeCvdStream::new(60_000, Classification::TickRule).unwrap();
+        stream.push(Trade::new(1_000, 10.0, 1.0));
+        stream.push(Trade::new(61_000, 10.5, 1.0));
+        // Late and invalid trades change nothing
+        assert!(stream.push(Trade::new(2_000, 9.0, 1.0)).is_none());
+        assert!(stream.push(Trade::new(62_000, f64::NAN, 1.0)).is_none());
+
+        let update = stream.push(Trade::new(63_000, 10.5, 2.0)).unwrap();
+        assert!((update.current.cvd.close - 3.0).abs() < 1e-12);
+        assert!((stream.advance(120_000).unwrap().cvd.close - 3.0).abs() < 1e-12);
+        assert!(stream.current().is_none());
+
+        stream.reset();
+        assert!((stream.cumulative() - 0.0).abs() < 1e-12);
+
+        // Before the first bar closes, an earlier interval is late too
+        stream.push(Trade::new(61_000, 10.0, 1.0));
+        assert!(stream.push(Trade::new(59_000, 9.0, 1.0)).is_none());
+        let update = stream.push(Trade::new(62_000, 10.5, 1.0)).unwrap();
+        assert!(update.closed.is_none());
+        assert_eq!(update.current.bar.trade_count, 2);
+    }
 }
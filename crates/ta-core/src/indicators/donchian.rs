@@ -0,0 +1,404 @@
+//! Donchian Channels indicator.
+//!
+//! Donchian Channels track the highest high and lowest low over a rolling
+//! window. They are the basis of classic breakout systems (e.g. the Turtle
+//! rules) and of the Ichimoku lines.
+//!
+//! # Components
+//! - **Upper**: Highest high over the period
+//! - **Lower**: Lowest low over the period
+//! - **Middle**: (Upper + Lower) / 2
+//! - **Width**: (Upper - Lower) / Middle
+//! - **Bars Since High/Low**: Bars since the window's high (low) was set,
+//!   0 on the bar that sets it; ties count from the most recent bar
+//!
+//! # Default Period
+//! - 20 periods
+//!
+//! # Example (Batch Mode)
+//! ```
+//! use ta_core::indicators::Donchian;
+//! use ta_core::traits::Indicator;
+//!
+//! let donchian = Donchian::new(3).unwrap();
+//! let highs = [10.0, 12.0, 11.0, 10.5];
+//! let lows = [8.0, 9.0, 7.0, 9.5];
+//! let result = donchian.calculate(&(&highs[..], &lows[..])).unwrap();
+//! assert_eq!(result[3].upper, 12.0);
+//! assert_eq!(result[3].bars_since_high, 2);
+//! ```
+
+use crate::indicators::monotonic::{Extremum, MonotonicDeque};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
+/// Donchian Channels output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DonchianOutput {
+    /// Highest high over the period
+    pub upper: f64,
+    /// Lowest low over the period
+    pub lower: f64,
+    /// Midpoint of upper and lower
+    pub middle: f64,
+    /// Width: (upper - lower) / middle
+    pub width: f64,
+    /// Bars since the highest high (0 = this bar)
+    pub bars_since_high: usize,
+    /// Bars since the lowest low (0 = this bar)
+    pub bars_since_low: usize,
+}
+
+impl DonchianOutput {
+    /// Creates an output from the window extremes and their ages.
+    #[must_use]
+    pub fn new(upper: f64, lower: f64, bars_since_high: usize, bars_since_low: usize) -> Self {
+        let middle = (upper + lower) / 2.0;
+        let width = if middle > 0.0 {
+            (upper - lower) / middle
+        } else {
+            0.0
+        };
+        Self {
+            upper,
+            lower,
+            middle,
+            width,
+            bars_since_high,
+            bars_since_low,
+        }
+    }
+
+    /// Creates a NaN output for insufficient data.
+    #[must_use]
+    pub fn nan() -> Self {
+        Self {
+            upper: f64::NAN,
+            lower: f64::NAN,
+            middle: f64::NAN,
+            width: f64::NAN,
+            bars_since_high: 0,
+            bars_since_low: 0,
+        }
+    }
+
+    /// Returns true if the channel is NaN.
+    #[must_use]
+    pub fn is_nan(&self) -> bool {
+        self.upper.is_nan() || self.lower.is_nan()
+    }
+}
+
+impl IndicatorOutput for DonchianOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
+/// Input type for Donchian Channels: (highs, lows)
+pub type DonchianInput<'a> = (&'a [f64], &'a [f64]);
+
+/// Input for streaming: (high, low) tuple
+pub type DonchianBar = (f64, f64);
+
+/// Donchian Channels calculator for batch operations.
+#[derive(Debug, Clone)]
+pub struct Donchian {
+    period: usize,
+}
+
+impl Donchian {
+    /// Creates a new Donchian Channels calculator.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if period is 0.
+    pub fn new(period: usize) -> IndicatorResult<Self> {
+        validate(period)?;
+        Ok(Self { period })
+    }
+
+    /// Returns the period.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Indicator<&DonchianInput<'_>, Vec<DonchianOutput>> for Donchian {
+    fn calculate(&self, data: &DonchianInput<'_>) -> IndicatorResult<Vec<DonchianOutput>> {
+        let (highs, lows) = *data;
+
+        check_lengths(&[("highs", highs.len()), ("lows", lows.len())])?;
+
+        let bars: Vec<DonchianBar> = highs.iter().copied().zip(lows.iter().copied()).collect();
+        DonchianStream::new(self.period)?.init(&bars)
+    }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
+}
+
+impl Donchian {
+    /// Calculates Donchian Channels from the high and low columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(
+        &self,
+        data: &OhlcvColumns<'_>,
+    ) -> IndicatorResult<Vec<DonchianOutput>> {
+        self.calculate(&(data.high(), data.low()))
+    }
+}
+
+fn validate(period: usize) -> IndicatorResult<()> {
+    if period == 0 {
+        return Err(IndicatorError::InvalidParameter(
+            "period must be greater than 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Streaming Donchian Channels calculator.
+///
+/// Uses monotonic deques to track the highest high and lowest low in O(1)
+/// amortized time per bar.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DonchianStream {
+    period: usize,
+    max_deque: MonotonicDeque, // highest high
+    min_deque: MonotonicDeque, // lowest low
+    index: usize,
+}
+
+impl DonchianStream {
+    /// Creates a new streaming Donchian Channels calculator.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if period is 0.
+    pub fn new(period: usize) -> IndicatorResult<Self> {
+        validate(period)?;
+        Ok(Self {
+            period,
+            max_deque: MonotonicDeque::new(Extremum::Max, period),
+            min_deque: MonotonicDeque::new(Extremum::Min, period),
+            index: 0,
+        })
+    }
+
+    /// Returns the period.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Output for the window ending at the latest pushed bar.
+    fn current_output(&self) -> Option<DonchianOutput> {
+        if self.index < self.period {
+            return None;
+        }
+        let last = self.index - 1;
+        Some(DonchianOutput::new(
+            self.max_deque.front()?,
+            self.min_deque.front()?,
+            last - self.max_deque.front_index()?,
+            last - self.min_deque.front_index()?,
+        ))
+    }
+
+    /// Checks the deques of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(self.period > 0, "period must be greater than 0")?;
+        self.max_deque
+            .check_state(Extremum::Max, self.period, self.index)?;
+        self.min_deque
+            .check_state(Extremum::Min, self.period, self.index)
+    }
+}
+
+impl StreamingIndicator<DonchianBar, DonchianOutput> for DonchianStream {
+    fn init(&mut self, data: &[DonchianBar]) -> IndicatorResult<Vec<DonchianOutput>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &bar in data {
+            results.push(self.next(bar).unwrap_or_else(DonchianOutput::nan));
+        }
+        Ok(results)
+    }
+
+    fn next(&mut self, bar: DonchianBar) -> Option<DonchianOutput> {
+        let (high, low) = bar;
+        self.max_deque.push(self.index, high);
+        self.min_deque.push(self.index, low);
+        self.index += 1;
+        self.current_output()
+    }
+
+    fn update(&mut self, bar: DonchianBar) -> Option<DonchianOutput> {
+        if self.index == 0 {
+            return self.next(bar);
+        }
+
+        self.max_deque.rollback();
+        self.min_deque.rollback();
+        self.index -= 1;
+        self.next(bar)
+    }
+
+    fn peek(&self, bar: DonchianBar) -> Option<DonchianOutput> {
+        if self.index + 1 < self.period {
+            return None;
+        }
+        let (high, low) = bar;
+        let (high_index, upper) = self.max_deque.peek_entry(self.index, high);
+        let (low_index, lower) = self.min_deque.peek_entry(self.index, low);
+        Some(DonchianOutput::new(
+            upper,
+            lower,
+            self.index - high_index,
+            self.index - low_index,
+        ))
+    }
+
+    fn reset(&mut self) {
+        self.max_deque.clear();
+        self.min_deque.clear();
+        self.index = 0;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.index >= self.period
+    }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-10;
+
+    fn assert_approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < EPSILON, "expected {b}, got {a}");
+    }
+
+    #[test]
+    fn test_donchian_channel_and_bars_since() {
+        let highs = [10.0, 12.0, 11.0, 10.5, 13.0];
+        let lows = [8.0, 9.0, 7.0, 9.5, 10.0];
+        let result = Donchian::new(3)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..]))
+            .unwrap();
+
+        assert!(result[1].is_nan());
+        assert_approx_eq(result[2].upper, 12.0);
+        assert_approx_eq(result[2].lower, 7.0);
+        assert_approx_eq(result[2].middle, 9.5);
+        assert_approx_eq(result[2].width, 5.0 / 9.5);
+        assert_eq!(
+            (result[2].bars_since_high, result[2].bars_since_low),
+            (1, 0)
+        );
+
+        // Index 1's high is still in the window; index 4 sets a new one
+        assert_eq!(result[3].bars_since_high, 2);
+        assert_approx_eq(result[4].upper, 13.0);
+        assert_eq!(
+            (result[4].bars_since_high, result[4].bars_since_low),
+            (0, 2)
+        );
+
+        assert!(Donchian::new(0).is_err());
+        assert!(Donchian::new(3)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..2]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_donchian_matches_window_scan() {
+        let highs: Vec<f64> = (0..50)
+            .map(|i| 100.0 + (f64::from(i) * 0.7).sin() * 5.0)
+            .collect();
+        let lows: Vec<f64> = highs.iter().map(|h| h - 2.0).collect();
+        let period = 7;
+        let result = Donchian::new(period)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..]))
+            .unwrap();
+
+        for i in period - 1..highs.len() {
+            let window = i + 1 - period..=i;
+            let upper = highs[window.clone()]
+                .iter()
+                .copied()
+                .fold(f64::MIN, f64::max);
+            let lower = lows[window].iter().copied().fold(f64::MAX, f64::min);
+            assert_approx_eq(result[i].upper, upper);
+            assert_approx_eq(result[i].lower, lower);
+            assert_approx_eq(highs[i - result[i].bars_since_high], upper);
+            assert_approx_eq(lows[i - result[i].bars_since_low], lower);
+        }
+    }
+
+    #[test]
+    fn test_donchian_skips_nan() {
+        let highs = [10.0, f64::NAN, 9.0, 8.0, f64::NAN];
+        let lows = [8.0, 7.0, f64::NAN, 6.0, 7.5];
+        let result = Donchian::new(3)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..]))
+            .unwrap();
+
+        assert_approx_eq(result[2].upper, 10.0);
+        assert_approx_eq(result[2].lower, 7.0);
+        assert_eq!(
+            (result[2].bars_since_high, result[2].bars_since_low),
+            (2, 1)
+        );
+        assert_approx_eq(result[4].upper, 9.0);
+        assert_approx_eq(result[4].lower, 6.0);
+        assert_eq!(
+            (result[4].bars_since_high, result[4].bars_since_low),
+            (2, 1)
+        );
+    }
+
+    #[test]
+    fn test_donchian_stream_update_and_peek() {
+        let bars: Vec<DonchianBar> = (0..30)
+            .map(|i| {
+                let x = f64::from(i);
+                (50.0 + (x * 0.9).cos() * 3.0, 47.0 + (x * 0.4).sin() * 2.0)
+            })
+            .collect();
+        let mut reference = DonchianStream::new(5).unwrap();
+        let expected = reference.init(&bars).unwrap();
+
+        let mut stream = DonchianStream::new(5).unwrap();
+        for (i, &bar) in bars.iter().enumerate() {
+            let peeked = stream.peek(bar);
+            stream.next((bar.0 + 10.0, bar.1 - 10.0));
+            let out = stream.update(bar);
+
+            assert_eq!(peeked, out);
+            match out {
+                Some(out) => assert_eq!(out, expected[i]),
+                None => assert!(expected[i].is_nan()),
+            }
+        }
+        assert!(stream.is_ready());
+    }
+}
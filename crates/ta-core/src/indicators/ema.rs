@@ -33,6 +33,9 @@
 use crate::traits::{Indicator, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
 /// Exponential Moving Average calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Ema {
@@ -109,18 +112,25 @@ impl Indicator<&[f64], Vec<f64>> for Ema {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 /// Exponential Moving Average calculator for streaming/real-time operations.
 ///
 /// Maintains only the previous EMA value for O(1) updates.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmaStream {
     period: usize,
     multiplier: f64,
     prev_ema: f64,
     count: usize,
     sum: f64, // For initial SMA calculation
+    // State before the most recent `next`, restored by `update`
+    prev_state: (f64, f64),
 }
 
 impl EmaStream {
@@ -141,6 +151,7 @@ impl EmaStream {
             prev_ema: 0.0,
             count: 0,
             sum: 0.0,
+            prev_state: (0.0, 0.0),
         })
     }
 
@@ -165,6 +176,7 @@ impl EmaStream {
             prev_ema: 0.0,
             count: 0,
             sum: 0.0,
+            prev_state: (0.0, 0.0),
         })
     }
 
@@ -189,6 +201,12 @@ impl EmaStream {
             None
         }
     }
+
+    /// Checks the period of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(self.period > 0, "period must be greater than 0")
+    }
 }
 
 impl StreamingIndicator<f64, f64> for EmaStream {
@@ -204,6 +222,7 @@ impl StreamingIndicator<f64, f64> for EmaStream {
 
     #[inline]
     fn next(&mut self, value: f64) -> Option<f64> {
+        self.prev_state = (self.prev_ema, self.sum);
         self.count += 1;
 
         if self.count < self.period {
@@ -222,15 +241,41 @@ impl StreamingIndicator<f64, f64> for EmaStream {
         }
     }
 
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        (self.prev_ema, self.sum) = self.prev_state;
+        self.count -= 1;
+        self.next(value)
+    }
+
+    fn peek(&self, value: f64) -> Option<f64> {
+        let count = self.count + 1;
+        if count < self.period {
+            None
+        } else if count == self.period {
+            Some((self.sum + value) / self.period as f64)
+        } else {
+            Some((value * self.multiplier) + (self.prev_ema * (1.0 - self.multiplier)))
+        }
+    }
+
     fn reset(&mut self) {
         self.prev_ema = 0.0;
         self.count = 0;
         self.sum = 0.0;
+        self.prev_state = (0.0, 0.0);
     }
 
     fn is_ready(&self) -> bool {
         self.count >= self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 #[cfg(test)]
@@ -332,6 +377,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ema_stream_update_matches_next() {
+        let data = [10.0, 11.0, 12.0, 11.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0];
+        let mut live = EmaStream::new(5).unwrap();
+        let mut reference = EmaStream::new(5).unwrap();
+
+        for &value in &data {
+            // Open the bar at a different price, revise it, then settle on the close
+            live.next(value - 1.0);
+            live.update(value + 2.0);
+            let amended = live.update(value);
+            let expected = reference.next(value);
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => assert_approx_eq(a, e),
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_ema_stream_reset() {
         let mut ema = EmaStream::new(3).unwrap();
@@ -0,0 +1,701 @@
+//! Footprint (volume at price per bar).
+//!
+//! A footprint splits every time bar into a ladder of price levels, one per
+//! tick, and records the buy and sell volume traded at each level. Trades are
+//! signed with a [`TradeClassifier`] exactly as for the trade-based CVD.
+//!
+//! # Output
+//! - **Levels**: buy, sell and total volume at every tick between the bar's
+//!   low and high
+//! - **Delta**: buy volume minus sell volume of the bar
+//! - **POC (Point of Control)**: level with the highest volume in the bar
+//! - **Imbalances**: diagonal comparisons of buying at a level against
+//!   selling one tick below, and selling at a level against buying one tick
+//!   above; runs of imbalanced levels are *stacked imbalances*
+//!
+//! # Example
+//! ```
+//! use ta_core::bars::{Classification, Side, Trade};
+//! use ta_core::indicators::Footprint;
+//! use ta_core::traits::Indicator;
+//!
+//! let trades = [
+//!     Trade::with_side(1_000, 100.00, 4.0, Side::Buy),
+//!     Trade::with_side(2_000, 100.25, 1.0, Side::Sell),
+//!     Trade::with_side(3_000, 100.50, 6.0, Side::Buy),
+//! ];
+//! let footprint = Footprint::new(60_000, 0.25, Classification::Aggressor).unwrap();
+//! let bars = footprint.calculate(&trades[..]).unwrap();
+//!
+//! assert_eq!(bars[0].levels.len(), 3);
+//! assert_eq!(bars[0].delta(), 9.0);
+//! assert_eq!(bars[0].poc().unwrap().price, 100.5);
+//! // Buying at 100.00 and 100.50 outweighs selling one tick below
+//! assert_eq!(bars[0].imbalances(3.0).len(), 2);
+//! ```
+
+use super::cvd::QuotedTrades;
+use super::frvp::VolumeProfileRow;
+use crate::bars::{Bar, BarAggregator, Classification, Quote, Side, Trade, TradeClassifier};
+use crate::traits::Indicator;
+use crate::types::{check_lengths, IndicatorError, IndicatorResult};
+
+/// Most levels a bar's ladder may span; a trade that would stretch the
+/// forming bar's ladder further (a bad print far from the market) is dropped.
+pub const MAX_LEVELS: usize = 10_000;
+
+// Largest tick index stored exactly in an `f64`
+const MAX_TICK: f64 = 9_007_199_254_740_992.0;
+
+// ============================================================================
+// Output Types
+// ============================================================================
+
+/// Volume traded at one price level of a bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootprintLevel {
+    /// Price of the level (a multiple of the tick size)
+    pub price: f64,
+    /// Volume of trades classified as buys
+    pub buy_volume: f64,
+    /// Volume of trades classified as sells
+    pub sell_volume: f64,
+    /// Volume of all trades, including unsigned ones
+    pub volume: f64,
+}
+
+impl FootprintLevel {
+    const fn empty(price: f64) -> Self {
+        Self {
+            price,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            volume: 0.0,
+        }
+    }
+
+    /// Buy volume minus sell volume.
+    #[must_use]
+    pub fn delta(&self) -> f64 {
+        self.buy_volume - self.sell_volume
+    }
+}
+
+/// A diagonal imbalance at one level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Imbalance {
+    /// Price of the imbalanced level
+    pub price: f64,
+    /// [`Side::Buy`] if buying at this level outweighs selling one tick
+    /// below; [`Side::Sell`] if selling outweighs buying one tick above
+    pub side: Side,
+}
+
+/// A run of consecutive levels imbalanced to the same side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StackedImbalance {
+    /// Side of every imbalance in the run
+    pub side: Side,
+    /// Price of the lowest level in the run
+    pub low: f64,
+    /// Price of the highest level in the run
+    pub high: f64,
+    /// Number of levels in the run
+    pub levels: usize,
+}
+
+/// A time bar with its volume at price.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootprintBar {
+    /// Price bar; its buy and sell volumes follow the classification
+    pub bar: Bar,
+    /// Distance between adjacent levels
+    pub tick_size: f64,
+    /// One level per tick from the lowest to the highest traded price,
+    /// ascending; ticks without trades have zero volume
+    pub levels: Vec<FootprintLevel>,
+}
+
+impl FootprintBar {
+    /// Buy volume minus sell volume of the bar.
+    #[must_use]
+    pub fn delta(&self) -> f64 {
+        self.bar.delta()
+    }
+
+    /// Level with the highest volume; ties go to the lowest price.
+    #[must_use]
+    pub fn poc(&self) -> Option<&FootprintLevel> {
+        self.levels.iter().reduce(|poc, level| {
+            if level.volume > poc.volume {
+                level
+            } else {
+                poc
+            }
+        })
+    }
+
+    /// Diagonal imbalances, ascending by price.
+    ///
+    /// A level is a buy imbalance when its buy volume is at least `ratio`
+    /// times the sell volume one tick below, and a sell imbalance when its
+    /// sell volume is at least `ratio` times the buy volume one tick above.
+    /// A level can be both. Levels without volume on the side in question
+    /// are never imbalanced.
+    #[must_use]
+    pub fn imbalances(&self, ratio: f64) -> Vec<Imbalance> {
+        self.imbalance_flags(ratio)
+            .zip(&self.levels)
+            .flat_map(|((buy, sell), level)| {
+                [(buy, Side::Buy), (sell, Side::Sell)]
+                    .into_iter()
+                    .filter(|&(flag, _)| flag)
+                    .map(|(_, side)| Imbalance {
+                        price: level.price,
+                        side,
+                    })
+            })
+            .collect()
+    }
+
+    /// Runs of at least `min_levels` consecutive levels with an imbalance
+    /// (see [`imbalances`](Self::imbalances)) to the same side, ascending by
+    /// their low price.
+    #[must_use]
+    pub fn stacked_imbalances(&self, ratio: f64, min_levels: usize) -> Vec<StackedImbalance> {
+        let mut stacks = Vec::new();
+        // Start index of the current buy and sell runs
+        let mut runs: [Option<usize>; 2] = [None, None];
+        let flags = self.imbalance_flags(ratio).chain([(false, false)]);
+        for (idx, (buy, sell)) in flags.enumerate() {
+            for (run, flag, side) in [(0, buy, Side::Buy), (1, sell, Side::Sell)] {
+                match (runs[run], flag) {
+                    (None, true) => runs[run] = Some(idx),
+                    (Some(start), false) => {
+                        runs[run] = None;
+                        if idx - start >= min_levels.max(1) {
+                            stacks.push(StackedImbalance {
+                                side,
+                                low: self.levels[start].price,
+                                high: self.levels[idx - 1].price,
+                                levels: idx - start,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        stacks.sort_by(|a, b| a.low.total_cmp(&b.low));
+        stacks
+    }
+
+    /// The levels as volume profile rows, each spanning half a tick either
+    /// side of its price.
+    #[must_use]
+    pub fn profile(&self) -> Vec<VolumeProfileRow> {
+        let half = self.tick_size / 2.0;
+        self.levels
+            .iter()
+            .map(|level| VolumeProfileRow {
+                price: level.price,
+                volume: level.volume,
+                low: level.price - half,
+                high: level.price + half,
+            })
+            .collect()
+    }
+
+    /// Whether each level is a (buy, sell) imbalance.
+    fn imbalance_flags(&self, ratio: f64) -> impl Iterator<Item = (bool, bool)> + '_ {
+        let levels = &self.levels;
+        (0..levels.len()).map(move |idx| {
+            let below = idx.checked_sub(1).map_or(0.0, |i| levels[i].sell_volume);
+            let above = levels.get(idx + 1).map_or(0.0, |level| level.buy_volume);
+            let level = &levels[idx];
+            (
+                level.buy_volume > 0.0 && level.buy_volume >= ratio * below,
+                level.sell_volume > 0.0 && level.sell_volume >= ratio * above,
+            )
+        })
+    }
+}
+
+/// What a trade did to the footprint bars.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FootprintUpdate {
+    /// The bar this trade closed, if it started a new interval
+    pub closed: Option<FootprintBar>,
+    /// The forming bar's candle including this trade
+    pub bar: Bar,
+    /// The level this trade added to, after the trade
+    pub level: FootprintLevel,
+    /// Whether this trade opened the forming bar
+    pub opened: bool,
+}
+
+// ============================================================================
+// Batch Calculator
+// ============================================================================
+
+/// Batch footprint calculator over trades.
+///
+/// Signs each trade with a [`TradeClassifier`], aggregates the trades into
+/// UTC-aligned time bars (see [`BarAggregator`]) and places each one on the
+/// nearest multiple of the tick size. The last, possibly incomplete, bar is
+/// included.
+///
+/// [`Classification::LeeReady`] needs quotes: calculate over
+/// `&(&trades[..], &quotes[..])`; over trades alone it falls back to the
+/// tick rule.
+#[derive(Debug, Clone)]
+pub struct Footprint {
+    bars: BarAggregator,
+    tick_size: f64,
+    rule: Classification,
+}
+
+impl Footprint {
+    /// Create a calculator for bars of `interval_ms` milliseconds aligned to
+    /// UTC, with levels `tick_size` apart.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` or `tick_size` is not
+    /// positive.
+    pub fn new(interval_ms: i64, tick_size: f64, rule: Classification) -> IndicatorResult<Self> {
+        Self::with_offset(interval_ms, 0, tick_size, rule)
+    }
+
+    /// Create a calculator whose bars open `offset_ms` after the UTC-aligned
+    /// boundaries (see [`BarAggregator::with_offset`]).
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` or `tick_size` is not
+    /// positive.
+    pub fn with_offset(
+        interval_ms: i64,
+        offset_ms: i64,
+        tick_size: f64,
+        rule: Classification,
+    ) -> IndicatorResult<Self> {
+        if !(tick_size.is_finite() && tick_size > 0.0) {
+            return Err(IndicatorError::InvalidParameter(
+                "tick_size must be positive and finite".to_string(),
+            ));
+        }
+        Ok(Self {
+            bars: BarAggregator::with_offset(interval_ms, offset_ms)?,
+            tick_size,
+            rule,
+        })
+    }
+
+    /// Distance between adjacent levels.
+    #[must_use]
+    pub const fn tick_size(&self) -> f64 {
+        self.tick_size
+    }
+
+    fn stream(&self) -> FootprintStream {
+        FootprintStream {
+            bars: self.bars.clone(),
+            classifier: TradeClassifier::new(self.rule),
+            tick_size: self.tick_size,
+            low_tick: 0,
+            levels: Vec::new(),
+        }
+    }
+}
+
+impl Indicator<&[Trade], Vec<FootprintBar>> for Footprint {
+    fn calculate(&self, trades: &[Trade]) -> IndicatorResult<Vec<FootprintBar>> {
+        let mut stream = self.stream();
+        let mut bars: Vec<FootprintBar> = trades
+            .iter()
+            .filter_map(|&trade| stream.push(trade)?.closed)
+            .collect();
+        bars.extend(stream.flush());
+        Ok(bars)
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+impl Indicator<&QuotedTrades<'_>, Vec<FootprintBar>> for Footprint {
+    fn calculate(&self, data: &QuotedTrades<'_>) -> IndicatorResult<Vec<FootprintBar>> {
+        let (trades, quotes) = *data;
+        check_lengths(&[("trades", trades.len()), ("quotes", quotes.len())])?;
+
+        let mut stream = self.stream();
+        let mut bars = Vec::new();
+        for (&trade, &quote) in trades.iter().zip(quotes) {
+            stream.set_quote(quote);
+            bars.extend(stream.push(trade).and_then(|update| update.closed));
+        }
+        bars.extend(stream.flush());
+        Ok(bars)
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+// ============================================================================
+// Streaming Calculator
+// ============================================================================
+
+/// Streaming footprint over trades, bar by bar.
+///
+/// Each accepted trade adds to one level of the forming bar and reports that
+/// level, so a chart can redraw a single cell per trade; a trade from a later
+/// interval closes the bar. Trades the [`BarAggregator`] drops (late or
+/// invalid), and trades that would stretch the ladder past [`MAX_LEVELS`],
+/// leave the classifier unchanged.
+///
+/// # Example
+/// ```
+/// use ta_core::bars::{Classification, Trade};
+/// use ta_core::indicators::FootprintStream;
+///
+/// let mut footprint = FootprintStream::new(60_000, 0.5, Classification::TickRule).unwrap();
+/// footprint.push(Trade::new(1_000, 100.0, 1.0));
+/// let update = footprint.push(Trade::new(2_000, 101.0, 2.0)).unwrap(); // uptick
+/// assert_eq!((update.level.price, update.level.buy_volume), (101.0, 2.0));
+///
+/// let update = footprint.push(Trade::new(61_000, 100.9, 1.0)).unwrap();
+/// // 100.0, 100.5 and 101.0
+/// assert_eq!(update.closed.unwrap().levels.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootprintStream {
+    bars: BarAggregator,
+    classifier: TradeClassifier,
+    tick_size: f64,
+    // Tick index of `levels[0]`
+    low_tick: i64,
+    // Levels of the forming bar
+    levels: Vec<FootprintLevel>,
+}
+
+impl FootprintStream {
+    /// Create a stream for bars of `interval_ms` milliseconds aligned to UTC,
+    /// with levels `tick_size` apart.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` or `tick_size` is not
+    /// positive.
+    pub fn new(interval_ms: i64, tick_size: f64, rule: Classification) -> IndicatorResult<Self> {
+        Ok(Footprint::new(interval_ms, tick_size, rule)?.stream())
+    }
+
+    /// Create a stream whose bars open `offset_ms` after the UTC-aligned
+    /// boundaries.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `interval_ms` or `tick_size` is not
+    /// positive.
+    pub fn with_offset(
+        interval_ms: i64,
+        offset_ms: i64,
+        tick_size: f64,
+        rule: Classification,
+    ) -> IndicatorResult<Self> {
+        Ok(Footprint::with_offset(interval_ms, offset_ms, tick_size, rule)?.stream())
+    }
+
+    /// Distance between adjacent levels.
+    #[must_use]
+    pub const fn tick_size(&self) -> f64 {
+        self.tick_size
+    }
+
+    /// Set the quote prevailing from now on, used by [`Classification::LeeReady`].
+    pub fn set_quote(&mut self, quote: Quote) {
+        self.classifier.set_quote(quote);
+    }
+
+    /// The forming bar, if any trade has arrived since the last close.
+    #[must_use]
+    pub fn current(&self) -> Option<FootprintBar> {
+        Some(FootprintBar {
+            bar: *self.bars.current()?,
+            tick_size: self.tick_size,
+            levels: self.levels.clone(),
+        })
+    }
+
+    /// Add a trade.
+    ///
+    /// Returns `None` if the trade was dropped.
+    pub fn push(&mut self, trade: Trade) -> Option<FootprintUpdate> {
+        let tick = self.tick(&trade)?;
+        // Classify a copy so a dropped trade leaves the tick state alone
+        let mut classifier = self.classifier;
+        let side = classifier.classify(&trade);
+        let update = self.bars.push(Trade { side, ..trade })?;
+        self.classifier = classifier;
+
+        let closed = update.closed.map(|bar| self.close(bar));
+        let level = self.add(tick, &Trade { side, ..trade });
+        Some(FootprintUpdate {
+            closed,
+            bar: update.current,
+            level,
+            opened: update.opened,
+        })
+    }
+
+    /// Close the forming bar if its interval has ended by `now`.
+    pub fn advance(&mut self, now: i64) -> Option<FootprintBar> {
+        let bar = self.bars.advance(now)?;
+        Some(self.close(bar))
+    }
+
+    /// Close the forming bar regardless of time.
+    pub fn flush(&mut self) -> Option<FootprintBar> {
+        let bar = self.bars.flush()?;
+        Some(self.close(bar))
+    }
+
+    /// Drop the forming bar and the classifier state.
+    pub fn reset(&mut self) {
+        self.bars.reset();
+        self.classifier.reset();
+        self.levels.clear();
+    }
+
+    /// Tick index of the trade's level, or `None` if the price has none or
+    /// the level would stretch the forming bar's ladder past [`MAX_LEVELS`].
+    fn tick(&self, trade: &Trade) -> Option<i64> {
+        let tick = (trade.price / self.tick_size).round();
+        if !(-MAX_TICK..=MAX_TICK).contains(&tick) {
+            return None;
+        }
+        let tick = tick as i64;
+
+        let start = self.bars.bar_start(trade.timestamp);
+        let same_bar = self
+            .bars
+            .current()
+            .is_some_and(|bar| bar.candle.timestamp == start);
+        if same_bar && !self.levels.is_empty() {
+            let span = match usize::try_from(tick - self.low_tick) {
+                Ok(offset) => self.levels.len().max(offset + 1),
+                Err(_) => usize::try_from(self.low_tick - tick)
+                    .map_or(usize::MAX, |below| below.saturating_add(self.levels.len())),
+            };
+            if span > MAX_LEVELS {
+                return None;
+            }
+        }
+        Some(tick)
+    }
+
+    /// Add a signed trade to its level at `tick`, growing the ladder as needed.
+    fn add(&mut self, tick: i64, trade: &Trade) -> FootprintLevel {
+        let tick_size = self.tick_size;
+        let empty = |tick: i64| FootprintLevel::empty(tick as f64 * tick_size);
+
+        if self.levels.is_empty() {
+            self.low_tick = tick;
+        } else if tick < self.low_tick {
+            self.levels.splice(0..0, (tick..self.low_tick).map(empty));
+            self.low_tick = tick;
+        }
+        let high_tick = self.low_tick + self.levels.len() as i64;
+        if tick >= high_tick {
+            self.levels.extend((high_tick..=tick).map(empty));
+        }
+
+        let level = &mut self.levels[usize::try_from(tick - self.low_tick).unwrap_or_default()];
+        level.volume += trade.size;
+        match trade.side {
+            Some(Side::Buy) => level.buy_volume += trade.size,
+            Some(Side::Sell) => level.sell_volume += trade.size,
+            None => {}
+        }
+        *level
+    }
+
+    fn close(&mut self, bar: Bar) -> FootprintBar {
+        FootprintBar {
+            bar,
+            tick_size: self.tick_size,
+            levels: std::mem::take(&mut self.levels),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(bar: &FootprintBar, price: f64) -> FootprintLevel {
+        *bar.levels
+            .iter()
+            .find(|level| (level.price - price).abs() < 1e-9)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_footprint_levels() {
+        let trades = [
+            Trade::with_side(1_000, 10.02, 2.0, Side::Buy),
+            Trade::with_side(2_000, 10.0, 3.0, Side::Sell),
+            Trade::new(3_000, 10.3, 1.0),
+            Trade::with_side(4_000, 9.96, 5.0, Side::Buy),
+            Trade::with_side(61_000, 10.1, 4.0, Side::Sell),
+        ];
+        let footprint = Footprint::new(60_000, 0.1, Classification::Aggressor).unwrap();
+        let bars = footprint.calculate(&trades[..]).unwrap();
+
+        assert_eq!(bars.len(), 2);
+        // Every tick from 10.0 to 10.3, including the empty ones
+        let prices: Vec<f64> = bars[0].levels.iter().map(|l| l.price).collect();
+        assert_eq!(prices.len(), 4);
+        assert!((prices[0] - 10.0).abs() < 1e-9 && (prices[3] - 10.3).abs() < 1e-9);
+
+        let bottom = level(&bars[0], 10.0);
+        assert!((bottom.buy_volume - 7.0).abs() < 1e-12);
+        assert!((bottom.sell_volume - 3.0).abs() < 1e-12);
+        let top = level(&bars[0], 10.3);
+        assert!((top.volume - 1.0).abs() < 1e-12 && top.delta().abs() < 1e-12);
+        assert!(level(&bars[0], 10.1).volume.abs() < 1e-12);
+
+        assert!((bars[0].delta() - 4.0).abs() < 1e-12);
+        assert!((bars[0].poc().unwrap().price - 10.0).abs() < 1e-9);
+        assert_eq!(bars[1].levels.len(), 1);
+
+        let profile = bars[0].profile();
+        assert_eq!(profile.len(), 4);
+        assert!((profile[0].low - 9.95).abs() < 1e-9 && (profile[0].high - 10.05).abs() < 1e-9);
+
+        assert!(matches!(
+            Footprint::new(60_000, 0.0, Classification::Aggressor),
+            Err(IndicatorError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_footprint_stacked_imbalances() {
+        // (buy, sell) per tick from 1.0 to 6.0
+        let ladder = [
+            (0.0, 2.0),
+            (9.0, 1.0),
+            (6.0, 2.0),
+            (8.0, 1.0),
+            (1.0, 9.0),
+            (0.0, 1.0),
+        ];
+        let levels = ladder
+            .iter()
+            .zip(1..)
+            .map(|(&(buy, sell), price)| FootprintLevel {
+                price: f64::from(price),
+                buy_volume: buy,
+                sell_volume: sell,
+                volume: buy + sell,
+            })
+            .collect();
+        let bar = FootprintBar {
+            bar: BarAggregator::aggregate(60_000, &[Trade::new(0, 1.0, 1.0)]).unwrap()[0],
+            tick_size: 1.0,
+            levels,
+        };
+
+        // Buys at 2, 3 and 4 are each at least 3x the selling one tick below;
+        // the sell at 5 is 9x the buying at 6, the sell at 6 has nothing above
+        let imbalances = bar.imbalances(3.0);
+        let buys: Vec<f64> = imbalances
+            .iter()
+            .filter(|i| i.side == Side::Buy)
+            .map(|i| i.price)
+            .collect();
+        assert_eq!(buys, [2.0, 3.0, 4.0]);
+        let sells: Vec<f64> = imbalances
+            .iter()
+            .filter(|i| i.side == Side::Sell)
+            .map(|i| i.price)
+            .collect();
+        assert_eq!(sells, [5.0, 6.0]);
+
+        let stacks = bar.stacked_imbalances(3.0, 3);
+        assert_eq!(stacks.len(), 1);
+        assert_eq!(stacks[0].side, Side::Buy);
+        assert_eq!(stacks[0].levels, 3);
+        assert!((stacks[0].low - 2.0).abs() < 1e-12 && (stacks[0].high - 4.0).abs() < 1e-12);
+        assert_eq!(bar.stacked_imbalances(3.0, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_footprint_stream_matches_batch() {
+        let trades: Vec<Trade> = (0..60)
+            .map(|i| {
+                let price = 100.0 + (f64::from(i) * 0.7).sin() * 2.0;
+                Trade::new(i64::from(i) * 5_000, price, 1.0 + f64::from(i % 4))
+            })
+            .collect();
+        let quotes: Vec<Quote> = trades
+            .iter()
+            .map(|t| Quote::new(t.price - 0.05, t.price + 0.1))
+            .collect();
+        let footprint = Footprint::new(60_000, 0.25, Classification::LeeReady).unwrap();
+        let expected = footprint.calculate(&(&trades[..], &quotes[..])).unwrap();
+
+        let mut stream = FootprintStream::new(60_000, 0.25, Classification::LeeReady).unwrap();
+        let mut closed = Vec::new();
+        for (&trade, &quote) in trades.iter().zip(&quotes) {
+            stream.set_quote(quote);
+            let update = stream.push(trade).unwrap();
+            closed.extend(update.closed);
+            let current = stream.current().unwrap();
+            assert_eq!(current.bar, update.bar);
+            assert!(current.levels.contains(&update.level));
+        }
+        // Late trades are dropped
+        assert!(stream.push(Trade::new(0, 100.0, 1.0)).is_none());
+        closed.extend(stream.flush());
+        assert_eq!(closed, expected);
+
+        for bar in &expected {
+            // Every trade is below the midpoint
+            assert!((bar.delta() + bar.bar.candle.volume).abs() < 1e-9);
+            let volume: f64 = bar.levels.iter().map(|l| l.volume).sum();
+            assert!((volume - bar.bar.candle.volume).abs() < 1e-9);
+        }
+
+        stream.reset();
+        assert!(stream.current().is_none());
+    }
+
+    #[test]
+    fn test_footprint_drops_outlier_prices() {
+        let mut stream = FootprintStream::new(60_000, 0.01, Classification::TickRule).unwrap();
+        stream.push(Trade::new(1_000, 100.0, 1.0)).unwrap();
+
+        // A bad print would need a ladder of a billion levels
+        assert!(stream.push(Trade::new(2_000, 1e7, 1.0)).is_none());
+        assert!(stream.push(Trade::new(2_000, 1e300, 1.0)).is_none());
+        assert!(stream.push(Trade::new(2_000, f64::INFINITY, 1.0)).is_none());
+        // The widest ladder allowed, up and down
+        assert!(stream.push(Trade::new(3_000, 199.99, 1.0)).is_some());
+        assert!(stream.push(Trade::new(4_000, 99.99, 1.0)).is_none());
+        let current = stream.current().unwrap();
+        assert_eq!(current.levels.len(), MAX_LEVELS);
+        assert_eq!(current.bar.trade_count, 2);
+        // Dropped trades do not move the tick rule: a downtick from 199.99
+        let update = stream.push(Trade::new(5_000, 150.0, 1.0)).unwrap();
+        assert!((update.level.sell_volume - 1.0).abs() < 1e-12);
+
+        // A new bar starts a new ladder
+        let update = stream.push(Trade::new(61_000, 1e7, 1.0)).unwrap();
+        assert_eq!(update.closed.unwrap().levels.len(), MAX_LEVELS);
+        assert_eq!(stream.current().unwrap().levels.len(), 1);
+    }
+}
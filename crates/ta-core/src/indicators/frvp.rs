@@ -26,7 +26,7 @@
 //! ```
 
 use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+use crate::types::{IndicatorError, IndicatorResult, OhlcvColumns, OHLCV};
 
 // ============================================================================
 // Constants
@@ -44,6 +44,7 @@ const VALUE_AREA_PERCENT: f64 = 0.70;
 
 /// A single row in the volume profile histogram.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VolumeProfileRow {
     /// Price level (center of the bin)
     pub price: f64,
@@ -57,6 +58,7 @@ pub struct VolumeProfileRow {
 
 /// Output from Fixed Range Volume Profile calculation.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrvpOutput {
     /// Point of Control - price level with highest volume
     pub poc: f64,
@@ -86,6 +88,7 @@ pub struct FrvpOutput {
 ///
 /// Calculates volume distribution across price levels within a fixed range of candles.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Frvp {
     /// Number of price bins (rows) in the histogram
     num_bins: usize,
@@ -129,9 +132,12 @@ impl Frvp {
             ));
         }
         if !(0.0..=1.0).contains(&value_area_percent) {
-            return Err(IndicatorError::InvalidParameter(
-                "value_area_percent must be between 0.0 and 1.0".to_string(),
-            ));
+            return Err(IndicatorError::OutOfRange {
+                name: "value_area_percent",
+                value: value_area_percent,
+                min: 0.0,
+                max: 1.0,
+            });
         }
 
         Ok(Self {
@@ -151,6 +157,14 @@ impl Frvp {
     pub const fn value_area_percent(&self) -> f64 {
         self.value_area_percent
     }
+
+    /// Checks the parameters of a restored calculator.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        Self::with_value_area(self.num_bins, self.value_area_percent)
+            .map(drop)
+            .map_err(|e| e.to_string())
+    }
 }
 
 impl Default for Frvp {
@@ -162,27 +176,25 @@ impl Default for Frvp {
     }
 }
 
-impl Indicator<&[OHLCV], FrvpOutput> for Frvp {
-    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<FrvpOutput> {
-        if data.is_empty() {
+impl Frvp {
+    /// Builds the profile from candles. The iterator is cloned to make
+    /// several passes, so it should be cheap to clone (a slice iterator).
+    fn profile<I>(&self, data: I) -> IndicatorResult<FrvpOutput>
+    where
+        I: Iterator<Item = OHLCV> + Clone,
+    {
+        if data.clone().next().is_none() {
             return Err(IndicatorError::InsufficientData {
                 required: 1,
                 provided: 0,
             });
         }
 
-        // Find the price range
-        let mut range_high = f64::NEG_INFINITY;
-        let mut range_low = f64::INFINITY;
-
-        for candle in data {
-            range_high = range_high.max(candle.high);
-            range_low = range_low.min(candle.low);
-        }
+        let (range_low, range_high) = price_range(data.clone())?;
 
         // Handle edge case: all prices are the same
         if (range_high - range_low).abs() < f64::EPSILON {
-            let total_volume: f64 = data.iter().map(|c| c.volume).sum();
+            let total_volume: f64 = data.clone().map(|c| c.volume).sum();
             let row = VolumeProfileRow {
                 price: range_high,
                 volume: total_volume,
@@ -306,6 +318,42 @@ impl Indicator<&[OHLCV], FrvpOutput> for Frvp {
     }
 }
 
+impl Indicator<&[OHLCV], FrvpOutput> for Frvp {
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<FrvpOutput> {
+        self.profile(data.iter().copied())
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+impl Frvp {
+    /// Calculates the volume profile from the candles of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slice.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<FrvpOutput> {
+        self.profile(data.iter())
+    }
+}
+
+/// Lowest low and highest high over the candles, rejecting any candle whose
+/// high, low or volume is not finite.
+fn price_range(data: impl Iterator<Item = OHLCV>) -> IndicatorResult<(f64, f64)> {
+    let mut range_high = f64::NEG_INFINITY;
+    let mut range_low = f64::INFINITY;
+
+    for (index, candle) in data.enumerate() {
+        if !(candle.high.is_finite() && candle.low.is_finite() && candle.volume.is_finite()) {
+            return Err(IndicatorError::NonFiniteInput { index });
+        }
+        range_high = range_high.max(candle.high);
+        range_low = range_low.min(candle.low);
+    }
+    Ok((range_low, range_high))
+}
+
 /// Calculate Value Area boundaries by expanding outward from POC.
 ///
 /// The algorithm:
@@ -366,6 +414,7 @@ fn calculate_value_area(bins: &[f64], poc_idx: usize, target_volume: f64) -> (us
 /// and recalculates on each update. This is still O(n) where n is the buffer size,
 /// but avoids re-passing all data from JS to WASM.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrvpStream {
     /// Number of price bins
     num_bins: usize,
@@ -405,9 +454,12 @@ impl FrvpStream {
             ));
         }
         if !(0.0..=1.0).contains(&value_area_percent) {
-            return Err(IndicatorError::InvalidParameter(
-                "value_area_percent must be between 0.0 and 1.0".to_string(),
-            ));
+            return Err(IndicatorError::OutOfRange {
+                name: "value_area_percent",
+                value: value_area_percent,
+                min: 0.0,
+                max: 1.0,
+            });
         }
 
         Ok(Self {
@@ -444,6 +496,14 @@ impl FrvpStream {
         };
         batch.calculate(&self.candles)
     }
+
+    /// Checks the parameters of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        Frvp::with_value_area(self.num_bins, self.value_area_percent)
+            .map(drop)
+            .map_err(|e| e.to_string())
+    }
 }
 
 impl StreamingIndicator<OHLCV, FrvpOutput> for FrvpStream {
@@ -465,6 +525,27 @@ impl StreamingIndicator<OHLCV, FrvpOutput> for FrvpStream {
         self.recalculate().ok()
     }
 
+    fn update(&mut self, candle: OHLCV) -> Option<FrvpOutput> {
+        match self.candles.last_mut() {
+            Some(last) => *last = candle,
+            None => self.candles.push(candle),
+        }
+        self.initialized = true;
+        self.recalculate().ok()
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<FrvpOutput> {
+        let mut candles = Vec::with_capacity(self.candles.len() + 1);
+        candles.extend_from_slice(&self.candles);
+        candles.push(candle);
+
+        let batch = Frvp {
+            num_bins: self.num_bins,
+            value_area_percent: self.value_area_percent,
+        };
+        batch.calculate(&candles).ok()
+    }
+
     fn reset(&mut self) {
         self.candles.clear();
         self.initialized = false;
@@ -473,6 +554,10 @@ impl StreamingIndicator<OHLCV, FrvpOutput> for FrvpStream {
     fn is_ready(&self) -> bool {
         self.initialized && !self.candles.is_empty()
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
 }
 
 // ============================================================================
@@ -542,7 +627,27 @@ mod tests {
     fn test_frvp_invalid_params() {
         assert!(Frvp::new(0).is_err());
         assert!(Frvp::with_value_area(10, -0.1).is_err());
-        assert!(Frvp::with_value_area(10, 1.5).is_err());
+        assert!(matches!(
+            Frvp::with_value_area(10, 1.5),
+            Err(IndicatorError::OutOfRange {
+                name: "value_area_percent",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_frvp_non_finite_input() {
+        let frvp = Frvp::new(10).unwrap();
+        let candles = [
+            make_candle(105.0, 100.0, 102.0, 1000.0),
+            make_candle(f64::NAN, 105.0, 108.0, 2000.0),
+        ];
+
+        assert!(matches!(
+            frvp.calculate(&candles[..]),
+            Err(IndicatorError::NonFiniteInput { index: 1 })
+        ));
     }
 
     #[test]
@@ -589,6 +694,26 @@ mod tests {
         assert!((output.total_volume - 4500.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_frvp_stream_update_replaces_last_candle() {
+        let mut stream = FrvpStream::new(10).unwrap();
+        stream
+            .init(&[
+                make_candle(105.0, 100.0, 102.0, 1000.0),
+                make_candle(110.0, 105.0, 108.0, 2000.0),
+            ])
+            .unwrap();
+
+        stream.next(make_candle(111.0, 108.0, 109.0, 500.0));
+        let output = stream
+            .update(make_candle(112.0, 108.0, 110.0, 1500.0))
+            .unwrap();
+
+        assert_eq!(stream.candle_count(), 3);
+        assert!((output.total_volume - 4500.0).abs() < 0.01);
+        assert!((output.range_high - 112.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_volume_distribution() {
         // Test that volume is correctly distributed across bins
@@ -604,4 +729,32 @@ mod tests {
             assert!((row.volume - 100.0).abs() < 1.0);
         }
     }
+
+    #[test]
+    fn test_frvp_columns_match_candles() {
+        let candles = vec![
+            make_candle(105.0, 100.0, 102.0, 1000.0),
+            make_candle(110.0, 105.0, 108.0, 2000.0),
+            make_candle(108.0, 102.0, 105.0, 1500.0),
+        ];
+        let timestamps = [0; 3];
+        let opens: Vec<f64> = candles.iter().map(|c| c.open).collect();
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let volumes: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+        let columns =
+            OhlcvColumns::new(&timestamps, &opens, &highs, &lows, &closes, &volumes).unwrap();
+
+        let frvp = Frvp::new(10).unwrap();
+        let expected = frvp.calculate(&candles).unwrap();
+        let result = frvp.calculate_columns(&columns).unwrap();
+        assert!((result.poc - expected.poc).abs() < 1e-12);
+        assert!((result.vah - expected.vah).abs() < 1e-12);
+        assert!((result.val - expected.val).abs() < 1e-12);
+        assert_eq!(result.histogram.len(), expected.histogram.len());
+
+        let empty = columns.slice(0..0);
+        assert!(frvp.calculate_columns(&empty).is_err());
+    }
 }
@@ -35,6 +35,9 @@ use crate::indicators::{Wma, WmaStream};
 use crate::traits::{Indicator, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
 /// Hull Moving Average calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Hma {
@@ -142,6 +145,10 @@ impl Indicator<&[f64], Vec<f64>> for Hma {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        Hma::lookback(self)
+    }
 }
 
 /// Streaming HMA calculator for real-time O(1) updates.
@@ -150,7 +157,8 @@ impl Indicator<&[f64], Vec<f64>> for Hma {
 /// - WMA(n/2) for the half-period
 /// - WMA(n) for the full period  
 /// - WMA(√n) for the final smoothing
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HmaStream {
     period: usize,
     half_period: usize,
@@ -208,6 +216,21 @@ impl HmaStream {
     pub const fn sqrt_period(&self) -> usize {
         self.sqrt_period
     }
+
+    /// Checks the inner WMAs of a restored stream against its periods.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(self.period >= 2, "period must be at least 2")?;
+        ensure(
+            self.wma_half.period() == self.half_period
+                && self.wma_full.period() == self.period
+                && self.wma_sqrt.period() == self.sqrt_period,
+            "WMA periods must match the stream",
+        )?;
+        self.wma_half.check_state()?;
+        self.wma_full.check_state()?;
+        self.wma_sqrt.check_state()
+    }
 }
 
 impl StreamingIndicator<f64, f64> for HmaStream {
@@ -237,6 +260,23 @@ impl StreamingIndicator<f64, f64> for HmaStream {
         }
     }
 
+    fn update(&mut self, value: f64) -> Option<f64> {
+        let wma_half_val = self.wma_half.update(value);
+        let wma_full_val = self.wma_full.update(value);
+
+        // If both WMAs are ready, the bar being amended also fed the sqrt WMA
+        match (wma_half_val, wma_full_val) {
+            (Some(half), Some(full)) => self.wma_sqrt.update(2.0 * half - full),
+            _ => None,
+        }
+    }
+
+    fn peek(&self, value: f64) -> Option<f64> {
+        let half = self.wma_half.peek(value)?;
+        let full = self.wma_full.peek(value)?;
+        self.wma_sqrt.peek(2.0 * half - full)
+    }
+
     fn reset(&mut self) {
         self.wma_half.reset();
         self.wma_full.reset();
@@ -249,6 +289,10 @@ impl StreamingIndicator<f64, f64> for HmaStream {
     fn is_ready(&self) -> bool {
         self.wma_sqrt.is_ready()
     }
+
+    fn lookback(&self) -> usize {
+        self.period + self.sqrt_period - 2
+    }
 }
 
 #[cfg(test)]
@@ -355,6 +399,29 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_hma_stream_update_matches_next() {
+        let prices = [
+            44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.85, 46.08, 45.89, 46.03, 46.83, 47.69,
+            46.49, 46.26, 47.09, 46.66,
+        ];
+        let mut live = HmaStream::new(9).unwrap();
+        let mut reference = HmaStream::new(9).unwrap();
+
+        for &price in &prices {
+            live.next(price + 0.5);
+            live.update(price - 0.25);
+            let amended = live.update(price);
+            let expected = reference.next(price);
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => assert_approx_eq(a, e),
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_hma_less_responsive_to_noise() {
         // HMA should be smoother than raw prices but responsive to trends
@@ -21,9 +21,12 @@
 //! - Senkou Span A/B: shift forward by kijun_period (26)
 //! - Chikou Span: shift backward by kijun_period (26)
 
-use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::{IndicatorError, IndicatorResult};
-use std::collections::VecDeque;
+use crate::indicators::monotonic::{rolling_max, rolling_min, Extremum, MonotonicDeque};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::{check_ring, ensure};
 
 /// Ichimoku Cloud output structure.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -54,6 +57,12 @@ impl IchimokuOutput {
     }
 }
 
+impl IndicatorOutput for IchimokuOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
 /// Ichimoku Cloud calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Ichimoku {
@@ -112,11 +121,13 @@ impl Ichimoku {
         self.senkou_b_period
     }
 
-    /// Calculate (highest high + lowest low) / 2 for a slice.
-    fn donchian_midpoint(highs: &[f64], lows: &[f64]) -> f64 {
-        let highest = highs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-        let lowest = lows.iter().copied().fold(f64::INFINITY, f64::min);
-        (highest + lowest) / 2.0
+    /// Rolling (highest high + lowest low) / 2, NaN until `period` bars are in.
+    fn donchian_midpoints(highs: &[f64], lows: &[f64], period: usize) -> Vec<f64> {
+        rolling_max(highs, period)
+            .into_iter()
+            .zip(rolling_min(lows, period))
+            .map(|(highest, lowest)| (highest + lowest) / 2.0)
+            .collect()
     }
 }
 
@@ -128,11 +139,11 @@ impl Indicator<&IchimokuInput<'_>, Vec<IchimokuOutput>> for Ichimoku {
         let (highs, lows, closes) = *data;
         let len = highs.len();
 
-        if lows.len() != len || closes.len() != len {
-            return Err(IndicatorError::InvalidParameter(
-                "highs, lows, and closes must have the same length".to_string(),
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
 
         let mut result = vec![IchimokuOutput::nan(); len];
 
@@ -140,31 +151,22 @@ impl Indicator<&IchimokuInput<'_>, Vec<IchimokuOutput>> for Ichimoku {
             return Ok(result);
         }
 
+        // One O(n) pass per line instead of rescanning every window
+        let tenkan = Self::donchian_midpoints(highs, lows, self.tenkan_period);
+        let kijun = Self::donchian_midpoints(highs, lows, self.kijun_period);
+        let senkou_b = Self::donchian_midpoints(highs, lows, self.senkou_b_period);
+
         for i in 0..len {
             let mut output = IchimokuOutput::nan();
-
-            // Tenkan-sen: available after tenkan_period values
-            if i >= self.tenkan_period - 1 {
-                let start = i + 1 - self.tenkan_period;
-                output.tenkan_sen = Self::donchian_midpoint(&highs[start..=i], &lows[start..=i]);
-            }
-
-            // Kijun-sen: available after kijun_period values
-            if i >= self.kijun_period - 1 {
-                let start = i + 1 - self.kijun_period;
-                output.kijun_sen = Self::donchian_midpoint(&highs[start..=i], &lows[start..=i]);
-            }
+            output.tenkan_sen = tenkan[i];
+            output.kijun_sen = kijun[i];
 
             // Senkou Span A: average of Tenkan and Kijun (when both available)
             if !output.tenkan_sen.is_nan() && !output.kijun_sen.is_nan() {
                 output.senkou_span_a = (output.tenkan_sen + output.kijun_sen) / 2.0;
             }
 
-            // Senkou Span B: available after senkou_b_period values
-            if i >= self.senkou_b_period - 1 {
-                let start = i + 1 - self.senkou_b_period;
-                output.senkou_span_b = Self::donchian_midpoint(&highs[start..=i], &lows[start..=i]);
-            }
+            output.senkou_span_b = senkou_b[i];
 
             // Chikou Span: just the close (caller handles the offset)
             output.chikou_span = closes[i];
@@ -174,6 +176,26 @@ impl Indicator<&IchimokuInput<'_>, Vec<IchimokuOutput>> for Ichimoku {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        self.tenkan_period
+            .max(self.kijun_period)
+            .max(self.senkou_b_period)
+            - 1
+    }
+}
+
+impl Ichimoku {
+    /// Calculates the Ichimoku Cloud from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(
+        &self,
+        data: &OhlcvColumns<'_>,
+    ) -> IndicatorResult<Vec<IchimokuOutput>> {
+        self.calculate(&(data.high(), data.low(), data.close()))
+    }
 }
 
 /// Input bar for Ichimoku streaming: (high, low, close)
@@ -182,7 +204,8 @@ pub type IchimokuBar = (f64, f64, f64);
 /// Streaming Ichimoku Cloud calculator for real-time O(1) updates.
 ///
 /// Uses monotonic deques to track min/max efficiently for each period.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IchimokuStream {
     tenkan_period: usize,
     kijun_period: usize,
@@ -195,14 +218,14 @@ pub struct IchimokuStream {
     count: usize,
     // Monotonic deques for efficient min/max tracking
     // For tenkan (shortest period)
-    tenkan_max_deque: VecDeque<(usize, f64)>,
-    tenkan_min_deque: VecDeque<(usize, f64)>,
+    tenkan_max_deque: MonotonicDeque,
+    tenkan_min_deque: MonotonicDeque,
     // For kijun (medium period)
-    kijun_max_deque: VecDeque<(usize, f64)>,
-    kijun_min_deque: VecDeque<(usize, f64)>,
+    kijun_max_deque: MonotonicDeque,
+    kijun_min_deque: MonotonicDeque,
     // For senkou_b (longest period)
-    senkou_max_deque: VecDeque<(usize, f64)>,
-    senkou_min_deque: VecDeque<(usize, f64)>,
+    senkou_max_deque: MonotonicDeque,
+    senkou_min_deque: MonotonicDeque,
 }
 
 impl IchimokuStream {
@@ -233,12 +256,12 @@ impl IchimokuStream {
             close_buffer: vec![0.0; max_period],
             head: 0,
             count: 0,
-            tenkan_max_deque: VecDeque::new(),
-            tenkan_min_deque: VecDeque::new(),
-            kijun_max_deque: VecDeque::new(),
-            kijun_min_deque: VecDeque::new(),
-            senkou_max_deque: VecDeque::new(),
-            senkou_min_deque: VecDeque::new(),
+            tenkan_max_deque: MonotonicDeque::new(Extremum::Max, tenkan_period),
+            tenkan_min_deque: MonotonicDeque::new(Extremum::Min, tenkan_period),
+            kijun_max_deque: MonotonicDeque::new(Extremum::Max, kijun_period),
+            kijun_min_deque: MonotonicDeque::new(Extremum::Min, kijun_period),
+            senkou_max_deque: MonotonicDeque::new(Extremum::Max, senkou_b_period),
+            senkou_min_deque: MonotonicDeque::new(Extremum::Min, senkou_b_period),
         })
     }
 
@@ -265,65 +288,47 @@ impl IchimokuStream {
         self.senkou_b_period
     }
 
-    /// Update a monotonic deque for max values.
-    fn update_max_deque(
-        deque: &mut VecDeque<(usize, f64)>,
-        index: usize,
-        value: f64,
-        period: usize,
-    ) {
-        // Remove elements older than the window
-        while let Some(&(idx, _)) = deque.front() {
-            if index >= period && idx <= index - period {
-                deque.pop_front();
-            } else {
-                break;
-            }
-        }
-        // Remove smaller elements from back
-        while let Some(&(_, v)) = deque.back() {
-            if v <= value {
-                deque.pop_back();
-            } else {
-                break;
-            }
-        }
-        deque.push_back((index, value));
-    }
-
-    /// Update a monotonic deque for min values.
-    fn update_min_deque(
-        deque: &mut VecDeque<(usize, f64)>,
-        index: usize,
-        value: f64,
-        period: usize,
-    ) {
-        // Remove elements older than the window
-        while let Some(&(idx, _)) = deque.front() {
-            if index >= period && idx <= index - period {
-                deque.pop_front();
-            } else {
-                break;
-            }
-        }
-        // Remove larger elements from back
-        while let Some(&(_, v)) = deque.back() {
-            if v >= value {
-                deque.pop_back();
-            } else {
-                break;
-            }
-        }
-        deque.push_back((index, value));
-    }
-
     /// Get midpoint from max and min deques.
-    fn get_midpoint(max_deque: &VecDeque<(usize, f64)>, min_deque: &VecDeque<(usize, f64)>) -> f64 {
+    fn get_midpoint(max_deque: &MonotonicDeque, min_deque: &MonotonicDeque) -> f64 {
         match (max_deque.front(), min_deque.front()) {
-            (Some(&(_, max)), Some(&(_, min))) => (max + min) / 2.0,
+            (Some(max), Some(min)) => (max + min) / 2.0,
             _ => f64::NAN,
         }
     }
+
+    /// Checks the ring buffers and deques of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(
+            self.tenkan_period > 0 && self.kijun_period > 0 && self.senkou_b_period > 0,
+            "all periods must be greater than 0",
+        )?;
+        let max_period = self
+            .senkou_b_period
+            .max(self.kijun_period)
+            .max(self.tenkan_period);
+        for buffer in [&self.high_buffer, &self.low_buffer, &self.close_buffer] {
+            check_ring(max_period, buffer.len(), self.head)?;
+        }
+        let (count, tenkan, kijun, senkou) = (
+            self.count,
+            self.tenkan_period,
+            self.kijun_period,
+            self.senkou_b_period,
+        );
+        self.tenkan_max_deque
+            .check_state(Extremum::Max, tenkan, count)?;
+        self.tenkan_min_deque
+            .check_state(Extremum::Min, tenkan, count)?;
+        self.kijun_max_deque
+            .check_state(Extremum::Max, kijun, count)?;
+        self.kijun_min_deque
+            .check_state(Extremum::Min, kijun, count)?;
+        self.senkou_max_deque
+            .check_state(Extremum::Max, senkou, count)?;
+        self.senkou_min_deque
+            .check_state(Extremum::Min, senkou, count)
+    }
 }
 
 impl StreamingIndicator<IchimokuBar, IchimokuOutput> for IchimokuStream {
@@ -350,14 +355,14 @@ impl StreamingIndicator<IchimokuBar, IchimokuOutput> for IchimokuStream {
         self.close_buffer[buf_idx] = close;
 
         // Update all deques with new high value
-        Self::update_max_deque(&mut self.tenkan_max_deque, idx, high, self.tenkan_period);
-        Self::update_max_deque(&mut self.kijun_max_deque, idx, high, self.kijun_period);
-        Self::update_max_deque(&mut self.senkou_max_deque, idx, high, self.senkou_b_period);
+        self.tenkan_max_deque.push(idx, high);
+        self.kijun_max_deque.push(idx, high);
+        self.senkou_max_deque.push(idx, high);
 
         // Update all deques with new low value
-        Self::update_min_deque(&mut self.tenkan_min_deque, idx, low, self.tenkan_period);
-        Self::update_min_deque(&mut self.kijun_min_deque, idx, low, self.kijun_period);
-        Self::update_min_deque(&mut self.senkou_min_deque, idx, low, self.senkou_b_period);
+        self.tenkan_min_deque.push(idx, low);
+        self.kijun_min_deque.push(idx, low);
+        self.senkou_min_deque.push(idx, low);
 
         let mut output = IchimokuOutput::nan();
 
@@ -388,6 +393,50 @@ impl StreamingIndicator<IchimokuBar, IchimokuOutput> for IchimokuStream {
         Some(output)
     }
 
+    fn update(&mut self, bar: IchimokuBar) -> Option<IchimokuOutput> {
+        if self.count == 0 {
+            return self.next(bar);
+        }
+
+        // Ring buffer slot is simply overwritten again; only the deques need undoing
+        self.count -= 1;
+        self.tenkan_max_deque.rollback();
+        self.kijun_max_deque.rollback();
+        self.senkou_max_deque.rollback();
+        self.tenkan_min_deque.rollback();
+        self.kijun_min_deque.rollback();
+        self.senkou_min_deque.rollback();
+
+        self.next(bar)
+    }
+
+    fn peek(&self, bar: IchimokuBar) -> Option<IchimokuOutput> {
+        let (high, low, close) = bar;
+        let idx = self.count;
+        let count = self.count + 1;
+
+        let midpoint = |max_deque: &MonotonicDeque, min_deque: &MonotonicDeque| {
+            (max_deque.peek(idx, high) + min_deque.peek(idx, low)) / 2.0
+        };
+
+        let mut output = IchimokuOutput::nan();
+        if count >= self.tenkan_period {
+            output.tenkan_sen = midpoint(&self.tenkan_max_deque, &self.tenkan_min_deque);
+        }
+        if count >= self.kijun_period {
+            output.kijun_sen = midpoint(&self.kijun_max_deque, &self.kijun_min_deque);
+        }
+        if !output.tenkan_sen.is_nan() && !output.kijun_sen.is_nan() {
+            output.senkou_span_a = (output.tenkan_sen + output.kijun_sen) / 2.0;
+        }
+        if count >= self.senkou_b_period {
+            output.senkou_span_b = midpoint(&self.senkou_max_deque, &self.senkou_min_deque);
+        }
+        output.chikou_span = close;
+
+        Some(output)
+    }
+
     fn reset(&mut self) {
         self.high_buffer.fill(0.0);
         self.low_buffer.fill(0.0);
@@ -406,6 +455,13 @@ impl StreamingIndicator<IchimokuBar, IchimokuOutput> for IchimokuStream {
         // Ready when we can compute all components
         self.count >= self.senkou_b_period
     }
+
+    fn lookback(&self) -> usize {
+        self.tenkan_period
+            .max(self.kijun_period)
+            .max(self.senkou_b_period)
+            - 1
+    }
 }
 
 #[cfg(test)]
@@ -486,8 +542,18 @@ mod tests {
 
         // Highest high = 12, Lowest low = 7
         // Midpoint = (12 + 7) / 2 = 9.5
-        let midpoint = Ichimoku::donchian_midpoint(&highs, &lows);
-        assert_approx_eq(midpoint, 9.5);
+        let midpoints = Ichimoku::donchian_midpoints(&highs, &lows, 3);
+        assert!(midpoints[1].is_nan());
+        assert_approx_eq(midpoints[2], 9.5);
+
+        // A NaN high is skipped, as a scan with f64::max would
+        let highs = [5.0, f64::NAN, 6.0, 1.0, 2.0];
+        let lows = [1.0; 5];
+        let midpoints = Ichimoku::donchian_midpoints(&highs, &lows, 3);
+        assert!(midpoints[1].is_nan());
+        for &midpoint in &midpoints[2..] {
+            assert_approx_eq(midpoint, 3.5);
+        }
     }
 
     #[test]
@@ -554,6 +620,39 @@ mod tests {
         assert!(!result.senkou_span_b.is_nan());
     }
 
+    #[test]
+    fn test_ichimoku_stream_update_matches_next() {
+        let bars: Vec<IchimokuBar> = (0..40)
+            .map(|i| {
+                let base = 100.0 + (f64::from(i) * 0.4).sin() * 8.0;
+                (base + 1.5, base - 1.5, base)
+            })
+            .collect();
+        let mut live = IchimokuStream::new(3, 5, 8).unwrap();
+        let mut reference = IchimokuStream::new(3, 5, 8).unwrap();
+
+        for &(high, low, close) in &bars {
+            live.next((high + 10.0, low - 10.0, close));
+            live.update((close, close, close));
+            let a = live.update((high, low, close)).unwrap();
+            let e = reference.next((high, low, close)).unwrap();
+
+            for (x, y) in [
+                (a.tenkan_sen, e.tenkan_sen),
+                (a.kijun_sen, e.kijun_sen),
+                (a.senkou_span_a, e.senkou_span_a),
+                (a.senkou_span_b, e.senkou_span_b),
+                (a.chikou_span, e.chikou_span),
+            ] {
+                if y.is_nan() {
+                    assert!(x.is_nan());
+                } else {
+                    assert_approx_eq(x, y);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_ichimoku_empty_data() {
         let ich = Ichimoku::new(9, 26, 52).unwrap();
@@ -0,0 +1,629 @@
+//! Keltner Channels indicator.
+//!
+//! Keltner Channels are a volatility envelope around a moving average of the
+//! typical price. Unlike Bollinger Bands, the band distance comes from the
+//! bar range (ATR) rather than the standard deviation of closes, which makes
+//! the pair the usual basis for "squeeze" setups.
+//!
+//! # Components
+//! - **Middle Line**: EMA (or SMA) of the typical price `(H + L + C) / 3`
+//! - **Upper Band**: Middle + (multiplier × ATR)
+//! - **Lower Band**: Middle - (multiplier × ATR)
+//! - **Width**: (Upper - Lower) / Middle — same scale as Bollinger bandwidth
+//!
+//! The band can alternatively use the average high-low range, smoothed with
+//! the same moving average type as the middle line (Keltner's original form).
+//!
+//! # Default Parameters
+//! - Period: 20
+//! - ATR period: 10
+//! - Multiplier: 2.0
+//!
+//! # Example (Batch Mode)
+//! ```
+//! use ta_core::indicators::{Keltner, KeltnerMa};
+//! use ta_core::traits::Indicator;
+//!
+//! let keltner = Keltner::new(20, 10, 2.0).unwrap().with_ma(KeltnerMa::Sma);
+//! let highs: Vec<f64> = (1..=30).map(|x| x as f64 + 1.0).collect();
+//! let lows: Vec<f64> = (1..=30).map(|x| x as f64 - 1.0).collect();
+//! let closes: Vec<f64> = (1..=30).map(|x| x as f64).collect();
+//! let result = keltner.calculate(&(&highs[..], &lows[..], &closes[..])).unwrap();
+//! assert!(result[29].upper > result[29].middle);
+//! ```
+
+use crate::indicators::{AtrStream, EmaStream, SmaStream};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
+/// Keltner Channels output containing all components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeltnerOutput {
+    /// Upper band (middle + multiplier * band)
+    pub upper: f64,
+    /// Middle line (moving average of typical price)
+    pub middle: f64,
+    /// Lower band (middle - multiplier * band)
+    pub lower: f64,
+    /// Width: (upper - lower) / middle
+    pub width: f64,
+}
+
+impl KeltnerOutput {
+    /// Creates a Keltner output from the middle line and band offset.
+    #[must_use]
+    pub fn new(middle: f64, offset: f64) -> Self {
+        let upper = middle + offset;
+        let lower = middle - offset;
+        let width = if middle > 0.0 {
+            (upper - lower) / middle
+        } else {
+            0.0
+        };
+        Self {
+            upper,
+            middle,
+            lower,
+            width,
+        }
+    }
+
+    /// Creates a NaN output for insufficient data.
+    #[must_use]
+    pub fn nan() -> Self {
+        Self {
+            upper: f64::NAN,
+            middle: f64::NAN,
+            lower: f64::NAN,
+            width: f64::NAN,
+        }
+    }
+
+    /// Returns true if any band is NaN.
+    #[must_use]
+    pub fn is_nan(&self) -> bool {
+        self.upper.is_nan() || self.middle.is_nan() || self.lower.is_nan()
+    }
+}
+
+impl IndicatorOutput for KeltnerOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
+/// Moving average used for the middle line (and the range band).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeltnerMa {
+    /// Exponential moving average (default)
+    #[default]
+    Ema,
+    /// Simple moving average
+    Sma,
+}
+
+/// Volatility measure used for the band distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeltnerBand {
+    /// Wilder's Average True Range over the ATR period (default)
+    #[default]
+    Atr,
+    /// Average high-low range over the ATR period, smoothed with the middle line's MA
+    Range,
+}
+
+/// Input type for Keltner Channels: (highs, lows, closes)
+pub type KeltnerInput<'a> = (&'a [f64], &'a [f64], &'a [f64]);
+
+/// Input for streaming: (high, low, close) tuple
+pub type KeltnerBar = (f64, f64, f64);
+
+/// Keltner Channels calculator for batch operations.
+#[derive(Debug, Clone)]
+pub struct Keltner {
+    period: usize,
+    atr_period: usize,
+    multiplier: f64,
+    ma: KeltnerMa,
+    band: KeltnerBand,
+}
+
+impl Keltner {
+    /// Creates a new Keltner Channels calculator with an EMA middle line and ATR bands.
+    ///
+    /// # Arguments
+    /// * `period` - The middle line period (typically 20)
+    /// * `atr_period` - The ATR (or range) period (typically 10)
+    /// * `multiplier` - Band multiplier (typically 2.0)
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if either period is 0 or the multiplier is not positive.
+    pub fn new(period: usize, atr_period: usize, multiplier: f64) -> IndicatorResult<Self> {
+        validate(period, atr_period, multiplier)?;
+        Ok(Self {
+            period,
+            atr_period,
+            multiplier,
+            ma: KeltnerMa::Ema,
+            band: KeltnerBand::Atr,
+        })
+    }
+
+    /// Creates with default parameters (20, 10, 2.0).
+    pub fn default_params() -> IndicatorResult<Self> {
+        Self::new(20, 10, 2.0)
+    }
+
+    /// Sets the moving average used for the middle line.
+    #[must_use]
+    pub const fn with_ma(mut self, ma: KeltnerMa) -> Self {
+        self.ma = ma;
+        self
+    }
+
+    /// Sets the volatility measure used for the bands.
+    #[must_use]
+    pub const fn with_band(mut self, band: KeltnerBand) -> Self {
+        self.band = band;
+        self
+    }
+
+    /// Returns the middle line period.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the ATR (or range) period.
+    #[must_use]
+    pub const fn atr_period(&self) -> usize {
+        self.atr_period
+    }
+
+    /// Returns the band multiplier.
+    #[must_use]
+    pub const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Returns the middle line moving average type.
+    #[must_use]
+    pub const fn ma(&self) -> KeltnerMa {
+        self.ma
+    }
+
+    /// Returns the band volatility measure.
+    #[must_use]
+    pub const fn band(&self) -> KeltnerBand {
+        self.band
+    }
+
+    /// Creates a streaming calculator with the same configuration.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the configuration is invalid.
+    pub fn stream(&self) -> IndicatorResult<KeltnerStream> {
+        KeltnerStream::with_options(
+            self.period,
+            self.atr_period,
+            self.multiplier,
+            self.ma,
+            self.band,
+        )
+    }
+}
+
+impl Indicator<&KeltnerInput<'_>, Vec<KeltnerOutput>> for Keltner {
+    fn calculate(&self, data: &KeltnerInput<'_>) -> IndicatorResult<Vec<KeltnerOutput>> {
+        let (highs, lows, closes) = *data;
+
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
+
+        let bars: Vec<KeltnerBar> = highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .map(|((&h, &l), &c)| (h, l, c))
+            .collect();
+
+        self.stream()?.init(&bars)
+    }
+
+    fn lookback(&self) -> usize {
+        self.period.max(self.atr_period) - 1
+    }
+}
+
+impl Keltner {
+    /// Calculates Keltner Channels from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(
+        &self,
+        data: &OhlcvColumns<'_>,
+    ) -> IndicatorResult<Vec<KeltnerOutput>> {
+        self.calculate(&(data.high(), data.low(), data.close()))
+    }
+}
+
+fn validate(period: usize, atr_period: usize, multiplier: f64) -> IndicatorResult<()> {
+    if period == 0 || atr_period == 0 {
+        return Err(IndicatorError::InvalidParameter(
+            "all periods must be greater than 0".to_string(),
+        ));
+    }
+    if multiplier <= 0.0 || !multiplier.is_finite() {
+        return Err(IndicatorError::InvalidParameter(
+            "multiplier must be a positive finite number".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Either moving average, dispatched per bar.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum MaStream {
+    Ema(EmaStream),
+    Sma(SmaStream),
+}
+
+impl MaStream {
+    fn new(ma: KeltnerMa, period: usize) -> IndicatorResult<Self> {
+        Ok(match ma {
+            KeltnerMa::Ema => Self::Ema(EmaStream::new(period)?),
+            KeltnerMa::Sma => Self::Sma(SmaStream::new(period)?),
+        })
+    }
+
+    fn next(&mut self, value: f64) -> Option<f64> {
+        match self {
+            Self::Ema(ema) => ema.next(value),
+            Self::Sma(sma) => sma.next(value),
+        }
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        match self {
+            Self::Ema(ema) => ema.update(value),
+            Self::Sma(sma) => sma.update(value),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::Ema(ema) => ema.reset(),
+            Self::Sma(sma) => sma.reset(),
+        }
+    }
+
+    fn peek(&self, value: f64) -> Option<f64> {
+        match self {
+            Self::Ema(ema) => ema.peek(value),
+            Self::Sma(sma) => sma.peek(value),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self {
+            Self::Ema(ema) => ema.is_ready(),
+            Self::Sma(sma) => sma.is_ready(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn check_state(&self, period: usize) -> Result<(), String> {
+        let (state, ma_period) = match self {
+            Self::Ema(ema) => (ema.check_state(), ema.period()),
+            Self::Sma(sma) => (sma.check_state(), sma.period()),
+        };
+        state?;
+        ensure(
+            ma_period == period,
+            "moving average period must match the stream",
+        )
+    }
+}
+
+/// Band volatility source: ATR over (H, L, C) or an average of H - L.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum BandStream {
+    Atr(AtrStream),
+    Range(MaStream),
+}
+
+impl BandStream {
+    fn next(&mut self, bar: KeltnerBar) -> Option<f64> {
+        match self {
+            Self::Atr(atr) => atr.next(bar),
+            Self::Range(ma) => ma.next(bar.0 - bar.1),
+        }
+    }
+
+    fn update(&mut self, bar: KeltnerBar) -> Option<f64> {
+        match self {
+            Self::Atr(atr) => atr.update(bar),
+            Self::Range(ma) => ma.update(bar.0 - bar.1),
+        }
+    }
+
+    fn peek(&self, bar: KeltnerBar) -> Option<f64> {
+        match self {
+            Self::Atr(atr) => atr.peek(bar),
+            Self::Range(ma) => ma.peek(bar.0 - bar.1),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::Atr(atr) => atr.reset(),
+            Self::Range(ma) => ma.reset(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self {
+            Self::Atr(atr) => atr.is_ready(),
+            Self::Range(ma) => ma.is_ready(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn check_state(&self, period: usize) -> Result<(), String> {
+        match self {
+            Self::Atr(atr) => {
+                atr.check_state()?;
+                ensure(atr.period() == period, "ATR period must match the stream")
+            }
+            Self::Range(ma) => ma.check_state(period),
+        }
+    }
+}
+
+/// Keltner Channels calculator for streaming/real-time operations.
+///
+/// Composes an `EmaStream`/`SmaStream` over the typical price with an
+/// `AtrStream` (or averaged range), so each bar is O(1).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeltnerStream {
+    period: usize,
+    atr_period: usize,
+    multiplier: f64,
+    middle: MaStream,
+    band: BandStream,
+}
+
+impl KeltnerStream {
+    /// Creates a new streaming Keltner calculator with an EMA middle line and ATR bands.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if either period is 0 or the multiplier is not positive.
+    pub fn new(period: usize, atr_period: usize, multiplier: f64) -> IndicatorResult<Self> {
+        Self::with_options(
+            period,
+            atr_period,
+            multiplier,
+            KeltnerMa::Ema,
+            KeltnerBand::Atr,
+        )
+    }
+
+    /// Creates a new streaming Keltner calculator with custom middle line and band types.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if either period is 0 or the multiplier is not positive.
+    pub fn with_options(
+        period: usize,
+        atr_period: usize,
+        multiplier: f64,
+        ma: KeltnerMa,
+        band: KeltnerBand,
+    ) -> IndicatorResult<Self> {
+        validate(period, atr_period, multiplier)?;
+        let band = match band {
+            KeltnerBand::Atr => BandStream::Atr(AtrStream::new(atr_period)?),
+            KeltnerBand::Range => BandStream::Range(MaStream::new(ma, atr_period)?),
+        };
+        Ok(Self {
+            period,
+            atr_period,
+            multiplier,
+            middle: MaStream::new(ma, period)?,
+            band,
+        })
+    }
+
+    /// Returns the middle line period.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the ATR (or range) period.
+    #[must_use]
+    pub const fn atr_period(&self) -> usize {
+        self.atr_period
+    }
+
+    /// Returns the band multiplier.
+    #[must_use]
+    pub const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    #[inline]
+    fn typical_price((high, low, close): KeltnerBar) -> f64 {
+        (high + low + close) / 3.0
+    }
+
+    fn output(&self, middle: Option<f64>, band: Option<f64>) -> Option<KeltnerOutput> {
+        Some(KeltnerOutput::new(middle?, self.multiplier * band?))
+    }
+
+    /// Checks the inner streams of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        validate(self.period, self.atr_period, self.multiplier).map_err(|e| e.to_string())?;
+        self.middle.check_state(self.period)?;
+        self.band.check_state(self.atr_period)
+    }
+}
+
+impl StreamingIndicator<KeltnerBar, KeltnerOutput> for KeltnerStream {
+    fn init(&mut self, data: &[KeltnerBar]) -> IndicatorResult<Vec<KeltnerOutput>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &bar in data {
+            results.push(self.next(bar).unwrap_or_else(KeltnerOutput::nan));
+        }
+        Ok(results)
+    }
+
+    fn next(&mut self, bar: KeltnerBar) -> Option<KeltnerOutput> {
+        let middle = self.middle.next(Self::typical_price(bar));
+        let band = self.band.next(bar);
+        self.output(middle, band)
+    }
+
+    fn update(&mut self, bar: KeltnerBar) -> Option<KeltnerOutput> {
+        // Both inner streams saw the amended bar, so amend it in each
+        let middle = self.middle.update(Self::typical_price(bar));
+        let band = self.band.update(bar);
+        self.output(middle, band)
+    }
+
+    fn peek(&self, bar: KeltnerBar) -> Option<KeltnerOutput> {
+        let middle = self.middle.peek(Self::typical_price(bar));
+        let band = self.band.peek(bar);
+        self.output(middle, band)
+    }
+
+    fn reset(&mut self) {
+        self.middle.reset();
+        self.band.reset();
+    }
+
+    fn is_ready(&self) -> bool {
+        self.middle.is_ready() && self.band.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        self.period.max(self.atr_period) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Atr;
+
+    const EPSILON: f64 = 1e-10;
+
+    fn assert_approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < EPSILON, "expected {b}, got {a}");
+    }
+
+    fn sample() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let closes: Vec<f64> = (0..40)
+            .map(|i| 100.0 + (f64::from(i) * 0.7).sin() * 5.0 + f64::from(i) * 0.2)
+            .collect();
+        let highs = closes.iter().map(|c| c + 1.5).collect();
+        let lows = closes.iter().map(|c| c - 1.0).collect();
+        (highs, lows, closes)
+    }
+
+    #[test]
+    fn test_keltner_ema_atr_matches_components() {
+        let (highs, lows, closes) = sample();
+        let keltner = Keltner::new(20, 10, 2.0).unwrap();
+        let result = keltner
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        let typical: Vec<f64> = (0..closes.len())
+            .map(|i| (highs[i] + lows[i] + closes[i]) / 3.0)
+            .collect();
+        let ema = EmaStream::new(20).unwrap().init(&typical).unwrap();
+        let atr = Atr::new(10)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        assert!(result[18].is_nan());
+        for i in 19..closes.len() {
+            assert_approx_eq(result[i].middle, ema[i]);
+            assert_approx_eq(result[i].upper, ema[i] + 2.0 * atr[i]);
+            assert_approx_eq(result[i].lower, ema[i] - 2.0 * atr[i]);
+            assert_approx_eq(result[i].width, 4.0 * atr[i] / ema[i]);
+        }
+        assert_eq!(
+            <Keltner as Indicator<&KeltnerInput<'_>, _>>::lookback(&keltner),
+            19
+        );
+    }
+
+    #[test]
+    fn test_keltner_sma_range_band() {
+        let highs = [11.0, 12.0, 14.0, 13.0];
+        let lows = [9.0, 10.0, 10.0, 12.0];
+        let closes = [10.0, 11.0, 12.0, 12.5];
+        let keltner = Keltner::new(3, 2, 1.5)
+            .unwrap()
+            .with_ma(KeltnerMa::Sma)
+            .with_band(KeltnerBand::Range);
+        let result = keltner
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        assert!(result[1].is_nan());
+        // Typical prices: 10, 11, 12, 12.5; ranges: 2, 2, 4, 1
+        assert_approx_eq(result[2].middle, 11.0);
+        assert_approx_eq(result[2].upper, 11.0 + 1.5 * 3.0);
+        assert_approx_eq(result[3].middle, 35.5 / 3.0);
+        assert_approx_eq(result[3].lower, 35.5 / 3.0 - 1.5 * 2.5);
+
+        assert!(Keltner::new(0, 10, 2.0).is_err());
+        assert!(Keltner::new(20, 10, 0.0).is_err());
+        assert!(Keltner::new(20, 10, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_keltner_stream_matches_batch_and_update() {
+        let (highs, lows, closes) = sample();
+        let keltner = Keltner::new(10, 5, 2.5)
+            .unwrap()
+            .with_band(KeltnerBand::Range);
+        let batch = keltner
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        let bars: Vec<KeltnerBar> = (0..closes.len())
+            .map(|i| (highs[i], lows[i], closes[i]))
+            .collect();
+        let mut stream = keltner.stream().unwrap();
+        stream.init(&bars[..30]).unwrap();
+        assert!(stream.is_ready());
+
+        for (i, &bar) in bars.iter().enumerate().skip(30) {
+            let peeked = stream.peek(bar).unwrap();
+            stream.next((bar.0 + 3.0, bar.1, bar.2 + 2.0));
+            let out = stream.update(bar).unwrap();
+            assert_approx_eq(out.middle, batch[i].middle);
+            assert_approx_eq(out.upper, batch[i].upper);
+            assert_approx_eq(peeked.lower, batch[i].lower);
+        }
+    }
+}
@@ -30,9 +30,12 @@
 //! - R close to 0: Weak correlation (sideways/noise)
 //! - R² indicates how much of the price movement is explained by the trend
 
-use crate::traits::{Indicator, StreamingIndicator};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::{check_ring, ensure};
+
 /// Linear Regression output structure.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct LinRegOutput {
@@ -65,6 +68,12 @@ impl LinRegOutput {
     }
 }
 
+impl IndicatorOutput for LinRegOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
 /// Linear Regression calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct LinReg {
@@ -80,7 +89,8 @@ impl LinReg {
     /// * `num_std_dev` - Number of standard deviations for the bands (default: 2.0)
     ///
     /// # Errors
-    /// Returns `InvalidParameter` if period is less than 2.
+    /// Returns `InvalidParameter` if period is less than 2, or `OutOfRange`
+    /// if `num_std_dev` is negative.
     pub fn new(period: usize, num_std_dev: f64) -> IndicatorResult<Self> {
         if period < 2 {
             return Err(IndicatorError::InvalidParameter(
@@ -88,9 +98,12 @@ impl LinReg {
             ));
         }
         if num_std_dev < 0.0 {
-            return Err(IndicatorError::InvalidParameter(
-                "num_std_dev must be non-negative".to_string(),
-            ));
+            return Err(IndicatorError::OutOfRange {
+                name: "num_std_dev",
+                value: num_std_dev,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
         }
         Ok(Self {
             period,
@@ -199,6 +212,10 @@ impl Indicator<&[f64], Vec<LinRegOutput>> for LinReg {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 /// Streaming Linear Regression calculator for real-time updates.
@@ -206,7 +223,8 @@ impl Indicator<&[f64], Vec<LinRegOutput>> for LinReg {
 /// Maintains a ring buffer for O(1) sliding window updates.
 /// Note: The full regression calculation is still O(period) per update,
 /// but data management is O(1).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinRegStream {
     period: usize,
     num_std_dev: f64,
@@ -219,7 +237,8 @@ impl LinRegStream {
     /// Creates a new streaming Linear Regression calculator.
     ///
     /// # Errors
-    /// Returns `InvalidParameter` if period is less than 2.
+    /// Returns `InvalidParameter` if period is less than 2, or `OutOfRange`
+    /// if `num_std_dev` is negative.
     pub fn new(period: usize, num_std_dev: f64) -> IndicatorResult<Self> {
         if period < 2 {
             return Err(IndicatorError::InvalidParameter(
@@ -227,9 +246,12 @@ impl LinRegStream {
             ));
         }
         if num_std_dev < 0.0 {
-            return Err(IndicatorError::InvalidParameter(
-                "num_std_dev must be non-negative".to_string(),
-            ));
+            return Err(IndicatorError::OutOfRange {
+                name: "num_std_dev",
+                value: num_std_dev,
+                min: 0.0,
+                max: f64::INFINITY,
+            });
         }
         Ok(Self {
             period,
@@ -266,6 +288,31 @@ impl LinRegStream {
         }
         data
     }
+
+    /// Regression output over `data`, oldest first.
+    fn regression_output(&self, data: &[f64]) -> LinRegOutput {
+        let (reg_value, slope, r, r_squared, std_dev) = LinReg::calculate_regression(data);
+
+        LinRegOutput {
+            value: reg_value,
+            upper: reg_value + self.num_std_dev * std_dev,
+            lower: reg_value - self.num_std_dev * std_dev,
+            slope,
+            r,
+            r_squared,
+        }
+    }
+
+    /// Checks the ring buffer and counter of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(self.period >= 2, "period must be at least 2")?;
+        check_ring(self.period, self.buffer.len(), self.head)?;
+        ensure(
+            self.count <= self.period,
+            "count must not exceed the period",
+        )
+    }
 }
 
 impl StreamingIndicator<f64, LinRegOutput> for LinRegStream {
@@ -287,17 +334,7 @@ impl StreamingIndicator<f64, LinRegOutput> for LinRegStream {
 
             if self.count == self.period {
                 // Calculate first regression
-                let (reg_value, slope, r, r_squared, std_dev) =
-                    LinReg::calculate_regression(&self.buffer);
-
-                return Some(LinRegOutput {
-                    value: reg_value,
-                    upper: reg_value + self.num_std_dev * std_dev,
-                    lower: reg_value - self.num_std_dev * std_dev,
-                    slope,
-                    r,
-                    r_squared,
-                });
+                return Some(self.regression_output(&self.buffer));
             }
             return None;
         }
@@ -308,16 +345,40 @@ impl StreamingIndicator<f64, LinRegOutput> for LinRegStream {
 
         // Get ordered data and calculate
         let data = self.get_ordered_data();
-        let (reg_value, slope, r, r_squared, std_dev) = LinReg::calculate_regression(&data);
+        Some(self.regression_output(&data))
+    }
 
-        Some(LinRegOutput {
-            value: reg_value,
-            upper: reg_value + self.num_std_dev * std_dev,
-            lower: reg_value - self.num_std_dev * std_dev,
-            slope,
-            r,
-            r_squared,
-        })
+    fn update(&mut self, value: f64) -> Option<LinRegOutput> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        if self.count < self.period {
+            // Still filling: the slot is simply written again
+            self.count -= 1;
+        } else {
+            // Step the head back onto the most recent slot so it gets overwritten
+            self.head = (self.head + self.period - 1) % self.period;
+        }
+        self.next(value)
+    }
+
+    fn peek(&self, value: f64) -> Option<LinRegOutput> {
+        if self.count + 1 < self.period {
+            return None;
+        }
+
+        // Window as `next` would see it: oldest value dropped once full
+        let mut data = Vec::with_capacity(self.period);
+        if self.count < self.period {
+            data.extend_from_slice(&self.buffer[..self.count]);
+        } else {
+            data.extend_from_slice(&self.buffer[self.head + 1..]);
+            data.extend_from_slice(&self.buffer[..self.head]);
+        }
+        data.push(value);
+
+        Some(self.regression_output(&data))
     }
 
     fn reset(&mut self) {
@@ -329,6 +390,10 @@ impl StreamingIndicator<f64, LinRegOutput> for LinRegStream {
     fn is_ready(&self) -> bool {
         self.count >= self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 #[cfg(test)]
@@ -467,6 +532,31 @@ mod tests {
         assert!(!result.r.is_nan());
     }
 
+    #[test]
+    fn test_linreg_stream_update_matches_next() {
+        let data = [10.0, 12.0, 11.0, 14.0, 13.0, 16.0, 15.0, 18.0, 17.0, 20.0];
+        let mut live = LinRegStream::new(4, 2.0).unwrap();
+        let mut reference = LinRegStream::new(4, 2.0).unwrap();
+
+        for &value in &data {
+            live.next(value * 3.0);
+            live.update(value - 5.0);
+            let amended = live.update(value);
+            let expected = reference.next(value);
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => {
+                    assert_approx_eq(a.value, e.value);
+                    assert_approx_eq(a.slope, e.slope);
+                    assert_approx_eq(a.upper, e.upper);
+                    assert_approx_eq(a.r_squared, e.r_squared);
+                }
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_linreg_empty_data() {
         let lr = LinReg::new(5, 2.0).unwrap();
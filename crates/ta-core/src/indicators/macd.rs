@@ -24,9 +24,12 @@
 //! ```
 
 use crate::indicators::{EmaStream, SmaStream};
-use crate::traits::{Indicator, StreamingIndicator};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
 /// MACD output containing all three components.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MacdOutput {
@@ -66,6 +69,12 @@ impl MacdOutput {
     }
 }
 
+impl IndicatorOutput for MacdOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
 /// Signal line type for MACD.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SignalType {
@@ -145,6 +154,12 @@ impl Macd {
     pub const fn signal_period(&self) -> usize {
         self.signal_period
     }
+
+    /// Returns the signal line type.
+    #[must_use]
+    pub const fn signal_type(&self) -> SignalType {
+        self.signal_type
+    }
 }
 
 impl Indicator<&[f64], Vec<MacdOutput>> for Macd {
@@ -212,10 +227,16 @@ impl Indicator<&[f64], Vec<MacdOutput>> for Macd {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        // The signal line starts once the slow EMA has produced `signal_period` values
+        self.slow_period + self.signal_period - 2
+    }
 }
 
 /// MACD calculator for streaming/real-time operations.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MacdStream {
     fast_ema: EmaStream,
     slow_ema: EmaStream,
@@ -292,6 +313,22 @@ impl MacdStream {
     pub const fn signal_period(&self) -> usize {
         self.signal_period
     }
+
+    /// Checks the EMAs and signal line of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(
+            self.slow_period > 0 && self.signal_period > 0,
+            "all periods must be greater than 0",
+        )?;
+        self.fast_ema.check_state()?;
+        self.slow_ema.check_state()?;
+        match (&self.signal_ema, &self.signal_sma) {
+            (Some(ema), None) => ema.check_state(),
+            (None, Some(sma)) => sma.check_state(),
+            _ => Err("exactly one signal line must be set".to_string()),
+        }
+    }
 }
 
 impl StreamingIndicator<f64, MacdOutput> for MacdStream {
@@ -332,6 +369,47 @@ impl StreamingIndicator<f64, MacdOutput> for MacdStream {
         }
     }
 
+    fn update(&mut self, value: f64) -> Option<MacdOutput> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        let (Some(fast_val), Some(slow_val)) =
+            (self.fast_ema.update(value), self.slow_ema.update(value))
+        else {
+            return None;
+        };
+
+        let macd = fast_val - slow_val;
+
+        // The amended bar already fed the signal line, so amend it there too
+        let signal = match (&mut self.signal_ema, &mut self.signal_sma) {
+            (Some(ema), None) => ema.update(macd),
+            (None, Some(sma)) => sma.update(macd),
+            _ => None,
+        };
+
+        match signal {
+            Some(sig) => Some(MacdOutput::new(macd, sig, macd - sig)),
+            None => Some(MacdOutput::new(macd, f64::NAN, f64::NAN)),
+        }
+    }
+
+    fn peek(&self, value: f64) -> Option<MacdOutput> {
+        let macd = self.fast_ema.peek(value)? - self.slow_ema.peek(value)?;
+
+        let signal = match (&self.signal_ema, &self.signal_sma) {
+            (Some(ema), None) => ema.peek(macd),
+            (None, Some(sma)) => sma.peek(macd),
+            _ => None,
+        };
+
+        match signal {
+            Some(sig) => Some(MacdOutput::new(macd, sig, macd - sig)),
+            None => Some(MacdOutput::new(macd, f64::NAN, f64::NAN)),
+        }
+    }
+
     fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
@@ -353,6 +431,10 @@ impl StreamingIndicator<f64, MacdOutput> for MacdStream {
         };
         self.slow_ema.is_ready() && signal_ready
     }
+
+    fn lookback(&self) -> usize {
+        self.slow_period + self.signal_period - 2
+    }
 }
 
 #[cfg(test)]
@@ -491,6 +573,37 @@ mod tests {
         assert!(found_difference, "EMA and SMA signal lines should differ");
     }
 
+    #[test]
+    fn test_macd_stream_update_matches_next() {
+        let data: Vec<f64> = vec![
+            10.0, 12.0, 11.0, 13.0, 15.0, 14.0, 16.0, 18.0, 17.0, 20.0, 19.0, 22.0, 21.0, 24.0,
+        ];
+
+        for signal_type in [SignalType::Ema, SignalType::Sma] {
+            let mut live = MacdStream::with_signal_type(3, 5, 3, signal_type).unwrap();
+            let mut reference = MacdStream::with_signal_type(3, 5, 3, signal_type).unwrap();
+
+            for &value in &data {
+                live.next(value + 4.0);
+                live.update(value - 2.0);
+                let amended = live.update(value).unwrap_or_else(MacdOutput::nan);
+                let expected = reference.next(value).unwrap_or_else(MacdOutput::nan);
+
+                for (a, e) in [
+                    (amended.macd, expected.macd),
+                    (amended.signal, expected.signal),
+                    (amended.histogram, expected.histogram),
+                ] {
+                    if e.is_nan() {
+                        assert!(a.is_nan());
+                    } else {
+                        assert_approx_eq(a, e);
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_macd_stream_reset() {
         let mut stream = MacdStream::new(3, 5, 3).unwrap();
@@ -501,4 +614,23 @@ mod tests {
         stream.reset();
         assert!(!stream.is_ready());
     }
+
+    #[test]
+    fn test_macd_lookback() {
+        let prices: Vec<f64> = (0..60)
+            .map(|i| 100.0 + (f64::from(i) * 0.3).sin())
+            .collect();
+        for macd in [
+            Macd::new(12, 26, 9).unwrap(),
+            Macd::with_signal_type(5, 13, 4, SignalType::Sma).unwrap(),
+        ] {
+            let result = macd.calculate(&prices).unwrap();
+            let lookback = macd.lookback();
+            assert!(result[..lookback].iter().all(|o| o.signal.is_nan()));
+            assert!(!result[lookback].signal.is_nan());
+        }
+
+        assert_eq!(Macd::new(12, 26, 9).unwrap().lookback(), 33);
+        assert_eq!(MacdStream::new(12, 26, 9).unwrap().lookback(), 33);
+    }
 }
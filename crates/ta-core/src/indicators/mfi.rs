@@ -32,7 +32,10 @@
 //! ```
 
 use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::{IndicatorError, IndicatorResult};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::check_ring;
 
 /// MFI calculator for batch operations.
 #[derive(Debug, Clone)]
@@ -72,11 +75,12 @@ impl Indicator<&MfiInput<'_>, Vec<f64>> for Mfi {
         let len = highs.len();
 
         // Validate lengths
-        if lows.len() != len || closes.len() != len || volumes.len() != len {
-            return Err(IndicatorError::InvalidParameter(
-                "all arrays must have the same length".to_string(),
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+            ("volumes", volumes.len()),
+        ])?;
 
         let mut result = vec![f64::NAN; len];
 
@@ -112,6 +116,21 @@ impl Indicator<&MfiInput<'_>, Vec<f64>> for Mfi {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        // The first value needs `period` typical price changes
+        self.period
+    }
+}
+
+impl Mfi {
+    /// Calculates MFI from the high, low, close and volume columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<f64>> {
+        self.calculate(&(data.high(), data.low(), data.close(), data.volume()))
+    }
 }
 
 /// Calculate MFI from positive and negative flow sums.
@@ -133,7 +152,8 @@ pub type MfiBar = (f64, f64, f64, f64);
 /// Streaming MFI calculator for real-time O(1) updates.
 ///
 /// Uses ring buffers to maintain running sums of positive and negative flows.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MfiStream {
     period: usize,
     positive_buffer: Vec<f64>,
@@ -143,6 +163,9 @@ pub struct MfiStream {
     prev_tp: Option<f64>,
     positive_sum: f64,
     negative_sum: f64,
+    // State before the most recent `next`, restored by `update`
+    prev_state: (Option<f64>, f64, f64), // (prev_tp, positive_sum, negative_sum)
+    prev_slot: (f64, f64),
 }
 
 impl MfiStream {
@@ -165,6 +188,8 @@ impl MfiStream {
             prev_tp: None,
             positive_sum: 0.0,
             negative_sum: 0.0,
+            prev_state: (None, 0.0, 0.0),
+            prev_slot: (0.0, 0.0),
         })
     }
 
@@ -183,6 +208,13 @@ impl MfiStream {
             None
         }
     }
+
+    /// Checks the flow ring buffers of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        check_ring(self.period, self.positive_buffer.len(), self.head)?;
+        check_ring(self.period, self.negative_buffer.len(), self.head)
+    }
 }
 
 impl StreamingIndicator<MfiBar, f64> for MfiStream {
@@ -199,6 +231,7 @@ impl StreamingIndicator<MfiBar, f64> for MfiStream {
         let (high, low, close, volume) = bar;
         let tp = (high + low + close) / 3.0;
         let raw_flow = tp * volume;
+        self.prev_state = (self.prev_tp, self.positive_sum, self.negative_sum);
 
         let (pos_flow, neg_flow) = match self.prev_tp {
             Some(prev) if tp > prev => (raw_flow, 0.0),
@@ -234,6 +267,7 @@ impl StreamingIndicator<MfiBar, f64> for MfiStream {
         // O(1) update: remove oldest, add newest
         let old_pos = self.positive_buffer[self.head];
         let old_neg = self.negative_buffer[self.head];
+        self.prev_slot = (old_pos, old_neg);
         self.positive_buffer[self.head] = pos_flow;
         self.negative_buffer[self.head] = neg_flow;
         self.head = (self.head + 1) % self.period;
@@ -244,6 +278,52 @@ impl StreamingIndicator<MfiBar, f64> for MfiStream {
         Some(calculate_mfi(self.positive_sum, self.negative_sum))
     }
 
+    fn update(&mut self, bar: MfiBar) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(bar);
+        }
+
+        if self.count - 1 > self.period {
+            // Last flow went through the ring buffer: rewind it
+            self.head = (self.head + self.period - 1) % self.period;
+            self.positive_buffer[self.head] = self.prev_slot.0;
+            self.negative_buffer[self.head] = self.prev_slot.1;
+        }
+        (self.prev_tp, self.positive_sum, self.negative_sum) = self.prev_state;
+        self.count -= 1;
+
+        self.next(bar)
+    }
+
+    fn peek(&self, bar: MfiBar) -> Option<f64> {
+        let (high, low, close, volume) = bar;
+        let tp = (high + low + close) / 3.0;
+        let raw_flow = tp * volume;
+
+        let (pos_flow, neg_flow) = match self.prev_tp {
+            Some(prev) if tp > prev => (raw_flow, 0.0),
+            Some(prev) if tp < prev => (0.0, raw_flow),
+            _ => (0.0, 0.0),
+        };
+
+        // Flows recorded so far, including this one
+        let effective_count = self.count;
+        if effective_count < self.period {
+            return None;
+        }
+        if effective_count == self.period {
+            return Some(calculate_mfi(
+                self.positive_sum + pos_flow,
+                self.negative_sum + neg_flow,
+            ));
+        }
+
+        Some(calculate_mfi(
+            self.positive_sum - self.positive_buffer[self.head] + pos_flow,
+            self.negative_sum - self.negative_buffer[self.head] + neg_flow,
+        ))
+    }
+
     fn reset(&mut self) {
         self.positive_buffer.fill(0.0);
         self.negative_buffer.fill(0.0);
@@ -252,11 +332,17 @@ impl StreamingIndicator<MfiBar, f64> for MfiStream {
         self.prev_tp = None;
         self.positive_sum = 0.0;
         self.negative_sum = 0.0;
+        self.prev_state = (None, 0.0, 0.0);
+        self.prev_slot = (0.0, 0.0);
     }
 
     fn is_ready(&self) -> bool {
         self.count > self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period
+    }
 }
 
 #[cfg(test)]
@@ -384,6 +470,31 @@ mod tests {
         assert!(mfi >= 0.0 && mfi <= 100.0);
     }
 
+    #[test]
+    fn test_mfi_stream_update_matches_next() {
+        let bars: Vec<MfiBar> = (0..25)
+            .map(|i| {
+                let base = 50.0 + (f64::from(i) * 0.9).sin() * 4.0;
+                (base + 1.0, base - 1.0, base, 1000.0 + f64::from(i) * 50.0)
+            })
+            .collect();
+        let mut live = MfiStream::new(5).unwrap();
+        let mut reference = MfiStream::new(5).unwrap();
+
+        for &(high, low, close, volume) in &bars {
+            live.next((high, low, low, volume * 0.1));
+            live.update((high + 3.0, low, high + 3.0, volume * 0.5));
+            let amended = live.update((high, low, close, volume));
+            let expected = reference.next((high, low, close, volume));
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => assert!((a - e).abs() < 1e-9, "{a} vs {e}"),
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_mfi_empty_data() {
         let mfi = Mfi::new(14).unwrap();
@@ -7,37 +7,67 @@ pub mod adx;
 pub mod atr;
 pub mod bbands;
 pub mod cvd;
+pub mod donchian;
 pub mod ema;
+pub mod footprint;
 pub mod frvp;
 pub mod hma;
 pub mod ichimoku;
+pub mod keltner;
 pub mod linreg;
 pub mod macd;
 pub mod mfi;
+pub mod monotonic;
 pub mod pivot_points;
+pub mod psar;
 pub mod rsi;
+pub mod session_profile;
 pub mod sma;
+pub mod squeeze;
 pub mod stoch_rsi;
 pub mod stochastic;
+pub mod supertrend;
 pub mod vwap;
 pub mod wma;
 
 pub use adx::{Adx, AdxBar, AdxOutput, AdxStream};
 pub use atr::{Atr, AtrBar, AtrStream};
 pub use bbands::{BBands, BBandsOutput, BBandsStream};
-pub use cvd::{Cvd, CvdBar, CvdOhlcv, CvdOhlcvStream, CvdStream};
+pub use cvd::{
+    Cvd, CvdBar, CvdOhlcv, CvdOhlcvStream, CvdStream, DeltaBar, DeltaUpdate, TradeCvd,
+    TradeCvdStream,
+};
+pub use donchian::{Donchian, DonchianBar, DonchianInput, DonchianOutput, DonchianStream};
 pub use ema::{Ema, EmaStream};
+pub use footprint::{
+    Footprint, FootprintBar, FootprintLevel, FootprintStream, FootprintUpdate, Imbalance,
+    StackedImbalance,
+};
 pub use frvp::{Frvp, FrvpOutput, FrvpStream, VolumeProfileRow};
 pub use hma::{Hma, HmaStream};
 pub use ichimoku::{Ichimoku, IchimokuBar, IchimokuOutput, IchimokuStream};
+pub use keltner::{
+    Keltner, KeltnerBand, KeltnerBar, KeltnerInput, KeltnerMa, KeltnerOutput, KeltnerStream,
+};
 pub use linreg::{LinReg, LinRegOutput, LinRegStream};
 pub use macd::{Macd, MacdOutput, MacdStream, SignalType};
 pub use mfi::{Mfi, MfiBar, MfiStream};
+pub use monotonic::{rolling_max, rolling_min, Extremum, MonotonicDeque};
 pub use pivot_points::{PivotPoints, PivotPointsOutput, PivotPointsVariant};
+pub use psar::{Psar, PsarBar, PsarInput, PsarOutput, PsarStream};
 pub use rsi::{Rsi, RsiStream};
+pub use session_profile::{
+    DevelopingProfile, ProfilePeriod, SessionProfile, SessionVolumeProfile,
+    SessionVolumeProfileOutput, SessionVolumeProfileStream,
+};
 pub use sma::{Sma, SmaStream};
+pub use squeeze::{Squeeze, SqueezeBar, SqueezeInput, SqueezeOutput, SqueezeStream};
 pub use stoch_rsi::{StochRsi, StochRsiOutput, StochRsiStream};
 pub use stochastic::{Stoch, StochBar, StochOutput, StochStream, StochType};
+pub use supertrend::{
+    SuperTrend, SuperTrendAtr, SuperTrendBar, SuperTrendInput, SuperTrendOutput, SuperTrendSource,
+    SuperTrendStream,
+};
 pub use vwap::{
     AnchoredVwap, AnchoredVwapStream, RollingVwap, RollingVwapStream, SessionVwap,
     SessionVwapStream,
@@ -0,0 +1,377 @@
+//! Monotonic deque for rolling window extremes.
+//!
+//! Tracks the highest (or lowest) of the last `period` values in amortized
+//! O(1) per push. Each push remembers the entries it evicted, so the most
+//! recent push can be rolled back - this is what lets the window-based
+//! streams amend a live bar via `update`.
+//!
+//! [`rolling_max`] and [`rolling_min`] apply the same deque to a whole series
+//! in O(n), independent of the window length.
+//!
+//! NaN values are skipped like `f64::max` skips them: the extreme is taken
+//! over the window's other values, and is NaN only when every value in the
+//! window is NaN.
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::monotonic::{rolling_max, Extremum, MonotonicDeque};
+//!
+//! let highs = [3.0, 1.0, 4.0, 1.0, 5.0];
+//! let max = rolling_max(&highs, 3);
+//! assert!(max[1].is_nan());
+//! assert_eq!(&max[2..], &[4.0, 4.0, 5.0]);
+//!
+//! let mut deque = MonotonicDeque::new(Extremum::Max, 3);
+//! for (i, &h) in highs.iter().enumerate() {
+//!     deque.push(i, h);
+//! }
+//! assert_eq!(deque.front(), Some(5.0));
+//! assert_eq!(deque.front_index(), Some(4));
+//! ```
+
+use std::collections::VecDeque;
+
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
+/// Which extreme a [`MonotonicDeque`] tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Extremum {
+    /// Rolling maximum
+    Max,
+    /// Rolling minimum
+    Min,
+}
+
+/// Rolling max/min over the last `period` indices.
+///
+/// Indices are supplied by the caller and must increase by one per push;
+/// an index may be pushed again only after [`rollback`](Self::rollback).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonotonicDeque {
+    extremum: Extremum,
+    period: usize,
+    entries: VecDeque<(usize, f64)>, // (index, value)
+    // Entries evicted by the most recent push, restored by `rollback`
+    evicted_back: Vec<(usize, f64)>,
+    evicted_front: Vec<(usize, f64)>,
+}
+
+impl MonotonicDeque {
+    /// Creates an empty deque over a window of `period` indices.
+    #[must_use]
+    pub fn new(extremum: Extremum, period: usize) -> Self {
+        Self {
+            extremum,
+            period,
+            entries: VecDeque::with_capacity(period),
+            evicted_back: Vec::new(),
+            evicted_front: Vec::new(),
+        }
+    }
+
+    /// Push the value at `index`; the window becomes `[index - period + 1, index]`.
+    #[inline]
+    pub fn push(&mut self, index: usize, value: f64) {
+        self.evicted_back.clear();
+        self.evicted_front.clear();
+
+        // Remove entries that fell out of the window
+        while let Some(&(idx, _)) = self.entries.front() {
+            if idx + self.period <= index {
+                self.evicted_front.extend(self.entries.pop_front());
+            } else {
+                break;
+            }
+        }
+
+        // Remove entries dominated by the new value
+        while let Some(&(_, v)) = self.entries.back() {
+            if self.dominated(v, value) {
+                self.evicted_back.extend(self.entries.pop_back());
+            } else {
+                break;
+            }
+        }
+
+        self.entries.push_back((index, value));
+    }
+
+    /// Undo the most recent [`push`](Self::push).
+    #[inline]
+    pub fn rollback(&mut self) {
+        self.entries.pop_back();
+        while let Some(entry) = self.evicted_back.pop() {
+            self.entries.push_back(entry);
+        }
+        while let Some(entry) = self.evicted_front.pop() {
+            self.entries.push_front(entry);
+        }
+        self.evicted_back.clear();
+        self.evicted_front.clear();
+    }
+
+    /// Extreme of the window [`push`](Self::push) would produce, without
+    /// pushing.
+    #[inline]
+    #[must_use]
+    pub fn peek(&self, index: usize, value: f64) -> f64 {
+        self.peek_entry(index, value).1
+    }
+
+    /// `(index, value)` of the extreme [`push`](Self::push) would produce,
+    /// without pushing.
+    #[inline]
+    #[must_use]
+    pub fn peek_entry(&self, index: usize, value: f64) -> (usize, f64) {
+        let current = self
+            .entries
+            .iter()
+            .find(|&&(idx, _)| idx + self.period > index);
+        match current {
+            Some(&(idx, v)) if !self.dominated(v, value) => (idx, v),
+            _ => (index, value),
+        }
+    }
+
+    /// Whether an existing entry `v` can never be the extreme again once
+    /// `value` is in the window.
+    ///
+    /// A NaN entry is dominated by anything, and a NaN value dominates
+    /// nothing, so a NaN is only ever the last entry and only the front of
+    /// an all-NaN window.
+    #[inline]
+    fn dominated(&self, v: f64, value: f64) -> bool {
+        if v.is_nan() {
+            return true;
+        }
+        match self.extremum {
+            Extremum::Max => v <= value,
+            Extremum::Min => v >= value,
+        }
+    }
+
+    /// Current extreme of the window, if any value has been pushed.
+    #[inline]
+    #[must_use]
+    pub fn front(&self) -> Option<f64> {
+        self.entries.front().map(|&(_, v)| v)
+    }
+
+    /// Index of the current extreme (the most recent one on ties).
+    #[inline]
+    #[must_use]
+    pub fn front_index(&self) -> Option<usize> {
+        self.entries.front().map(|&(idx, _)| idx)
+    }
+
+    /// Returns the window length.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Removes every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.evicted_back.clear();
+        self.evicted_front.clear();
+    }
+
+    /// Checks a restored deque against the stream that owns it: the same
+    /// extreme and window, and entries in increasing index order, all
+    /// before `next_index`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(
+        &self,
+        extremum: Extremum,
+        period: usize,
+        next_index: usize,
+    ) -> Result<(), String> {
+        ensure(
+            self.extremum == extremum && self.period == period,
+            "deque window must match the stream",
+        )?;
+        let mut prev = None;
+        for &(idx, _) in &self.entries {
+            ensure(
+                idx < next_index && prev.is_none_or(|p| p < idx),
+                "deque indices must increase and precede the next index",
+            )?;
+            prev = Some(idx);
+        }
+        ensure(
+            self.evicted_back
+                .iter()
+                .chain(&self.evicted_front)
+                .all(|&(idx, _)| idx < next_index),
+            "evicted deque indices must precede the next index",
+        )
+    }
+}
+
+/// Rolling maximum over `period` values, NaN until the window is full.
+///
+/// O(n) regardless of `period`; a `period` of 0 yields all NaN.
+#[must_use]
+pub fn rolling_max(data: &[f64], period: usize) -> Vec<f64> {
+    rolling(data, period, Extremum::Max)
+}
+
+/// Rolling minimum over `period` values, NaN until the window is full.
+///
+/// O(n) regardless of `period`; a `period` of 0 yields all NaN.
+#[must_use]
+pub fn rolling_min(data: &[f64], period: usize) -> Vec<f64> {
+    rolling(data, period, Extremum::Min)
+}
+
+fn rolling(data: &[f64], period: usize, extremum: Extremum) -> Vec<f64> {
+    let mut result = vec![f64::NAN; data.len()];
+    if period == 0 {
+        return result;
+    }
+
+    let mut deque = MonotonicDeque::new(extremum, period);
+    for (i, &value) in data.iter().enumerate() {
+        deque.push(i, value);
+        if i + 1 >= period {
+            result[i] = deque.front().unwrap_or(f64::NAN);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monotonic_deque_rolling_max_and_min() {
+        let values = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let mut max = MonotonicDeque::new(Extremum::Max, 3);
+        let mut min = MonotonicDeque::new(Extremum::Min, 3);
+
+        for (i, &v) in values.iter().enumerate() {
+            max.push(i, v);
+            min.push(i, v);
+
+            let window = &values[i.saturating_sub(2)..=i];
+            let expected_max = window.iter().copied().fold(f64::MIN, f64::max);
+            let expected_min = window.iter().copied().fold(f64::MAX, f64::min);
+            assert_eq!(max.front(), Some(expected_max));
+            assert_eq!(min.front(), Some(expected_min));
+        }
+    }
+
+    #[test]
+    fn test_monotonic_deque_rollback_restores_window() {
+        let mut max = MonotonicDeque::new(Extremum::Max, 3);
+        max.push(0, 5.0);
+        max.push(1, 3.0);
+        max.push(2, 4.0);
+
+        // Evicts index 0 from the front and 3.0/4.0 from the back
+        max.push(3, 10.0);
+        assert_eq!(max.front(), Some(10.0));
+
+        max.rollback();
+        assert_eq!(max.front(), Some(5.0));
+
+        // Same index pushed again with a smaller value
+        max.push(3, 1.0);
+        assert_eq!(max.front(), Some(4.0));
+    }
+
+    #[test]
+    fn test_monotonic_deque_peek_matches_push() {
+        let values = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0];
+        let mut max = MonotonicDeque::new(Extremum::Max, 3);
+        let mut min = MonotonicDeque::new(Extremum::Min, 3);
+
+        for (i, &v) in values.iter().enumerate() {
+            for candidate in [0.0, 2.5, v, 7.0] {
+                let (mut max_after, mut min_after) = (max.clone(), min.clone());
+                max_after.push(i, candidate);
+                min_after.push(i, candidate);
+                assert_eq!(Some(max.peek(i, candidate)), max_after.front());
+                assert_eq!(Some(min.peek(i, candidate)), min_after.front());
+            }
+            max.push(i, v);
+            min.push(i, v);
+        }
+    }
+
+    #[test]
+    fn test_rolling_max_min_match_window_scan() {
+        let values = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0];
+        for period in 1..=4 {
+            let max = rolling_max(&values, period);
+            let min = rolling_min(&values, period);
+            assert!(max[..period - 1].iter().all(|v| v.is_nan()));
+            assert!(min[..period - 1].iter().all(|v| v.is_nan()));
+
+            let windows = values.windows(period);
+            let expected_max: Vec<f64> = windows
+                .clone()
+                .map(|w| w.iter().copied().fold(f64::MIN, f64::max))
+                .collect();
+            let expected_min: Vec<f64> = windows
+                .map(|w| w.iter().copied().fold(f64::MAX, f64::min))
+                .collect();
+            assert_eq!(&max[period - 1..], &expected_max[..]);
+            assert_eq!(&min[period - 1..], &expected_min[..]);
+        }
+        assert!(rolling_max(&values, 0).iter().all(|v| v.is_nan()));
+
+        // Ties resolve to the most recent index
+        let mut max = MonotonicDeque::new(Extremum::Max, 3);
+        max.push(0, 2.0);
+        max.push(1, 2.0);
+        assert_eq!(max.front_index(), Some(1));
+        assert_eq!(max.peek_entry(2, 1.0), (1, 2.0));
+        assert_eq!(max.peek_entry(4, 1.0), (4, 1.0));
+    }
+
+    #[test]
+    fn test_rolling_max_min_skip_nan() {
+        let values = [5.0, f64::NAN, 6.0, 1.0, f64::NAN, f64::NAN, f64::NAN, 2.0];
+        for period in 1..=4 {
+            let max = rolling_max(&values, period);
+            let min = rolling_min(&values, period);
+            for (i, window) in values.windows(period).enumerate() {
+                let i = i + period - 1;
+                if window.iter().all(|v| v.is_nan()) {
+                    assert!(max[i].is_nan() && min[i].is_nan());
+                    continue;
+                }
+                let expected_max = window.iter().copied().fold(f64::NAN, f64::max);
+                let expected_min = window.iter().copied().fold(f64::NAN, f64::min);
+                assert_eq!(
+                    max[i].to_bits(),
+                    expected_max.to_bits(),
+                    "period {period} at {i}"
+                );
+                assert_eq!(
+                    min[i].to_bits(),
+                    expected_min.to_bits(),
+                    "period {period} at {i}"
+                );
+            }
+        }
+
+        // Peek and rollback see through a NaN the same way
+        let mut max = MonotonicDeque::new(Extremum::Max, 2);
+        max.push(0, 5.0);
+        max.push(1, f64::NAN);
+        assert_eq!(max.front(), Some(5.0));
+        assert_eq!(max.peek_entry(2, 3.0), (2, 3.0));
+        assert!(max.peek(2, f64::NAN).is_nan());
+        max.push(2, 3.0);
+        max.rollback();
+        max.push(2, f64::NAN);
+        assert!(max.front().is_some_and(f64::is_nan));
+    }
+}
@@ -52,8 +52,8 @@
 //! println!("Pivot: {}, R1: {}, S1: {}", result.pivot, result.r1, result.s1);
 //! ```
 
-use crate::traits::Indicator;
-use crate::types::IndicatorResult;
+use crate::traits::{Indicator, IndicatorOutput};
+use crate::types::{check_lengths, IndicatorResult, OhlcvColumns};
 
 // ============================================================================
 // Types
@@ -124,6 +124,12 @@ impl PivotPointsOutput {
     }
 }
 
+impl IndicatorOutput for PivotPointsOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
 /// Input type for pivot points: (high, low, close)
 pub type PivotInput = (f64, f64, f64);
 
@@ -229,6 +235,10 @@ impl Indicator<&PivotInput, PivotPointsOutput> for PivotPoints {
         let (high, low, close) = *data;
         Ok(self.calculate_single(high, low, close))
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
 }
 
 /// Calculate pivot points from arrays of (highs, lows, closes).
@@ -239,11 +249,11 @@ impl Indicator<PivotBatchInput<'_>, Vec<PivotPointsOutput>> for PivotPoints {
     fn calculate(&self, data: PivotBatchInput<'_>) -> IndicatorResult<Vec<PivotPointsOutput>> {
         let (highs, lows, closes) = data;
 
-        if highs.len() != lows.len() || lows.len() != closes.len() {
-            return Err(crate::types::IndicatorError::InvalidParameter(
-                "highs, lows, and closes must have the same length".to_string(),
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
 
         let result: Vec<PivotPointsOutput> = highs
             .iter()
@@ -254,6 +264,23 @@ impl Indicator<PivotBatchInput<'_>, Vec<PivotPointsOutput>> for PivotPoints {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+impl PivotPoints {
+    /// Calculates pivot points from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(
+        &self,
+        data: &OhlcvColumns<'_>,
+    ) -> IndicatorResult<Vec<PivotPointsOutput>> {
+        self.calculate((data.high(), data.low(), data.close()))
+    }
 }
 
 // ============================================================================
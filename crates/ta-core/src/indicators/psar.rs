@@ -0,0 +1,483 @@
+//! Parabolic SAR (Stop and Reverse) indicator.
+//!
+//! Wilder's Parabolic SAR trails price with a stop that accelerates toward
+//! the extreme point of the current trend. When a bar's range touches the
+//! stop, the trend reverses and the SAR jumps to the prior extreme point.
+//!
+//! # Formula
+//! - **Next SAR**: SAR + AF × (EP - SAR)
+//! - **EP**: Highest high of an uptrend (lowest low of a downtrend)
+//! - **AF**: Starts at `start`, grows by `increment` on each new EP, capped at `maximum`
+//!
+//! In an uptrend the next SAR may not rise above the lows of the current or
+//! previous bar (in a downtrend, fall below their highs). On a reversal bar
+//! the SAR becomes the prior EP, pushed outside the last two bars' range,
+//! and AF resets to `start`.
+//!
+//! The initial direction comes from the first two bars, as in TA-Lib: short
+//! if the second bar's low drop exceeds its high gain, otherwise long.
+//!
+//! # Default Parameters
+//! - Start: 0.02
+//! - Increment: 0.02
+//! - Maximum: 0.2
+//!
+//! # Example (Batch Mode)
+//! ```
+//! use ta_core::indicators::Psar;
+//! use ta_core::traits::Indicator;
+//!
+//! let psar = Psar::default_params().unwrap();
+//! let highs: Vec<f64> = (1..=20).map(|x| x as f64 + 1.0).collect();
+//! let lows: Vec<f64> = (1..=20).map(|x| x as f64).collect();
+//! let result = psar.calculate(&(&highs[..], &lows[..])).unwrap();
+//! assert!(result[0].sar.is_nan());
+//! assert_eq!(result[19].direction, 1);
+//! assert!(result[19].sar < lows[19]);
+//! ```
+
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+/// Parabolic SAR output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PsarOutput {
+    /// Stop level for this bar
+    pub sar: f64,
+    /// Trend direction after this bar: 1 = long, -1 = short, 0 = no value yet
+    pub direction: i8,
+    /// Acceleration factor after this bar (applied to the next SAR)
+    pub af: f64,
+    /// Extreme point of the current trend after this bar
+    pub ep: f64,
+}
+
+impl PsarOutput {
+    /// Creates a NaN output for insufficient data.
+    #[must_use]
+    pub fn nan() -> Self {
+        Self {
+            sar: f64::NAN,
+            direction: 0,
+            af: f64::NAN,
+            ep: f64::NAN,
+        }
+    }
+
+    /// Returns true if the SAR is NaN.
+    #[must_use]
+    pub fn is_nan(&self) -> bool {
+        self.sar.is_nan()
+    }
+}
+
+impl IndicatorOutput for PsarOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
+/// Input type for Parabolic SAR: (highs, lows)
+pub type PsarInput<'a> = (&'a [f64], &'a [f64]);
+
+/// Input for streaming: (high, low) tuple
+pub type PsarBar = (f64, f64);
+
+/// Parabolic SAR calculator for batch operations.
+#[derive(Debug, Clone)]
+pub struct Psar {
+    start: f64,
+    increment: f64,
+    maximum: f64,
+}
+
+impl Psar {
+    /// Creates a new Parabolic SAR calculator.
+    ///
+    /// # Arguments
+    /// * `start` - Initial acceleration factor (typically 0.02)
+    /// * `increment` - AF step on each new extreme point (typically 0.02)
+    /// * `maximum` - AF cap (typically 0.2)
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `start` is not positive, `increment` is
+    /// negative, or `maximum` is below `start`.
+    pub fn new(start: f64, increment: f64, maximum: f64) -> IndicatorResult<Self> {
+        validate(start, increment, maximum)?;
+        Ok(Self {
+            start,
+            increment,
+            maximum,
+        })
+    }
+
+    /// Creates with default parameters (0.02, 0.02, 0.2).
+    pub fn default_params() -> IndicatorResult<Self> {
+        Self::new(0.02, 0.02, 0.2)
+    }
+
+    /// Returns the initial acceleration factor.
+    #[must_use]
+    pub const fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Returns the acceleration factor increment.
+    #[must_use]
+    pub const fn increment(&self) -> f64 {
+        self.increment
+    }
+
+    /// Returns the maximum acceleration factor.
+    #[must_use]
+    pub const fn maximum(&self) -> f64 {
+        self.maximum
+    }
+}
+
+impl Indicator<&PsarInput<'_>, Vec<PsarOutput>> for Psar {
+    fn calculate(&self, data: &PsarInput<'_>) -> IndicatorResult<Vec<PsarOutput>> {
+        let (highs, lows) = *data;
+
+        check_lengths(&[("highs", highs.len()), ("lows", lows.len())])?;
+
+        let bars: Vec<PsarBar> = highs.iter().copied().zip(lows.iter().copied()).collect();
+        PsarStream::new(self.start, self.increment, self.maximum)?.init(&bars)
+    }
+
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+impl Psar {
+    /// Calculates Parabolic SAR from the high and low columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<PsarOutput>> {
+        self.calculate(&(data.high(), data.low()))
+    }
+}
+
+fn validate(start: f64, increment: f64, maximum: f64) -> IndicatorResult<()> {
+    if start <= 0.0 || !start.is_finite() {
+        return Err(IndicatorError::InvalidParameter(
+            "start must be a positive finite number".to_string(),
+        ));
+    }
+    if increment < 0.0 || !increment.is_finite() {
+        return Err(IndicatorError::InvalidParameter(
+            "increment must be a non-negative finite number".to_string(),
+        ));
+    }
+    if maximum < start || !maximum.is_finite() {
+        return Err(IndicatorError::InvalidParameter(
+            "maximum must be finite and at least start".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Trend state carried from one bar to the next.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct SarState {
+    long: bool,
+    sar: f64, // SAR for the next bar
+    ep: f64,
+    af: f64,
+    prev_high: f64,
+    prev_low: f64,
+}
+
+/// Streaming Parabolic SAR calculator.
+///
+/// Keeps only the trend state and the previous bar's range, so each bar is
+/// O(1) and `update` restores a single snapshot.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PsarStream {
+    start: f64,
+    increment: f64,
+    maximum: f64,
+    first: Option<PsarBar>, // first bar, held until the direction can be seeded
+    state: Option<SarState>,
+    count: usize,
+    // (first, state) before the most recent `next`, restored by `update`
+    prev_state: (Option<PsarBar>, Option<SarState>),
+}
+
+impl PsarStream {
+    /// Creates a new streaming Parabolic SAR calculator.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `start` is not positive, `increment` is
+    /// negative, or `maximum` is below `start`.
+    pub fn new(start: f64, increment: f64, maximum: f64) -> IndicatorResult<Self> {
+        validate(start, increment, maximum)?;
+        Ok(Self {
+            start,
+            increment,
+            maximum,
+            first: None,
+            state: None,
+            count: 0,
+            prev_state: (None, None),
+        })
+    }
+
+    /// Returns the initial acceleration factor.
+    #[must_use]
+    pub const fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Returns the acceleration factor increment.
+    #[must_use]
+    pub const fn increment(&self) -> f64 {
+        self.increment
+    }
+
+    /// Returns the maximum acceleration factor.
+    #[must_use]
+    pub const fn maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    /// Seeds the trend from the first two bars. The second bar then counts
+    /// as its own previous bar for the two-bar rule.
+    fn seed(&self, (high0, low0): PsarBar, (high1, low1): PsarBar) -> SarState {
+        let plus_dm = high1 - high0;
+        let minus_dm = low0 - low1;
+        let long = !(minus_dm > 0.0 && minus_dm > plus_dm);
+        let (sar, ep) = if long { (low0, high1) } else { (high0, low1) };
+        SarState {
+            long,
+            sar,
+            ep,
+            af: self.start,
+            prev_high: high1,
+            prev_low: low1,
+        }
+    }
+
+    /// Applies a bar to the trend state, returning the new state and output.
+    fn advance(&self, mut s: SarState, (high, low): PsarBar) -> (SarState, PsarOutput) {
+        let sar = if s.long {
+            if low <= s.sar {
+                // Reversal: stop at the prior EP, outside the last two highs
+                let sar = s.ep.max(s.prev_high).max(high);
+                s.long = false;
+                s.af = self.start;
+                s.ep = low;
+                s.sar = (sar + s.af * (s.ep - sar)).max(s.prev_high).max(high);
+                sar
+            } else {
+                let sar = s.sar;
+                if high > s.ep {
+                    s.ep = high;
+                    s.af = (s.af + self.increment).min(self.maximum);
+                }
+                s.sar = (sar + s.af * (s.ep - sar)).min(s.prev_low).min(low);
+                sar
+            }
+        } else if high >= s.sar {
+            // Reversal: stop at the prior EP, outside the last two lows
+            let sar = s.ep.min(s.prev_low).min(low);
+            s.long = true;
+            s.af = self.start;
+            s.ep = high;
+            s.sar = (sar + s.af * (s.ep - sar)).min(s.prev_low).min(low);
+            sar
+        } else {
+            let sar = s.sar;
+            if low < s.ep {
+                s.ep = low;
+                s.af = (s.af + self.increment).min(self.maximum);
+            }
+            s.sar = (sar + s.af * (s.ep - sar)).max(s.prev_high).max(high);
+            sar
+        };
+
+        s.prev_high = high;
+        s.prev_low = low;
+        let output = PsarOutput {
+            sar,
+            direction: if s.long { 1 } else { -1 },
+            af: s.af,
+            ep: s.ep,
+        };
+        (s, output)
+    }
+
+    /// Computes the state after `bar` without committing it.
+    fn step(&self, bar: PsarBar) -> (Option<PsarBar>, Option<SarState>, Option<PsarOutput>) {
+        let state = match (self.state, self.first) {
+            (Some(state), _) => state,
+            (None, Some(first)) => self.seed(first, bar),
+            (None, None) => return (Some(bar), None, None),
+        };
+        let (state, output) = self.advance(state, bar);
+        (self.first, Some(state), Some(output))
+    }
+
+    /// Checks the acceleration schedule of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        validate(self.start, self.increment, self.maximum).map_err(|e| e.to_string())
+    }
+}
+
+impl StreamingIndicator<PsarBar, PsarOutput> for PsarStream {
+    fn init(&mut self, data: &[PsarBar]) -> IndicatorResult<Vec<PsarOutput>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &bar in data {
+            results.push(self.next(bar).unwrap_or_else(PsarOutput::nan));
+        }
+        Ok(results)
+    }
+
+    fn next(&mut self, bar: PsarBar) -> Option<PsarOutput> {
+        self.prev_state = (self.first, self.state);
+        self.count += 1;
+
+        let (first, state, output) = self.step(bar);
+        self.first = first;
+        self.state = state;
+        output
+    }
+
+    fn update(&mut self, bar: PsarBar) -> Option<PsarOutput> {
+        if self.count == 0 {
+            return self.next(bar);
+        }
+
+        (self.first, self.state) = self.prev_state;
+        self.count -= 1;
+        self.next(bar)
+    }
+
+    fn peek(&self, bar: PsarBar) -> Option<PsarOutput> {
+        self.step(bar).2
+    }
+
+    fn reset(&mut self) {
+        self.first = None;
+        self.state = None;
+        self.count = 0;
+        self.prev_state = (None, None);
+    }
+
+    fn is_ready(&self) -> bool {
+        self.state.is_some()
+    }
+
+    fn lookback(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-10;
+
+    fn assert_approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < EPSILON, "expected {b}, got {a}");
+    }
+
+    #[test]
+    fn test_psar_uptrend_and_reversal() {
+        let highs = [10.0, 11.0, 12.0, 13.0, 12.5, 12.0];
+        let lows = [9.0, 10.0, 11.0, 12.0, 9.0, 8.5];
+        let result = Psar::default_params()
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..]))
+            .unwrap();
+
+        assert!(result[0].is_nan());
+        // Seeded long: SAR at the first low, EP at the second high
+        assert_approx_eq(result[1].sar, 9.0);
+        assert_eq!(result[1].direction, 1);
+        assert_approx_eq(result[2].sar, 9.04);
+        assert_approx_eq(result[2].af, 0.04);
+        assert_approx_eq(result[3].sar, 9.1584);
+        assert_approx_eq(result[3].ep, 13.0);
+
+        // Low pierces the SAR: reverse to the prior EP with AF reset
+        assert_eq!(result[4].direction, -1);
+        assert_approx_eq(result[4].sar, 13.0);
+        assert_approx_eq(result[4].af, 0.02);
+        assert_approx_eq(result[4].ep, 9.0);
+        // 13 + 0.02 * (9 - 13) = 12.92 is below the prior high, so it holds at 13
+        assert_approx_eq(result[5].sar, 13.0);
+        assert_approx_eq(result[5].ep, 8.5);
+        assert_approx_eq(result[5].af, 0.04);
+    }
+
+    #[test]
+    fn test_psar_two_bar_rule_and_short_seed() {
+        // A fast AF pulls the SAR above the previous bar's low; the rule caps it
+        let highs = [10.0, 11.0, 11.2, 11.5];
+        let lows = [9.0, 10.0, 10.9, 11.0];
+        let result = Psar::new(0.5, 0.0, 0.5)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..]))
+            .unwrap();
+        assert_approx_eq(result[2].sar, 10.0);
+        // 10 + 0.5 * (11.2 - 10) = 10.6, capped at the previous low
+        assert_approx_eq(result[3].sar, 10.0);
+
+        let highs = [20.0, 19.5, 19.0];
+        let lows = [19.0, 18.0, 17.5];
+        let result = Psar::default_params()
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..]))
+            .unwrap();
+        assert_eq!(result[1].direction, -1);
+        assert_approx_eq(result[1].sar, 20.0);
+        assert_approx_eq(result[2].ep, 17.5);
+
+        assert!(Psar::new(0.0, 0.02, 0.2).is_err());
+        assert!(Psar::new(0.02, -0.01, 0.2).is_err());
+        assert!(Psar::new(0.02, 0.02, 0.01).is_err());
+        assert!(Psar::default_params()
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..2]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_psar_stream_update_and_peek() {
+        let bars: Vec<PsarBar> = (0..80)
+            .map(|i| {
+                let mid = 100.0 + (f64::from(i) * 0.15).sin() * 10.0;
+                (mid + 1.0, mid - 1.0)
+            })
+            .collect();
+        let mut reference = PsarStream::new(0.02, 0.02, 0.2).unwrap();
+        let expected = reference.init(&bars).unwrap();
+        assert!(expected
+            .windows(2)
+            .any(|w| w[0].direction != w[1].direction));
+
+        let mut stream = PsarStream::new(0.02, 0.02, 0.2).unwrap();
+        for (i, &(high, low)) in bars.iter().enumerate() {
+            let peeked = stream.peek((high, low));
+            // Provisional bar that reverses the trend either way
+            stream.next((high + 50.0, low - 50.0));
+            let out = stream.update((high, low));
+
+            assert_eq!(peeked, out, "index {i}");
+            match out {
+                Some(out) => assert_eq!(out, expected[i], "index {i}"),
+                None => assert!(expected[i].is_nan(), "index {i}"),
+            }
+        }
+        assert!(stream.is_ready());
+    }
+}
@@ -43,6 +43,9 @@
 use crate::traits::{Indicator, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
 /// Relative Strength Index calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Rsi {
@@ -114,12 +117,18 @@ impl Indicator<&[f64], Vec<f64>> for Rsi {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        // The first value needs `period` price changes
+        self.period
+    }
 }
 
 /// Relative Strength Index calculator for streaming/real-time operations.
 ///
 /// Maintains smoothed average gain/loss for O(1) updates.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RsiStream {
     period: usize,
     alpha: f64,
@@ -129,6 +138,8 @@ pub struct RsiStream {
     count: usize,
     initial_gains: Vec<f64>,
     initial_losses: Vec<f64>,
+    // (avg_gain, avg_loss, prev_value) before the most recent `next`, restored by `update`
+    prev_state: (f64, f64, f64),
 }
 
 impl RsiStream {
@@ -151,6 +162,7 @@ impl RsiStream {
             count: 0,
             initial_gains: Vec::with_capacity(period),
             initial_losses: Vec::with_capacity(period),
+            prev_state: (0.0, 0.0, f64::NAN),
         })
     }
 
@@ -169,6 +181,17 @@ impl RsiStream {
             None
         }
     }
+
+    /// Checks the seeding buffers of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(self.period > 0, "period must be greater than 0")?;
+        ensure(
+            self.initial_gains.len() == self.initial_losses.len()
+                && self.initial_gains.len() <= self.period,
+            "seeding buffers must match and not exceed the period",
+        )
+    }
 }
 
 impl StreamingIndicator<f64, f64> for RsiStream {
@@ -183,6 +206,7 @@ impl StreamingIndicator<f64, f64> for RsiStream {
     }
 
     fn next(&mut self, value: f64) -> Option<f64> {
+        self.prev_state = (self.avg_gain, self.avg_loss, self.prev_value);
         self.count += 1;
 
         // First value: just store it, no change to calculate
@@ -218,14 +242,17 @@ impl StreamingIndicator<f64, f64> for RsiStream {
             self.avg_gain = self.initial_gains.iter().sum::<f64>() / self.period as f64;
             self.avg_loss = self.initial_losses.iter().sum::<f64>() / self.period as f64;
 
-            // Clear initial vectors to free memory
+            // Return first RSI (same as batch at index=period)
+            return Some(calculate_rsi(self.avg_gain, self.avg_loss));
+        }
+
+        // Clear initial vectors to free memory (kept one bar longer so the
+        // seeding bar can still be amended via `update`)
+        if !self.initial_gains.is_empty() {
             self.initial_gains.clear();
             self.initial_gains.shrink_to_fit();
             self.initial_losses.clear();
             self.initial_losses.shrink_to_fit();
-
-            // Return first RSI (same as batch at index=period)
-            return Some(calculate_rsi(self.avg_gain, self.avg_loss));
         }
 
         // Subsequent RSIs: apply Wilder's smoothing then calculate
@@ -235,6 +262,48 @@ impl StreamingIndicator<f64, f64> for RsiStream {
         Some(calculate_rsi(self.avg_gain, self.avg_loss))
     }
 
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        // Drop the change recorded by the last bar while still accumulating
+        if self.count >= 2 && self.count <= self.period + 1 {
+            self.initial_gains.pop();
+            self.initial_losses.pop();
+        }
+        (self.avg_gain, self.avg_loss, self.prev_value) = self.prev_state;
+        self.count -= 1;
+
+        self.next(value)
+    }
+
+    fn peek(&self, value: f64) -> Option<f64> {
+        let count = self.count + 1;
+        if count <= self.period {
+            return None;
+        }
+
+        let change = value - self.prev_value;
+        let (gain, loss) = if change > 0.0 {
+            (change, 0.0)
+        } else {
+            (0.0, -change)
+        };
+
+        if count == self.period + 1 {
+            let avg_gain =
+                self.initial_gains.iter().chain([&gain]).sum::<f64>() / self.period as f64;
+            let avg_loss =
+                self.initial_losses.iter().chain([&loss]).sum::<f64>() / self.period as f64;
+            return Some(calculate_rsi(avg_gain, avg_loss));
+        }
+
+        let avg_gain = (self.avg_gain * (1.0 - self.alpha)) + (gain * self.alpha);
+        let avg_loss = (self.avg_loss * (1.0 - self.alpha)) + (loss * self.alpha);
+        Some(calculate_rsi(avg_gain, avg_loss))
+    }
+
     fn reset(&mut self) {
         self.avg_gain = 0.0;
         self.avg_loss = 0.0;
@@ -242,11 +311,16 @@ impl StreamingIndicator<f64, f64> for RsiStream {
         self.count = 0;
         self.initial_gains.clear();
         self.initial_losses.clear();
+        self.prev_state = (0.0, 0.0, f64::NAN);
     }
 
     fn is_ready(&self) -> bool {
         self.count > self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period
+    }
 }
 
 /// Calculate RSI from average gain and loss.
@@ -389,6 +463,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rsi_stream_update_matches_next() {
+        let data = [
+            44.0, 44.5, 43.5, 44.5, 45.0, 45.5, 46.0, 45.0, 44.0, 45.5, 46.5, 47.0,
+        ];
+        let mut live = RsiStream::new(5).unwrap();
+        let mut reference = RsiStream::new(5).unwrap();
+
+        for &value in &data {
+            live.next(value + 1.0);
+            live.update(value - 3.0);
+            let amended = live.update(value);
+            let expected = reference.next(value);
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => assert_approx_eq(a, e),
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_rsi_stream_reset() {
         let mut rsi = RsiStream::new(3).unwrap();
@@ -408,4 +504,27 @@ mod tests {
 
         assert!(result.iter().all(|v| v.is_nan()));
     }
+
+    #[test]
+    fn test_rsi_lookback() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 100.0 + (f64::from(i) * 0.5).sin())
+            .collect();
+        let rsi = Rsi::new(14).unwrap();
+        let result = rsi.calculate(&prices).unwrap();
+
+        assert_eq!(rsi.lookback(), 14);
+        assert_eq!(rsi.warmup_len(), 15);
+        assert!(result[..14].iter().all(|v| v.is_nan()));
+        assert!(!result[14].is_nan());
+
+        let mut stream = RsiStream::new(14).unwrap();
+        assert_eq!(stream.lookback(), rsi.lookback());
+        assert!(stream
+            .init(&prices[..14])
+            .unwrap()
+            .iter()
+            .all(|v| v.is_nan()));
+        assert!(stream.next(prices[14]).is_some());
+    }
 }
@@ -0,0 +1,650 @@
+//! Session and periodic volume profiles.
+//!
+//! Splits candles into trading sessions (see [`Session`]) or calendar periods
+//! (days, weeks, months) and builds a [`Frvp`] profile for each one, so a
+//! chart gets a POC/VAH/VAL series without slicing the data by hand.
+//!
+//! # Output
+//! - **Session profiles**: POC, VAH, VAL and histogram of every session
+//! - **Developing lines**: POC, VAH and VAL of the session so far, bar by bar
+//! - **Naked POCs**: session POCs no later candle has traded through yet
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::{ProfilePeriod, SessionVolumeProfile};
+//! use ta_core::traits::Indicator;
+//! use ta_core::types::OHLCV;
+//!
+//! const DAY: i64 = 86_400_000;
+//! let profile = SessionVolumeProfile::new(ProfilePeriod::default(), 10).unwrap();
+//! let candles = [
+//!     OHLCV::new(0, 100.0, 101.0, 99.0, 100.0, 1000.0),
+//!     OHLCV::new(3_600_000, 100.0, 102.0, 100.0, 101.0, 500.0),
+//!     // Next UTC day, trading above the first day's POC
+//!     OHLCV::new(DAY, 103.0, 105.0, 103.0, 104.0, 800.0),
+//! ];
+//! let result = profile.calculate(&candles[..]).unwrap();
+//!
+//! assert_eq!(result.sessions.len(), 2);
+//! assert_eq!(result.developing.len(), 3);
+//! assert!(result.sessions[0].is_naked());
+//! ```
+
+use std::mem;
+
+use super::frvp::{Frvp, FrvpOutput};
+use crate::resample::Timeframe;
+use crate::session::Session;
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{IndicatorError, IndicatorResult, OhlcvColumns, OHLCV};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
+// ============================================================================
+// Periods
+// ============================================================================
+
+/// How candles are split into profiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProfilePeriod {
+    /// One profile per session of a calendar; candles outside its sessions
+    /// (pre-market, weekends, holidays) are skipped
+    Session(Session),
+    /// One profile per calendar period, e.g. `Timeframe::Weeks(1)`, of the
+    /// trading dates of a calendar (see [`Resampler::with_session`]);
+    /// candles outside its sessions are skipped
+    ///
+    /// [`Resampler::with_session`]: crate::resample::Resampler::with_session
+    Calendar {
+        /// Length of each period
+        timeframe: Timeframe,
+        /// Calendar whose trading dates make up the periods
+        session: Session,
+    },
+}
+
+impl ProfilePeriod {
+    /// Periods of `timeframe` over UTC days.
+    #[must_use]
+    pub const fn calendar(timeframe: Timeframe) -> Self {
+        Self::Calendar {
+            timeframe,
+            session: Session::utc(),
+        }
+    }
+
+    fn validate(&self) -> IndicatorResult<()> {
+        match self {
+            Self::Calendar { timeframe, .. } if timeframe.count() == 0 => Err(
+                IndicatorError::InvalidParameter("timeframe must be greater than 0".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the session calendar.
+    const fn session(&self) -> &Session {
+        match self {
+            Self::Session(session) | Self::Calendar { session, .. } => session,
+        }
+    }
+
+    /// Key of the period `timestamp` falls in: the trading date of a session
+    /// or the key of a calendar period's bar.
+    fn key(&self, timestamp: i64) -> Option<i64> {
+        match self {
+            Self::Session(session) => session.trading_date(timestamp),
+            Self::Calendar { timeframe, session } => timeframe.bar_key(timestamp, session),
+        }
+    }
+
+    /// Open time of the period with `key`.
+    fn start(&self, key: i64) -> i64 {
+        match self {
+            Self::Session(session) => session.open_time(key),
+            Self::Calendar { timeframe, session } => timeframe.key_open(key, session),
+        }
+    }
+}
+
+/// UTC days.
+impl Default for ProfilePeriod {
+    fn default() -> Self {
+        Self::Session(Session::utc())
+    }
+}
+
+// ============================================================================
+// Output Types
+// ============================================================================
+
+/// Volume profile of one completed session or period.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionProfile {
+    /// Open time of the session or period
+    pub start: i64,
+    /// Timestamp of its last candle
+    pub end: i64,
+    /// Number of candles in it
+    pub candles: usize,
+    /// Its profile
+    pub profile: FrvpOutput,
+    /// Timestamp of the first later candle whose range included the POC;
+    /// `None` while the POC is naked
+    pub poc_revisited: Option<i64>,
+}
+
+impl SessionProfile {
+    /// Whether no later candle has traded through the POC.
+    #[must_use]
+    pub const fn is_naked(&self) -> bool {
+        self.poc_revisited.is_none()
+    }
+}
+
+/// POC, VAH and VAL of the session so far.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DevelopingProfile {
+    /// Developing Point of Control
+    pub poc: f64,
+    /// Developing Value Area High
+    pub vah: f64,
+    /// Developing Value Area Low
+    pub val: f64,
+}
+
+impl DevelopingProfile {
+    /// Creates a NaN output for candles outside any session.
+    #[must_use]
+    pub const fn nan() -> Self {
+        Self {
+            poc: f64::NAN,
+            vah: f64::NAN,
+            val: f64::NAN,
+        }
+    }
+}
+
+impl IndicatorOutput for DevelopingProfile {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
+impl From<&FrvpOutput> for DevelopingProfile {
+    fn from(profile: &FrvpOutput) -> Self {
+        Self {
+            poc: profile.poc,
+            vah: profile.vah,
+            val: profile.val,
+        }
+    }
+}
+
+/// Output from session volume profile calculation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionVolumeProfileOutput {
+    /// Profile of every session, oldest first; the last one may be incomplete
+    pub sessions: Vec<SessionProfile>,
+    /// Developing POC/VAH/VAL for every candle (`NaN` outside sessions)
+    pub developing: Vec<DevelopingProfile>,
+}
+
+impl SessionVolumeProfileOutput {
+    /// Sessions whose POC no later candle has traded through.
+    pub fn naked_pocs(&self) -> impl Iterator<Item = &SessionProfile> {
+        self.sessions.iter().filter(|session| session.is_naked())
+    }
+}
+
+// ============================================================================
+// Batch Calculator
+// ============================================================================
+
+/// Session volume profile calculator for batch operations.
+///
+/// Builds a [`Frvp`] profile per session or period and the developing
+/// POC/VAH/VAL after each candle. Candles are expected in time order;
+/// candles before the calendar's time zone rules are rejected with
+/// `InvalidData`.
+#[derive(Debug, Clone)]
+pub struct SessionVolumeProfile {
+    period: ProfilePeriod,
+    frvp: Frvp,
+}
+
+impl SessionVolumeProfile {
+    /// Creates a calculator with `num_bins` price bins per profile.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `num_bins` is 0 or a calendar period's
+    /// timeframe count is 0.
+    pub fn new(period: ProfilePeriod, num_bins: usize) -> IndicatorResult<Self> {
+        period.validate()?;
+        Ok(Self {
+            period,
+            frvp: Frvp::new(num_bins)?,
+        })
+    }
+
+    /// Creates a calculator with a custom value area percentage.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `num_bins` is 0 or a calendar period's
+    /// timeframe count is 0, and `OutOfRange` if `value_area_percent` is not
+    /// between 0 and 1.
+    pub fn with_value_area(
+        period: ProfilePeriod,
+        num_bins: usize,
+        value_area_percent: f64,
+    ) -> IndicatorResult<Self> {
+        period.validate()?;
+        Ok(Self {
+            period,
+            frvp: Frvp::with_value_area(num_bins, value_area_percent)?,
+        })
+    }
+
+    /// Returns how candles are split into profiles.
+    #[must_use]
+    pub const fn period(&self) -> &ProfilePeriod {
+        &self.period
+    }
+
+    fn stream(&self) -> SessionVolumeProfileStream {
+        SessionVolumeProfileStream {
+            period: self.period.clone(),
+            frvp: self.frvp.clone(),
+            key: None,
+            candles: Vec::new(),
+            sessions: Vec::new(),
+            naked: Vec::new(),
+            amendable: false,
+            touched: Vec::new(),
+            reopened: None,
+        }
+    }
+
+    fn profiles(
+        &self,
+        data: impl ExactSizeIterator<Item = OHLCV>,
+    ) -> IndicatorResult<SessionVolumeProfileOutput> {
+        let mut stream = self.stream();
+        let mut developing = Vec::with_capacity(data.len());
+        for (index, candle) in data.enumerate() {
+            self.period
+                .session()
+                .check_timestamp(index, candle.timestamp)?;
+            if self.period.key(candle.timestamp).is_some() && !is_finite(&candle) {
+                return Err(IndicatorError::NonFiniteInput { index });
+            }
+            developing.push(stream.next(candle).unwrap_or_else(DevelopingProfile::nan));
+        }
+
+        let current = stream.current_session();
+        let mut sessions = stream.sessions;
+        sessions.extend(current);
+        Ok(SessionVolumeProfileOutput {
+            sessions,
+            developing,
+        })
+    }
+}
+
+impl Indicator<&[OHLCV], SessionVolumeProfileOutput> for SessionVolumeProfile {
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<SessionVolumeProfileOutput> {
+        self.profiles(data.iter().copied())
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+impl SessionVolumeProfile {
+    /// Calculates the session profiles from the candles of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slice.
+    pub fn calculate_columns(
+        &self,
+        data: &OhlcvColumns<'_>,
+    ) -> IndicatorResult<SessionVolumeProfileOutput> {
+        self.profiles(data.iter())
+    }
+}
+
+/// Whether the candle's high, low and volume are finite.
+fn is_finite(candle: &OHLCV) -> bool {
+    candle.high.is_finite() && candle.low.is_finite() && candle.volume.is_finite()
+}
+
+// ============================================================================
+// Streaming Calculator
+// ============================================================================
+
+/// Streaming session volume profile.
+///
+/// Each candle returns the developing POC/VAH/VAL of its session; the
+/// profile of a session is completed when the first candle of the next one
+/// arrives. Like [`FrvpStream`](super::FrvpStream), the developing profile is
+/// rebuilt from the session's candles on every update.
+///
+/// Candles outside any session, and candles with a non-finite high, low or
+/// volume, return `None` and leave the state unchanged.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionVolumeProfileStream {
+    period: ProfilePeriod,
+    frvp: Frvp,
+    // Key of the forming session and its candles
+    key: Option<i64>,
+    candles: Vec<OHLCV>,
+    sessions: Vec<SessionProfile>,
+    // Indices into `sessions` of the naked POCs, ascending
+    naked: Vec<usize>,
+    // Whether the most recent candle was accepted, so `update` can undo it
+    amendable: bool,
+    // Sessions whose POC the most recent candle revisited
+    touched: Vec<usize>,
+    // Key and candles of the session the most recent candle closed
+    reopened: Option<(i64, Vec<OHLCV>)>,
+}
+
+impl SessionVolumeProfileStream {
+    /// Creates a stream with `num_bins` price bins per profile.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `num_bins` is 0 or a calendar period's
+    /// timeframe count is 0.
+    pub fn new(period: ProfilePeriod, num_bins: usize) -> IndicatorResult<Self> {
+        Ok(SessionVolumeProfile::new(period, num_bins)?.stream())
+    }
+
+    /// Creates a stream with a custom value area percentage.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `num_bins` is 0 or a calendar period's
+    /// timeframe count is 0, and `OutOfRange` if `value_area_percent` is not
+    /// between 0 and 1.
+    pub fn with_value_area(
+        period: ProfilePeriod,
+        num_bins: usize,
+        value_area_percent: f64,
+    ) -> IndicatorResult<Self> {
+        Ok(SessionVolumeProfile::with_value_area(period, num_bins, value_area_percent)?.stream())
+    }
+
+    /// Completed sessions, oldest first.
+    #[must_use]
+    pub fn sessions(&self) -> &[SessionProfile] {
+        &self.sessions
+    }
+
+    /// Completed sessions whose POC no later candle has traded through.
+    pub fn naked_pocs(&self) -> impl Iterator<Item = &SessionProfile> {
+        self.naked.iter().map(|&idx| &self.sessions[idx])
+    }
+
+    /// Profile of the forming session, if it has any candles.
+    #[must_use]
+    pub fn current_session(&self) -> Option<SessionProfile> {
+        self.profile(self.key?, &self.candles)
+    }
+
+    fn profile(&self, key: i64, candles: &[OHLCV]) -> Option<SessionProfile> {
+        Some(SessionProfile {
+            start: self.period.start(key),
+            end: candles.last()?.timestamp,
+            candles: candles.len(),
+            profile: self.frvp.calculate(candles).ok()?,
+            poc_revisited: None,
+        })
+    }
+
+    /// Mark the naked POCs within the candle's range as revisited.
+    fn touch(&mut self, candle: &OHLCV) {
+        let sessions = &mut self.sessions;
+        let touched = &mut self.touched;
+        self.naked.retain(|&idx| {
+            let session = &mut sessions[idx];
+            let poc = session.profile.poc;
+            if candle.low <= poc && poc <= candle.high {
+                session.poc_revisited = Some(candle.timestamp);
+                touched.push(idx);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Undo the most recent candle.
+    fn undo(&mut self) {
+        self.candles.pop();
+        for idx in self.touched.drain(..) {
+            self.sessions[idx].poc_revisited = None;
+            self.naked.push(idx);
+        }
+        self.naked.sort_unstable();
+
+        if let Some((key, candles)) = self.reopened.take() {
+            self.sessions.pop();
+            self.naked.retain(|&idx| idx < self.sessions.len());
+            self.key = Some(key);
+            self.candles = candles;
+        } else if self.candles.is_empty() {
+            self.key = None;
+        }
+        self.amendable = false;
+    }
+
+    /// Checks the period, profile settings and POC indices of a restored
+    /// stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        self.period.validate().map_err(|e| e.to_string())?;
+        self.period.session().check_state()?;
+        self.frvp.check_state()?;
+        ensure(
+            self.naked
+                .iter()
+                .chain(&self.touched)
+                .all(|&idx| idx < self.sessions.len()),
+            "POC indices must refer to closed sessions",
+        )
+    }
+}
+
+impl StreamingIndicator<OHLCV, DevelopingProfile> for SessionVolumeProfileStream {
+    fn init(&mut self, data: &[OHLCV]) -> IndicatorResult<Vec<DevelopingProfile>> {
+        self.reset();
+        Ok(data
+            .iter()
+            .map(|&candle| self.next(candle).unwrap_or_else(DevelopingProfile::nan))
+            .collect())
+    }
+
+    fn next(&mut self, candle: OHLCV) -> Option<DevelopingProfile> {
+        self.amendable = false;
+        let key = self.period.key(candle.timestamp)?;
+        if !is_finite(&candle) {
+            return None;
+        }
+        self.touched.clear();
+        self.reopened = None;
+
+        if self.key != Some(key) {
+            if let Some(prev) = self.key {
+                let candles = mem::take(&mut self.candles);
+                if let Some(session) = self.profile(prev, &candles) {
+                    self.naked.push(self.sessions.len());
+                    self.sessions.push(session);
+                }
+                self.reopened = Some((prev, candles));
+            }
+            self.key = Some(key);
+        }
+
+        self.touch(&candle);
+        self.candles.push(candle);
+        self.amendable = true;
+        self.frvp
+            .calculate(self.candles.as_slice())
+            .ok()
+            .map(|profile| DevelopingProfile::from(&profile))
+    }
+
+    fn update(&mut self, candle: OHLCV) -> Option<DevelopingProfile> {
+        if self.amendable {
+            self.undo();
+        }
+        self.next(candle)
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<DevelopingProfile> {
+        self.clone().next(candle)
+    }
+
+    fn reset(&mut self) {
+        self.key = None;
+        self.candles.clear();
+        self.sessions.clear();
+        self.naked.clear();
+        self.amendable = false;
+        self.touched.clear();
+        self.reopened = None;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.key.is_some()
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{TimeZone, Weekday};
+
+    const HOUR: i64 = 3_600_000;
+    const DAY: i64 = 24 * HOUR;
+
+    fn candle(timestamp: i64, high: f64, low: f64, volume: f64) -> OHLCV {
+        OHLCV::new(timestamp, low, high, low, high, volume)
+    }
+
+    fn sample() -> Vec<OHLCV> {
+        vec![
+            candle(0, 101.0, 99.0, 1000.0),
+            candle(HOUR, 102.0, 100.0, 500.0),
+            // Day 2 stays above day 1
+            candle(DAY, 106.0, 104.0, 800.0),
+            candle(DAY + HOUR, 107.0, 105.0, 800.0),
+            // Day 3 trades back down through day 2's POC
+            candle(2 * DAY, 106.0, 103.0, 300.0),
+        ]
+    }
+
+    #[test]
+    fn test_session_profile_matches_frvp_per_session() {
+        let candles = sample();
+        let profile = SessionVolumeProfile::new(ProfilePeriod::default(), 20).unwrap();
+        let result = profile.calculate(&candles[..]).unwrap();
+        let frvp = Frvp::new(20).unwrap();
+
+        assert_eq!(result.sessions.len(), 3);
+        for (session, range) in result.sessions.iter().zip([0..2, 2..4, 4..5]) {
+            let expected = frvp.calculate(&candles[range.clone()]).unwrap();
+            assert_eq!(session.profile, expected);
+            assert_eq!(session.candles, range.len());
+            assert_eq!(session.end, candles[range.end - 1].timestamp);
+        }
+        assert_eq!(result.sessions[1].start, DAY);
+
+        // Developing values over the session so far
+        let partial = frvp.calculate(&candles[2..4]).unwrap();
+        assert_eq!(result.developing[3], DevelopingProfile::from(&partial));
+        let first = frvp.calculate(&candles[..1]).unwrap();
+        assert_eq!(result.developing[0], DevelopingProfile::from(&first));
+
+        // Day 1's POC is never revisited, day 2's is on day 3
+        let naked: Vec<i64> = result.naked_pocs().map(|s| s.start).collect();
+        assert_eq!(naked, [0, 2 * DAY]);
+        assert_eq!(result.sessions[1].poc_revisited, Some(2 * DAY));
+    }
+
+    #[test]
+    fn test_session_profile_calendar_and_sessions() {
+        let candles = sample();
+        let weekly = SessionVolumeProfile::new(ProfilePeriod::calendar(Timeframe::Weeks(1)), 20)
+            .unwrap()
+            .calculate(&candles[..])
+            .unwrap();
+        // 1970-01-01 was a Thursday: all in the week opening Monday 1969-12-29
+        assert_eq!(weekly.sessions.len(), 1);
+        assert_eq!(weekly.sessions[0].start, -3 * DAY);
+
+        // 00:30-17:00 UTC sessions, every day, skip the midnight candles
+        let session = Session::new(TimeZone::utc(), (0, 30), (17, 0))
+            .unwrap()
+            .with_trading_days(&Weekday::ALL);
+        let profile = SessionVolumeProfile::new(ProfilePeriod::Session(session), 20).unwrap();
+        let result = profile.calculate(&candles[..]).unwrap();
+        assert!(result.developing[0].poc.is_nan());
+        assert!(!result.developing[1].poc.is_nan());
+        assert_eq!(result.sessions[0].candles, 1);
+
+        assert!(matches!(
+            SessionVolumeProfile::new(ProfilePeriod::calendar(Timeframe::Days(0)), 20),
+            Err(IndicatorError::InvalidParameter(_))
+        ));
+        let mut bad = candles.clone();
+        bad[3].high = f64::NAN;
+        assert!(matches!(
+            SessionVolumeProfile::new(ProfilePeriod::default(), 20)
+                .unwrap()
+                .calculate(&bad[..]),
+            Err(IndicatorError::NonFiniteInput { index: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_session_profile_stream() {
+        let candles = sample();
+        let batch = SessionVolumeProfile::new(ProfilePeriod::default(), 20)
+            .unwrap()
+            .calculate(&candles[..])
+            .unwrap();
+
+        let mut stream = SessionVolumeProfileStream::new(ProfilePeriod::default(), 20).unwrap();
+        let developing = stream.init(&candles[..4]).unwrap();
+        assert_eq!(developing, batch.developing[..4]);
+        assert_eq!(stream.sessions().len(), 1);
+
+        // A provisional candle through day 2's POC, then its final version
+        let poc = stream.current_session().unwrap().profile.poc;
+        stream.next(candle(2 * DAY, poc + 1.0, poc - 1.0, 300.0));
+        assert_eq!(stream.naked_pocs().count(), 1);
+        stream.update(candle(2 * DAY, 120.0, 119.0, 300.0));
+        assert_eq!(stream.naked_pocs().count(), 2);
+        assert_eq!(stream.update(candles[4]), Some(batch.developing[4]));
+        assert_eq!(stream.sessions(), &batch.sessions[..2]);
+        assert_eq!(stream.current_session().as_ref(), batch.sessions.last());
+
+        // Updating the first candle of a session reopens the previous one
+        stream.update(candle(DAY + 2 * HOUR, 107.0, 105.0, 10.0));
+        assert_eq!(stream.sessions().len(), 1);
+        assert_eq!(stream.current_session().unwrap().candles, 3);
+
+        stream.reset();
+        assert!(!stream.is_ready());
+    }
+}
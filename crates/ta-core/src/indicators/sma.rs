@@ -31,6 +31,9 @@
 use crate::traits::{Indicator, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::{check_ring, ensure};
+
 /// Simple Moving Average calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Sma {
@@ -79,18 +82,27 @@ impl Indicator<&[f64], Vec<f64>> for Sma {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 /// Simple Moving Average calculator for streaming/real-time operations.
 ///
 /// Maintains a ring buffer for O(1) updates.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SmaStream {
     period: usize,
     buffer: Vec<f64>,
     head: usize,
     sum: f64,
     count: usize,
+    // State before the most recent `next`, restored by `update`
+    prev_sum: f64,
+    prev_slot: f64,
+    prev_count: usize,
 }
 
 impl SmaStream {
@@ -110,6 +122,9 @@ impl SmaStream {
             head: 0,
             sum: 0.0,
             count: 0,
+            prev_sum: 0.0,
+            prev_slot: 0.0,
+            prev_count: 0,
         })
     }
 
@@ -118,6 +133,16 @@ impl SmaStream {
     pub const fn period(&self) -> usize {
         self.period
     }
+
+    /// Checks the ring buffer and counters of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        check_ring(self.period, self.buffer.len(), self.head)?;
+        ensure(
+            self.count <= self.period && self.prev_count <= self.period,
+            "count must not exceed the period",
+        )
+    }
 }
 
 impl StreamingIndicator<f64, f64> for SmaStream {
@@ -133,6 +158,10 @@ impl StreamingIndicator<f64, f64> for SmaStream {
 
     #[inline]
     fn next(&mut self, value: f64) -> Option<f64> {
+        self.prev_sum = self.sum;
+        self.prev_slot = self.buffer[self.head];
+        self.prev_count = self.count;
+
         // Subtract the value being replaced (if buffer is full)
         if self.count >= self.period {
             self.sum -= self.buffer[self.head];
@@ -158,16 +187,49 @@ impl StreamingIndicator<f64, f64> for SmaStream {
         }
     }
 
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        // Rewind the head and restore the slot the last value overwrote
+        self.head = (self.head + self.period - 1) % self.period;
+        self.buffer[self.head] = self.prev_slot;
+        self.sum = self.prev_sum;
+        self.count = self.prev_count;
+
+        self.next(value)
+    }
+
+    fn peek(&self, value: f64) -> Option<f64> {
+        if self.count + 1 < self.period {
+            return None;
+        }
+        let evicted = if self.count >= self.period {
+            self.buffer[self.head]
+        } else {
+            0.0
+        };
+        Some((self.sum - evicted + value) / self.period as f64)
+    }
+
     fn reset(&mut self) {
         self.buffer.fill(0.0);
         self.head = 0;
         self.sum = 0.0;
         self.count = 0;
+        self.prev_sum = 0.0;
+        self.prev_slot = 0.0;
+        self.prev_count = 0;
     }
 
     fn is_ready(&self) -> bool {
         self.count >= self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 #[cfg(test)]
@@ -249,6 +311,30 @@ mod tests {
         assert_eq!(sma.next(1.0), None);
     }
 
+    #[test]
+    fn test_sma_stream_update_replaces_last() {
+        let mut sma = SmaStream::new(3).unwrap();
+        sma.init(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+
+        // Forming bar revised several times, then closed at 5.0
+        assert_approx_eq(sma.update(10.0).unwrap(), 5.0); // (2+3+10)/3
+        assert_approx_eq(sma.update(4.5).unwrap(), 9.5 / 3.0);
+        assert_approx_eq(sma.update(5.0).unwrap(), 10.0 / 3.0);
+
+        let mut reference = SmaStream::new(3).unwrap();
+        reference.init(&[1.0, 2.0, 3.0, 5.0]).unwrap();
+        assert_approx_eq(sma.next(6.0).unwrap(), reference.next(6.0).unwrap());
+    }
+
+    #[test]
+    fn test_sma_stream_update_during_warmup() {
+        let mut sma = SmaStream::new(3).unwrap();
+        assert_eq!(sma.update(1.0), None); // Acts like next on empty stream
+        assert_eq!(sma.update(2.0), None);
+        assert_eq!(sma.next(3.0), None);
+        assert_approx_eq(sma.next(4.0).unwrap(), 3.0); // (2+3+4)/3
+    }
+
     #[test]
     fn test_sma_insufficient_data() {
         let sma = Sma::new(5).unwrap();
@@ -0,0 +1,673 @@
+//! TTM Squeeze (squeeze momentum) indicator.
+//!
+//! Detects volatility compression: the market is "in a squeeze" while the
+//! Bollinger Bands sit inside the Keltner Channels, and the squeeze "fires"
+//! when they expand back out. A momentum histogram gives the likely direction
+//! of the breakout.
+//!
+//! # Components
+//! - **Squeeze On**: BB upper < KC upper and BB lower > KC lower
+//! - **Squeeze Bars**: consecutive squeeze-on bars, 0 once it is off
+//! - **Momentum**: linear regression value of `close - midline` over the period
+//!
+//! # Formulas
+//! ```text
+//! BB = SMA(close) ± bb_mult × σ(close)
+//! KC = SMA(close) ± kc_mult × ATR
+//! Midline = (Donchian midpoint + SMA(close)) / 2
+//! Donchian midpoint = (highest high + lowest low) / 2
+//! Momentum = LinReg(close - Midline, period)
+//! ```
+//!
+//! # Default Parameters
+//! - Period: 20
+//! - BB multiplier: 2.0
+//! - KC multiplier: 1.5
+//!
+//! # Example (Batch Mode)
+//! ```
+//! use ta_core::indicators::Squeeze;
+//! use ta_core::traits::Indicator;
+//!
+//! let squeeze = Squeeze::new(20, 2.0, 1.5).unwrap();
+//! let closes: Vec<f64> = (0..60).map(|x| 100.0 + (x as f64 * 0.3).sin()).collect();
+//! let highs: Vec<f64> = closes.iter().map(|c| c + 1.0).collect();
+//! let lows: Vec<f64> = closes.iter().map(|c| c - 1.0).collect();
+//! let result = squeeze.calculate(&(&highs[..], &lows[..], &closes[..])).unwrap();
+//! assert!(result[59].squeeze_on);
+//! ```
+
+use crate::indicators::monotonic::{Extremum, MonotonicDeque};
+use crate::indicators::{AtrStream, BBandsOutput, BBandsStream};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::{check_ring, ensure};
+
+/// Squeeze output: state, duration, and momentum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SqueezeOutput {
+    /// Bollinger Bands are inside the Keltner Channels
+    pub squeeze_on: bool,
+    /// Consecutive squeeze-on bars including this one (0 when off)
+    pub squeeze_bars: usize,
+    /// Momentum histogram (NaN until the regression window is full)
+    pub momentum: f64,
+}
+
+impl SqueezeOutput {
+    /// Creates a NaN output for insufficient data.
+    #[must_use]
+    pub fn nan() -> Self {
+        Self {
+            squeeze_on: false,
+            squeeze_bars: 0,
+            momentum: f64::NAN,
+        }
+    }
+}
+
+impl IndicatorOutput for SqueezeOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
+/// Input type for Squeeze: (highs, lows, closes)
+pub type SqueezeInput<'a> = (&'a [f64], &'a [f64], &'a [f64]);
+
+/// Input for streaming: (high, low, close) tuple
+pub type SqueezeBar = (f64, f64, f64);
+
+/// TTM Squeeze calculator for batch operations.
+#[derive(Debug, Clone)]
+pub struct Squeeze {
+    period: usize,
+    bb_mult: f64,
+    kc_mult: f64,
+}
+
+impl Squeeze {
+    /// Creates a new Squeeze calculator.
+    ///
+    /// # Arguments
+    /// * `period` - Period for the bands, Donchian midline and momentum regression (typically 20)
+    /// * `bb_mult` - Bollinger Bands standard deviation multiplier (typically 2.0)
+    /// * `kc_mult` - Keltner Channels ATR multiplier (typically 1.5)
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if period is less than 2 or a multiplier is not positive.
+    pub fn new(period: usize, bb_mult: f64, kc_mult: f64) -> IndicatorResult<Self> {
+        validate(period, bb_mult, kc_mult)?;
+        Ok(Self {
+            period,
+            bb_mult,
+            kc_mult,
+        })
+    }
+
+    /// Creates with default parameters (20, 2.0, 1.5).
+    pub fn default_params() -> IndicatorResult<Self> {
+        Self::new(20, 2.0, 1.5)
+    }
+
+    /// Returns the period.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the Bollinger Bands multiplier.
+    #[must_use]
+    pub const fn bb_mult(&self) -> f64 {
+        self.bb_mult
+    }
+
+    /// Returns the Keltner Channels multiplier.
+    #[must_use]
+    pub const fn kc_mult(&self) -> f64 {
+        self.kc_mult
+    }
+}
+
+impl Indicator<&SqueezeInput<'_>, Vec<SqueezeOutput>> for Squeeze {
+    fn calculate(&self, data: &SqueezeInput<'_>) -> IndicatorResult<Vec<SqueezeOutput>> {
+        let (highs, lows, closes) = *data;
+
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
+
+        let bars: Vec<SqueezeBar> = highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .map(|((&h, &l), &c)| (h, l, c))
+            .collect();
+
+        SqueezeStream::new(self.period, self.bb_mult, self.kc_mult)?.init(&bars)
+    }
+
+    fn lookback(&self) -> usize {
+        // Momentum regresses `period` values that start once the bands do
+        2 * self.period - 2
+    }
+}
+
+impl Squeeze {
+    /// Calculates the squeeze from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(
+        &self,
+        data: &OhlcvColumns<'_>,
+    ) -> IndicatorResult<Vec<SqueezeOutput>> {
+        self.calculate(&(data.high(), data.low(), data.close()))
+    }
+}
+
+fn validate(period: usize, bb_mult: f64, kc_mult: f64) -> IndicatorResult<()> {
+    if period < 2 {
+        return Err(IndicatorError::InvalidParameter(
+            "period must be at least 2 for the momentum regression".to_string(),
+        ));
+    }
+    if bb_mult <= 0.0 || !bb_mult.is_finite() || kc_mult <= 0.0 || !kc_mult.is_finite() {
+        return Err(IndicatorError::InvalidParameter(
+            "multipliers must be positive finite numbers".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Rolling least-squares fit of the last `period` values, reporting the
+/// fitted value at the newest one.
+///
+/// Σy and Σxy (x = 0 for the oldest value) are running sums, so each value
+/// is O(1) once the window is full. They are recomputed from the window once
+/// every `period` values, so rounding does not build up, and while they are
+/// not finite, so a NaN stops affecting the fit once it leaves the window.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct RollingRegression {
+    period: usize,
+    buffer: Vec<f64>,
+    // Slot of the oldest value once the window is full
+    head: usize,
+    count: usize,
+    sum_y: f64,
+    sum_xy: f64,
+    // (Σy, Σxy, evicted value) before the most recent `next`, restored by `update`
+    prev: (f64, f64, f64),
+}
+
+impl RollingRegression {
+    fn new(period: usize) -> Self {
+        Self {
+            period,
+            buffer: vec![0.0; period],
+            head: 0,
+            count: 0,
+            sum_y: 0.0,
+            sum_xy: 0.0,
+            prev: (0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Window values, oldest first.
+    fn window(&self) -> impl Iterator<Item = f64> + '_ {
+        self.buffer[self.head..self.count]
+            .iter()
+            .chain(&self.buffer[..self.head])
+            .copied()
+    }
+
+    /// (Σy, Σxy) of `values`, the first at x = 0.
+    fn sums(values: impl Iterator<Item = f64>) -> (f64, f64) {
+        let mut x = 0.0;
+        let (mut total, mut weighted) = (0.0, 0.0);
+        for y in values {
+            total += y;
+            weighted += x * y;
+            x += 1.0;
+        }
+        (total, weighted)
+    }
+
+    /// (Σy, Σxy) once `value` is pushed.
+    fn pushed_sums(&self, value: f64) -> (f64, f64) {
+        let skip = if self.count < self.period {
+            // Still filling: at most `period` values to sum
+            0
+        } else {
+            // Every remaining value moves one step closer to x = 0
+            let kept = self.sum_y - self.buffer[self.head];
+            let total = kept + value;
+            let weighted = self.sum_xy - kept + self.last_x() * value;
+            let wraps = self.head + 1 == self.period;
+            if !wraps && total.is_finite() && weighted.is_finite() {
+                return (total, weighted);
+            }
+            1
+        };
+        Self::sums(self.window().skip(skip).chain(std::iter::once(value)))
+    }
+
+    /// x of the newest value in a full window.
+    fn last_x(&self) -> f64 {
+        (self.period - 1) as f64
+    }
+
+    /// Fitted value at the newest of `period` values with these sums.
+    fn fit(&self, (total, weighted): (f64, f64)) -> f64 {
+        let last = self.last_x();
+        let n = last + 1.0;
+        let x_total = n * last / 2.0;
+        let x_squares = x_total * (2.0 * last + 1.0) / 3.0;
+        let slope = (n * weighted - x_total * total) / (n * x_squares - x_total * x_total);
+        let intercept = (total - slope * x_total) / n;
+        intercept + slope * last
+    }
+
+    fn next(&mut self, value: f64) -> Option<f64> {
+        let sums = self.pushed_sums(value);
+        if self.count < self.period {
+            self.prev = (self.sum_y, self.sum_xy, 0.0);
+            self.buffer[self.count] = value;
+            self.count += 1;
+        } else {
+            self.prev = (self.sum_y, self.sum_xy, self.buffer[self.head]);
+            self.buffer[self.head] = value;
+            self.head = (self.head + 1) % self.period;
+        }
+        (self.sum_y, self.sum_xy) = sums;
+        self.is_ready().then(|| self.fit(sums))
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+        if self.count < self.period {
+            self.count -= 1;
+        } else {
+            self.head = (self.head + self.period - 1) % self.period;
+            self.buffer[self.head] = self.prev.2;
+        }
+        (self.sum_y, self.sum_xy) = (self.prev.0, self.prev.1);
+        self.next(value)
+    }
+
+    fn peek(&self, value: f64) -> Option<f64> {
+        (self.count + 1 >= self.period).then(|| self.fit(self.pushed_sums(value)))
+    }
+
+    fn reset(&mut self) {
+        self.head = 0;
+        self.count = 0;
+        self.sum_y = 0.0;
+        self.sum_xy = 0.0;
+        self.prev = (0.0, 0.0, 0.0);
+    }
+
+    const fn is_ready(&self) -> bool {
+        self.count == self.period
+    }
+
+    /// Checks the ring buffer and counter of a restored regression.
+    #[cfg(feature = "serde")]
+    fn check_state(&self) -> Result<(), String> {
+        check_ring(self.period, self.buffer.len(), self.head)?;
+        ensure(
+            self.count == self.period || (self.count < self.period && self.head == 0),
+            "count must not exceed the period, and the ring only turns once full",
+        )
+    }
+}
+
+/// Streaming TTM Squeeze calculator.
+///
+/// Composes `BBandsStream` (whose middle band doubles as the Keltner
+/// midline), `AtrStream`, monotonic deques for the Donchian midline, and a
+/// rolling regression of running sums for momentum, so each bar is O(1).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SqueezeStream {
+    period: usize,
+    kc_mult: f64,
+    bbands: BBandsStream,
+    atr: AtrStream,
+    momentum: RollingRegression,
+    max_deque: MonotonicDeque, // highest high
+    min_deque: MonotonicDeque, // lowest low
+    index: usize,
+    squeeze_bars: usize,
+    // (squeeze_bars, momentum fed) before the most recent `next`, restored by `update`
+    prev_state: (usize, bool),
+}
+
+impl SqueezeStream {
+    /// Creates a new streaming Squeeze calculator.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if period is less than 2 or a multiplier is not positive.
+    pub fn new(period: usize, bb_mult: f64, kc_mult: f64) -> IndicatorResult<Self> {
+        validate(period, bb_mult, kc_mult)?;
+        Ok(Self {
+            period,
+            kc_mult,
+            bbands: BBandsStream::new(period, bb_mult)?,
+            atr: AtrStream::new(period)?,
+            momentum: RollingRegression::new(period),
+            max_deque: MonotonicDeque::new(Extremum::Max, period),
+            min_deque: MonotonicDeque::new(Extremum::Min, period),
+            index: 0,
+            squeeze_bars: 0,
+            prev_state: (0, false),
+        })
+    }
+
+    /// Returns the period.
+    #[must_use]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the Bollinger Bands multiplier.
+    #[must_use]
+    pub const fn bb_mult(&self) -> f64 {
+        self.bbands.k()
+    }
+
+    /// Returns the Keltner Channels multiplier.
+    #[must_use]
+    pub const fn kc_mult(&self) -> f64 {
+        self.kc_mult
+    }
+
+    /// Whether the bands are inside the Keltner Channels around the same midline.
+    #[inline]
+    fn is_squeezed(&self, bb: &BBandsOutput, atr: f64) -> bool {
+        let offset = self.kc_mult * atr;
+        bb.upper < bb.middle + offset && bb.lower > bb.middle - offset
+    }
+
+    /// Momentum input: close minus the average of the Donchian and SMA midlines.
+    #[inline]
+    fn delta(close: f64, highest: f64, lowest: f64, sma: f64) -> f64 {
+        close - ((highest + lowest) / 2.0 + sma) / 2.0
+    }
+
+    /// Push a bar, as a new one or as a replacement for the last (`amend`).
+    fn push(&mut self, bar: SqueezeBar, amend: bool) -> Option<SqueezeOutput> {
+        let (high, low, close) = bar;
+        self.max_deque.push(self.index, high);
+        self.min_deque.push(self.index, low);
+        self.index += 1;
+
+        let (bb, atr) = if amend {
+            (self.bbands.update(close), self.atr.update(bar))
+        } else {
+            (self.bbands.next(close), self.atr.next(bar))
+        };
+        let (Some(bb), Some(atr)) = (bb, atr) else {
+            return None;
+        };
+
+        let delta = Self::delta(
+            close,
+            self.max_deque.front()?,
+            self.min_deque.front()?,
+            bb.middle,
+        );
+        let momentum = if amend && self.prev_state.1 {
+            self.momentum.update(delta)
+        } else {
+            self.momentum.next(delta)
+        };
+        self.prev_state.1 = true;
+
+        let squeeze_on = self.is_squeezed(&bb, atr);
+        self.squeeze_bars = if squeeze_on { self.squeeze_bars + 1 } else { 0 };
+
+        Some(SqueezeOutput {
+            squeeze_on,
+            squeeze_bars: self.squeeze_bars,
+            momentum: momentum.unwrap_or(f64::NAN),
+        })
+    }
+
+    /// Checks the inner streams and deques of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        validate(self.period, self.bbands.k(), self.kc_mult).map_err(|e| e.to_string())?;
+        ensure(
+            self.bbands.period() == self.period
+                && self.atr.period() == self.period
+                && self.momentum.period == self.period,
+            "inner stream periods must match the stream",
+        )?;
+        self.bbands.check_state()?;
+        self.atr.check_state()?;
+        self.momentum.check_state()?;
+        self.max_deque
+            .check_state(Extremum::Max, self.period, self.index)?;
+        self.min_deque
+            .check_state(Extremum::Min, self.period, self.index)
+    }
+}
+
+impl StreamingIndicator<SqueezeBar, SqueezeOutput> for SqueezeStream {
+    fn init(&mut self, data: &[SqueezeBar]) -> IndicatorResult<Vec<SqueezeOutput>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &bar in data {
+            results.push(self.next(bar).unwrap_or_else(SqueezeOutput::nan));
+        }
+        Ok(results)
+    }
+
+    fn next(&mut self, bar: SqueezeBar) -> Option<SqueezeOutput> {
+        self.prev_state = (self.squeeze_bars, false);
+        self.push(bar, false)
+    }
+
+    fn update(&mut self, bar: SqueezeBar) -> Option<SqueezeOutput> {
+        if self.index == 0 {
+            return self.next(bar);
+        }
+
+        self.max_deque.rollback();
+        self.min_deque.rollback();
+        self.index -= 1;
+        self.squeeze_bars = self.prev_state.0;
+        self.push(bar, true)
+    }
+
+    fn peek(&self, bar: SqueezeBar) -> Option<SqueezeOutput> {
+        let (high, low, close) = bar;
+        let bb = self.bbands.peek(close)?;
+        let atr = self.atr.peek(bar)?;
+
+        let delta = Self::delta(
+            close,
+            self.max_deque.peek(self.index, high),
+            self.min_deque.peek(self.index, low),
+            bb.middle,
+        );
+        let squeeze_on = self.is_squeezed(&bb, atr);
+
+        Some(SqueezeOutput {
+            squeeze_on,
+            squeeze_bars: if squeeze_on { self.squeeze_bars + 1 } else { 0 },
+            momentum: self.momentum.peek(delta).unwrap_or(f64::NAN),
+        })
+    }
+
+    fn reset(&mut self) {
+        self.bbands.reset();
+        self.atr.reset();
+        self.momentum.reset();
+        self.max_deque.clear();
+        self.min_deque.clear();
+        self.index = 0;
+        self.squeeze_bars = 0;
+        self.prev_state = (0, false);
+    }
+
+    fn is_ready(&self) -> bool {
+        self.momentum.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        2 * self.period - 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{BBands, LinReg};
+
+    const EPSILON: f64 = 1e-9;
+
+    fn assert_approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < EPSILON, "expected {b}, got {a}");
+    }
+
+    /// Quiet, tight-ranged bars followed by a wide trending breakout.
+    fn sample() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let closes: Vec<f64> = (0..60)
+            .map(|i| {
+                let x = f64::from(i);
+                if i < 40 {
+                    100.0 + (x * 0.9).sin() * 0.3
+                } else {
+                    100.0 + (x - 39.0) * 2.0
+                }
+            })
+            .collect();
+        let highs = closes.iter().map(|c| c + 1.0).collect();
+        let lows = closes.iter().map(|c| c - 1.0).collect();
+        (highs, lows, closes)
+    }
+
+    #[test]
+    fn test_squeeze_state_and_count() {
+        let (highs, lows, closes) = sample();
+        let result = Squeeze::default_params()
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        assert!(!result[18].squeeze_on && result[18].momentum.is_nan());
+        assert!(result[19].squeeze_on);
+        assert_eq!(result[19].squeeze_bars, 1);
+        assert!(result[19].momentum.is_nan());
+        assert_eq!(result[39].squeeze_bars, 21);
+
+        // The breakout widens BB faster than ATR and releases the squeeze
+        let released = (40..60).find(|&i| !result[i].squeeze_on).unwrap();
+        assert_eq!(result[released].squeeze_bars, 0);
+        assert!(result[released].momentum > 0.0);
+
+        assert!(Squeeze::new(1, 2.0, 1.5).is_err());
+        assert!(Squeeze::new(20, 2.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_squeeze_momentum_matches_components() {
+        let (highs, lows, closes) = sample();
+        let period = 10;
+        let result = Squeeze::new(period, 2.0, 1.5)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        let sma = BBands::new(period, 2.0)
+            .unwrap()
+            .calculate(&closes[..])
+            .unwrap();
+        let deltas: Vec<f64> = (period - 1..closes.len())
+            .map(|i| {
+                let window = i + 1 - period..=i;
+                let highest = highs[window.clone()]
+                    .iter()
+                    .copied()
+                    .fold(f64::MIN, f64::max);
+                let lowest = lows[window].iter().copied().fold(f64::MAX, f64::min);
+                closes[i] - ((highest + lowest) / 2.0 + sma[i].middle) / 2.0
+            })
+            .collect();
+        let expected = LinReg::new(period, 0.0)
+            .unwrap()
+            .calculate(&deltas)
+            .unwrap();
+
+        for (j, e) in expected.iter().enumerate().skip(period - 1) {
+            assert_approx_eq(result[j + period - 1].momentum, e.value);
+        }
+        assert!(result[2 * period - 3].momentum.is_nan());
+    }
+
+    #[test]
+    fn test_rolling_regression_matches_linreg() {
+        let mut values: Vec<f64> = (0..500)
+            .map(|i| 1e6 + (f64::from(i) * 0.37).sin() * 50.0 + f64::from(i))
+            .collect();
+        values[100] = f64::NAN;
+        let period = 7;
+        let expected = LinReg::new(period, 0.0)
+            .unwrap()
+            .calculate(&values)
+            .unwrap();
+
+        let mut regression = RollingRegression::new(period);
+        for (i, &value) in values.iter().enumerate() {
+            let peeked = regression.peek(value);
+            regression.next(value + 3.0);
+            let fitted = regression.update(value);
+            assert_eq!(peeked.map(f64::to_bits), fitted.map(f64::to_bits));
+
+            match fitted {
+                // The NaN leaves the fit with the window
+                Some(v) if (100..100 + period).contains(&i) => assert!(v.is_nan()),
+                Some(v) => assert!((v - expected[i].value).abs() < 1e-6, "at {i}"),
+                None => assert!(i + 1 < period),
+            }
+        }
+    }
+
+    #[test]
+    fn test_squeeze_stream_update_and_peek_match_batch() {
+        let (highs, lows, closes) = sample();
+        let squeeze = Squeeze::new(10, 2.0, 1.5).unwrap();
+        let batch = squeeze
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        let mut stream = SqueezeStream::new(10, 2.0, 1.5).unwrap();
+        for i in 0..closes.len() {
+            let bar = (highs[i], lows[i], closes[i]);
+            let peeked = stream.peek(bar);
+            stream.next((bar.0 + 5.0, bar.1 - 5.0, bar.2 + 3.0));
+            let out = stream.update(bar).unwrap_or_else(SqueezeOutput::nan);
+
+            assert_eq!(
+                peeked.unwrap_or_else(SqueezeOutput::nan).squeeze_bars,
+                out.squeeze_bars
+            );
+            assert_eq!(out.squeeze_on, batch[i].squeeze_on);
+            assert_eq!(out.squeeze_bars, batch[i].squeeze_bars);
+            if !batch[i].momentum.is_nan() {
+                assert_approx_eq(out.momentum, batch[i].momentum);
+            }
+        }
+        assert!(stream.is_ready());
+    }
+}
@@ -12,10 +12,14 @@
 
 use std::collections::VecDeque;
 
+use crate::indicators::monotonic::{Extremum, MonotonicDeque};
 use crate::indicators::RsiStream;
-use crate::traits::{Indicator, StreamingIndicator};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
 /// Stochastic RSI output values.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct StochRsiOutput {
@@ -25,6 +29,15 @@ pub struct StochRsiOutput {
     pub d: f64,
 }
 
+impl IndicatorOutput for StochRsiOutput {
+    fn nan() -> Self {
+        Self {
+            k: f64::NAN,
+            d: f64::NAN,
+        }
+    }
+}
+
 /// Batch Stochastic RSI calculator.
 ///
 /// # Example
@@ -205,6 +218,11 @@ impl Indicator<&[f64], Vec<StochRsiOutput>> for StochRsi {
 
         Ok(results)
     }
+
+    fn lookback(&self) -> usize {
+        // RSI, then each of the three windows stacked on top of it
+        self.rsi_period + self.stoch_period + self.k_smooth + self.d_period - 3
+    }
 }
 
 /// Streaming Stochastic RSI calculator for O(1) updates.
@@ -225,6 +243,8 @@ impl Indicator<&[f64], Vec<StochRsiOutput>> for StochRsi {
 ///     println!("K: {}, D: {}", output.k, output.d);
 /// }
 /// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StochRsiStream {
     rsi_period: usize,
     stoch_period: usize,
@@ -238,8 +258,8 @@ pub struct StochRsiStream {
     rsi_window: VecDeque<f64>,
 
     // Monotonic deques for O(1) min/max
-    min_deque: VecDeque<(usize, f64)>, // (index, value)
-    max_deque: VecDeque<(usize, f64)>,
+    min_deque: MonotonicDeque,
+    max_deque: MonotonicDeque,
     rsi_index: usize,
 
     // K smoothing (SMA)
@@ -251,6 +271,11 @@ pub struct StochRsiStream {
     d_sum: f64,
 
     ready: bool,
+
+    // State before the most recent `process_rsi`, restored by `update`
+    prev_sums: (f64, f64),
+    prev_ready: bool,
+    evicted: (Option<f64>, Option<f64>, Option<f64>), // (rsi, k, d) window fronts
 }
 
 impl StochRsiStream {
@@ -291,14 +316,17 @@ impl StochRsiStream {
             d_period,
             rsi_stream,
             rsi_window: VecDeque::with_capacity(stoch_period),
-            min_deque: VecDeque::new(),
-            max_deque: VecDeque::new(),
+            min_deque: MonotonicDeque::new(Extremum::Min, stoch_period),
+            max_deque: MonotonicDeque::new(Extremum::Max, stoch_period),
             rsi_index: 0,
             k_window: VecDeque::with_capacity(k_smooth),
             k_sum: 0.0,
             d_window: VecDeque::with_capacity(d_period),
             d_sum: 0.0,
             ready: false,
+            prev_sums: (0.0, 0.0),
+            prev_ready: false,
+            evicted: (None, None, None),
         })
     }
 
@@ -328,37 +356,19 @@ impl StochRsiStream {
 
     /// Process a new RSI value and update stochastic calculations.
     fn process_rsi(&mut self, rsi: f64) -> Option<StochRsiOutput> {
+        self.prev_sums = (self.k_sum, self.d_sum);
+        self.prev_ready = self.ready;
+        self.evicted = (None, None, None);
+
         // Update RSI window
         self.rsi_window.push_back(rsi);
         if self.rsi_window.len() > self.stoch_period {
-            self.rsi_window.pop_front();
+            self.evicted.0 = self.rsi_window.pop_front();
         }
 
-        // Maintain min deque (monotonically increasing)
-        while self.min_deque.back().is_some_and(|(_, v)| *v >= rsi) {
-            self.min_deque.pop_back();
-        }
-        self.min_deque.push_back((self.rsi_index, rsi));
-
-        // Remove old entries from min deque
-        while self.min_deque.front().is_some_and(|(idx, _)| {
-            self.rsi_index >= self.stoch_period && *idx <= self.rsi_index - self.stoch_period
-        }) {
-            self.min_deque.pop_front();
-        }
-
-        // Maintain max deque (monotonically decreasing)
-        while self.max_deque.back().is_some_and(|(_, v)| *v <= rsi) {
-            self.max_deque.pop_back();
-        }
-        self.max_deque.push_back((self.rsi_index, rsi));
-
-        // Remove old entries from max deque
-        while self.max_deque.front().is_some_and(|(idx, _)| {
-            self.rsi_index >= self.stoch_period && *idx <= self.rsi_index - self.stoch_period
-        }) {
-            self.max_deque.pop_front();
-        }
+        // Maintain min/max deques
+        self.min_deque.push(self.rsi_index, rsi);
+        self.max_deque.push(self.rsi_index, rsi);
 
         self.rsi_index += 1;
 
@@ -368,8 +378,8 @@ impl StochRsiStream {
         }
 
         // Calculate raw Stochastic RSI
-        let min_rsi = self.min_deque.front().map(|(_, v)| *v)?;
-        let max_rsi = self.max_deque.front().map(|(_, v)| *v)?;
+        let min_rsi = self.min_deque.front()?;
+        let max_rsi = self.max_deque.front()?;
 
         let range = max_rsi - min_rsi;
         let stoch_rsi_raw = if range > 0.0 {
@@ -382,6 +392,7 @@ impl StochRsiStream {
         if self.k_window.len() == self.k_smooth {
             let old = self.k_window.pop_front().unwrap();
             self.k_sum -= old;
+            self.evicted.1 = Some(old);
         }
         self.k_window.push_back(stoch_rsi_raw);
         self.k_sum += stoch_rsi_raw;
@@ -396,6 +407,7 @@ impl StochRsiStream {
         if self.d_window.len() == self.d_period {
             let old = self.d_window.pop_front().unwrap();
             self.d_sum -= old;
+            self.evicted.2 = Some(old);
         }
         self.d_window.push_back(k);
         self.d_sum += k;
@@ -410,6 +422,60 @@ impl StochRsiStream {
 
         Some(StochRsiOutput { k, d })
     }
+
+    /// Undo the most recent `process_rsi`.
+    fn rollback_rsi(&mut self) {
+        let (rsi_front, k_front, d_front) = self.evicted;
+
+        // Mirror how far `process_rsi` got: %K needs a full window, %D a full K window
+        if self.rsi_window.len() >= self.stoch_period {
+            if self.k_window.len() >= self.k_smooth {
+                self.d_window.pop_back();
+                if let Some(v) = d_front {
+                    self.d_window.push_front(v);
+                }
+            }
+            self.k_window.pop_back();
+            if let Some(v) = k_front {
+                self.k_window.push_front(v);
+            }
+        }
+
+        self.rsi_window.pop_back();
+        if let Some(v) = rsi_front {
+            self.rsi_window.push_front(v);
+        }
+        self.min_deque.rollback();
+        self.max_deque.rollback();
+        self.rsi_index -= 1;
+
+        (self.k_sum, self.d_sum) = self.prev_sums;
+        self.ready = self.prev_ready;
+    }
+
+    /// Checks the RSI, windows and deques of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(
+            self.stoch_period > 0 && self.k_smooth > 0 && self.d_period > 0,
+            "all periods must be greater than 0",
+        )?;
+        ensure(
+            self.rsi_stream.period() == self.rsi_period,
+            "RSI period must match the stream",
+        )?;
+        self.rsi_stream.check_state()?;
+        ensure(
+            self.rsi_window.len() <= self.stoch_period
+                && self.k_window.len() <= self.k_smooth
+                && self.d_window.len() <= self.d_period,
+            "windows must not exceed their periods",
+        )?;
+        self.min_deque
+            .check_state(Extremum::Min, self.stoch_period, self.rsi_index)?;
+        self.max_deque
+            .check_state(Extremum::Max, self.stoch_period, self.rsi_index)
+    }
 }
 
 impl StreamingIndicator<f64, StochRsiOutput> for StochRsiStream {
@@ -450,6 +516,58 @@ impl StreamingIndicator<f64, StochRsiOutput> for StochRsiStream {
         self.process_rsi(rsi)
     }
 
+    fn update(&mut self, value: f64) -> Option<StochRsiOutput> {
+        // The RSI is only defined once warmed up, so the bar being amended
+        // reached the stochastic stage exactly when the amended RSI does
+        let rsi = self.rsi_stream.update(value)?;
+        if self.rsi_index > 0 {
+            self.rollback_rsi();
+        }
+        self.process_rsi(rsi)
+    }
+
+    fn peek(&self, value: f64) -> Option<StochRsiOutput> {
+        let rsi = self.rsi_stream.peek(value)?;
+
+        // Mirror `process_rsi` without touching the windows
+        if self.rsi_window.len() + 1 < self.stoch_period {
+            return None;
+        }
+        let min_rsi = self.min_deque.peek(self.rsi_index, rsi);
+        let max_rsi = self.max_deque.peek(self.rsi_index, rsi);
+
+        let range = max_rsi - min_rsi;
+        let stoch_rsi_raw = if range > 0.0 {
+            ((rsi - min_rsi) / range) * 100.0
+        } else {
+            50.0
+        };
+
+        let mut k_sum = self.k_sum;
+        if self.k_window.len() == self.k_smooth {
+            k_sum -= self.k_window.front().copied()?;
+        }
+        k_sum += stoch_rsi_raw;
+        if self.k_window.len() + 1 < self.k_smooth {
+            return None;
+        }
+        let k = k_sum / self.k_smooth as f64;
+
+        let mut d_sum = self.d_sum;
+        if self.d_window.len() == self.d_period {
+            d_sum -= self.d_window.front().copied()?;
+        }
+        d_sum += k;
+        if self.d_window.len() + 1 < self.d_period {
+            return Some(StochRsiOutput { k, d: f64::NAN });
+        }
+
+        Some(StochRsiOutput {
+            k,
+            d: d_sum / self.d_period as f64,
+        })
+    }
+
     fn reset(&mut self) {
         self.rsi_stream.reset();
         self.rsi_window.clear();
@@ -461,11 +579,18 @@ impl StreamingIndicator<f64, StochRsiOutput> for StochRsiStream {
         self.d_window.clear();
         self.d_sum = 0.0;
         self.ready = false;
+        self.prev_sums = (0.0, 0.0);
+        self.prev_ready = false;
+        self.evicted = (None, None, None);
     }
 
     fn is_ready(&self) -> bool {
         self.ready
     }
+
+    fn lookback(&self) -> usize {
+        self.rsi_period + self.stoch_period + self.k_smooth + self.d_period - 3
+    }
 }
 
 #[cfg(test)]
@@ -610,6 +735,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stoch_rsi_stream_update_matches_next() {
+        let prices = sample_prices();
+        let mut live = StochRsiStream::new(5, 5, 3, 3).unwrap();
+        let mut reference = StochRsiStream::new(5, 5, 3, 3).unwrap();
+
+        for &price in &prices {
+            live.next(price + 1.0);
+            live.update(price - 1.0);
+            let amended = live.update(price);
+            let expected = reference.next(price);
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => {
+                    assert!((a.k - e.k).abs() < 1e-10, "k: {} vs {}", a.k, e.k);
+                    assert_eq!(a.d.is_nan(), e.d.is_nan());
+                    if !e.d.is_nan() {
+                        assert!((a.d - e.d).abs() < 1e-10, "d: {} vs {}", a.d, e.d);
+                    }
+                }
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+        assert_eq!(live.is_ready(), reference.is_ready());
+    }
+
     #[test]
     fn test_stoch_rsi_insufficient_data_returns_nan() {
         // Should handle gracefully - return NaN, not error
@@ -674,4 +826,17 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_stoch_rsi_lookback() {
+        let prices = sample_prices();
+        let stoch_rsi = StochRsi::new(5, 5, 3, 3).unwrap();
+        let result = stoch_rsi.calculate(&prices).unwrap();
+
+        // RSI(5) first at 5, stoch window +4, %K smoothing +2, %D +2
+        assert_eq!(stoch_rsi.lookback(), 13);
+        assert!(result[..13].iter().all(|o| o.d.is_nan()));
+        assert!(!result[13].k.is_nan() && !result[13].d.is_nan());
+        assert_eq!(StochRsiStream::new(5, 5, 3, 3).unwrap().lookback(), 13);
+    }
 }
@@ -65,12 +65,17 @@
 //! let output = stoch.next((128.22, 126.80, 127.50));
 //! ```
 
-use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::{IndicatorError, IndicatorResult};
+use crate::indicators::monotonic::{Extremum, MonotonicDeque};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
 use std::collections::VecDeque;
 
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
 /// Type of Stochastic Oscillator calculation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StochType {
     /// Fast Stochastic: Raw %K with %D as simple moving average
     Fast,
@@ -98,6 +103,12 @@ impl StochOutput {
     }
 }
 
+impl IndicatorOutput for StochOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
 /// Stochastic Oscillator calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Stoch {
@@ -209,11 +220,11 @@ impl Indicator<&StochInput<'_>, Vec<StochOutput>> for Stoch {
         let len = highs.len();
 
         // Validate input lengths match
-        if lows.len() != len || closes.len() != len {
-            return Err(IndicatorError::InvalidParameter(
-                "highs, lows, and closes must have the same length".to_string(),
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
 
         let mut result = vec![StochOutput::nan(); len];
 
@@ -293,12 +304,30 @@ impl Indicator<&StochInput<'_>, Vec<StochOutput>> for Stoch {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        match self.stoch_type {
+            StochType::Fast => self.k_period + self.d_period - 2,
+            StochType::Slow => self.k_period + self.slowing + self.d_period - 3,
+        }
+    }
+}
+
+impl Stoch {
+    /// Calculates the Stochastic Oscillator from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<StochOutput>> {
+        self.calculate(&(data.high(), data.low(), data.close()))
+    }
 }
 
 /// Streaming Stochastic Oscillator calculator for real-time O(1) updates.
 ///
 /// Uses monotonic deques to track min/max in O(1) amortized time.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StochStream {
     k_period: usize,
     d_period: usize,
@@ -310,8 +339,8 @@ pub struct StochStream {
     lows: VecDeque<f64>,
 
     // Monotonic deques for O(1) min/max
-    max_deque: VecDeque<(usize, f64)>, // (index, value) for highest high
-    min_deque: VecDeque<(usize, f64)>, // (index, value) for lowest low
+    max_deque: MonotonicDeque, // highest high
+    min_deque: MonotonicDeque, // lowest low
 
     // Buffer for raw %K values (used for smoothing)
     raw_k_buffer: VecDeque<f64>,
@@ -325,6 +354,19 @@ pub struct StochStream {
     // State tracking
     count: usize,
     initialized: bool,
+
+    // Values evicted by the most recent `next`, restored by `update`
+    evicted: StochEvicted,
+}
+
+/// Buffer entries evicted by the last bar of a [`StochStream`].
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct StochEvicted {
+    high: Option<f64>,
+    low: Option<f64>,
+    raw_k: Option<f64>,
+    smoothed_k: Option<f64>,
 }
 
 impl StochStream {
@@ -371,13 +413,14 @@ impl StochStream {
             stoch_type,
             highs: VecDeque::with_capacity(k_period),
             lows: VecDeque::with_capacity(k_period),
-            max_deque: VecDeque::with_capacity(k_period),
-            min_deque: VecDeque::with_capacity(k_period),
+            max_deque: MonotonicDeque::new(Extremum::Max, k_period),
+            min_deque: MonotonicDeque::new(Extremum::Min, k_period),
             raw_k_buffer: VecDeque::with_capacity(d_period.max(slowing)),
             smoothed_k_buffer: VecDeque::with_capacity(d_period),
             index: 0,
             count: 0,
             initialized: false,
+            evicted: StochEvicted::default(),
         })
     }
 
@@ -408,61 +451,62 @@ impl StochStream {
     /// Add a new high value and update the max deque.
     #[inline]
     fn add_high(&mut self, high: f64) {
-        // Remove elements from back that are smaller than current
-        while let Some(&(_, v)) = self.max_deque.back() {
-            if v <= high {
-                self.max_deque.pop_back();
-            } else {
-                break;
-            }
-        }
-        self.max_deque.push_back((self.index, high));
-
-        // Remove elements that are out of window
-        let window_start = self.index.saturating_sub(self.k_period - 1);
-        while let Some(&(idx, _)) = self.max_deque.front() {
-            if idx < window_start {
-                self.max_deque.pop_front();
-            } else {
-                break;
-            }
-        }
+        self.max_deque.push(self.index, high);
 
         // Update ring buffer
-        if self.highs.len() >= self.k_period {
-            self.highs.pop_front();
-        }
+        self.evicted.high = if self.highs.len() >= self.k_period {
+            self.highs.pop_front()
+        } else {
+            None
+        };
         self.highs.push_back(high);
     }
 
     /// Add a new low value and update the min deque.
     #[inline]
     fn add_low(&mut self, low: f64) {
-        // Remove elements from back that are larger than current
-        while let Some(&(_, v)) = self.min_deque.back() {
-            if v >= low {
-                self.min_deque.pop_back();
-            } else {
-                break;
-            }
-        }
-        self.min_deque.push_back((self.index, low));
+        self.min_deque.push(self.index, low);
 
-        // Remove elements that are out of window
-        let window_start = self.index.saturating_sub(self.k_period - 1);
-        while let Some(&(idx, _)) = self.min_deque.front() {
-            if idx < window_start {
-                self.min_deque.pop_front();
-            } else {
-                break;
+        // Update ring buffer
+        self.evicted.low = if self.lows.len() >= self.k_period {
+            self.lows.pop_front()
+        } else {
+            None
+        };
+        self.lows.push_back(low);
+    }
+
+    /// Undo the most recent `next` so the bar can be replaced.
+    fn rollback(&mut self) {
+        let evicted = self.evicted;
+
+        // Smoothing buffers were only touched once raw %K was available
+        if self.count >= self.k_period {
+            if self.stoch_type == StochType::Slow && self.raw_k_buffer.len() >= self.slowing {
+                self.smoothed_k_buffer.pop_back();
+                if let Some(v) = evicted.smoothed_k {
+                    self.smoothed_k_buffer.push_front(v);
+                }
+            }
+            self.raw_k_buffer.pop_back();
+            if let Some(v) = evicted.raw_k {
+                self.raw_k_buffer.push_front(v);
             }
         }
 
-        // Update ring buffer
-        if self.lows.len() >= self.k_period {
-            self.lows.pop_front();
+        self.max_deque.rollback();
+        self.min_deque.rollback();
+        self.highs.pop_back();
+        self.lows.pop_back();
+        if let Some(v) = evicted.high {
+            self.highs.push_front(v);
         }
-        self.lows.push_back(low);
+        if let Some(v) = evicted.low {
+            self.lows.push_front(v);
+        }
+
+        self.count -= 1;
+        self.index -= 1;
     }
 
     /// Calculate raw %K from current state.
@@ -472,18 +516,59 @@ impl StochStream {
             return None;
         }
 
-        let highest = self.max_deque.front().map(|(_, v)| *v)?;
-        let lowest = self.min_deque.front().map(|(_, v)| *v)?;
+        let highest = self.max_deque.front()?;
+        let lowest = self.min_deque.front()?;
+        Some(raw_k(highest, lowest, close))
+    }
 
-        let range = highest - lowest;
-        if range == 0.0 {
-            Some(50.0)
-        } else {
-            Some(100.0 * (close - lowest) / range)
-        }
+    /// Checks the buffers, deques and counters of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        ensure(
+            self.k_period > 0 && self.d_period > 0 && self.slowing > 0,
+            "all periods must be greater than 0",
+        )?;
+        ensure(
+            self.highs.len() <= self.k_period && self.lows.len() <= self.k_period,
+            "high/low buffers must not exceed k_period",
+        )?;
+        let (raw_k_len, smoothed_k_len) = match self.stoch_type {
+            StochType::Fast => (self.d_period, 0),
+            StochType::Slow => (self.slowing, self.d_period),
+        };
+        ensure(
+            self.raw_k_buffer.len() <= raw_k_len && self.smoothed_k_buffer.len() <= smoothed_k_len,
+            "%K buffers must not exceed their periods",
+        )?;
+        ensure(self.count <= self.index, "count must not exceed the index")?;
+        self.max_deque
+            .check_state(Extremum::Max, self.k_period, self.index)?;
+        self.min_deque
+            .check_state(Extremum::Min, self.k_period, self.index)
     }
 }
 
+/// Raw %K of `close` within the `[lowest, highest]` range.
+#[inline]
+fn raw_k(highest: f64, lowest: f64, close: f64) -> f64 {
+    let range = highest - lowest;
+    if range == 0.0 {
+        50.0
+    } else {
+        100.0 * (close - lowest) / range
+    }
+}
+
+/// Mean `next` would compute over `buffer` once `value` is pushed into its
+/// `period`-sized window, or `None` if the window would not be full.
+fn peek_window_mean(buffer: &VecDeque<f64>, period: usize, value: f64) -> Option<f64> {
+    let skip = usize::from(buffer.len() >= period);
+    if buffer.len() - skip + 1 < period {
+        return None;
+    }
+    Some(buffer.iter().skip(skip).chain([&value]).sum::<f64>() / period as f64)
+}
+
 /// Input for streaming: (high, low, close) tuple
 pub type StochBar = (f64, f64, f64);
 
@@ -515,9 +600,11 @@ impl StreamingIndicator<StochBar, StochOutput> for StochStream {
             StochType::Fast => {
                 // Fast: %K = raw %K, %D = SMA(%K, d_period)
                 // We need to track raw_k values for %D calculation
-                if self.raw_k_buffer.len() >= self.d_period {
-                    self.raw_k_buffer.pop_front();
-                }
+                self.evicted.raw_k = if self.raw_k_buffer.len() >= self.d_period {
+                    self.raw_k_buffer.pop_front()
+                } else {
+                    None
+                };
                 self.raw_k_buffer.push_back(raw_k);
 
                 let d = if self.raw_k_buffer.len() >= self.d_period {
@@ -532,9 +619,11 @@ impl StreamingIndicator<StochBar, StochOutput> for StochStream {
             StochType::Slow => {
                 // Slow: %K = SMA(raw %K, slowing), %D = SMA(%K, d_period)
                 // First, add raw_k to buffer for slowing calculation
-                if self.raw_k_buffer.len() >= self.slowing {
-                    self.raw_k_buffer.pop_front();
-                }
+                self.evicted.raw_k = if self.raw_k_buffer.len() >= self.slowing {
+                    self.raw_k_buffer.pop_front()
+                } else {
+                    None
+                };
                 self.raw_k_buffer.push_back(raw_k);
 
                 // Calculate smoothed %K
@@ -545,9 +634,11 @@ impl StreamingIndicator<StochBar, StochOutput> for StochStream {
                 let smoothed_k: f64 = self.raw_k_buffer.iter().sum::<f64>() / self.slowing as f64;
 
                 // Add smoothed %K to buffer for %D calculation
-                if self.smoothed_k_buffer.len() >= self.d_period {
-                    self.smoothed_k_buffer.pop_front();
-                }
+                self.evicted.smoothed_k = if self.smoothed_k_buffer.len() >= self.d_period {
+                    self.smoothed_k_buffer.pop_front()
+                } else {
+                    None
+                };
                 self.smoothed_k_buffer.push_back(smoothed_k);
 
                 // Calculate %D
@@ -562,6 +653,47 @@ impl StreamingIndicator<StochBar, StochOutput> for StochStream {
         }
     }
 
+    fn update(&mut self, value: StochBar) -> Option<StochOutput> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        self.rollback();
+        self.next(value)
+    }
+
+    fn peek(&self, value: StochBar) -> Option<StochOutput> {
+        let (high, low, close) = value;
+        if self.count + 1 < self.k_period {
+            return None;
+        }
+
+        let highest = self.max_deque.peek(self.index, high);
+        let lowest = self.min_deque.peek(self.index, low);
+        let raw_k = raw_k(highest, lowest, close);
+
+        match self.stoch_type {
+            StochType::Fast => {
+                let d = peek_window_mean(&self.raw_k_buffer, self.d_period, raw_k);
+                Some(StochOutput {
+                    k: raw_k,
+                    d: d.unwrap_or(f64::NAN),
+                })
+            }
+            StochType::Slow => {
+                let Some(smoothed_k) = peek_window_mean(&self.raw_k_buffer, self.slowing, raw_k)
+                else {
+                    return Some(StochOutput::nan());
+                };
+                let d = peek_window_mean(&self.smoothed_k_buffer, self.d_period, smoothed_k);
+                Some(StochOutput {
+                    k: smoothed_k,
+                    d: d.unwrap_or(f64::NAN),
+                })
+            }
+        }
+    }
+
     fn reset(&mut self) {
         self.highs.clear();
         self.lows.clear();
@@ -572,11 +704,19 @@ impl StreamingIndicator<StochBar, StochOutput> for StochStream {
         self.index = 0;
         self.count = 0;
         self.initialized = false;
+        self.evicted = StochEvicted::default();
     }
 
     fn is_ready(&self) -> bool {
         self.count >= self.k_period
     }
+
+    fn lookback(&self) -> usize {
+        match self.stoch_type {
+            StochType::Fast => self.k_period + self.d_period - 2,
+            StochType::Slow => self.k_period + self.slowing + self.d_period - 3,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -800,6 +940,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stoch_stream_update_matches_next() {
+        let bars: Vec<StochBar> = HIGHS
+            .iter()
+            .zip(LOWS.iter())
+            .zip(CLOSES.iter())
+            .map(|((&h, &l), &c)| (h, l, c))
+            .collect();
+
+        for stoch_type in [StochType::Fast, StochType::Slow] {
+            let mut live = StochStream::new(5, 3, stoch_type).unwrap();
+            let mut reference = StochStream::new(5, 3, stoch_type).unwrap();
+
+            for &(high, low, close) in &bars {
+                // New extremes evict deque entries, then the bar settles back
+                live.next((high + 5.0, low - 5.0, close));
+                live.update((close, close, close));
+                let amended = live.update((high, low, close));
+                let expected = reference.next((high, low, close));
+
+                match (amended, expected) {
+                    (Some(a), Some(e)) => {
+                        assert_eq!(a.k.is_nan(), e.k.is_nan());
+                        assert_eq!(a.d.is_nan(), e.d.is_nan());
+                        if !e.k.is_nan() {
+                            assert!((a.k - e.k).abs() < 1e-10);
+                        }
+                        if !e.d.is_nan() {
+                            assert!((a.d - e.d).abs() < 1e-10);
+                        }
+                    }
+                    (None, None) => {}
+                    other => panic!("mismatch: {other:?}"),
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_stoch_reset() {
         let mut stream = StochStream::new(5, 3, StochType::Fast).unwrap();
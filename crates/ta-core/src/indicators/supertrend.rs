@@ -0,0 +1,663 @@
+//! Supertrend indicator.
+//!
+//! Supertrend is an ATR trailing stop that flips sides when price closes
+//! through it. In an uptrend the line is the lower band and can only rise;
+//! in a downtrend it is the upper band and can only fall.
+//!
+//! # Formula
+//! - **Basic Bands**: source ± (multiplier × ATR)
+//! - **Final Lower Band**: max(basic lower, previous final lower), unless the
+//!   previous close was below the previous final lower band
+//! - **Final Upper Band**: min(basic upper, previous final upper), unless the
+//!   previous close was above the previous final upper band
+//! - **Direction**: flips up when close rises above the final upper band and
+//!   down when close falls below the final lower band
+//!
+//! The first complete bar starts in a downtrend, matching TradingView's
+//! built-in `ta.supertrend`.
+//!
+//! # Default Parameters
+//! - ATR period: 10
+//! - Multiplier: 3.0
+//! - Source: (high + low) / 2
+//! - ATR smoothing: Wilder
+//!
+//! # Example (Batch Mode)
+//! ```
+//! use ta_core::indicators::{SuperTrend, SuperTrendSource};
+//! use ta_core::traits::Indicator;
+//!
+//! let supertrend = SuperTrend::new(10, 3.0)
+//!     .unwrap()
+//!     .with_source(SuperTrendSource::Close);
+//! let closes: Vec<f64> = (1..=30).map(|x| 100.0 + x as f64).collect();
+//! let highs: Vec<f64> = closes.iter().map(|c| c + 1.0).collect();
+//! let lows: Vec<f64> = closes.iter().map(|c| c - 1.0).collect();
+//! let result = supertrend.calculate(&(&highs[..], &lows[..], &closes[..])).unwrap();
+//! assert_eq!(result[29].direction, 1);
+//! assert!(result[29].value < closes[29]);
+//! ```
+
+use crate::indicators::{AtrStream, SmaStream};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, IndicatorResult, OhlcvColumns};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
+const UP: i8 = 1;
+const DOWN: i8 = -1;
+
+/// Supertrend output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuperTrendOutput {
+    /// Supertrend line: the lower band in an uptrend, the upper band in a downtrend
+    pub value: f64,
+    /// Trend direction: 1 = up, -1 = down, 0 = no value yet
+    pub direction: i8,
+    /// True on the bar where the direction flips
+    pub flip: bool,
+}
+
+impl SuperTrendOutput {
+    /// Creates a NaN output for insufficient data.
+    #[must_use]
+    pub fn nan() -> Self {
+        Self {
+            value: f64::NAN,
+            direction: 0,
+            flip: false,
+        }
+    }
+
+    /// Returns true if the line is NaN.
+    #[must_use]
+    pub fn is_nan(&self) -> bool {
+        self.value.is_nan()
+    }
+}
+
+impl IndicatorOutput for SuperTrendOutput {
+    fn nan() -> Self {
+        Self::nan()
+    }
+}
+
+/// Price the bands are centered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SuperTrendSource {
+    /// Median price (high + low) / 2 (default)
+    #[default]
+    Hl2,
+    /// Typical price (high + low + close) / 3
+    Hlc3,
+    /// Close
+    Close,
+}
+
+impl SuperTrendSource {
+    /// Returns the source price of a (high, low, close) bar.
+    #[must_use]
+    pub fn price(self, (high, low, close): SuperTrendBar) -> f64 {
+        match self {
+            Self::Hl2 => (high + low) / 2.0,
+            Self::Hlc3 => (high + low + close) / 3.0,
+            Self::Close => close,
+        }
+    }
+}
+
+/// Smoothing applied to the true range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SuperTrendAtr {
+    /// Wilder's smoothing, as in `AtrStream` (default)
+    #[default]
+    Wilder,
+    /// Simple moving average of the true range
+    Sma,
+}
+
+/// Input type for Supertrend: (highs, lows, closes)
+pub type SuperTrendInput<'a> = (&'a [f64], &'a [f64], &'a [f64]);
+
+/// Input for streaming: (high, low, close) tuple
+pub type SuperTrendBar = (f64, f64, f64);
+
+/// Supertrend calculator for batch operations.
+#[derive(Debug, Clone)]
+pub struct SuperTrend {
+    atr_period: usize,
+    multiplier: f64,
+    source: SuperTrendSource,
+    atr: SuperTrendAtr,
+}
+
+impl SuperTrend {
+    /// Creates a new Supertrend calculator on `(high + low) / 2` with Wilder ATR.
+    ///
+    /// # Arguments
+    /// * `atr_period` - The ATR period (typically 10)
+    /// * `multiplier` - ATR multiplier for the bands (typically 3.0)
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the period is 0 or the multiplier is not positive.
+    pub fn new(atr_period: usize, multiplier: f64) -> IndicatorResult<Self> {
+        validate(atr_period, multiplier)?;
+        Ok(Self {
+            atr_period,
+            multiplier,
+            source: SuperTrendSource::Hl2,
+            atr: SuperTrendAtr::Wilder,
+        })
+    }
+
+    /// Creates with default parameters (10, 3.0).
+    pub fn default_params() -> IndicatorResult<Self> {
+        Self::new(10, 3.0)
+    }
+
+    /// Sets the price the bands are centered on.
+    #[must_use]
+    pub const fn with_source(mut self, source: SuperTrendSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Sets the true range smoothing.
+    #[must_use]
+    pub const fn with_atr(mut self, atr: SuperTrendAtr) -> Self {
+        self.atr = atr;
+        self
+    }
+
+    /// Returns the ATR period.
+    #[must_use]
+    pub const fn atr_period(&self) -> usize {
+        self.atr_period
+    }
+
+    /// Returns the ATR multiplier.
+    #[must_use]
+    pub const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Returns the band source.
+    #[must_use]
+    pub const fn source(&self) -> SuperTrendSource {
+        self.source
+    }
+
+    /// Returns the true range smoothing.
+    #[must_use]
+    pub const fn atr(&self) -> SuperTrendAtr {
+        self.atr
+    }
+
+    /// Creates a streaming calculator with the same configuration.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the configuration is invalid.
+    pub fn stream(&self) -> IndicatorResult<SuperTrendStream> {
+        SuperTrendStream::with_options(self.atr_period, self.multiplier, self.source, self.atr)
+    }
+}
+
+impl Indicator<&SuperTrendInput<'_>, Vec<SuperTrendOutput>> for SuperTrend {
+    fn calculate(&self, data: &SuperTrendInput<'_>) -> IndicatorResult<Vec<SuperTrendOutput>> {
+        let (highs, lows, closes) = *data;
+
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])?;
+
+        let bars: Vec<SuperTrendBar> = highs
+            .iter()
+            .zip(lows)
+            .zip(closes)
+            .map(|((&h, &l), &c)| (h, l, c))
+            .collect();
+
+        self.stream()?.init(&bars)
+    }
+
+    fn lookback(&self) -> usize {
+        self.atr_period - 1
+    }
+}
+
+impl SuperTrend {
+    /// Calculates Supertrend from the high, low and close columns of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slices.
+    pub fn calculate_columns(
+        &self,
+        data: &OhlcvColumns<'_>,
+    ) -> IndicatorResult<Vec<SuperTrendOutput>> {
+        self.calculate(&(data.high(), data.low(), data.close()))
+    }
+}
+
+fn validate(atr_period: usize, multiplier: f64) -> IndicatorResult<()> {
+    if atr_period == 0 {
+        return Err(IndicatorError::InvalidParameter(
+            "atr_period must be greater than 0".to_string(),
+        ));
+    }
+    if multiplier <= 0.0 || !multiplier.is_finite() {
+        return Err(IndicatorError::InvalidParameter(
+            "multiplier must be a positive finite number".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Either ATR smoothing, dispatched per bar. The SMA variant is fed the true
+/// range computed from the Supertrend stream's previous close.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum AtrSmoother {
+    Wilder(AtrStream),
+    Sma(SmaStream),
+}
+
+impl AtrSmoother {
+    fn new(atr: SuperTrendAtr, period: usize) -> IndicatorResult<Self> {
+        Ok(match atr {
+            SuperTrendAtr::Wilder => Self::Wilder(AtrStream::new(period)?),
+            SuperTrendAtr::Sma => Self::Sma(SmaStream::new(period)?),
+        })
+    }
+
+    fn next(&mut self, bar: SuperTrendBar, prev_close: Option<f64>) -> Option<f64> {
+        match self {
+            Self::Wilder(atr) => atr.next(bar),
+            Self::Sma(sma) => sma.next(AtrStream::true_range(bar.0, bar.1, prev_close)),
+        }
+    }
+
+    fn update(&mut self, bar: SuperTrendBar, prev_close: Option<f64>) -> Option<f64> {
+        match self {
+            Self::Wilder(atr) => atr.update(bar),
+            Self::Sma(sma) => sma.update(AtrStream::true_range(bar.0, bar.1, prev_close)),
+        }
+    }
+
+    fn peek(&self, bar: SuperTrendBar, prev_close: Option<f64>) -> Option<f64> {
+        match self {
+            Self::Wilder(atr) => atr.peek(bar),
+            Self::Sma(sma) => sma.peek(AtrStream::true_range(bar.0, bar.1, prev_close)),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::Wilder(atr) => atr.reset(),
+            Self::Sma(sma) => sma.reset(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self {
+            Self::Wilder(atr) => atr.is_ready(),
+            Self::Sma(sma) => sma.is_ready(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn check_state(&self, period: usize) -> Result<(), String> {
+        let (state, atr_period) = match self {
+            Self::Wilder(atr) => (atr.check_state(), atr.period()),
+            Self::Sma(sma) => (sma.check_state(), sma.period()),
+        };
+        state?;
+        ensure(atr_period == period, "ATR period must match the stream")
+    }
+}
+
+/// Streaming Supertrend calculator.
+///
+/// Keeps the previous final bands, close and direction on top of an
+/// `AtrStream` (or SMA of true range), so each bar is O(1).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuperTrendStream {
+    atr_period: usize,
+    multiplier: f64,
+    source: SuperTrendSource,
+    atr: AtrSmoother,
+    prev_close: Option<f64>,
+    bands: Option<(f64, f64)>, // final (upper, lower) of the last bar
+    direction: i8,
+    count: usize,
+    // (prev_close, bands, direction) before the most recent `next`, restored by `update`
+    prev_state: (Option<f64>, Option<(f64, f64)>, i8),
+}
+
+impl SuperTrendStream {
+    /// Creates a new streaming Supertrend calculator on `(high + low) / 2`
+    /// with Wilder ATR.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the period is 0 or the multiplier is not positive.
+    pub fn new(atr_period: usize, multiplier: f64) -> IndicatorResult<Self> {
+        Self::with_options(
+            atr_period,
+            multiplier,
+            SuperTrendSource::Hl2,
+            SuperTrendAtr::Wilder,
+        )
+    }
+
+    /// Creates a new streaming Supertrend calculator with a custom source and
+    /// ATR smoothing.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the period is 0 or the multiplier is not positive.
+    pub fn with_options(
+        atr_period: usize,
+        multiplier: f64,
+        source: SuperTrendSource,
+        atr: SuperTrendAtr,
+    ) -> IndicatorResult<Self> {
+        validate(atr_period, multiplier)?;
+        Ok(Self {
+            atr_period,
+            multiplier,
+            source,
+            atr: AtrSmoother::new(atr, atr_period)?,
+            prev_close: None,
+            bands: None,
+            direction: 0,
+            count: 0,
+            prev_state: (None, None, 0),
+        })
+    }
+
+    /// Returns the ATR period.
+    #[must_use]
+    pub const fn atr_period(&self) -> usize {
+        self.atr_period
+    }
+
+    /// Returns the ATR multiplier.
+    #[must_use]
+    pub const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Returns the band source.
+    #[must_use]
+    pub const fn source(&self) -> SuperTrendSource {
+        self.source
+    }
+
+    /// Ratchets the bands and resolves the direction for a bar with a known
+    /// ATR. Returns the final (upper, lower) bands, direction and output.
+    fn step(&self, bar: SuperTrendBar, atr: f64) -> ((f64, f64), i8, SuperTrendOutput) {
+        let close = bar.2;
+        let src = self.source.price(bar);
+        let mut upper = src + self.multiplier * atr;
+        let mut lower = src - self.multiplier * atr;
+
+        let direction = match (self.bands, self.prev_close) {
+            (Some((prev_upper, prev_lower)), Some(prev_close)) => {
+                if prev_close <= prev_upper {
+                    upper = upper.min(prev_upper);
+                }
+                if prev_close >= prev_lower {
+                    lower = lower.max(prev_lower);
+                }
+                match self.direction {
+                    DOWN if close > upper => UP,
+                    UP if close < lower => DOWN,
+                    direction => direction,
+                }
+            }
+            _ => DOWN,
+        };
+
+        let output = SuperTrendOutput {
+            value: if direction == UP { lower } else { upper },
+            direction,
+            flip: self.direction != 0 && direction != self.direction,
+        };
+        ((upper, lower), direction, output)
+    }
+
+    /// Push a bar, as a new one or as a replacement for the last (`amend`).
+    fn push(&mut self, bar: SuperTrendBar, amend: bool) -> Option<SuperTrendOutput> {
+        let atr = if amend {
+            self.atr.update(bar, self.prev_close)
+        } else {
+            self.atr.next(bar, self.prev_close)
+        };
+        let stepped = atr.map(|atr| self.step(bar, atr));
+        self.prev_close = Some(bar.2);
+
+        let (bands, direction, output) = stepped?;
+        self.bands = Some(bands);
+        self.direction = direction;
+        Some(output)
+    }
+
+    /// Checks the ATR of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        validate(self.atr_period, self.multiplier).map_err(|e| e.to_string())?;
+        self.atr.check_state(self.atr_period)
+    }
+}
+
+impl StreamingIndicator<SuperTrendBar, SuperTrendOutput> for SuperTrendStream {
+    fn init(&mut self, data: &[SuperTrendBar]) -> IndicatorResult<Vec<SuperTrendOutput>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &bar in data {
+            results.push(self.next(bar).unwrap_or_else(SuperTrendOutput::nan));
+        }
+        Ok(results)
+    }
+
+    fn next(&mut self, bar: SuperTrendBar) -> Option<SuperTrendOutput> {
+        self.prev_state = (self.prev_close, self.bands, self.direction);
+        self.count += 1;
+        self.push(bar, false)
+    }
+
+    fn update(&mut self, bar: SuperTrendBar) -> Option<SuperTrendOutput> {
+        if self.count == 0 {
+            return self.next(bar);
+        }
+
+        (self.prev_close, self.bands, self.direction) = self.prev_state;
+        self.push(bar, true)
+    }
+
+    fn peek(&self, bar: SuperTrendBar) -> Option<SuperTrendOutput> {
+        let atr = self.atr.peek(bar, self.prev_close)?;
+        Some(self.step(bar, atr).2)
+    }
+
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.prev_close = None;
+        self.bands = None;
+        self.direction = 0;
+        self.count = 0;
+        self.prev_state = (None, None, 0);
+    }
+
+    fn is_ready(&self) -> bool {
+        self.atr.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        self.atr_period - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Atr, Sma};
+
+    const EPSILON: f64 = 1e-10;
+
+    fn assert_approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < EPSILON, "expected {b}, got {a}");
+    }
+
+    /// A slow sine swing, so the trend flips up and back down.
+    fn sample() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let closes: Vec<f64> = (0..100)
+            .map(|i| {
+                let x = f64::from(i);
+                100.0 + (x * 0.1).sin() * 15.0 + (x * 1.3).cos()
+            })
+            .collect();
+        let highs = closes.iter().map(|c| c + 1.2).collect();
+        let lows = closes.iter().map(|c| c - 0.9).collect();
+        (highs, lows, closes)
+    }
+
+    /// Textbook loop over precomputed ATR and source values.
+    fn reference(closes: &[f64], src: &[f64], atr: &[f64], mult: f64) -> Vec<(f64, i8)> {
+        let mut out = vec![(f64::NAN, 0); closes.len()];
+        let (mut upper, mut lower, mut dir) = (f64::NAN, f64::NAN, 0);
+        for i in 0..closes.len() {
+            if atr[i].is_nan() {
+                continue;
+            }
+            let mut up = src[i] + mult * atr[i];
+            let mut lo = src[i] - mult * atr[i];
+            if dir == 0 {
+                dir = DOWN;
+            } else {
+                if !(up < upper || closes[i - 1] > upper) {
+                    up = upper;
+                }
+                if !(lo > lower || closes[i - 1] < lower) {
+                    lo = lower;
+                }
+                dir = if dir == DOWN {
+                    if closes[i] > up {
+                        UP
+                    } else {
+                        DOWN
+                    }
+                } else if closes[i] < lo {
+                    DOWN
+                } else {
+                    UP
+                };
+            }
+            (upper, lower) = (up, lo);
+            out[i] = (if dir == UP { lo } else { up }, dir);
+        }
+        out
+    }
+
+    fn assert_matches(result: &[SuperTrendOutput], expected: &[(f64, i8)]) {
+        for (i, (out, &(value, direction))) in result.iter().zip(expected).enumerate() {
+            if value.is_nan() {
+                assert!(out.is_nan(), "index {i}");
+                continue;
+            }
+            assert_approx_eq(out.value, value);
+            assert_eq!(out.direction, direction, "index {i}");
+            let flipped = i > 0 && expected[i - 1].1 != 0 && expected[i - 1].1 != direction;
+            assert_eq!(out.flip, flipped, "index {i}");
+        }
+    }
+
+    #[test]
+    fn test_supertrend_wilder_hl2_matches_reference() {
+        let (highs, lows, closes) = sample();
+        let result = SuperTrend::new(10, 3.0)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        let atr = Atr::new(10)
+            .unwrap()
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+        let src: Vec<f64> = highs
+            .iter()
+            .zip(&lows)
+            .map(|(h, l)| (h + l) / 2.0)
+            .collect();
+        assert_matches(&result, &reference(&closes, &src, &atr, 3.0));
+
+        assert!(result[8].is_nan());
+        assert_eq!(result[9].direction, DOWN);
+        let flips: Vec<i8> = result
+            .iter()
+            .filter(|o| o.flip)
+            .map(|o| o.direction)
+            .collect();
+        assert_eq!(flips, [UP, DOWN]);
+    }
+
+    #[test]
+    fn test_supertrend_sma_atr_and_source_options() {
+        let (highs, lows, closes) = sample();
+        let result = SuperTrend::new(7, 2.0)
+            .unwrap()
+            .with_source(SuperTrendSource::Close)
+            .with_atr(SuperTrendAtr::Sma)
+            .calculate(&(&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+
+        let true_ranges: Vec<f64> = (0..closes.len())
+            .map(|i| AtrStream::true_range(highs[i], lows[i], i.checked_sub(1).map(|p| closes[p])))
+            .collect();
+        let atr = Sma::new(7).unwrap().calculate(&true_ranges[..]).unwrap();
+        assert_matches(&result, &reference(&closes, &closes, &atr, 2.0));
+
+        assert!(SuperTrend::new(0, 3.0).is_err());
+        assert!(SuperTrend::new(10, 0.0).is_err());
+        assert!(SuperTrend::new(10, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_supertrend_stream_parity_update_and_peek() {
+        let (highs, lows, closes) = sample();
+        let bars: Vec<SuperTrendBar> = (0..closes.len())
+            .map(|i| (highs[i], lows[i], closes[i]))
+            .collect();
+
+        for atr in [SuperTrendAtr::Wilder, SuperTrendAtr::Sma] {
+            let supertrend = SuperTrend::new(10, 3.0)
+                .unwrap()
+                .with_source(SuperTrendSource::Hlc3)
+                .with_atr(atr);
+            let batch = supertrend
+                .calculate(&(&highs[..], &lows[..], &closes[..]))
+                .unwrap();
+
+            let mut stream = supertrend.stream().unwrap();
+            for (i, &(high, low, close)) in bars.iter().enumerate() {
+                let peeked = stream.peek((high, low, close));
+                // Provisional bar far away from the final one
+                stream.next((high + 20.0, low + 20.0, close + 20.0));
+                let out = stream.update((high, low, close));
+
+                assert_eq!(peeked, out, "index {i}");
+                match out {
+                    Some(out) => assert_eq!(out, batch[i], "index {i}"),
+                    None => assert!(batch[i].is_nan(), "index {i}"),
+                }
+            }
+            assert!(stream.is_ready());
+            assert_eq!(stream.lookback(), 9);
+        }
+    }
+}
@@ -10,7 +10,8 @@
 //! ```
 //!
 //! # Modes
-//! - **Session VWAP**: Resets at the start of each trading day (UTC)
+//! - **Session VWAP**: Resets at the start of each trading session (UTC days
+//!   by default, or any [`Session`] calendar)
 //! - **Rolling VWAP**: Uses a sliding window of N bars
 //! - **Anchored VWAP**: Calculates from a specific starting index
 //!
@@ -43,27 +44,17 @@
 //! let new_vwap = vwap.next(OHLCV::new(1700001200000, 103.0, 107.0, 102.0, 105.0, 2000.0));
 //! ```
 
+use crate::session::Session;
 use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+use crate::types::{IndicatorError, IndicatorResult, OhlcvColumns, OHLCV};
 
-// ============================================================================
-// Constants
-// ============================================================================
-
-/// Milliseconds in one day (24 hours)
-const MS_PER_DAY: i64 = 86_400_000;
+#[cfg(feature = "serde")]
+use crate::snapshot::check_ring;
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Get the UTC day number from a Unix timestamp in milliseconds.
-/// This is used to detect session boundaries.
-#[inline]
-fn utc_day(timestamp_ms: i64) -> i64 {
-    timestamp_ms / MS_PER_DAY
-}
-
 /// Calculate typical price for a candle.
 #[inline]
 fn typical_price(candle: &OHLCV) -> f64 {
@@ -71,45 +62,63 @@ fn typical_price(candle: &OHLCV) -> f64 {
 }
 
 // ============================================================================
-// Session VWAP (Daily Reset)
+// Session VWAP (Session Reset)
 // ============================================================================
 
 /// Session VWAP calculator for batch operations.
 ///
-/// Resets at the start of each UTC day.
+/// Resets at the start of each session: each UTC day by default, or the
+/// sessions of a [`Session`] calendar. Candles outside a session (pre-market,
+/// weekends, holidays) are `NaN` and don't count towards any session;
+/// candles before the calendar's time zone rules are rejected with
+/// `InvalidData`.
 #[derive(Debug, Clone, Default)]
-pub struct SessionVwap;
+pub struct SessionVwap {
+    session: Session,
+}
 
 impl SessionVwap {
-    /// Creates a new Session VWAP calculator.
+    /// Creates a new Session VWAP calculator resetting at UTC midnight.
     #[must_use]
     pub const fn new() -> Self {
-        Self
+        Self::with_session(Session::utc())
     }
-}
 
-impl Indicator<&[OHLCV], Vec<f64>> for SessionVwap {
-    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
-        if data.is_empty() {
-            return Ok(vec![]);
-        }
+    /// Creates a Session VWAP calculator resetting at each open of `session`.
+    #[must_use]
+    pub const fn with_session(session: Session) -> Self {
+        Self { session }
+    }
+
+    /// Returns the session calendar.
+    #[must_use]
+    pub const fn session(&self) -> &Session {
+        &self.session
+    }
+}
 
+impl SessionVwap {
+    fn vwap(&self, data: impl ExactSizeIterator<Item = OHLCV>) -> IndicatorResult<Vec<f64>> {
         let mut result = Vec::with_capacity(data.len());
         let mut cum_tp_vol = 0.0;
         let mut cum_vol = 0.0;
-        let mut current_day = utc_day(data[0].timestamp);
+        let mut current_day = None;
 
-        for candle in data {
-            let day = utc_day(candle.timestamp);
+        for (index, candle) in data.enumerate() {
+            self.session.check_timestamp(index, candle.timestamp)?;
+            let Some(day) = self.session.trading_date(candle.timestamp) else {
+                result.push(f64::NAN);
+                continue;
+            };
 
-            // Reset on new day
-            if day != current_day {
+            // Reset on new session
+            if current_day != Some(day) {
                 cum_tp_vol = 0.0;
                 cum_vol = 0.0;
-                current_day = day;
+                current_day = Some(day);
             }
 
-            let tp = typical_price(candle);
+            let tp = typical_price(&candle);
             cum_tp_vol += tp * candle.volume;
             cum_vol += candle.volume;
 
@@ -124,15 +133,40 @@ impl Indicator<&[OHLCV], Vec<f64>> for SessionVwap {
     }
 }
 
+impl Indicator<&[OHLCV], Vec<f64>> for SessionVwap {
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
+        self.vwap(data.iter().copied())
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+impl SessionVwap {
+    /// Calculates VWAP from the candles of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slice.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<f64>> {
+        self.vwap(data.iter())
+    }
+}
+
 /// Streaming Session VWAP calculator for real-time O(1) updates.
 ///
-/// Resets at the start of each UTC day.
+/// Resets at the start of each session, like [`SessionVwap`]. Candles outside
+/// a session return `None` and leave the state unchanged.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SessionVwapStream {
+    session: Session,
     cum_tp_vol: f64,
     cum_vol: f64,
     current_day: i64,
     initialized: bool,
+    // State before the most recent candle, restored by `update`
+    prev_state: (f64, f64, i64, bool),
 }
 
 impl Default for SessionVwapStream {
@@ -142,14 +176,23 @@ impl Default for SessionVwapStream {
 }
 
 impl SessionVwapStream {
-    /// Creates a new streaming Session VWAP calculator.
+    /// Creates a new streaming Session VWAP calculator resetting at UTC midnight.
     #[must_use]
     pub const fn new() -> Self {
+        Self::with_session(Session::utc())
+    }
+
+    /// Creates a streaming Session VWAP calculator resetting at each open of
+    /// `session`.
+    #[must_use]
+    pub const fn with_session(session: Session) -> Self {
         Self {
+            session,
             cum_tp_vol: 0.0,
             cum_vol: 0.0,
             current_day: 0,
             initialized: false,
+            prev_state: (0.0, 0.0, 0, false),
         }
     }
 
@@ -174,6 +217,12 @@ impl SessionVwapStream {
     pub fn cumulative_volume(&self) -> f64 {
         self.cum_vol
     }
+
+    /// Checks the session of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        self.session.check_state()
+    }
 }
 
 impl StreamingIndicator<OHLCV, f64> for SessionVwapStream {
@@ -185,36 +234,23 @@ impl StreamingIndicator<OHLCV, f64> for SessionVwapStream {
         }
 
         let mut result = Vec::with_capacity(data.len());
-        self.current_day = utc_day(data[0].timestamp);
-        self.initialized = true;
-
         for candle in data {
-            let day = utc_day(candle.timestamp);
-
-            if day != self.current_day {
-                self.cum_tp_vol = 0.0;
-                self.cum_vol = 0.0;
-                self.current_day = day;
-            }
-
-            let tp = typical_price(candle);
-            self.cum_tp_vol += tp * candle.volume;
-            self.cum_vol += candle.volume;
-
-            if self.cum_vol > 0.0 {
-                result.push(self.cum_tp_vol / self.cum_vol);
-            } else {
-                result.push(f64::NAN);
-            }
+            result.push(self.next(*candle).unwrap_or(f64::NAN));
         }
 
         Ok(result)
     }
 
     fn next(&mut self, candle: OHLCV) -> Option<f64> {
-        let day = utc_day(candle.timestamp);
+        self.prev_state = (
+            self.cum_tp_vol,
+            self.cum_vol,
+            self.current_day,
+            self.initialized,
+        );
+        let day = self.session.trading_date(candle.timestamp)?;
 
-        // Reset on new day
+        // Reset on new session
         if !self.initialized || day != self.current_day {
             self.cum_tp_vol = 0.0;
             self.cum_vol = 0.0;
@@ -233,16 +269,44 @@ impl StreamingIndicator<OHLCV, f64> for SessionVwapStream {
         }
     }
 
+    fn update(&mut self, candle: OHLCV) -> Option<f64> {
+        (
+            self.cum_tp_vol,
+            self.cum_vol,
+            self.current_day,
+            self.initialized,
+        ) = self.prev_state;
+        self.next(candle)
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<f64> {
+        let day = self.session.trading_date(candle.timestamp)?;
+        let (mut cum_tp_vol, mut cum_vol) = if !self.initialized || day != self.current_day {
+            (0.0, 0.0)
+        } else {
+            (self.cum_tp_vol, self.cum_vol)
+        };
+        cum_tp_vol += typical_price(&candle) * candle.volume;
+        cum_vol += candle.volume;
+
+        (cum_vol > 0.0).then(|| cum_tp_vol / cum_vol)
+    }
+
     fn reset(&mut self) {
         self.cum_tp_vol = 0.0;
         self.cum_vol = 0.0;
         self.current_day = 0;
         self.initialized = false;
+        self.prev_state = (0.0, 0.0, 0, false);
     }
 
     fn is_ready(&self) -> bool {
         self.initialized
     }
+
+    fn lookback(&self) -> usize {
+        0
+    }
 }
 
 // ============================================================================
@@ -278,18 +342,19 @@ impl RollingVwap {
     }
 }
 
-impl Indicator<&[OHLCV], Vec<f64>> for RollingVwap {
-    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
+impl RollingVwap {
+    fn vwap(&self, data: impl ExactSizeIterator<Item = OHLCV>) -> Vec<f64> {
         let len = data.len();
         let mut result = vec![f64::NAN; len];
 
         if len < self.period {
-            return Ok(result);
+            return result;
         }
 
         // Precompute typical price × volume and volume for each candle
-        let tp_vols: Vec<f64> = data.iter().map(|c| typical_price(c) * c.volume).collect();
-        let volumes: Vec<f64> = data.iter().map(|c| c.volume).collect();
+        let (tp_vols, volumes): (Vec<f64>, Vec<f64>) = data
+            .map(|c| (typical_price(&c) * c.volume, c.volume))
+            .unzip();
 
         // Initial window sum
         let mut sum_tp_vol: f64 = tp_vols[..self.period].iter().sum();
@@ -309,7 +374,27 @@ impl Indicator<&[OHLCV], Vec<f64>> for RollingVwap {
             }
         }
 
-        Ok(result)
+        result
+    }
+}
+
+impl Indicator<&[OHLCV], Vec<f64>> for RollingVwap {
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
+        Ok(self.vwap(data.iter().copied()))
+    }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
+}
+
+impl RollingVwap {
+    /// Calculates VWAP from the candles of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slice.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<f64>> {
+        Ok(self.vwap(data.iter()))
     }
 }
 
@@ -317,6 +402,7 @@ impl Indicator<&[OHLCV], Vec<f64>> for RollingVwap {
 ///
 /// Uses a ring buffer for the sliding window.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RollingVwapStream {
     period: usize,
     tp_vol_buffer: Vec<f64>,
@@ -325,6 +411,9 @@ pub struct RollingVwapStream {
     sum_tp_vol: f64,
     sum_vol: f64,
     count: usize,
+    // State before the most recent candle, restored by `update`
+    prev_sums: (f64, f64),
+    prev_slot: (f64, f64),
 }
 
 impl RollingVwapStream {
@@ -346,6 +435,8 @@ impl RollingVwapStream {
             sum_tp_vol: 0.0,
             sum_vol: 0.0,
             count: 0,
+            prev_sums: (0.0, 0.0),
+            prev_slot: (0.0, 0.0),
         })
     }
 
@@ -364,6 +455,13 @@ impl RollingVwapStream {
             None
         }
     }
+
+    /// Checks the ring buffers of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        check_ring(self.period, self.tp_vol_buffer.len(), self.buffer_idx)?;
+        check_ring(self.period, self.vol_buffer.len(), self.buffer_idx)
+    }
 }
 
 impl StreamingIndicator<OHLCV, f64> for RollingVwapStream {
@@ -386,6 +484,11 @@ impl StreamingIndicator<OHLCV, f64> for RollingVwapStream {
     fn next(&mut self, candle: OHLCV) -> Option<f64> {
         let tp_vol = typical_price(&candle) * candle.volume;
         let vol = candle.volume;
+        self.prev_sums = (self.sum_tp_vol, self.sum_vol);
+        self.prev_slot = (
+            self.tp_vol_buffer[self.buffer_idx],
+            self.vol_buffer[self.buffer_idx],
+        );
 
         // Remove oldest value from sum if buffer is full
         if self.count >= self.period {
@@ -410,6 +513,35 @@ impl StreamingIndicator<OHLCV, f64> for RollingVwapStream {
         }
     }
 
+    fn update(&mut self, candle: OHLCV) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(candle);
+        }
+
+        // Rewind the write index and restore the slot the last candle overwrote
+        self.buffer_idx = (self.buffer_idx + self.period - 1) % self.period;
+        (
+            self.tp_vol_buffer[self.buffer_idx],
+            self.vol_buffer[self.buffer_idx],
+        ) = self.prev_slot;
+        (self.sum_tp_vol, self.sum_vol) = self.prev_sums;
+        self.count -= 1;
+
+        self.next(candle)
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<f64> {
+        let (mut sum_tp_vol, mut sum_vol) = (self.sum_tp_vol, self.sum_vol);
+        if self.count >= self.period {
+            sum_tp_vol -= self.tp_vol_buffer[self.buffer_idx];
+            sum_vol -= self.vol_buffer[self.buffer_idx];
+        }
+        sum_tp_vol += typical_price(&candle) * candle.volume;
+        sum_vol += candle.volume;
+
+        (self.count.saturating_add(1) >= self.period && sum_vol > 0.0).then(|| sum_tp_vol / sum_vol)
+    }
+
     fn reset(&mut self) {
         self.tp_vol_buffer.fill(0.0);
         self.vol_buffer.fill(0.0);
@@ -417,11 +549,17 @@ impl StreamingIndicator<OHLCV, f64> for RollingVwapStream {
         self.sum_tp_vol = 0.0;
         self.sum_vol = 0.0;
         self.count = 0;
+        self.prev_sums = (0.0, 0.0);
+        self.prev_slot = (0.0, 0.0);
     }
 
     fn is_ready(&self) -> bool {
         self.count >= self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 // ============================================================================
@@ -460,20 +598,15 @@ impl AnchoredVwap {
     }
 }
 
-impl Indicator<&[OHLCV], Vec<f64>> for AnchoredVwap {
-    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
-        let len = data.len();
-        let mut result = vec![f64::NAN; len];
-
-        if self.anchor_index >= len {
-            return Ok(result);
-        }
+impl AnchoredVwap {
+    fn vwap(&self, data: impl ExactSizeIterator<Item = OHLCV>) -> Vec<f64> {
+        let mut result = vec![f64::NAN; data.len()];
 
         let mut cum_tp_vol = 0.0;
         let mut cum_vol = 0.0;
 
-        for (i, candle) in data.iter().enumerate().skip(self.anchor_index) {
-            let tp = typical_price(candle);
+        for (i, candle) in data.enumerate().skip(self.anchor_index) {
+            let tp = typical_price(&candle);
             cum_tp_vol += tp * candle.volume;
             cum_vol += candle.volume;
 
@@ -482,7 +615,28 @@ impl Indicator<&[OHLCV], Vec<f64>> for AnchoredVwap {
             }
         }
 
-        Ok(result)
+        result
+    }
+}
+
+impl Indicator<&[OHLCV], Vec<f64>> for AnchoredVwap {
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<f64>> {
+        Ok(self.vwap(data.iter().copied()))
+    }
+
+    fn lookback(&self) -> usize {
+        // Counted from the anchor; candles before it have no value
+        0
+    }
+}
+
+impl AnchoredVwap {
+    /// Calculates VWAP from the candles of an [`OhlcvColumns`] view.
+    ///
+    /// # Errors
+    /// Returns the same errors as `calculate` on the equivalent slice.
+    pub fn calculate_columns(&self, data: &OhlcvColumns<'_>) -> IndicatorResult<Vec<f64>> {
+        Ok(self.vwap(data.iter()))
     }
 }
 
@@ -490,11 +644,14 @@ impl Indicator<&[OHLCV], Vec<f64>> for AnchoredVwap {
 ///
 /// Once anchored, accumulates from that point forward.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnchoredVwapStream {
     cum_tp_vol: f64,
     cum_vol: f64,
     anchor_timestamp: Option<i64>,
     anchored: bool,
+    // State before the most recent candle, restored by `update`
+    prev_state: (f64, f64, Option<i64>, bool),
 }
 
 impl Default for AnchoredVwapStream {
@@ -514,6 +671,7 @@ impl AnchoredVwapStream {
             cum_vol: 0.0,
             anchor_timestamp: None,
             anchored: false,
+            prev_state: (0.0, 0.0, None, false),
         }
     }
 
@@ -525,6 +683,7 @@ impl AnchoredVwapStream {
             cum_vol: 0.0,
             anchor_timestamp: Some(anchor_timestamp),
             anchored: false,
+            prev_state: (0.0, 0.0, Some(anchor_timestamp), false),
         }
     }
 
@@ -534,6 +693,7 @@ impl AnchoredVwapStream {
         self.anchored = false;
         self.cum_tp_vol = 0.0;
         self.cum_vol = 0.0;
+        self.prev_state = (0.0, 0.0, self.anchor_timestamp, false);
     }
 
     /// Anchors at the next candle received.
@@ -542,6 +702,7 @@ impl AnchoredVwapStream {
         self.anchored = false;
         self.cum_tp_vol = 0.0;
         self.cum_vol = 0.0;
+        self.prev_state = (0.0, 0.0, None, false);
     }
 
     /// Returns the current VWAP value without consuming a new candle.
@@ -579,6 +740,7 @@ impl StreamingIndicator<OHLCV, f64> for AnchoredVwapStream {
         self.cum_tp_vol = 0.0;
         self.cum_vol = 0.0;
         self.anchored = false;
+        self.prev_state = (0.0, 0.0, self.anchor_timestamp, false);
 
         let mut result = Vec::with_capacity(data.len());
 
@@ -593,6 +755,13 @@ impl StreamingIndicator<OHLCV, f64> for AnchoredVwapStream {
     }
 
     fn next(&mut self, candle: OHLCV) -> Option<f64> {
+        self.prev_state = (
+            self.cum_tp_vol,
+            self.cum_vol,
+            self.anchor_timestamp,
+            self.anchored,
+        );
+
         // Check if we should start anchoring
         if !self.anchored {
             match self.anchor_timestamp {
@@ -619,16 +788,46 @@ impl StreamingIndicator<OHLCV, f64> for AnchoredVwapStream {
         }
     }
 
+    fn update(&mut self, candle: OHLCV) -> Option<f64> {
+        (
+            self.cum_tp_vol,
+            self.cum_vol,
+            self.anchor_timestamp,
+            self.anchored,
+        ) = self.prev_state;
+        self.next(candle)
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<f64> {
+        if !self.anchored {
+            if let Some(ts) = self.anchor_timestamp {
+                if candle.timestamp < ts {
+                    return None;
+                }
+            }
+        }
+
+        let cum_tp_vol = self.cum_tp_vol + typical_price(&candle) * candle.volume;
+        let cum_vol = self.cum_vol + candle.volume;
+        (cum_vol > 0.0).then(|| cum_tp_vol / cum_vol)
+    }
+
     fn reset(&mut self) {
         self.cum_tp_vol = 0.0;
         self.cum_vol = 0.0;
         self.anchor_timestamp = None;
         self.anchored = false;
+        self.prev_state = (0.0, 0.0, None, false);
     }
 
     fn is_ready(&self) -> bool {
         self.anchored
     }
+
+    fn lookback(&self) -> usize {
+        // Counted from the anchor; candles before it have no value
+        0
+    }
 }
 
 // ============================================================================
@@ -740,6 +939,48 @@ mod tests {
         assert!(next_val > 0.0);
     }
 
+    #[test]
+    fn test_session_vwap_exchange_session() {
+        // 30-minute candles over 2024-03-08 (EST) and 2024-03-11 (EDT): the
+        // NYSE open moves from 14:30 to 13:30 UTC across the DST change
+        let day = |y, m, d| crate::resample::days_from_civil(y, m, d) * 86_400_000;
+        let mut candles = Vec::new();
+        for start in [day(2024, 3, 8), day(2024, 3, 11)] {
+            for i in 0..48 {
+                let p = 100.0 + f64::from(i);
+                candles.push(OHLCV::new(
+                    start + i64::from(i) * 1_800_000,
+                    p,
+                    p,
+                    p,
+                    p,
+                    1.0,
+                ));
+            }
+        }
+
+        let session = Session::nyse();
+        let batch = SessionVwap::with_session(session.clone())
+            .calculate(&candles)
+            .unwrap();
+
+        // Friday: 14:30 UTC is candle 29, the close at 21:00 UTC candle 42
+        assert!(batch[28].is_nan());
+        assert!(approx_eq(batch[29], 129.0, 1e-10));
+        assert!(approx_eq(batch[41], 135.0, 1e-10));
+        assert!(batch[42].is_nan());
+        // Monday resets at 13:30 UTC, candle 27
+        assert!(batch[48 + 26].is_nan());
+        assert!(approx_eq(batch[48 + 27], 127.0, 1e-10));
+        assert!(approx_eq(batch[48 + 39], 133.0, 1e-10));
+
+        let mut stream = SessionVwapStream::with_session(session);
+        let streamed = stream.init(&candles).unwrap();
+        for (b, s) in batch.iter().zip(&streamed) {
+            assert!(approx_eq(*b, *s, 1e-10));
+        }
+    }
+
     // ========== Rolling VWAP Tests ==========
 
     #[test]
@@ -885,6 +1126,50 @@ mod tests {
         assert_eq!(stream.anchor_timestamp(), Some(1700000000000));
     }
 
+    // ========== Live Bar Updates ==========
+
+    #[test]
+    fn test_vwap_streams_update_match_next() {
+        // Crosses a UTC day boundary so the session reset is amended too
+        let candles = vec![
+            OHLCV::new(86_280_000, 100.0, 105.0, 99.0, 102.0, 1000.0),
+            OHLCV::new(86_340_000, 102.0, 106.0, 101.0, 104.0, 1500.0),
+            OHLCV::new(86_400_000, 50.0, 55.0, 49.0, 52.0, 2000.0),
+            OHLCV::new(86_460_000, 52.0, 56.0, 51.0, 54.0, 1000.0),
+            OHLCV::new(86_520_000, 54.0, 57.0, 53.0, 56.0, 1200.0),
+        ];
+        let forming = |c: &OHLCV| OHLCV::new(c.timestamp, c.open, c.open, c.open, c.open, 10.0);
+
+        let mut session = (SessionVwapStream::new(), SessionVwapStream::new());
+        let mut rolling = (
+            RollingVwapStream::new(3).unwrap(),
+            RollingVwapStream::new(3).unwrap(),
+        );
+        let mut anchored = (
+            AnchoredVwapStream::with_anchor(86_340_000),
+            AnchoredVwapStream::with_anchor(86_340_000),
+        );
+
+        for candle in &candles {
+            session.0.next(forming(candle));
+            rolling.0.next(forming(candle));
+            anchored.0.next(forming(candle));
+
+            let pairs = [
+                (session.0.update(*candle), session.1.next(*candle)),
+                (rolling.0.update(*candle), rolling.1.next(*candle)),
+                (anchored.0.update(*candle), anchored.1.next(*candle)),
+            ];
+            for (amended, expected) in pairs {
+                match (amended, expected) {
+                    (Some(a), Some(e)) => assert!(approx_eq(a, e, 1e-10), "{a} vs {e}"),
+                    (None, None) => {}
+                    other => panic!("mismatch: {other:?}"),
+                }
+            }
+        }
+    }
+
     // ========== Edge Cases ==========
 
     #[test]
@@ -909,4 +1194,52 @@ mod tests {
         let result = vwap.calculate(&candles).unwrap();
         assert!(result[0].is_nan());
     }
+
+    #[test]
+    fn test_vwap_columns_match_candles() {
+        let candles: Vec<OHLCV> = (0..120)
+            .map(|i| {
+                let p = 100.0 + (f64::from(i) * 0.3).sin() * 5.0;
+                // Hourly from 2024-01-01, within the New York zone's rules
+                OHLCV::new(
+                    1_704_067_200_000 + i64::from(i) * 3_600_000,
+                    p,
+                    p + 1.0,
+                    p - 1.5,
+                    p + 0.2,
+                    100.0 + f64::from(i),
+                )
+            })
+            .collect();
+        let timestamps: Vec<i64> = candles.iter().map(|c| c.timestamp).collect();
+        let opens: Vec<f64> = candles.iter().map(|c| c.open).collect();
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let volumes: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+        let columns =
+            OhlcvColumns::new(&timestamps, &opens, &highs, &lows, &closes, &volumes).unwrap();
+
+        let session = SessionVwap::with_session(Session::nyse());
+        let rolling = RollingVwap::new(10).unwrap();
+        let anchored = AnchoredVwap::new(30);
+        for (a, b) in [
+            (
+                session.calculate_columns(&columns),
+                session.calculate(&candles),
+            ),
+            (
+                rolling.calculate_columns(&columns),
+                rolling.calculate(&candles),
+            ),
+            (
+                anchored.calculate_columns(&columns),
+                anchored.calculate(&candles),
+            ),
+        ] {
+            let (a, b) = (a.unwrap(), b.unwrap());
+            assert_eq!(a.len(), b.len());
+            assert!(a.iter().zip(&b).all(|(x, y)| approx_eq(*x, *y, 1e-12)));
+        }
+    }
 }
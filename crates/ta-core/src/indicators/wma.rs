@@ -22,6 +22,9 @@
 use crate::traits::{Indicator, StreamingIndicator};
 use crate::types::{IndicatorError, IndicatorResult};
 
+#[cfg(feature = "serde")]
+use crate::snapshot::{check_ring, ensure};
+
 /// Weighted Moving Average calculator for batch operations.
 #[derive(Debug, Clone)]
 pub struct Wma {
@@ -89,12 +92,17 @@ impl Indicator<&[f64], Vec<f64>> for Wma {
 
         Ok(result)
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 /// Weighted Moving Average calculator for streaming/real-time operations.
 ///
 /// Uses a ring buffer and maintains running weighted sum for O(1) updates.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WmaStream {
     period: usize,
     weight_sum: f64,
@@ -105,6 +113,10 @@ pub struct WmaStream {
     simple_sum: f64,
     /// Current weighted sum
     weighted_sum: f64,
+    /// State before the most recent `next`, restored by `update`
+    prev_sums: (f64, f64),
+    prev_slot: f64,
+    prev_count: usize,
 }
 
 impl WmaStream {
@@ -127,6 +139,9 @@ impl WmaStream {
             count: 0,
             simple_sum: 0.0,
             weighted_sum: 0.0,
+            prev_sums: (0.0, 0.0),
+            prev_slot: 0.0,
+            prev_count: 0,
         })
     }
 
@@ -135,6 +150,16 @@ impl WmaStream {
     pub const fn period(&self) -> usize {
         self.period
     }
+
+    /// Checks the ring buffer and counters of a restored stream.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        check_ring(self.period, self.buffer.len(), self.head)?;
+        ensure(
+            self.count <= self.period && self.prev_count <= self.period,
+            "count must not exceed the period",
+        )
+    }
 }
 
 impl StreamingIndicator<f64, f64> for WmaStream {
@@ -150,6 +175,9 @@ impl StreamingIndicator<f64, f64> for WmaStream {
 
     #[inline]
     fn next(&mut self, value: f64) -> Option<f64> {
+        self.prev_sums = (self.simple_sum, self.weighted_sum);
+        self.prev_count = self.count;
+
         if self.count < self.period {
             // Still filling the buffer
             self.buffer[self.count] = value;
@@ -179,6 +207,7 @@ impl StreamingIndicator<f64, f64> for WmaStream {
         // (because subtracting simple_sum reduces each weight by 1, removing oldest)
 
         let oldest = self.buffer[self.head];
+        self.prev_slot = oldest;
         self.buffer[self.head] = value;
         self.head = (self.head + 1) % self.period;
 
@@ -189,17 +218,58 @@ impl StreamingIndicator<f64, f64> for WmaStream {
         Some(self.weighted_sum / self.weight_sum)
     }
 
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if self.count == 0 {
+            return self.next(value);
+        }
+
+        if self.prev_count >= self.period {
+            // Last value went through the ring buffer: rewind it
+            self.head = (self.head + self.period - 1) % self.period;
+            self.buffer[self.head] = self.prev_slot;
+        }
+        (self.simple_sum, self.weighted_sum) = self.prev_sums;
+        self.count = self.prev_count;
+
+        self.next(value)
+    }
+
+    fn peek(&self, value: f64) -> Option<f64> {
+        if self.count + 1 < self.period {
+            return None;
+        }
+        if self.count + 1 == self.period {
+            // Initial weighted sum over the buffer completed by `value`
+            let weighted_sum = self.buffer[..self.count]
+                .iter()
+                .chain(std::iter::once(&value))
+                .enumerate()
+                .fold(0.0, |sum, (i, &val)| sum + val * (i + 1) as f64);
+            return Some(weighted_sum / self.weight_sum);
+        }
+
+        let weighted_sum = self.weighted_sum - self.simple_sum + value * self.period as f64;
+        Some(weighted_sum / self.weight_sum)
+    }
+
     fn reset(&mut self) {
         self.buffer.fill(0.0);
         self.head = 0;
         self.count = 0;
         self.simple_sum = 0.0;
         self.weighted_sum = 0.0;
+        self.prev_sums = (0.0, 0.0);
+        self.prev_slot = 0.0;
+        self.prev_count = 0;
     }
 
     fn is_ready(&self) -> bool {
         self.count >= self.period
     }
+
+    fn lookback(&self) -> usize {
+        self.period - 1
+    }
 }
 
 #[cfg(test)]
@@ -284,6 +354,26 @@ mod tests {
         assert_approx_eq(next2, full_batch[4]);
     }
 
+    #[test]
+    fn test_wma_stream_update_matches_next() {
+        let data = [1.0, 3.0, 2.0, 5.0, 4.0, 6.0, 8.0, 7.0];
+        let mut live = WmaStream::new(3).unwrap();
+        let mut reference = WmaStream::new(3).unwrap();
+
+        for &value in &data {
+            live.next(value * 2.0);
+            live.update(value - 1.0);
+            let amended = live.update(value);
+            let expected = reference.next(value);
+
+            match (amended, expected) {
+                (Some(a), Some(e)) => assert_approx_eq(a, e),
+                (None, None) => {}
+                other => panic!("mismatch: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn test_wma_stream_reset() {
         let mut wma = WmaStream::new(3).unwrap();
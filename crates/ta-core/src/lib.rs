@@ -8,12 +8,35 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod append;
+pub mod bars;
+pub mod charts;
+pub mod corporate_actions;
 pub mod indicators;
+pub mod nan;
+pub mod pipe;
+pub mod registry;
+pub mod resample;
+pub mod session;
+pub mod source;
 pub mod traits;
 pub mod types;
+pub mod validate;
+
+#[cfg(feature = "serde")]
+pub mod snapshot;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
-pub use traits::{Indicator, StreamingIndicator};
-pub use types::OHLCV;
+pub use append::Appendable;
+pub use nan::{NanGuard, NanGuardBatch, NanInput, NanPolicy};
+pub use pipe::{Pipe, PipeBatch};
+pub use resample::{Mtf, MtfBatch, Resampler, Timeframe};
+pub use session::{Session, TimeZone};
+#[cfg(feature = "serde")]
+pub use snapshot::{Snapshot, StreamSnapshot};
+pub use source::{PriceSource, Sourced};
+pub use traits::{DynIndicator, Indicator, IndicatorOutput, StreamingIndicator};
+pub use types::{OhlcvColumns, OHLCV};
+pub use validate::{ValidationReport, Validator};
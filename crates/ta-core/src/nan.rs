@@ -0,0 +1,984 @@
+//! Handling of missing (`NaN`) input bars.
+//!
+//! Indicators differ in what a `NaN` input does to them: `Cvd` skips it and
+//! keeps cumulating, while running sums and Wilder smoothing are poisoned by
+//! it for good. A [`NanPolicy`] makes the behaviour explicit and uniform:
+//! - [`NanGuard`] applies it to any stream, bar by bar
+//! - [`NanGuardBatch`] applies it to any batch calculator
+//!
+//! Both forms produce identical results. A bar counts as missing when any of
+//! its fields is `NaN` (see [`NanInput`]). Output stays aligned with the input:
+//! bars the indicator never sees get a `NaN` output.
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::{Sma, SmaStream};
+//! use ta_core::{Indicator, NanPolicy, StreamingIndicator};
+//!
+//! let prices = [1.0, 2.0, 3.0, f64::NAN, 4.0, 5.0];
+//!
+//! // Without a policy the gap poisons SMA's running sum forever
+//! let plain = Sma::new(2).unwrap().calculate(&prices).unwrap();
+//! assert!(plain[5].is_nan());
+//!
+//! // Skipping the bar lets it continue as if the gap never happened
+//! let batch = Sma::new(2).unwrap().with_nan_policy(NanPolicy::SkipBar);
+//! let skipped = batch.calculate(&prices).unwrap();
+//! assert!(skipped[3].is_nan());
+//! assert_eq!(skipped[4], 3.5);
+//!
+//! let mut stream = SmaStream::new(2).unwrap().with_nan_policy(NanPolicy::SkipBar);
+//! let streamed = stream.init(&prices).unwrap();
+//! assert_eq!(streamed[5], 4.5);
+//! ```
+
+use crate::indicators::{Frvp, FrvpOutput};
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorResult, OHLCV};
+
+/// What an indicator does with a missing input bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NanPolicy {
+    /// Pass the bar through unchanged, leaving it to the indicator's own
+    /// arithmetic (default; usually poisons running state)
+    #[default]
+    Propagate,
+    /// Drop the bar: the output is `NaN` and the state is left untouched
+    SkipBar,
+    /// Replace the bar with the last valid one (see [`NanInput::fill_from`]);
+    /// bars before the first valid one are skipped
+    ForwardFill,
+    /// Discard the state: the output is `NaN` and the indicator warms up
+    /// again from the next valid bar
+    ResetState,
+}
+
+/// Indicator input bar that may be missing.
+pub trait NanInput: Copy {
+    /// Returns `true` if any field of the bar is `NaN`.
+    fn is_missing(&self) -> bool;
+
+    /// Stand-in for a missing bar, derived from `last`, the most recent
+    /// valid bar.
+    ///
+    /// Prices repeat the last close and volume is zero, so the filled bar is
+    /// flat and adds no volume.
+    #[must_use]
+    fn fill_from(self, last: &Self) -> Self;
+}
+
+impl NanInput for f64 {
+    fn is_missing(&self) -> bool {
+        self.is_nan()
+    }
+
+    fn fill_from(self, last: &Self) -> Self {
+        *last
+    }
+}
+
+/// (high, low). Without a close, the filled bar is flat at the last bar's
+/// midpoint.
+impl NanInput for (f64, f64) {
+    fn is_missing(&self) -> bool {
+        self.0.is_nan() || self.1.is_nan()
+    }
+
+    fn fill_from(self, last: &Self) -> Self {
+        let mid = f64::midpoint(last.0, last.1);
+        (mid, mid)
+    }
+}
+
+/// (high, low, close)
+impl NanInput for (f64, f64, f64) {
+    fn is_missing(&self) -> bool {
+        self.0.is_nan() || self.1.is_nan() || self.2.is_nan()
+    }
+
+    fn fill_from(self, last: &Self) -> Self {
+        (last.2, last.2, last.2)
+    }
+}
+
+/// (high, low, close, volume)
+impl NanInput for (f64, f64, f64, f64) {
+    fn is_missing(&self) -> bool {
+        self.0.is_nan() || self.1.is_nan() || self.2.is_nan() || self.3.is_nan()
+    }
+
+    fn fill_from(self, last: &Self) -> Self {
+        (last.2, last.2, last.2, 0.0)
+    }
+}
+
+impl NanInput for OHLCV {
+    fn is_missing(&self) -> bool {
+        self.open.is_nan()
+            || self.high.is_nan()
+            || self.low.is_nan()
+            || self.close.is_nan()
+            || self.volume.is_nan()
+    }
+
+    /// Keeps the bar's own timestamp, so session boundaries stay intact.
+    fn fill_from(self, last: &Self) -> Self {
+        OHLCV::new(
+            self.timestamp,
+            last.close,
+            last.close,
+            last.close,
+            last.close,
+            0.0,
+        )
+    }
+}
+
+// ============================================================================
+// Streaming
+// ============================================================================
+
+/// A stream with a [`NanPolicy`] applied to its input.
+///
+/// Usually built with [`StreamingIndicator::with_nan_policy`]. Works with
+/// every stream producing one output per input, i.e. all but `FrvpStream`.
+///
+/// Revising the last bar into one the policy drops takes it back out: the
+/// next bar to reach the wrapped stream replaces it via `update`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NanGuard<T, I> {
+    inner: T,
+    policy: NanPolicy,
+    // Most recent valid bar, the source for forward-filling
+    last: Option<I>,
+    // `last` before the most recent `next`, restored by `update`
+    prev_last: Option<I>,
+    // Whether the most recent bar reached `inner`, so `update` knows whether
+    // to amend its last value or feed a new one
+    fed: bool,
+    // Whether `inner`'s last bar was revised into a dropped one, so the next
+    // bar to reach `inner` replaces it rather than following it
+    stale: bool,
+}
+
+impl<T, I> NanGuard<T, I> {
+    /// Applies `policy` to every bar fed to `inner`.
+    #[must_use]
+    pub const fn new(inner: T, policy: NanPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            last: None,
+            prev_last: None,
+            fed: false,
+            stale: false,
+        }
+    }
+
+    /// Returns the wrapped stream.
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the policy.
+    #[must_use]
+    pub const fn policy(&self) -> NanPolicy {
+        self.policy
+    }
+}
+
+impl<T, I: NanInput> NanGuard<T, I> {
+    /// The bar `inner` should see for `value`, if any.
+    fn admit<O>(&mut self, value: I) -> Option<I>
+    where
+        T: StreamingIndicator<I, O>,
+    {
+        if !value.is_missing() {
+            self.last = Some(value);
+            return Some(value);
+        }
+
+        match self.policy {
+            NanPolicy::Propagate => Some(value),
+            NanPolicy::SkipBar => None,
+            NanPolicy::ForwardFill => self.last.map(|last| value.fill_from(&last)),
+            NanPolicy::ResetState => {
+                self.inner.reset();
+                self.fed = false;
+                self.stale = false;
+                None
+            }
+        }
+    }
+
+    /// Feed an admitted bar to `inner`, replacing a stale last bar.
+    fn feed<O>(&mut self, value: I) -> Option<O>
+    where
+        T: StreamingIndicator<I, O>,
+    {
+        if self.stale {
+            self.stale = false;
+            self.inner.update(value)
+        } else {
+            self.inner.next(value)
+        }
+    }
+}
+
+impl<T, I, O> StreamingIndicator<I, O> for NanGuard<T, I>
+where
+    I: NanInput,
+    T: StreamingIndicator<I, O>,
+    O: IndicatorOutput,
+{
+    fn init(&mut self, data: &[I]) -> IndicatorResult<Vec<O>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &value in data {
+            results.push(self.next(value).unwrap_or_else(O::nan));
+        }
+        Ok(results)
+    }
+
+    #[inline]
+    fn next(&mut self, value: I) -> Option<O> {
+        self.prev_last = self.last;
+        let admitted = self.admit(value);
+        self.fed = admitted.is_some();
+        self.feed(admitted?)
+    }
+
+    fn update(&mut self, value: I) -> Option<O> {
+        self.last = self.prev_last;
+
+        // `inner` has no way to drop a bar it already consumed, so when the
+        // revised bar is dropped the next admitted bar goes in through
+        // `inner.update` in its place. A reset caused by the original bar
+        // is not undone.
+        let Some(admitted) = self.admit(value) else {
+            self.stale |= self.fed;
+            self.fed = false;
+            return None;
+        };
+        if self.fed {
+            self.inner.update(admitted)
+        } else {
+            self.fed = true;
+            self.feed(admitted)
+        }
+    }
+
+    /// Returns `None` while a revision has dropped a bar `inner` already
+    /// consumed, until the next bar replaces it.
+    fn peek(&self, value: I) -> Option<O> {
+        if self.stale {
+            return None;
+        }
+        if !value.is_missing() {
+            return self.inner.peek(value);
+        }
+
+        match self.policy {
+            NanPolicy::Propagate => self.inner.peek(value),
+            NanPolicy::SkipBar | NanPolicy::ResetState => None,
+            NanPolicy::ForwardFill => self.inner.peek(value.fill_from(&self.last?)),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.last = None;
+        self.prev_last = None;
+        self.fed = false;
+        self.stale = false;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+// ============================================================================
+// Batch
+// ============================================================================
+
+/// A batch calculator with a [`NanPolicy`] applied to its input.
+///
+/// Usually built with [`Indicator::with_nan_policy`]. Supports every input
+/// shape the batch calculators take. Index-based parameters (such as
+/// `AnchoredVwap`'s anchor index) count the bars the calculator actually
+/// receives, so they shift when bars are skipped.
+#[derive(Debug, Clone)]
+pub struct NanGuardBatch<T> {
+    inner: T,
+    policy: NanPolicy,
+}
+
+impl<T> NanGuardBatch<T> {
+    /// Applies `policy` to the data passed to `inner`.
+    #[must_use]
+    pub const fn new(inner: T, policy: NanPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// Returns the wrapped calculator.
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the policy.
+    #[must_use]
+    pub const fn policy(&self) -> NanPolicy {
+        self.policy
+    }
+
+    /// Run `calc` over the bars `policy` admits, aligning its output with
+    /// `bars`.
+    fn guard<B, O>(
+        &self,
+        bars: &[B],
+        mut calc: impl FnMut(&[B]) -> IndicatorResult<Vec<O>>,
+    ) -> IndicatorResult<Vec<O>>
+    where
+        B: NanInput,
+        O: IndicatorOutput,
+    {
+        if self.policy == NanPolicy::ResetState {
+            // Each run between missing bars starts from scratch
+            let mut output = Vec::with_capacity(bars.len());
+            for (i, run) in bars.split(NanInput::is_missing).enumerate() {
+                if i > 0 {
+                    output.push(O::nan());
+                }
+                if !run.is_empty() {
+                    output.extend(calc(run)?);
+                }
+            }
+            return Ok(output);
+        }
+
+        let admitted = admit_all(self.policy, bars);
+        let kept: Vec<B> = admitted.iter().flatten().copied().collect();
+        let mut values = calc(&kept)?.into_iter();
+
+        Ok(admitted
+            .iter()
+            .map(|bar| match bar {
+                Some(_) => values.next().unwrap_or_else(O::nan),
+                None => O::nan(),
+            })
+            .collect())
+    }
+}
+
+/// The bar each input becomes under `policy`, or `None` if it is dropped.
+///
+/// `ResetState` drops missing bars like `SkipBar`; the caller handles the
+/// reset itself.
+fn admit_all<B: NanInput>(policy: NanPolicy, bars: &[B]) -> Vec<Option<B>> {
+    let mut last = None;
+    bars.iter()
+        .map(|&bar| {
+            if !bar.is_missing() {
+                last = Some(bar);
+                return Some(bar);
+            }
+            match policy {
+                NanPolicy::Propagate => Some(bar),
+                NanPolicy::SkipBar | NanPolicy::ResetState => None,
+                NanPolicy::ForwardFill => last.map(|last| bar.fill_from(&last)),
+            }
+        })
+        .collect()
+}
+
+fn has_missing<B: NanInput>(bars: &[B]) -> bool {
+    bars.iter().any(NanInput::is_missing)
+}
+
+/// Single-series and bar-slice input: prices, deltas, `OHLCV` candles, CVD bars.
+impl<T, B, O> Indicator<&[B], Vec<O>> for NanGuardBatch<T>
+where
+    B: NanInput,
+    T: for<'a> Indicator<&'a [B], Vec<O>>,
+    O: IndicatorOutput,
+{
+    fn calculate(&self, data: &[B]) -> IndicatorResult<Vec<O>> {
+        if self.policy == NanPolicy::Propagate || !has_missing(data) {
+            return self.inner.calculate(data);
+        }
+        self.guard(data, |bars| self.inner.calculate(bars))
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+/// (highs, lows) input: Donchian Channels, Parabolic SAR.
+impl<'a, T, O> Indicator<&(&'a [f64], &'a [f64]), Vec<O>> for NanGuardBatch<T>
+where
+    T: for<'b> Indicator<&'b (&'b [f64], &'b [f64]), Vec<O>>,
+    O: IndicatorOutput,
+{
+    fn calculate(&self, data: &(&'a [f64], &'a [f64])) -> IndicatorResult<Vec<O>> {
+        let &(highs, lows) = data;
+        check_lengths(&[("highs", highs.len()), ("lows", lows.len())])?;
+        let bars: Vec<(f64, f64)> = highs.iter().copied().zip(lows.iter().copied()).collect();
+        if self.policy == NanPolicy::Propagate || !has_missing(&bars) {
+            return self.inner.calculate(data);
+        }
+        self.guard(&bars, |bars| {
+            let (h, l): (Vec<f64>, Vec<f64>) = bars.iter().copied().unzip();
+            self.inner.calculate(&(&h, &l))
+        })
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+/// Zip (highs, lows, closes) into bars, rejecting mismatched lengths.
+fn hlc_bars(highs: &[f64], lows: &[f64], closes: &[f64]) -> IndicatorResult<Vec<(f64, f64, f64)>> {
+    check_lengths(&[
+        ("highs", highs.len()),
+        ("lows", lows.len()),
+        ("closes", closes.len()),
+    ])?;
+    Ok(highs
+        .iter()
+        .zip(lows)
+        .zip(closes)
+        .map(|((&h, &l), &c)| (h, l, c))
+        .collect())
+}
+
+fn unzip_hlc(bars: &[(f64, f64, f64)]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut columns = (
+        Vec::with_capacity(bars.len()),
+        Vec::with_capacity(bars.len()),
+        Vec::with_capacity(bars.len()),
+    );
+    for &(h, l, c) in bars {
+        columns.0.push(h);
+        columns.1.push(l);
+        columns.2.push(c);
+    }
+    columns
+}
+
+/// (highs, lows, closes) input: ATR, ADX, Stochastic, Ichimoku.
+impl<'a, T, O> Indicator<&(&'a [f64], &'a [f64], &'a [f64]), Vec<O>> for NanGuardBatch<T>
+where
+    T: for<'b> Indicator<&'b (&'b [f64], &'b [f64], &'b [f64]), Vec<O>>,
+    O: IndicatorOutput,
+{
+    fn calculate(&self, data: &(&'a [f64], &'a [f64], &'a [f64])) -> IndicatorResult<Vec<O>> {
+        let &(highs, lows, closes) = data;
+        let bars = hlc_bars(highs, lows, closes)?;
+        if self.policy == NanPolicy::Propagate || !has_missing(&bars) {
+            return self.inner.calculate(data);
+        }
+        self.guard(&bars, |bars| {
+            let (h, l, c) = unzip_hlc(bars);
+            self.inner.calculate(&(&h, &l, &c))
+        })
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+/// (highs, lows, closes) passed by value: pivot points.
+impl<'a, T, O> Indicator<(&'a [f64], &'a [f64], &'a [f64]), Vec<O>> for NanGuardBatch<T>
+where
+    T: for<'b> Indicator<(&'b [f64], &'b [f64], &'b [f64]), Vec<O>>,
+    O: IndicatorOutput,
+{
+    fn calculate(&self, data: (&'a [f64], &'a [f64], &'a [f64])) -> IndicatorResult<Vec<O>> {
+        let (highs, lows, closes) = data;
+        let bars = hlc_bars(highs, lows, closes)?;
+        if self.policy == NanPolicy::Propagate || !has_missing(&bars) {
+            return self.inner.calculate(data);
+        }
+        self.guard(&bars, |bars| {
+            let (h, l, c) = unzip_hlc(bars);
+            self.inner.calculate((&h, &l, &c))
+        })
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+/// (highs, lows, closes, volumes) input: MFI.
+impl<'a, T, O> Indicator<&(&'a [f64], &'a [f64], &'a [f64], &'a [f64]), Vec<O>> for NanGuardBatch<T>
+where
+    T: for<'b> Indicator<&'b (&'b [f64], &'b [f64], &'b [f64], &'b [f64]), Vec<O>>,
+    O: IndicatorOutput,
+{
+    fn calculate(
+        &self,
+        data: &(&'a [f64], &'a [f64], &'a [f64], &'a [f64]),
+    ) -> IndicatorResult<Vec<O>> {
+        let &(highs, lows, closes, volumes) = data;
+        let len = highs.len();
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+            ("volumes", volumes.len()),
+        ])?;
+        let bars: Vec<_> = (0..len)
+            .map(|i| (highs[i], lows[i], closes[i], volumes[i]))
+            .collect();
+        if self.policy == NanPolicy::Propagate || !has_missing(&bars) {
+            return self.inner.calculate(data);
+        }
+        self.guard(&bars, |bars| {
+            let h: Vec<f64> = bars.iter().map(|b| b.0).collect();
+            let l: Vec<f64> = bars.iter().map(|b| b.1).collect();
+            let c: Vec<f64> = bars.iter().map(|b| b.2).collect();
+            let v: Vec<f64> = bars.iter().map(|b| b.3).collect();
+            self.inner.calculate(&(&h, &l, &c, &v))
+        })
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+/// FRVP builds one profile over all candles: skipped bars are left out of it,
+/// and with [`NanPolicy::ResetState`] it covers the candles after the last
+/// missing one. Under [`NanPolicy::Propagate`] a missing candle fails the
+/// profile with `IndicatorError::NonFiniteInput`.
+impl Indicator<&[OHLCV], FrvpOutput> for NanGuardBatch<Frvp> {
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<FrvpOutput> {
+        match self.policy {
+            NanPolicy::Propagate => self.inner.calculate(data),
+            NanPolicy::ResetState => {
+                let start = data
+                    .iter()
+                    .rposition(NanInput::is_missing)
+                    .map_or(0, |i| i + 1);
+                self.inner.calculate(&data[start..])
+            }
+            NanPolicy::SkipBar | NanPolicy::ForwardFill => {
+                let kept: Vec<OHLCV> = admit_all(self.policy, data).into_iter().flatten().collect();
+                self.inner.calculate(&kept)
+            }
+        }
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        Adx, AdxStream, Atr, AtrStream, Donchian, DonchianStream, EmaStream, Frvp, Macd,
+        MacdStream, Mfi, MfiStream, PivotPoints, PivotPointsVariant, Psar, PsarStream, Rsi,
+        RsiStream, SessionVwap, SessionVwapStream, Sma, SmaStream,
+    };
+    use crate::types::IndicatorError;
+
+    const POLICIES: [NanPolicy; 3] = [
+        NanPolicy::SkipBar,
+        NanPolicy::ForwardFill,
+        NanPolicy::ResetState,
+    ];
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a.is_nan() && b.is_nan()) || (a - b).abs() < 1e-9
+    }
+
+    fn assert_series_eq(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, b)) in actual.iter().zip(expected).enumerate() {
+            assert!(approx_eq(*a, *b), "index {i}: {a} vs {b}");
+        }
+    }
+
+    /// Prices with a missing bar at 25 and a two-bar gap at 40.
+    fn gappy_prices() -> Vec<f64> {
+        (0..70)
+            .map(|i| match i {
+                25 | 40 | 41 => f64::NAN,
+                _ => 100.0 + (f64::from(i) * 0.37).sin() * 5.0 + f64::from(i) * 0.05,
+            })
+            .collect()
+    }
+
+    fn gappy_candles() -> Vec<OHLCV> {
+        gappy_prices()
+            .iter()
+            .zip(0_i64..)
+            .map(|(&close, i)| {
+                let volume = if close.is_nan() { f64::NAN } else { 1000.0 };
+                OHLCV::new(
+                    i * 3_600_000,
+                    close,
+                    close + 1.0,
+                    close - 1.0,
+                    close,
+                    volume,
+                )
+            })
+            .collect()
+    }
+
+    /// The runs of bars each policy feeds the indicator, fresh from each run.
+    fn cleaned(policy: NanPolicy, prices: &[f64]) -> Vec<Vec<f64>> {
+        match policy {
+            NanPolicy::ResetState => prices.split(|p| p.is_nan()).map(<[f64]>::to_vec).collect(),
+            _ => vec![admit_all(policy, prices).into_iter().flatten().collect()],
+        }
+    }
+
+    #[test]
+    fn test_propagate_keeps_native_behaviour() {
+        let prices = gappy_prices();
+
+        let expected = Sma::new(5).unwrap().calculate(&prices).unwrap();
+        let batch = Sma::new(5)
+            .unwrap()
+            .with_nan_policy(NanPolicy::default())
+            .calculate(&prices)
+            .unwrap();
+        assert_series_eq(&batch, &expected);
+
+        // Running sums are poisoned for good
+        let mut stream = SmaStream::new(5)
+            .unwrap()
+            .with_nan_policy(NanPolicy::Propagate);
+        let streamed = stream.init(&prices).unwrap();
+        assert!(streamed[25..].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_stream_recovers_after_gap() {
+        let prices = gappy_prices();
+
+        for policy in POLICIES {
+            let mut guarded = EmaStream::new(5).unwrap().with_nan_policy(policy);
+            let actual = guarded.init(&prices).unwrap();
+
+            // The valid bars must produce what a clean run over the bars the
+            // policy admits produces
+            let mut expected = Vec::new();
+            for segment in cleaned(policy, &prices) {
+                let mut clean = EmaStream::new(5).unwrap();
+                expected.extend(clean.init(&segment).unwrap());
+            }
+            let valid: Vec<f64> = actual
+                .iter()
+                .zip(&prices)
+                .filter(|(_, p)| !p.is_nan() || policy == NanPolicy::ForwardFill)
+                .map(|(a, _)| *a)
+                .collect();
+            assert_series_eq(&valid, &expected);
+
+            assert!(!actual[69].is_nan(), "{policy:?} did not recover");
+            match policy {
+                NanPolicy::ForwardFill => assert!(!actual[40].is_nan()),
+                _ => assert!(actual[40].is_nan() && actual[41].is_nan()),
+            }
+        }
+
+        // After a reset the indicator warms up again
+        let mut rsi = RsiStream::new(5)
+            .unwrap()
+            .with_nan_policy(NanPolicy::ResetState);
+        let values = rsi.init(&prices).unwrap();
+        assert!(values[42..47].iter().all(|v| v.is_nan()));
+        assert!(!values[47].is_nan());
+    }
+
+    #[test]
+    fn test_stream_matches_batch() {
+        let prices = gappy_prices();
+        let candles = gappy_candles();
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let volumes: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+        let bars: Vec<(f64, f64, f64)> = candles.iter().map(|c| (c.high, c.low, c.close)).collect();
+        let mfi_bars: Vec<(f64, f64, f64, f64)> = candles
+            .iter()
+            .map(|c| (c.high, c.low, c.close, c.volume))
+            .collect();
+
+        for policy in POLICIES {
+            let batch = Rsi::new(7).unwrap().with_nan_policy(policy);
+            let mut stream = RsiStream::new(7).unwrap().with_nan_policy(policy);
+            assert_series_eq(
+                &stream.init(&prices).unwrap(),
+                &batch.calculate(&prices).unwrap(),
+            );
+
+            let batch = Macd::new(5, 13, 4).unwrap().with_nan_policy(policy);
+            let mut stream = MacdStream::new(5, 13, 4).unwrap().with_nan_policy(policy);
+            let (a, b) = (
+                stream.init(&prices).unwrap(),
+                batch.calculate(&prices).unwrap(),
+            );
+            assert_series_eq(
+                &a.iter().map(|o| o.histogram).collect::<Vec<_>>(),
+                &b.iter().map(|o| o.histogram).collect::<Vec<_>>(),
+            );
+
+            let batch = Atr::new(5).unwrap().with_nan_policy(policy);
+            let mut stream = AtrStream::new(5).unwrap().with_nan_policy(policy);
+            assert_series_eq(
+                &stream.init(&bars).unwrap(),
+                &batch.calculate(&(&highs, &lows, &prices)).unwrap(),
+            );
+
+            let batch = Adx::new(5).unwrap().with_nan_policy(policy);
+            let mut stream = AdxStream::new(5).unwrap().with_nan_policy(policy);
+            let (a, b) = (
+                stream.init(&bars).unwrap(),
+                batch.calculate(&(&highs, &lows, &prices)).unwrap(),
+            );
+            assert_series_eq(
+                &a.iter().map(|o| o.adx).collect::<Vec<_>>(),
+                &b.iter().map(|o| o.adx).collect::<Vec<_>>(),
+            );
+
+            let batch = Mfi::new(5).unwrap().with_nan_policy(policy);
+            let mut stream = MfiStream::new(5).unwrap().with_nan_policy(policy);
+            assert_series_eq(
+                &stream.init(&mfi_bars).unwrap(),
+                &batch
+                    .calculate(&(&highs, &lows, &prices, &volumes))
+                    .unwrap(),
+            );
+
+            let batch = SessionVwap::new().with_nan_policy(policy);
+            let mut stream = SessionVwapStream::new().with_nan_policy(policy);
+            let actual = stream.init(&candles).unwrap();
+            assert_series_eq(&actual, &batch.calculate(&candles).unwrap());
+            assert!(!actual[69].is_nan());
+        }
+    }
+
+    #[test]
+    fn test_psar_high_low_bars() {
+        let candles = gappy_candles();
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let bars: Vec<(f64, f64)> = highs.iter().copied().zip(lows.iter().copied()).collect();
+
+        for policy in [
+            NanPolicy::Propagate,
+            NanPolicy::SkipBar,
+            NanPolicy::ForwardFill,
+            NanPolicy::ResetState,
+        ] {
+            let batch = Psar::new(0.02, 0.02, 0.2)
+                .unwrap()
+                .with_nan_policy(policy)
+                .calculate(&(&highs[..], &lows[..]))
+                .unwrap();
+            let mut stream = PsarStream::new(0.02, 0.02, 0.2)
+                .unwrap()
+                .with_nan_policy(policy);
+            let streamed = stream.init(&bars).unwrap();
+
+            assert_series_eq(
+                &streamed.iter().map(|o| o.sar).collect::<Vec<_>>(),
+                &batch.iter().map(|o| o.sar).collect::<Vec<_>>(),
+            );
+            assert_eq!(
+                streamed.iter().map(|o| o.direction).collect::<Vec<_>>(),
+                batch.iter().map(|o| o.direction).collect::<Vec<_>>(),
+                "{policy:?}"
+            );
+            match policy {
+                NanPolicy::Propagate => {
+                    let native = Psar::new(0.02, 0.02, 0.2)
+                        .unwrap()
+                        .calculate(&(&highs[..], &lows[..]))
+                        .unwrap();
+                    assert_eq!(format!("{batch:?}"), format!("{native:?}"));
+                }
+                NanPolicy::ForwardFill => assert!(!batch[25].sar.is_nan()),
+                NanPolicy::SkipBar | NanPolicy::ResetState => {
+                    assert!(batch[25].sar.is_nan() && !batch[69].sar.is_nan());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_donchian_high_low_bars() {
+        let candles = gappy_candles();
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let bars: Vec<(f64, f64)> = highs.iter().copied().zip(lows.iter().copied()).collect();
+
+        for policy in POLICIES {
+            let batch = Donchian::new(5)
+                .unwrap()
+                .with_nan_policy(policy)
+                .calculate(&(&highs[..], &lows[..]))
+                .unwrap();
+            let mut stream = DonchianStream::new(5).unwrap().with_nan_policy(policy);
+            let streamed = stream.init(&bars).unwrap();
+
+            assert_series_eq(
+                &streamed.iter().map(|o| o.upper).collect::<Vec<_>>(),
+                &batch.iter().map(|o| o.upper).collect::<Vec<_>>(),
+            );
+            assert_series_eq(
+                &streamed.iter().map(|o| o.lower).collect::<Vec<_>>(),
+                &batch.iter().map(|o| o.lower).collect::<Vec<_>>(),
+            );
+            assert_eq!(
+                streamed
+                    .iter()
+                    .map(|o| o.bars_since_high)
+                    .collect::<Vec<_>>(),
+                batch.iter().map(|o| o.bars_since_high).collect::<Vec<_>>(),
+                "{policy:?}"
+            );
+
+            // The channel comes back once the window refills with valid bars
+            assert!(!batch[69].is_nan(), "{policy:?} did not recover");
+            match policy {
+                NanPolicy::ForwardFill => assert!(!batch[25].is_nan()),
+                _ => assert!(batch[25].is_nan()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_across_gap() {
+        let prices = gappy_prices();
+
+        // A missing bar under `ResetState` has already discarded the state,
+        // so revising it cannot restore what came before
+        for policy in [NanPolicy::SkipBar, NanPolicy::ForwardFill] {
+            let mut live = SmaStream::new(3).unwrap().with_nan_policy(policy);
+            let mut reference = SmaStream::new(3).unwrap().with_nan_policy(policy);
+
+            // Every bar opens missing before its real value arrives
+            for &price in &prices {
+                live.next(f64::NAN);
+                let amended = live.update(price);
+                let expected = reference.next(price);
+                match (amended, expected) {
+                    (Some(a), Some(b)) => assert!(approx_eq(a, b), "{policy:?}"),
+                    (None, None) => {}
+                    other => panic!("{policy:?} readiness mismatch: {other:?}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_revision_to_missing_matches_batch() {
+        let prices = gappy_prices();
+        let mut revised = prices[..=20].to_vec();
+        revised.push(f64::NAN);
+        revised.extend_from_slice(&prices[22..30]);
+
+        for policy in [
+            NanPolicy::Propagate,
+            NanPolicy::SkipBar,
+            NanPolicy::ForwardFill,
+            NanPolicy::ResetState,
+        ] {
+            let expected = Rsi::new(5)
+                .unwrap()
+                .with_nan_policy(policy)
+                .calculate(&revised)
+                .unwrap();
+
+            // Bar 21 arrives valid and is then revised to missing
+            let mut stream = RsiStream::new(5).unwrap().with_nan_policy(policy);
+            let mut actual = stream.init(&prices[..21]).unwrap();
+            stream.next(prices[21]);
+            actual.push(stream.update(f64::NAN).unwrap_or(f64::NAN));
+            for &price in &revised[22..] {
+                actual.push(stream.next(price).unwrap_or(f64::NAN));
+            }
+            assert_series_eq(&actual, &expected);
+        }
+    }
+
+    #[test]
+    fn test_peek_applies_policy() {
+        let prices = gappy_prices();
+
+        let mut ffill = SmaStream::new(3)
+            .unwrap()
+            .with_nan_policy(NanPolicy::ForwardFill);
+        ffill.init(&prices[..10]).unwrap();
+        let peeked = ffill.peek(f64::NAN).unwrap();
+        assert!(approx_eq(peeked, ffill.next(prices[9]).unwrap()));
+
+        let mut skip = SmaStream::new(3)
+            .unwrap()
+            .with_nan_policy(NanPolicy::SkipBar);
+        skip.init(&prices[..10]).unwrap();
+        assert!(skip.peek(f64::NAN).is_none());
+        assert!(skip.is_ready());
+    }
+
+    #[test]
+    fn test_batch_rejects_mismatched_lengths() {
+        let batch = Atr::new(3).unwrap().with_nan_policy(NanPolicy::SkipBar);
+        let result = batch.calculate(&(&[1.0, 2.0][..], &[1.0][..], &[1.0, 2.0][..]));
+        assert!(matches!(
+            result,
+            Err(IndicatorError::LengthMismatch {
+                name: "lows",
+                len: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_pivots_and_frvp() {
+        let candles = gappy_candles();
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+        // PivotPoints implements `Indicator` for two input shapes
+        let pivots = NanGuardBatch::new(
+            PivotPoints::new(PivotPointsVariant::Standard),
+            NanPolicy::ForwardFill,
+        );
+        let pivots = pivots
+            .calculate((&highs[..], &lows[..], &closes[..]))
+            .unwrap();
+        assert!(pivots[25].is_valid());
+        assert!(approx_eq(pivots[25].pivot, closes[24]));
+
+        let frvp = Frvp::new(20).unwrap();
+        let expected = frvp.calculate(&candles[42..]).unwrap();
+        let reset = frvp
+            .with_nan_policy(NanPolicy::ResetState)
+            .calculate(&candles)
+            .unwrap();
+        assert!(approx_eq(reset.poc, expected.poc));
+    }
+}
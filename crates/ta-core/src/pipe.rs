@@ -0,0 +1,322 @@
+//! Indicator chaining: feed one indicator's output into another.
+//!
+//! Derived series like SMA of RSI, EMA of ATR, or Bollinger Bands on CVD are
+//! built by piping a single-value indicator into a second one:
+//! - [`Pipe`] chains two streams; each tick flows through both in O(1)
+//! - [`PipeBatch`] chains two batch calculators over historical data
+//!
+//! The second stage only sees values the first stage actually produced: while
+//! the first stage warms up (`None`/`NaN`), the second is not fed, so its own
+//! warmup starts at the first real value. Both forms produce identical results.
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::{Rsi, RsiStream, Sma, SmaStream};
+//! use ta_core::{Indicator, StreamingIndicator};
+//!
+//! let prices: Vec<f64> = (0..60).map(|i| 100.0 + (f64::from(i) * 0.4).sin() * 3.0).collect();
+//!
+//! // SMA(9) of RSI(14), batch
+//! let batch = Rsi::new(14).unwrap().pipe(Sma::new(9).unwrap());
+//! let smoothed = batch.calculate(&prices).unwrap();
+//!
+//! // Same chain, streaming
+//! let mut stream = RsiStream::new(14).unwrap().pipe(SmaStream::new(9).unwrap());
+//! let streamed = stream.init(&prices).unwrap();
+//!
+//! // RSI is ready at index 14, SMA of RSI 8 values later
+//! assert!(smoothed[21].is_nan());
+//! assert!((smoothed[22] - streamed[22]).abs() < 1e-10);
+//! ```
+
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::IndicatorResult;
+
+// ============================================================================
+// Streaming
+// ============================================================================
+
+/// Two streams chained so the first one's output is the second one's input.
+///
+/// Usually built with [`StreamingIndicator::pipe`]. Pipes nest, so longer
+/// chains are built by piping a `Pipe` into another stream.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pipe<A, B> {
+    first: A,
+    second: B,
+    // Whether the most recent input reached `second`, so `update` knows
+    // whether to amend its last value or feed a new one
+    fed: bool,
+}
+
+impl<A, B> Pipe<A, B> {
+    /// Chains `first` into `second`.
+    #[must_use]
+    pub const fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            fed: false,
+        }
+    }
+
+    /// Returns the first stage.
+    #[must_use]
+    pub const fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second stage.
+    #[must_use]
+    pub const fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<I, O, A, B> StreamingIndicator<I, O> for Pipe<A, B>
+where
+    I: Copy,
+    A: StreamingIndicator<I, f64>,
+    B: StreamingIndicator<f64, O>,
+    O: IndicatorOutput,
+{
+    fn init(&mut self, data: &[I]) -> IndicatorResult<Vec<O>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &value in data {
+            results.push(self.next(value).unwrap_or_else(O::nan));
+        }
+        Ok(results)
+    }
+
+    #[inline]
+    fn next(&mut self, value: I) -> Option<O> {
+        let v = self.first.next(value).filter(|v| !v.is_nan());
+        self.fed = v.is_some();
+        self.second.next(v?)
+    }
+
+    fn update(&mut self, value: I) -> Option<O> {
+        // If the revised value no longer reaches `second`, the stale value it
+        // already consumed stays until the next value replaces it
+        let v = self.first.update(value).filter(|v| !v.is_nan())?;
+        if self.fed {
+            self.second.update(v)
+        } else {
+            self.fed = true;
+            self.second.next(v)
+        }
+    }
+
+    fn peek(&self, value: I) -> Option<O> {
+        let v = self.first.peek(value).filter(|v| !v.is_nan())?;
+        self.second.peek(v)
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.fed = false;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.second.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        // `second` starts warming up at the first value `first` produces
+        self.first.lookback() + self.second.lookback()
+    }
+}
+
+// ============================================================================
+// Batch
+// ============================================================================
+
+/// Two batch calculators chained so the first one's output is the second
+/// one's input.
+///
+/// Usually built with [`Indicator::pipe`]. `NaN` values from the first stage
+/// are skipped rather than passed on, so they neither poison the second
+/// stage's running state nor count towards its warmup. Their positions are
+/// `NaN` in the output, which stays aligned with the input.
+#[derive(Debug, Clone)]
+pub struct PipeBatch<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> PipeBatch<A, B> {
+    /// Chains `first` into `second`.
+    #[must_use]
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<In, O, A, B> Indicator<In, Vec<O>> for PipeBatch<A, B>
+where
+    A: Indicator<In, Vec<f64>>,
+    B: for<'a> Indicator<&'a [f64], Vec<O>>,
+    O: IndicatorOutput,
+{
+    fn calculate(&self, data: In) -> IndicatorResult<Vec<O>> {
+        let first = self.first.calculate(data)?;
+
+        let values: Vec<f64> = first.iter().copied().filter(|v| !v.is_nan()).collect();
+        let mut second = self.second.calculate(&values)?.into_iter();
+
+        Ok(first
+            .iter()
+            .map(|v| {
+                if v.is_nan() {
+                    O::nan()
+                } else {
+                    second.next().unwrap_or_else(O::nan)
+                }
+            })
+            .collect())
+    }
+
+    fn lookback(&self) -> usize {
+        self.first.lookback() + self.second.lookback()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        Atr, AtrStream, BBands, BBandsStream, Ema, EmaStream, Rsi, RsiStream, Sma, SmaStream,
+    };
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a.is_nan() && b.is_nan()) || (a - b).abs() < 1e-10
+    }
+
+    fn prices(n: i32) -> Vec<f64> {
+        (0..n)
+            .map(|i| 100.0 + (f64::from(i) * 0.37).sin() * 5.0 + f64::from(i) * 0.05)
+            .collect()
+    }
+
+    #[test]
+    fn test_pipe_stream_matches_batch() {
+        let data = prices(80);
+
+        let batch = Rsi::new(14).unwrap().pipe(Sma::new(9).unwrap());
+        let expected = batch.calculate(&data).unwrap();
+
+        let mut stream = RsiStream::new(14).unwrap().pipe(SmaStream::new(9).unwrap());
+        let actual = stream.init(&data).unwrap();
+
+        // RSI(14) first value at 14, SMA(9) of it at 14 + 8
+        assert!(expected[..22].iter().all(|v| v.is_nan()));
+        assert!(!expected[22].is_nan());
+        assert_eq!(batch.lookback(), 22);
+        assert_eq!(stream.lookback(), 22);
+        for (a, b) in expected.iter().zip(&actual) {
+            assert!(approx_eq(*a, *b), "{a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn test_pipe_batch_skips_warmup() {
+        let data = prices(60);
+
+        // Feeding RSI's NaN prefix straight into EMA would poison it forever
+        let rsi = Rsi::new(14).unwrap().calculate(&data).unwrap();
+        let naive = Ema::new(5).unwrap().calculate(&rsi).unwrap();
+        assert!(naive.iter().all(|v| v.is_nan()));
+
+        let piped = Rsi::new(14)
+            .unwrap()
+            .pipe(Ema::new(5).unwrap())
+            .calculate(&data)
+            .unwrap();
+        let expected = Ema::new(5).unwrap().calculate(&rsi[14..]).unwrap();
+        assert_eq!(piped.len(), data.len());
+        for (a, b) in piped[14..].iter().zip(&expected) {
+            assert!(approx_eq(*a, *b));
+        }
+    }
+
+    #[test]
+    fn test_pipe_bar_input_and_multi_output() {
+        let data = prices(80);
+        let bars: Vec<(f64, f64, f64)> = data.iter().map(|&c| (c + 1.0, c - 1.0, c)).collect();
+
+        // EMA of ATR
+        let highs: Vec<f64> = bars.iter().map(|b| b.0).collect();
+        let lows: Vec<f64> = bars.iter().map(|b| b.1).collect();
+        let batch = Atr::new(14).unwrap().pipe(Ema::new(10).unwrap());
+        let expected = batch.calculate(&(&highs, &lows, &data)).unwrap();
+
+        let mut stream = AtrStream::new(14)
+            .unwrap()
+            .pipe(EmaStream::new(10).unwrap());
+        let actual = stream.init(&bars).unwrap();
+        for (a, b) in expected.iter().zip(&actual) {
+            assert!(approx_eq(*a, *b), "{a} vs {b}");
+        }
+
+        // Bollinger Bands of RSI
+        let batch = Rsi::new(14).unwrap().pipe(BBands::new(20, 2.0).unwrap());
+        let expected = batch.calculate(&data).unwrap();
+        let mut stream = RsiStream::new(14)
+            .unwrap()
+            .pipe(BBandsStream::new(20, 2.0).unwrap());
+        let actual = stream.init(&data).unwrap();
+        assert!(expected[32].upper.is_nan());
+        assert!(!expected[33].upper.is_nan());
+        for (a, b) in expected.iter().zip(&actual) {
+            assert!(approx_eq(a.upper, b.upper) && approx_eq(a.lower, b.lower));
+        }
+    }
+
+    #[test]
+    fn test_pipe_nested_and_boxed() {
+        let data = prices(80);
+
+        let mut nested = RsiStream::new(14)
+            .unwrap()
+            .pipe(SmaStream::new(5).unwrap())
+            .pipe(EmaStream::new(3).unwrap());
+
+        let first: Box<dyn StreamingIndicator<f64, f64>> = Box::new(RsiStream::new(14).unwrap());
+        let second: Box<dyn StreamingIndicator<f64, f64>> = Box::new(SmaStream::new(5).unwrap());
+        let third: Box<dyn StreamingIndicator<f64, f64>> = Box::new(EmaStream::new(3).unwrap());
+        let mut boxed = first.pipe(second).pipe(third);
+
+        let a = nested.init(&data).unwrap();
+        let b = boxed.init(&data).unwrap();
+        assert!(a[..20].iter().all(|v| v.is_nan()));
+        assert!(!a[20].is_nan());
+        for (x, y) in a.iter().zip(&b) {
+            assert!(approx_eq(*x, *y));
+        }
+        assert!(nested.is_ready());
+    }
+
+    #[test]
+    fn test_pipe_stream_update_matches_next() {
+        let data = prices(40);
+        let mut live = RsiStream::new(5).unwrap().pipe(SmaStream::new(3).unwrap());
+        let mut reference = RsiStream::new(5).unwrap().pipe(SmaStream::new(3).unwrap());
+
+        // Amend every bar, including the ones where the pipe is warming up
+        for &price in &data {
+            live.next(price + 0.7);
+            live.update(price - 0.4);
+            let amended = live.update(price);
+            let expected = reference.next(price);
+            match (amended, expected) {
+                (Some(a), Some(b)) => assert!(approx_eq(a, b)),
+                (None, None) => {}
+                other => panic!("readiness mismatch: {other:?}"),
+            }
+        }
+    }
+}
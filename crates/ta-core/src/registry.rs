@@ -0,0 +1,1074 @@
+//! Name-based indicator registry.
+//!
+//! Builds any streaming indicator as a boxed [`DynIndicator`] from a name and
+//! a parameter map, so indicator sets can come from a config file. Each entry
+//! also describes its parameters (names, defaults, valid ranges) and output
+//! columns for building UIs.
+//!
+//! Single-series indicators (SMA, RSI, MACD, ...) are fed candle closes.
+//! Fixed Range Volume Profile is not registered: it produces one profile over
+//! a range rather than a value per candle.
+//!
+//! # Example
+//! ```
+//! use std::collections::HashMap;
+//! use ta_core::registry;
+//! use ta_core::OHLCV;
+//!
+//! let params = HashMap::from([("fast".to_string(), 8.0), ("slow".to_string(), 21.0)]);
+//! let mut macd = registry::create("macd", &params).unwrap(); // signal defaults to 9
+//! assert_eq!(macd.columns(), ["macd", "signal", "histogram"]);
+//!
+//! let candle = OHLCV::new(0, 100.0, 101.0, 99.0, 100.5, 1000.0);
+//! let values = macd.next(candle);
+//! assert_eq!(values.len(), 3);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+
+use crate::indicators::{
+    AdxOutput, AdxStream, AnchoredVwapStream, AtrStream, BBandsOutput, BBandsStream,
+    CvdOhlcvStream, DonchianOutput, DonchianStream, EmaStream, HmaStream, IchimokuOutput,
+    IchimokuStream, KeltnerBand, KeltnerMa, KeltnerOutput, KeltnerStream, LinRegOutput,
+    LinRegStream, MacdOutput, MacdStream, MfiStream, PivotPoints, PivotPointsOutput,
+    PivotPointsVariant, PsarOutput, PsarStream, RollingVwapStream, RsiStream, SessionVwapStream,
+    SmaStream, SqueezeOutput, SqueezeStream, StochOutput, StochRsiOutput, StochRsiStream,
+    StochStream, StochType, SuperTrendAtr, SuperTrendOutput, SuperTrendSource, SuperTrendStream,
+    WmaStream,
+};
+use crate::traits::{DynIndicator, StreamingIndicator};
+use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+
+// ============================================================================
+// Metadata
+// ============================================================================
+
+/// Description of a single indicator parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSpec {
+    /// Parameter name as used in the parameter map
+    pub name: &'static str,
+    /// Human-readable description
+    pub description: &'static str,
+    /// Value used when the parameter is omitted
+    pub default: f64,
+    /// Smallest valid value (inclusive)
+    pub min: f64,
+    /// Largest valid value (inclusive); always finite
+    pub max: f64,
+    /// Whether the value must be a whole number
+    pub integer: bool,
+}
+
+/// Largest period the registry accepts; streams allocate their window up
+/// front, so an unbounded period could abort on allocation.
+const MAX_PERIOD: f64 = 1_000_000.0;
+
+/// Largest anchor timestamp, JavaScript's `Number.MAX_SAFE_INTEGER`.
+const MAX_TIMESTAMP: f64 = 9_007_199_254_740_991.0;
+
+impl ParamSpec {
+    const fn period(name: &'static str, description: &'static str, default: f64) -> Self {
+        Self {
+            name,
+            description,
+            default,
+            min: 1.0,
+            max: MAX_PERIOD,
+            integer: true,
+        }
+    }
+
+    const fn multiplier(name: &'static str, description: &'static str, default: f64) -> Self {
+        Self {
+            name,
+            description,
+            default,
+            min: 0.0,
+            max: f64::MAX,
+            integer: false,
+        }
+    }
+
+    fn validate(&self, indicator: &str, value: f64) -> IndicatorResult<()> {
+        if !(self.min..=self.max).contains(&value) {
+            return Err(IndicatorError::OutOfRange {
+                name: self.name,
+                value,
+                min: self.min,
+                max: self.max,
+            });
+        }
+        if self.integer && value.fract() != 0.0 {
+            return Err(IndicatorError::InvalidParameter(format!(
+                "{indicator}: {} must be a whole number, got {value}",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+type Builder = fn(&[f64]) -> IndicatorResult<Box<dyn DynIndicator>>;
+
+/// Registry entry describing an indicator and how to build it.
+#[derive(Clone, Copy)]
+pub struct IndicatorSpec {
+    /// Registry name (e.g. `"macd"`)
+    pub name: &'static str,
+    /// Human-readable description
+    pub description: &'static str,
+    /// Accepted parameters
+    pub params: &'static [ParamSpec],
+    /// Output column names
+    pub columns: &'static [&'static str],
+    build: Builder,
+}
+
+impl std::fmt::Debug for IndicatorSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndicatorSpec")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("params", &self.params)
+            .field("columns", &self.columns)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IndicatorSpec {
+    /// Default value of every parameter, keyed by name.
+    #[must_use]
+    pub fn default_params(&self) -> HashMap<String, f64> {
+        self.params
+            .iter()
+            .map(|p| (p.name.to_string(), p.default))
+            .collect()
+    }
+
+    /// Build the indicator. Omitted parameters take their defaults.
+    ///
+    /// # Errors
+    /// Returns `OutOfRange` for values outside the declared range, and
+    /// `InvalidParameter` for unknown parameter names, fractional values of
+    /// integer parameters, or combinations the indicator rejects (e.g. a MACD
+    /// fast period not below the slow period).
+    pub fn create<H: BuildHasher>(
+        &self,
+        params: &HashMap<String, f64, H>,
+    ) -> IndicatorResult<Box<dyn DynIndicator>> {
+        if let Some(unknown) = params
+            .keys()
+            .find(|key| !self.params.iter().any(|p| p.name == key.as_str()))
+        {
+            return Err(IndicatorError::InvalidParameter(format!(
+                "{}: unknown parameter '{unknown}'",
+                self.name
+            )));
+        }
+
+        let mut values = Vec::with_capacity(self.params.len());
+        for spec in self.params {
+            let value = params.get(spec.name).copied().unwrap_or(spec.default);
+            spec.validate(self.name, value)?;
+            values.push(value);
+        }
+
+        (self.build)(&values)
+    }
+}
+
+/// All registered indicators.
+#[must_use]
+pub fn indicators() -> &'static [IndicatorSpec] {
+    REGISTRY
+}
+
+/// Look up an indicator by name.
+#[must_use]
+pub fn find(name: &str) -> Option<&'static IndicatorSpec> {
+    REGISTRY.iter().find(|spec| spec.name == name)
+}
+
+/// Build an indicator by name. Omitted parameters take their defaults.
+///
+/// # Errors
+/// Returns `InvalidParameter` if the name is not registered or the
+/// parameters are invalid (see [`IndicatorSpec::create`]).
+pub fn create<H: BuildHasher>(
+    name: &str,
+    params: &HashMap<String, f64, H>,
+) -> IndicatorResult<Box<dyn DynIndicator>> {
+    find(name)
+        .ok_or_else(|| IndicatorError::InvalidParameter(format!("unknown indicator '{name}'")))?
+        .create(params)
+}
+
+// ============================================================================
+// Stream Adapter
+// ============================================================================
+
+/// Conversion of a stream output into registry columns.
+trait Columns {
+    const COLUMNS: &'static [&'static str];
+
+    fn push_columns(&self, out: &mut Vec<f64>);
+}
+
+impl Columns for f64 {
+    const COLUMNS: &'static [&'static str] = &["value"];
+
+    fn push_columns(&self, out: &mut Vec<f64>) {
+        out.push(*self);
+    }
+}
+
+macro_rules! impl_columns {
+    ($output:ty => $($field:ident),+ $(,)?) => {
+        impl Columns for $output {
+            const COLUMNS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn push_columns(&self, out: &mut Vec<f64>) {
+                $(out.push(self.$field);)*
+            }
+        }
+    };
+}
+
+impl_columns!(MacdOutput => macd, signal, histogram);
+impl_columns!(BBandsOutput => upper, middle, lower, percent_b, bandwidth);
+impl_columns!(StochOutput => k, d);
+impl_columns!(StochRsiOutput => k, d);
+impl_columns!(AdxOutput => adx, plus_di, minus_di);
+impl_columns!(IchimokuOutput => tenkan_sen, kijun_sen, senkou_span_a, senkou_span_b, chikou_span);
+impl_columns!(KeltnerOutput => upper, middle, lower, width);
+impl_columns!(LinRegOutput => value, upper, lower, slope, r, r_squared);
+impl_columns!(PivotPointsOutput => pivot, r1, r2, r3, s1, s2, s3);
+
+impl Columns for DonchianOutput {
+    const COLUMNS: &'static [&'static str] = &[
+        "upper",
+        "lower",
+        "middle",
+        "width",
+        "bars_since_high",
+        "bars_since_low",
+    ];
+
+    fn push_columns(&self, out: &mut Vec<f64>) {
+        out.extend([self.upper, self.lower, self.middle, self.width]);
+        out.push(self.bars_since_high as f64);
+        out.push(self.bars_since_low as f64);
+    }
+}
+
+impl Columns for SqueezeOutput {
+    const COLUMNS: &'static [&'static str] = &["squeeze_on", "squeeze_bars", "momentum"];
+
+    fn push_columns(&self, out: &mut Vec<f64>) {
+        out.push(if self.squeeze_on { 1.0 } else { 0.0 });
+        out.push(self.squeeze_bars as f64);
+        out.push(self.momentum);
+    }
+}
+
+impl Columns for SuperTrendOutput {
+    const COLUMNS: &'static [&'static str] = &["value", "direction", "flip"];
+
+    fn push_columns(&self, out: &mut Vec<f64>) {
+        out.push(self.value);
+        out.push(f64::from(self.direction));
+        out.push(if self.flip { 1.0 } else { 0.0 });
+    }
+}
+
+impl Columns for PsarOutput {
+    const COLUMNS: &'static [&'static str] = &["sar", "direction", "af", "ep"];
+
+    fn push_columns(&self, out: &mut Vec<f64>) {
+        out.push(self.sar);
+        out.push(f64::from(self.direction));
+        out.push(self.af);
+        out.push(self.ep);
+    }
+}
+
+/// Adapts a typed stream to [`DynIndicator`] by mapping each candle to the
+/// stream's input.
+struct StreamAdapter<S, I, O> {
+    name: &'static str,
+    stream: S,
+    input: fn(&OHLCV) -> I,
+    output: PhantomData<fn() -> O>,
+}
+
+impl<S, I, O> StreamAdapter<S, I, O>
+where
+    S: StreamingIndicator<I, O> + 'static,
+    I: 'static,
+    O: Columns + 'static,
+{
+    fn boxed(name: &'static str, stream: S, input: fn(&OHLCV) -> I) -> Box<dyn DynIndicator> {
+        Box::new(Self {
+            name,
+            stream,
+            input,
+            output: PhantomData,
+        })
+    }
+
+    fn row(value: Option<O>) -> Vec<f64> {
+        let mut row = Vec::with_capacity(O::COLUMNS.len());
+        match value {
+            Some(output) => output.push_columns(&mut row),
+            None => row.resize(O::COLUMNS.len(), f64::NAN),
+        }
+        row
+    }
+}
+
+impl<S, I, O> DynIndicator for StreamAdapter<S, I, O>
+where
+    S: StreamingIndicator<I, O> + 'static,
+    I: 'static,
+    O: Columns + 'static,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn columns(&self) -> &'static [&'static str] {
+        O::COLUMNS
+    }
+
+    fn init(&mut self, candles: &[OHLCV]) -> IndicatorResult<Vec<Vec<f64>>> {
+        self.stream.reset();
+        let mut columns = vec![Vec::with_capacity(candles.len()); O::COLUMNS.len()];
+        for candle in candles {
+            for (column, value) in columns.iter_mut().zip(self.next(*candle)) {
+                column.push(value);
+            }
+        }
+        Ok(columns)
+    }
+
+    fn next(&mut self, candle: OHLCV) -> Vec<f64> {
+        Self::row(self.stream.next((self.input)(&candle)))
+    }
+
+    fn update(&mut self, candle: OHLCV) -> Vec<f64> {
+        Self::row(self.stream.update((self.input)(&candle)))
+    }
+
+    fn peek(&self, candle: &OHLCV) -> Vec<f64> {
+        Self::row(self.stream.peek((self.input)(candle)))
+    }
+
+    fn reset(&mut self) {
+        self.stream.reset();
+    }
+
+    fn is_ready(&self) -> bool {
+        self.stream.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        self.stream.lookback()
+    }
+}
+
+/// Pivot points are computed independently from each candle.
+struct PivotAdapter {
+    pivots: PivotPoints,
+    ready: bool,
+}
+
+impl DynIndicator for PivotAdapter {
+    fn name(&self) -> &'static str {
+        "pivot_points"
+    }
+
+    fn columns(&self) -> &'static [&'static str] {
+        PivotPointsOutput::COLUMNS
+    }
+
+    fn init(&mut self, candles: &[OHLCV]) -> IndicatorResult<Vec<Vec<f64>>> {
+        self.reset();
+        let mut columns = vec![Vec::with_capacity(candles.len()); PivotPointsOutput::COLUMNS.len()];
+        for candle in candles {
+            for (column, value) in columns.iter_mut().zip(self.next(*candle)) {
+                column.push(value);
+            }
+        }
+        Ok(columns)
+    }
+
+    fn next(&mut self, candle: OHLCV) -> Vec<f64> {
+        self.ready = true;
+        self.peek(&candle)
+    }
+
+    fn update(&mut self, candle: OHLCV) -> Vec<f64> {
+        self.next(candle)
+    }
+
+    fn peek(&self, candle: &OHLCV) -> Vec<f64> {
+        let mut row = Vec::with_capacity(PivotPointsOutput::COLUMNS.len());
+        self.pivots
+            .calculate_single(candle.high, candle.low, candle.close)
+            .push_columns(&mut row);
+        row
+    }
+
+    fn reset(&mut self) {
+        self.ready = false;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Parameter values are validated as whole numbers before reaching a builder.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn whole(value: f64) -> usize {
+    value as usize
+}
+
+fn close(c: &OHLCV) -> f64 {
+    c.close
+}
+
+fn hl(c: &OHLCV) -> (f64, f64) {
+    (c.high, c.low)
+}
+
+fn hlc(c: &OHLCV) -> (f64, f64, f64) {
+    (c.high, c.low, c.close)
+}
+
+fn hlcv(c: &OHLCV) -> (f64, f64, f64, f64) {
+    (c.high, c.low, c.close, c.volume)
+}
+
+fn candle(c: &OHLCV) -> OHLCV {
+    *c
+}
+
+const PERIOD_14: &[ParamSpec] = &[ParamSpec::period("period", "Lookback period", 14.0)];
+const PERIOD_20: &[ParamSpec] = &[ParamSpec::period("period", "Lookback period", 20.0)];
+const VALUE: &[&str] = &["value"];
+
+static REGISTRY: &[IndicatorSpec] = &[
+    IndicatorSpec {
+        name: "sma",
+        description: "Simple Moving Average",
+        params: PERIOD_20,
+        columns: VALUE,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "sma",
+                SmaStream::new(whole(p[0]))?,
+                close,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "ema",
+        description: "Exponential Moving Average",
+        params: PERIOD_20,
+        columns: VALUE,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "ema",
+                EmaStream::new(whole(p[0]))?,
+                close,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "wma",
+        description: "Weighted Moving Average",
+        params: PERIOD_20,
+        columns: VALUE,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "wma",
+                WmaStream::new(whole(p[0]))?,
+                close,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "hma",
+        description: "Hull Moving Average",
+        params: &[ParamSpec::period("period", "Lookback period", 9.0)],
+        columns: VALUE,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "hma",
+                HmaStream::new(whole(p[0]))?,
+                close,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "rsi",
+        description: "Relative Strength Index",
+        params: PERIOD_14,
+        columns: VALUE,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "rsi",
+                RsiStream::new(whole(p[0]))?,
+                close,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "macd",
+        description: "Moving Average Convergence Divergence",
+        params: &[
+            ParamSpec::period("fast", "Fast EMA period", 12.0),
+            ParamSpec::period("slow", "Slow EMA period", 26.0),
+            ParamSpec::period("signal", "Signal line period", 9.0),
+        ],
+        columns: MacdOutput::COLUMNS,
+        build: |p| {
+            let stream = MacdStream::new(whole(p[0]), whole(p[1]), whole(p[2]))?;
+            Ok(StreamAdapter::boxed("macd", stream, close))
+        },
+    },
+    IndicatorSpec {
+        name: "bbands",
+        description: "Bollinger Bands",
+        params: &[
+            ParamSpec::period("period", "Lookback period", 20.0),
+            ParamSpec::multiplier("k", "Standard deviation multiplier", 2.0),
+        ],
+        columns: BBandsOutput::COLUMNS,
+        build: |p| {
+            let stream = BBandsStream::new(whole(p[0]), p[1])?;
+            Ok(StreamAdapter::boxed("bbands", stream, close))
+        },
+    },
+    IndicatorSpec {
+        name: "linreg",
+        description: "Linear Regression Channel",
+        params: &[
+            ParamSpec {
+                min: 2.0,
+                ..ParamSpec::period("period", "Lookback period", 14.0)
+            },
+            ParamSpec::multiplier("std_dev", "Channel width in standard deviations", 2.0),
+        ],
+        columns: LinRegOutput::COLUMNS,
+        build: |p| {
+            let stream = LinRegStream::new(whole(p[0]), p[1])?;
+            Ok(StreamAdapter::boxed("linreg", stream, close))
+        },
+    },
+    IndicatorSpec {
+        name: "stoch_rsi",
+        description: "Stochastic RSI",
+        params: &[
+            ParamSpec::period("rsi_period", "RSI period", 14.0),
+            ParamSpec::period("stoch_period", "Stochastic lookback period", 14.0),
+            ParamSpec::period("k_smooth", "%K smoothing period", 3.0),
+            ParamSpec::period("d_period", "%D smoothing period", 3.0),
+        ],
+        columns: StochRsiOutput::COLUMNS,
+        build: |p| {
+            let stream = StochRsiStream::new(whole(p[0]), whole(p[1]), whole(p[2]), whole(p[3]))?;
+            Ok(StreamAdapter::boxed("stoch_rsi", stream, close))
+        },
+    },
+    IndicatorSpec {
+        name: "atr",
+        description: "Average True Range",
+        params: PERIOD_14,
+        columns: VALUE,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "atr",
+                AtrStream::new(whole(p[0]))?,
+                hlc,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "adx",
+        description: "Average Directional Index with +DI/-DI",
+        params: PERIOD_14,
+        columns: AdxOutput::COLUMNS,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "adx",
+                AdxStream::new(whole(p[0]))?,
+                hlc,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "donchian",
+        description: "Donchian Channels with bars since the high/low",
+        params: PERIOD_20,
+        columns: DonchianOutput::COLUMNS,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "donchian",
+                DonchianStream::new(whole(p[0]))?,
+                hl,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "keltner",
+        description: "Keltner Channels",
+        params: &[
+            ParamSpec::period("period", "Middle line period", 20.0),
+            ParamSpec::period("atr_period", "ATR (or range) period", 10.0),
+            ParamSpec::multiplier("multiplier", "Band multiplier", 2.0),
+            ParamSpec {
+                name: "ma",
+                description: "Middle line average: 0 = EMA, 1 = SMA",
+                default: 0.0,
+                min: 0.0,
+                max: 1.0,
+                integer: true,
+            },
+            ParamSpec {
+                name: "band",
+                description: "Band width: 0 = ATR, 1 = high-low range",
+                default: 0.0,
+                min: 0.0,
+                max: 1.0,
+                integer: true,
+            },
+        ],
+        columns: KeltnerOutput::COLUMNS,
+        build: |p| {
+            let ma = if whole(p[3]) == 0 {
+                KeltnerMa::Ema
+            } else {
+                KeltnerMa::Sma
+            };
+            let band = if whole(p[4]) == 0 {
+                KeltnerBand::Atr
+            } else {
+                KeltnerBand::Range
+            };
+            let stream = KeltnerStream::with_options(whole(p[0]), whole(p[1]), p[2], ma, band)?;
+            Ok(StreamAdapter::boxed("keltner", stream, hlc))
+        },
+    },
+    IndicatorSpec {
+        name: "squeeze",
+        description: "TTM Squeeze (Bollinger Bands inside Keltner Channels) with momentum",
+        params: &[
+            ParamSpec::period("period", "Band, Donchian and momentum period", 20.0),
+            ParamSpec::multiplier(
+                "bb_mult",
+                "Bollinger Bands standard deviation multiplier",
+                2.0,
+            ),
+            ParamSpec::multiplier("kc_mult", "Keltner Channels ATR multiplier", 1.5),
+        ],
+        columns: SqueezeOutput::COLUMNS,
+        build: |p| {
+            let stream = SqueezeStream::new(whole(p[0]), p[1], p[2])?;
+            Ok(StreamAdapter::boxed("squeeze", stream, hlc))
+        },
+    },
+    IndicatorSpec {
+        name: "supertrend",
+        description: "Supertrend ATR trailing stop with direction and flip flag",
+        params: &[
+            ParamSpec::period("atr_period", "ATR period", 10.0),
+            ParamSpec::multiplier("multiplier", "ATR multiplier", 3.0),
+            ParamSpec {
+                name: "source",
+                description: "Band source: 0 = (H + L) / 2, 1 = (H + L + C) / 3, 2 = close",
+                default: 0.0,
+                min: 0.0,
+                max: 2.0,
+                integer: true,
+            },
+            ParamSpec {
+                name: "atr",
+                description: "ATR smoothing: 0 = Wilder, 1 = SMA",
+                default: 0.0,
+                min: 0.0,
+                max: 1.0,
+                integer: true,
+            },
+        ],
+        columns: SuperTrendOutput::COLUMNS,
+        build: |p| {
+            let source = match whole(p[2]) {
+                0 => SuperTrendSource::Hl2,
+                1 => SuperTrendSource::Hlc3,
+                _ => SuperTrendSource::Close,
+            };
+            let atr = if whole(p[3]) == 0 {
+                SuperTrendAtr::Wilder
+            } else {
+                SuperTrendAtr::Sma
+            };
+            let stream = SuperTrendStream::with_options(whole(p[0]), p[1], source, atr)?;
+            Ok(StreamAdapter::boxed("supertrend", stream, hlc))
+        },
+    },
+    IndicatorSpec {
+        name: "psar",
+        description: "Parabolic SAR with direction, acceleration factor and extreme point",
+        params: &[
+            ParamSpec::multiplier("start", "Initial acceleration factor", 0.02),
+            ParamSpec::multiplier("increment", "Acceleration factor step", 0.02),
+            ParamSpec::multiplier("maximum", "Maximum acceleration factor", 0.2),
+        ],
+        columns: PsarOutput::COLUMNS,
+        build: |p| {
+            let stream = PsarStream::new(p[0], p[1], p[2])?;
+            Ok(StreamAdapter::boxed("psar", stream, hl))
+        },
+    },
+    IndicatorSpec {
+        name: "stoch_fast",
+        description: "Fast Stochastic Oscillator",
+        params: &[
+            ParamSpec::period("k_period", "%K lookback period", 14.0),
+            ParamSpec::period("d_period", "%D smoothing period", 3.0),
+        ],
+        columns: StochOutput::COLUMNS,
+        build: |p| {
+            let stream = StochStream::new(whole(p[0]), whole(p[1]), StochType::Fast)?;
+            Ok(StreamAdapter::boxed("stoch_fast", stream, hlc))
+        },
+    },
+    IndicatorSpec {
+        name: "stoch_slow",
+        description: "Slow Stochastic Oscillator",
+        params: &[
+            ParamSpec::period("k_period", "%K lookback period", 14.0),
+            ParamSpec::period("d_period", "%D smoothing period", 3.0),
+            ParamSpec::period("slowing", "%K slowing period", 3.0),
+        ],
+        columns: StochOutput::COLUMNS,
+        build: |p| {
+            let stream = StochStream::new_with_slowing(
+                whole(p[0]),
+                whole(p[1]),
+                whole(p[2]),
+                StochType::Slow,
+            )?;
+            Ok(StreamAdapter::boxed("stoch_slow", stream, hlc))
+        },
+    },
+    IndicatorSpec {
+        name: "ichimoku",
+        description: "Ichimoku Cloud",
+        params: &[
+            ParamSpec::period("tenkan", "Tenkan-sen (conversion line) period", 9.0),
+            ParamSpec::period("kijun", "Kijun-sen (base line) period", 26.0),
+            ParamSpec::period("senkou_b", "Senkou Span B period", 52.0),
+        ],
+        columns: IchimokuOutput::COLUMNS,
+        build: |p| {
+            let stream = IchimokuStream::new(whole(p[0]), whole(p[1]), whole(p[2]))?;
+            Ok(StreamAdapter::boxed("ichimoku", stream, hlc))
+        },
+    },
+    IndicatorSpec {
+        name: "mfi",
+        description: "Money Flow Index",
+        params: PERIOD_14,
+        columns: VALUE,
+        build: |p| {
+            Ok(StreamAdapter::boxed(
+                "mfi",
+                MfiStream::new(whole(p[0]))?,
+                hlcv,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "cvd",
+        description: "Cumulative Volume Delta (OHLCV approximation)",
+        params: &[],
+        columns: VALUE,
+        build: |_| Ok(StreamAdapter::boxed("cvd", CvdOhlcvStream::new(), hlcv)),
+    },
+    IndicatorSpec {
+        name: "session_vwap",
+        description: "Session VWAP, reset each UTC day",
+        params: &[],
+        columns: VALUE,
+        build: |_| {
+            Ok(StreamAdapter::boxed(
+                "session_vwap",
+                SessionVwapStream::new(),
+                candle,
+            ))
+        },
+    },
+    IndicatorSpec {
+        name: "rolling_vwap",
+        description: "Rolling VWAP over a fixed window",
+        params: PERIOD_20,
+        columns: VALUE,
+        build: |p| {
+            let stream = RollingVwapStream::new(whole(p[0]))?;
+            Ok(StreamAdapter::boxed("rolling_vwap", stream, candle))
+        },
+    },
+    IndicatorSpec {
+        name: "anchored_vwap",
+        description: "Anchored VWAP from a timestamp",
+        params: &[ParamSpec {
+            name: "anchor",
+            description:
+                "Anchor timestamp (Unix ms); accumulation starts at the first candle at or after it",
+            default: 0.0,
+            min: 0.0,
+            max: MAX_TIMESTAMP,
+            integer: true,
+        }],
+        columns: VALUE,
+        build: |p| {
+            #[allow(clippy::cast_possible_truncation)]
+            let stream = AnchoredVwapStream::with_anchor(p[0] as i64);
+            Ok(StreamAdapter::boxed("anchored_vwap", stream, candle))
+        },
+    },
+    IndicatorSpec {
+        name: "pivot_points",
+        description: "Pivot Points from each candle's high, low, and close",
+        params: &[ParamSpec {
+            name: "variant",
+            description: "0 = Standard, 1 = Fibonacci, 2 = Woodie",
+            default: 0.0,
+            min: 0.0,
+            max: 2.0,
+            integer: true,
+        }],
+        columns: PivotPointsOutput::COLUMNS,
+        build: |p| {
+            let variant = match whole(p[0]) {
+                0 => PivotPointsVariant::Standard,
+                1 => PivotPointsVariant::Fibonacci,
+                _ => PivotPointsVariant::Woodie,
+            };
+            Ok(Box::new(PivotAdapter {
+                pivots: PivotPoints::new(variant),
+                ready: false,
+            }))
+        },
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Macd;
+    use crate::traits::Indicator;
+
+    fn params(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs.iter().map(|&(k, v)| (k.to_string(), v)).collect()
+    }
+
+    fn candles(n: i32) -> Vec<OHLCV> {
+        (0..n)
+            .map(|i| {
+                let x = f64::from(i);
+                let close = 100.0 + (x * 0.3).sin() * 4.0;
+                OHLCV::new(
+                    i64::from(i) * 60_000,
+                    close - 0.5,
+                    close + 1.0,
+                    close - 1.0,
+                    close,
+                    1000.0 + x,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_registry_builds_every_indicator_with_defaults() {
+        let data = candles(120);
+        for spec in indicators() {
+            let mut indicator = spec.create(&HashMap::new()).unwrap();
+            assert_eq!(indicator.name(), spec.name);
+            assert_eq!(indicator.columns(), spec.columns);
+
+            let columns = indicator.init(&data).unwrap();
+            assert_eq!(columns.len(), spec.columns.len(), "{}", spec.name);
+            assert!(
+                columns.iter().all(|c| c.len() == data.len()),
+                "{}",
+                spec.name
+            );
+            assert!(
+                indicator.is_ready(),
+                "{} not ready after 120 candles",
+                spec.name
+            );
+            assert_eq!(indicator.next(data[0]).len(), spec.columns.len());
+        }
+    }
+
+    #[test]
+    fn test_registry_lookback_matches_output() {
+        let data = candles(200);
+        let custom = [
+            (
+                "macd",
+                params(&[("fast", 5.0), ("slow", 13.0), ("signal", 4.0)]),
+            ),
+            ("stoch_slow", params(&[("slowing", 5.0)])),
+            ("ichimoku", params(&[("tenkan", 30.0), ("kijun", 10.0)])),
+            (
+                "keltner",
+                params(&[("period", 5.0), ("atr_period", 12.0), ("band", 1.0)]),
+            ),
+            ("supertrend", params(&[("atr_period", 7.0), ("atr", 1.0)])),
+        ];
+        let cases = indicators()
+            .iter()
+            .map(|spec| (spec.name, HashMap::new()))
+            .chain(custom);
+
+        for (name, params) in cases {
+            let mut indicator = create(name, &params).unwrap();
+            let columns = indicator.init(&data).unwrap();
+            let first_complete = (0..data.len())
+                .find(|&i| columns.iter().all(|c| !c[i].is_nan()))
+                .unwrap();
+            assert_eq!(indicator.lookback(), first_complete, "{name} {params:?}");
+            assert_eq!(indicator.warmup_len(), first_complete + 1);
+        }
+    }
+
+    #[test]
+    fn test_registry_peek_matches_next() {
+        let data = candles(120);
+
+        for spec in indicators() {
+            let mut indicator = spec.create(&HashMap::new()).unwrap();
+            for candle in &data {
+                // Probe a few hypothetical bars before committing the real one
+                for shift in [-2.5_f64, 1.5] {
+                    let mut probe = *candle;
+                    probe.high += shift.max(0.0);
+                    probe.low += shift.min(0.0);
+                    probe.close += shift;
+                    indicator.peek(&probe);
+                }
+
+                let peeked = indicator.peek(candle);
+                let actual = indicator.next(*candle);
+                for (p, a) in peeked.iter().zip(&actual) {
+                    assert!(
+                        (p.is_nan() && a.is_nan()) || (p - a).abs() < 1e-9,
+                        "{}: peek {p} vs next {a}",
+                        spec.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry_macd_matches_batch() {
+        let data = candles(80);
+        let closes: Vec<f64> = data.iter().map(|c| c.close).collect();
+        let expected = Macd::new(8, 21, 5).unwrap().calculate(&closes).unwrap();
+
+        let mut macd = create(
+            "macd",
+            &params(&[("fast", 8.0), ("slow", 21.0), ("signal", 5.0)]),
+        )
+        .unwrap();
+        let columns = macd.init(&data).unwrap();
+
+        for (i, out) in expected.iter().enumerate() {
+            for (col, value) in [out.macd, out.signal, out.histogram]
+                .into_iter()
+                .enumerate()
+            {
+                let actual = columns[col][i];
+                assert!(
+                    (value.is_nan() && actual.is_nan()) || (value - actual).abs() < 1e-10,
+                    "column {col} at {i}: {value} vs {actual}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry_heterogeneous_collection() {
+        let data = candles(60);
+        let mut set: Vec<Box<dyn DynIndicator>> = vec![
+            create("rsi", &params(&[("period", 7.0)])).unwrap(),
+            create("bbands", &params(&[("k", 2.5)])).unwrap(),
+            create("adx", &HashMap::new()).unwrap(),
+        ];
+
+        for indicator in &mut set {
+            indicator.init(&data[..59]).unwrap();
+        }
+        let widths: Vec<usize> = set.iter_mut().map(|i| i.next(data[59]).len()).collect();
+        assert_eq!(widths, vec![1, 5, 3]);
+    }
+
+    #[test]
+    fn test_registry_metadata() {
+        let spec = find("macd").unwrap();
+        let names: Vec<&str> = spec.params.iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["fast", "slow", "signal"]);
+        assert_eq!(spec.default_params().get("slow"), Some(&26.0));
+        assert!(spec.params.iter().all(|p| p.integer && p.min >= 1.0));
+
+        assert!(find("frvp").is_none());
+    }
+
+    #[test]
+    fn test_registry_rejects_invalid_params() {
+        assert!(matches!(
+            create("nope", &HashMap::new()),
+            Err(IndicatorError::InvalidParameter(_))
+        ));
+        // Unknown parameter name
+        assert!(create("sma", &params(&[("length", 10.0)])).is_err());
+        // Out of range
+        assert!(matches!(
+            create("sma", &params(&[("period", 0.0)])),
+            Err(IndicatorError::OutOfRange { name: "period", .. })
+        ));
+        assert!(create("pivot_points", &params(&[("variant", 3.0)])).is_err());
+        // Bounded so a huge period fails instead of aborting on allocation
+        assert!(matches!(
+            create("sma", &params(&[("period", 1e18)])),
+            Err(IndicatorError::OutOfRange { name: "period", .. })
+        ));
+        assert!(create("bbands", &params(&[("k", f64::INFINITY)])).is_err());
+        assert!(indicators()
+            .iter()
+            .all(|spec| spec.params.iter().all(|p| p.max.is_finite())));
+        // Not a whole number
+        assert!(create("rsi", &params(&[("period", 14.5)])).is_err());
+        // Rejected by the indicator itself
+        assert!(create("macd", &params(&[("fast", 30.0)])).is_err());
+    }
+}
@@ -0,0 +1,961 @@
+//! Timeframe resampling and multi-timeframe (MTF) indicators.
+//!
+//! [`Resampler`] aggregates lower-timeframe candles into higher-timeframe bars
+//! (minutes, hours, days, weeks or calendar months) over the trading dates
+//! of a [`Session`] calendar, UTC days by default. On top of it:
+//! - [`MtfBatch`] runs a batch indicator on the higher timeframe and maps its
+//!   values back onto the lower-timeframe candles
+//! - [`Mtf`] does the same on a stream, updating the forming higher-timeframe
+//!   bar with every lower-timeframe candle
+//!
+//! Mapped values never look ahead: a lower-timeframe candle only sees
+//! higher-timeframe bars that had closed before it opened. The forming bar's
+//! value, which changes until the bar closes, is available from the stream.
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::{Rsi, RsiStream};
+//! use ta_core::resample::{Mtf, MtfBatch, Resampler, Timeframe};
+//! use ta_core::session::Session;
+//! use ta_core::{Indicator, PriceSource, Sourced, StreamingIndicator, OHLCV};
+//!
+//! // Ten hours of 1-minute candles
+//! let candles: Vec<OHLCV> = (0..600)
+//!     .map(|i| {
+//!         let c = 100.0 + (f64::from(i) * 0.05).sin() * 4.0;
+//!         OHLCV::new(i64::from(i) * 60_000, c, c + 0.5, c - 0.5, c, 10.0)
+//!     })
+//!     .collect();
+//!
+//! // 1h RSI(3) on 1m candles
+//! let hourly = Resampler::new(Timeframe::Hours(1)).unwrap();
+//! let rsi = MtfBatch::new(Sourced::new(Rsi::new(3).unwrap(), PriceSource::Close), hourly.clone());
+//! let values = rsi.calculate(&candles).unwrap();
+//!
+//! // The first hourly RSI is complete when the fourth hour closes
+//! assert!(values[239].is_nan());
+//! assert!(!values[240].is_nan());
+//!
+//! // The stream also reports the forming hour
+//! let stream = Sourced::new(RsiStream::new(3).unwrap(), PriceSource::Close);
+//! let mut mtf = Mtf::new(stream, hourly);
+//! let forming = mtf.init(&candles).unwrap();
+//! assert!(!forming[180].is_nan());
+//! assert_eq!(mtf.closed(), Some(&values[599]));
+//!
+//! // Daily bars of New York sessions, opening at 09:30 local time
+//! let daily = Resampler::with_session(Timeframe::Days(1), Session::nyse()).unwrap();
+//! // 2024-03-11 13:30 UTC is 09:30 in New York (daylight time)
+//! assert_eq!(daily.bar_start(1_710_163_800_000), Some(1_710_163_800_000));
+//! // Pre-market candles belong to no bar
+//! assert_eq!(daily.bar_start(1_710_163_740_000), None);
+//! ```
+
+use std::str::FromStr;
+
+use crate::session::Session;
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+
+const MINUTE: i64 = 60_000;
+const HOUR: i64 = 60 * MINUTE;
+
+/// The Unix epoch was a Thursday; weeks open on Monday, 4 days later.
+const MONDAY_OFFSET: i64 = 4;
+
+/// Days since the Unix epoch of a proleptic Gregorian calendar date.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Calendar (year, month) of a day count since the Unix epoch.
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month)
+}
+
+/// Largest multiple of `interval` not above `t`.
+const fn floor_to(t: i64, interval: i64) -> i64 {
+    t - t.rem_euclid(interval)
+}
+
+/// Bar size of a higher timeframe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Timeframe {
+    /// `n`-minute bars aligned to the session open
+    Minutes(u32),
+    /// `n`-hour bars aligned to the session open
+    Hours(u32),
+    /// `n`-day bars aligned to the Unix epoch
+    Days(u32),
+    /// `n`-week bars opening on Monday
+    Weeks(u32),
+    /// `n`-month calendar bars aligned to the year (3 for quarters)
+    Months(u32),
+}
+
+impl Timeframe {
+    /// Number of units per bar.
+    #[must_use]
+    pub const fn count(self) -> u32 {
+        match self {
+            Self::Minutes(n)
+            | Self::Hours(n)
+            | Self::Days(n)
+            | Self::Weeks(n)
+            | Self::Months(n) => n,
+        }
+    }
+
+    /// Open time of the bar containing `timestamp` over the trading dates of
+    /// `session`, or `None` if `timestamp` is outside its sessions. Intraday
+    /// bars start at the session open and end at its close; longer bars
+    /// group trading dates and open with the session of their first
+    /// calendar date.
+    #[must_use]
+    pub fn bar_start(self, timestamp: i64, session: &Session) -> Option<i64> {
+        let key = self.bar_key(timestamp, session)?;
+        Some(self.key_open(key, session))
+    }
+
+    /// Key of the bar containing `timestamp`: its open time for intraday
+    /// bars, its first calendar date (days since the epoch) otherwise.
+    pub(crate) fn bar_key(self, timestamp: i64, session: &Session) -> Option<i64> {
+        let date = session.trading_date(timestamp)?;
+        let n = i64::from(self.count());
+        let key = match self {
+            Self::Minutes(_) | Self::Hours(_) => {
+                let unit = if matches!(self, Self::Minutes(_)) {
+                    MINUTE
+                } else {
+                    HOUR
+                };
+                let open = session.open_time(date);
+                open + floor_to(timestamp - open, n * unit)
+            }
+            Self::Days(_) => floor_to(date, n),
+            Self::Weeks(_) => floor_to(date - MONDAY_OFFSET, n * 7) + MONDAY_OFFSET,
+            Self::Months(_) => {
+                let (year, month) = civil_from_days(date);
+                let index = year * 12 + month - 1;
+                let index = index - index.rem_euclid(n);
+                days_from_civil(index.div_euclid(12), index.rem_euclid(12) + 1, 1)
+            }
+        };
+        Some(key)
+    }
+
+    /// Open time of the bar with `key` (see [`bar_key`](Self::bar_key)).
+    pub(crate) fn key_open(self, key: i64, session: &Session) -> i64 {
+        match self {
+            Self::Minutes(_) | Self::Hours(_) => key,
+            Self::Days(_) | Self::Weeks(_) | Self::Months(_) => session.open_time(key),
+        }
+    }
+}
+
+/// Parses `"15m"`, `"1h"`, `"4h"`, `"1d"`, `"1w"`, `"1M"` (months; also
+/// `"1mo"`). The count defaults to 1 (`"h"`, `"D"`).
+impl FromStr for Timeframe {
+    type Err = IndicatorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || IndicatorError::InvalidParameter(format!("invalid timeframe '{s}'"));
+
+        let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let (count, unit) = s.split_at(split);
+        let count = if count.is_empty() {
+            1
+        } else {
+            count.parse().map_err(|_| invalid())?
+        };
+        if count == 0 {
+            return Err(invalid());
+        }
+
+        match unit {
+            "m" | "min" => Ok(Self::Minutes(count)),
+            "h" | "H" => Ok(Self::Hours(count)),
+            "d" | "D" => Ok(Self::Days(count)),
+            "w" | "W" => Ok(Self::Weeks(count)),
+            "M" | "mo" => Ok(Self::Months(count)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// What a lower-timeframe candle did to the higher-timeframe bars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResampleUpdate {
+    /// The bar this candle closed, if it opened a new one
+    pub closed: Option<OHLCV>,
+    /// The forming bar including this candle
+    pub current: OHLCV,
+    /// Whether this candle opened `current`; feed it to streams with `next`
+    /// if so, `update` otherwise
+    pub opened: bool,
+}
+
+/// Aggregates lower-timeframe candles into higher-timeframe bars.
+///
+/// Bars are labelled with their open time. Candles are expected in time
+/// order; a candle belonging to a bar that has already closed is dropped, as
+/// is one outside the calendar's sessions.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Resampler {
+    timeframe: Timeframe,
+    session: Session,
+    // Key of the forming bar (see `Timeframe::bar_key`) and the bar
+    current: Option<(i64, OHLCV)>,
+    // `current` before the most recent candle, restored by `update`
+    prev: Option<OHLCV>,
+    // Whether the most recent candle was accepted, so `update` can amend it
+    amendable: bool,
+}
+
+impl Resampler {
+    /// Creates a resampler over UTC days.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the timeframe count is 0.
+    pub fn new(timeframe: Timeframe) -> IndicatorResult<Self> {
+        Self::with_session(timeframe, Session::utc())
+    }
+
+    /// Creates a resampler over the trading dates of `session`: intraday
+    /// bars align to each session open, and days, weeks and months group its
+    /// trading dates, so bars follow the exchange's local clock through
+    /// daylight-saving changes. Candles outside its sessions are dropped.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the timeframe count is 0.
+    pub fn with_session(timeframe: Timeframe, session: Session) -> IndicatorResult<Self> {
+        if timeframe.count() == 0 {
+            return Err(IndicatorError::InvalidParameter(
+                "timeframe must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self {
+            timeframe,
+            session,
+            current: None,
+            prev: None,
+            amendable: false,
+        })
+    }
+
+    /// Returns the target timeframe.
+    #[must_use]
+    pub const fn timeframe(&self) -> Timeframe {
+        self.timeframe
+    }
+
+    /// Returns the session calendar.
+    #[must_use]
+    pub const fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Open time of the bar containing `timestamp`, or `None` if it is
+    /// outside the calendar's sessions.
+    #[must_use]
+    pub fn bar_start(&self, timestamp: i64) -> Option<i64> {
+        self.timeframe.bar_start(timestamp, &self.session)
+    }
+
+    /// The forming bar, if any.
+    #[must_use]
+    pub fn current(&self) -> Option<&OHLCV> {
+        self.current.as_ref().map(|(_, bar)| bar)
+    }
+
+    /// Aggregate `candles` into bars, including the last, possibly incomplete
+    /// one. Does not touch this resampler's streaming state.
+    #[must_use]
+    pub fn resample(&self, candles: &[OHLCV]) -> Vec<OHLCV> {
+        let mut resampler = self.fresh();
+        let mut bars: Vec<OHLCV> = candles
+            .iter()
+            .filter_map(|&candle| resampler.push(candle)?.closed)
+            .collect();
+        bars.extend(resampler.flush());
+        bars
+    }
+
+    /// Map higher-timeframe values back onto lower-timeframe candles.
+    ///
+    /// `higher` holds one value per bar of [`resample`](Self::resample) over
+    /// candles with these `timestamps`. Each candle gets the value of the last
+    /// bar that closed before it, so nothing from its own, still forming bar
+    /// leaks in; candles in the first bar get `NaN`.
+    #[must_use]
+    pub fn align<O: IndicatorOutput + Clone>(&self, timestamps: &[i64], higher: &[O]) -> Vec<O> {
+        let mut resampler = self.fresh();
+        let mut bar = None::<usize>;
+        timestamps
+            .iter()
+            .map(|&timestamp| {
+                let probe = OHLCV::new(timestamp, 0.0, 0.0, 0.0, 0.0, 0.0);
+                if resampler.push(probe).is_some_and(|update| update.opened) {
+                    bar = Some(bar.map_or(0, |b| b + 1));
+                }
+                bar.and_then(|b| b.checked_sub(1))
+                    .and_then(|closed| higher.get(closed).cloned())
+                    .unwrap_or_else(O::nan)
+            })
+            .collect()
+    }
+
+    /// Preview what [`push`](Self::push) would do with `candle`.
+    #[must_use]
+    pub fn peek(&self, candle: OHLCV) -> Option<ResampleUpdate> {
+        self.step(candle).map(|(_, update)| update)
+    }
+
+    /// Key of the bar `candle` goes into and what it does to the bars.
+    fn step(&self, candle: OHLCV) -> Option<(i64, ResampleUpdate)> {
+        let key = self.timeframe.bar_key(candle.timestamp, &self.session)?;
+        let update = match self.current {
+            Some((current, bar)) if key == current => ResampleUpdate {
+                closed: None,
+                current: merge(bar, &candle),
+                opened: false,
+            },
+            // Belongs to a bar that has closed
+            Some((current, _)) if key < current => return None,
+            closed => ResampleUpdate {
+                closed: closed.map(|(_, bar)| bar),
+                current: OHLCV {
+                    timestamp: self.timeframe.key_open(key, &self.session),
+                    ..candle
+                },
+                opened: true,
+            },
+        };
+        Some((key, update))
+    }
+
+    /// Add a lower-timeframe candle.
+    ///
+    /// Returns `None` if the candle was dropped (see [`Resampler`]).
+    pub fn push(&mut self, candle: OHLCV) -> Option<ResampleUpdate> {
+        let step = self.step(candle);
+        self.amendable = step.is_some();
+        let (key, update) = step?;
+        self.prev = if update.opened {
+            None
+        } else {
+            self.current().copied()
+        };
+        self.current = Some((key, update.current));
+        Some(update)
+    }
+
+    /// Replace the most recent candle with a revised one (live candle update).
+    ///
+    /// The revised candle stays in the bar of the original. If nothing has
+    /// been pushed yet, or the last candle was dropped, this behaves like
+    /// [`push`](Self::push).
+    pub fn update(&mut self, candle: OHLCV) -> Option<ResampleUpdate> {
+        let Some((key, bar)) = self.current.filter(|_| self.amendable) else {
+            return self.push(candle);
+        };
+
+        let current = match self.prev {
+            Some(prev) => merge(prev, &candle),
+            None => OHLCV {
+                timestamp: bar.timestamp,
+                ..candle
+            },
+        };
+        self.current = Some((key, current));
+        Some(ResampleUpdate {
+            closed: None,
+            current,
+            opened: false,
+        })
+    }
+
+    /// Close the forming bar regardless of time, e.g. at the end of a batch.
+    pub fn flush(&mut self) -> Option<OHLCV> {
+        self.prev = None;
+        self.amendable = false;
+        self.current.take().map(|(_, bar)| bar)
+    }
+
+    /// Drop the forming bar.
+    pub fn reset(&mut self) {
+        *self = self.fresh();
+    }
+
+    fn fresh(&self) -> Self {
+        Self {
+            timeframe: self.timeframe,
+            session: self.session.clone(),
+            current: None,
+            prev: None,
+            amendable: false,
+        }
+    }
+}
+
+/// `bar` extended by `candle`.
+fn merge(bar: OHLCV, candle: &OHLCV) -> OHLCV {
+    OHLCV {
+        high: bar.high.max(candle.high),
+        low: bar.low.min(candle.low),
+        close: candle.close,
+        volume: bar.volume + candle.volume,
+        ..bar
+    }
+}
+
+// ============================================================================
+// Multi-Timeframe
+// ============================================================================
+
+/// A stream running on a higher timeframe, fed with lower-timeframe candles.
+///
+/// Each candle updates the forming higher-timeframe bar: the stream's `next`
+/// when the candle opens a bar, its `update` otherwise. `next` returns the
+/// value on the forming bar, which changes until the bar closes;
+/// [`closed`](Self::closed) returns the value of the last closed bar, which
+/// matches [`MtfBatch`].
+///
+/// [`lookback`](StreamingIndicator::lookback) counts higher-timeframe bars.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mtf<S, O> {
+    inner: S,
+    resampler: Resampler,
+    forming: Option<O>,
+    closed: Option<O>,
+}
+
+impl<S, O> Mtf<S, O> {
+    /// Runs `inner` on the bars of `resampler`.
+    #[must_use]
+    pub const fn new(inner: S, resampler: Resampler) -> Self {
+        Self {
+            inner,
+            resampler,
+            forming: None,
+            closed: None,
+        }
+    }
+
+    /// Returns the wrapped stream.
+    #[must_use]
+    pub const fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// The forming higher-timeframe bar, if any.
+    #[must_use]
+    pub fn current_bar(&self) -> Option<&OHLCV> {
+        self.resampler.current()
+    }
+
+    /// Value of the last closed higher-timeframe bar.
+    #[must_use]
+    pub const fn closed(&self) -> Option<&O> {
+        self.closed.as_ref()
+    }
+}
+
+impl<S, O> StreamingIndicator<OHLCV, O> for Mtf<S, O>
+where
+    S: StreamingIndicator<OHLCV, O> + Clone,
+    O: IndicatorOutput + Clone,
+{
+    fn init(&mut self, data: &[OHLCV]) -> IndicatorResult<Vec<O>> {
+        self.reset();
+
+        let mut results = Vec::with_capacity(data.len());
+        for &candle in data {
+            results.push(self.next(candle).unwrap_or_else(O::nan));
+        }
+        Ok(results)
+    }
+
+    fn next(&mut self, candle: OHLCV) -> Option<O> {
+        // A dropped candle leaves the forming bar as it was
+        let Some(update) = self.resampler.push(candle) else {
+            return self.forming.clone();
+        };
+
+        if update.opened {
+            self.closed = self.forming.take();
+            self.forming = self.inner.next(update.current);
+        } else {
+            self.forming = self.inner.update(update.current);
+        }
+        self.forming.clone()
+    }
+
+    fn update(&mut self, candle: OHLCV) -> Option<O> {
+        let Some(update) = self.resampler.update(candle) else {
+            return self.forming.clone();
+        };
+
+        if update.opened {
+            self.closed = self.forming.take();
+            self.forming = self.inner.next(update.current);
+        } else {
+            self.forming = self.inner.update(update.current);
+        }
+        self.forming.clone()
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<O> {
+        let Some(update) = self.resampler.peek(candle) else {
+            return self.forming.clone();
+        };
+
+        if update.opened {
+            self.inner.peek(update.current)
+        } else {
+            // Streams can only preview a new input, so revise a copy
+            self.inner.clone().update(update.current)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.resampler.reset();
+        self.forming = None;
+        self.closed = None;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+/// A batch calculator running on a higher timeframe, fed with
+/// lower-timeframe candles.
+///
+/// The candles are resampled, `inner` runs over the bars, and each candle
+/// gets the value of the last bar that closed before it (see
+/// [`Resampler::align`]). Output is aligned with the lower-timeframe candles.
+/// Candles before the calendar's time zone rules are rejected with
+/// `InvalidData`.
+///
+/// [`lookback`](Indicator::lookback) counts higher-timeframe bars.
+#[derive(Debug, Clone)]
+pub struct MtfBatch<T> {
+    inner: T,
+    resampler: Resampler,
+}
+
+impl<T> MtfBatch<T> {
+    /// Runs `inner` on the bars of `resampler`.
+    #[must_use]
+    pub const fn new(inner: T, resampler: Resampler) -> Self {
+        Self { inner, resampler }
+    }
+
+    /// Returns the wrapped calculator.
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T, O> Indicator<&[OHLCV], Vec<O>> for MtfBatch<T>
+where
+    T: for<'a> Indicator<&'a [OHLCV], Vec<O>>,
+    O: IndicatorOutput + Clone,
+{
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<Vec<O>> {
+        let session = self.resampler.session();
+        for (index, candle) in data.iter().enumerate() {
+            session.check_timestamp(index, candle.timestamp)?;
+        }
+        let bars = self.resampler.resample(data);
+        let values = self.inner.calculate(&bars)?;
+        let timestamps: Vec<i64> = data.iter().map(|c| c.timestamp).collect();
+        Ok(self.resampler.align(&timestamps, &values))
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Macd, MacdStream, PivotPoints, PivotPointsVariant, Rsi, RsiStream};
+    use crate::session::{TimeZone, Weekday};
+    use crate::source::{PriceSource, Sourced};
+
+    const DAY: i64 = 24 * HOUR;
+
+    /// `n` one-minute candles starting at `start`.
+    fn minutes(start: i64, n: i32) -> Vec<OHLCV> {
+        (0..n)
+            .map(|i| {
+                let x = f64::from(i);
+                let close = 100.0 + (x * 0.05).sin() * 4.0 + (x * 0.31).cos();
+                OHLCV::new(
+                    start + i64::from(i) * MINUTE,
+                    close - 0.2,
+                    close + 0.5,
+                    close - 0.6,
+                    close,
+                    10.0 + x % 7.0,
+                )
+            })
+            .collect()
+    }
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a.is_nan() && b.is_nan()) || (a - b).abs() < 1e-9
+    }
+
+    fn rsi_close() -> Sourced<Rsi> {
+        Sourced::new(Rsi::new(3).unwrap(), PriceSource::Close)
+    }
+
+    #[test]
+    fn test_civil_round_trip() {
+        for days in [-719_468, -1, 0, 59, 11_016, 19_782, 2_932_896] {
+            let (year, month) = civil_from_days(days);
+            let first = days_from_civil(year, month, 1);
+            assert!(first <= days && days - first < 31, "{days}");
+        }
+        // 2024-02-29
+        assert_eq!(civil_from_days(19_782), (2024, 2));
+        assert_eq!(days_from_civil(2024, 3, 1), 19_783);
+    }
+
+    #[test]
+    fn test_bar_start() {
+        // 2024-03-06 13:37:00 UTC, a Wednesday
+        let ts = 1_709_732_220_000;
+        let day = 1_709_683_200_000;
+        let utc = Session::utc();
+
+        assert_eq!(
+            Timeframe::Minutes(15).bar_start(ts, &utc),
+            Some(day + 13 * HOUR + 30 * MINUTE)
+        );
+        assert_eq!(
+            Timeframe::Hours(4).bar_start(ts, &utc),
+            Some(day + 12 * HOUR)
+        );
+        assert_eq!(Timeframe::Days(1).bar_start(ts, &utc), Some(day));
+        assert_eq!(Timeframe::Weeks(1).bar_start(ts, &utc), Some(day - 2 * DAY));
+        assert_eq!(
+            Timeframe::Months(1).bar_start(ts, &utc),
+            Some(day - 5 * DAY)
+        );
+        // Q1 opened on January 1st
+        assert_eq!(
+            Timeframe::Months(3).bar_start(ts, &utc),
+            Some(1_704_067_200_000)
+        );
+
+        // Days starting at midnight UTC+2, 22:00 UTC the evening before
+        let session = Session::new(TimeZone::fixed(2 * HOUR), (0, 0), (24, 0))
+            .unwrap()
+            .with_trading_days(&Weekday::ALL);
+        assert_eq!(
+            Timeframe::Days(1).bar_start(ts, &session),
+            Some(day - 2 * HOUR)
+        );
+        assert_eq!(
+            Timeframe::Days(1).bar_start(day + 22 * HOUR, &session),
+            Some(day + 22 * HOUR)
+        );
+        assert_eq!(
+            Timeframe::Weeks(1).bar_start(ts, &session),
+            Some(day - 2 * DAY - 2 * HOUR)
+        );
+    }
+
+    #[test]
+    fn test_bar_start_follows_session() {
+        let nyse = Session::nyse();
+        // 2024-03-06 (winter): the 09:30 EST open is 14:30 UTC
+        let winter = 1_709_683_200_000;
+        let open = winter + 14 * HOUR + 30 * MINUTE;
+        assert_eq!(Timeframe::Days(1).bar_start(open + HOUR, &nyse), Some(open));
+        assert_eq!(
+            Timeframe::Hours(1).bar_start(open + 90 * MINUTE, &nyse),
+            Some(open + HOUR)
+        );
+        // The last hourly bar ends at the 16:00 close
+        assert_eq!(
+            Timeframe::Hours(1).bar_start(open + 6 * HOUR + 29 * MINUTE, &nyse),
+            Some(open + 6 * HOUR)
+        );
+        assert_eq!(
+            Timeframe::Hours(1).bar_start(open + 6 * HOUR + 30 * MINUTE, &nyse),
+            None
+        );
+        assert_eq!(Timeframe::Days(1).bar_start(open - MINUTE, &nyse), None);
+
+        // A week later, Wednesday 2024-03-13, New York is on daylight time:
+        // the open is 13:30 UTC
+        let summer = winter + 7 * DAY + 13 * HOUR + 30 * MINUTE;
+        assert_eq!(Timeframe::Days(1).bar_start(summer, &nyse), Some(summer));
+        assert_eq!(
+            Timeframe::Minutes(30).bar_start(summer + 45 * MINUTE, &nyse),
+            Some(summer + 30 * MINUTE)
+        );
+        // The week opened on Monday 2024-03-11
+        assert_eq!(
+            Timeframe::Weeks(1).bar_start(summer + 2 * DAY, &nyse),
+            Some(summer - 2 * DAY)
+        );
+
+        // Globex weeks open with Monday's session on Sunday 17:00 CDT
+        let globex = Session::cme_globex();
+        let sunday_open = summer - 3 * DAY + 8 * HOUR + 30 * MINUTE;
+        assert_eq!(
+            Timeframe::Weeks(1).bar_start(summer + 2 * DAY, &globex),
+            Some(sunday_open)
+        );
+        assert_eq!(
+            Timeframe::Days(1).bar_start(sunday_open + 6 * HOUR, &globex),
+            Some(sunday_open)
+        );
+
+        // Batches reject candles from before the zone's rules (1970 here)
+        let daily = Resampler::with_session(Timeframe::Days(1), nyse).unwrap();
+        assert!(matches!(
+            MtfBatch::new(rsi_close(), daily).calculate(&minutes(0, 10)),
+            Err(IndicatorError::InvalidData { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_timeframe_from_str() {
+        assert_eq!("15m".parse(), Ok(Timeframe::Minutes(15)));
+        assert_eq!("4h".parse(), Ok(Timeframe::Hours(4)));
+        assert_eq!("D".parse(), Ok(Timeframe::Days(1)));
+        assert_eq!("1w".parse(), Ok(Timeframe::Weeks(1)));
+        assert_eq!("3M".parse(), Ok(Timeframe::Months(3)));
+        assert_eq!("1mo".parse(), Ok(Timeframe::Months(1)));
+        for bad in ["", "0h", "5", "1y", "h1", "-1d"] {
+            assert!(bad.parse::<Timeframe>().is_err(), "{bad}");
+        }
+        assert!(Resampler::new(Timeframe::Hours(0)).is_err());
+    }
+
+    #[test]
+    fn test_resample_ohlcv() {
+        let candles = minutes(0, 150);
+        let bars = Resampler::new(Timeframe::Hours(1))
+            .unwrap()
+            .resample(&candles);
+
+        assert_eq!(bars.len(), 3);
+        for (bar, chunk) in bars.iter().zip(candles.chunks(60)) {
+            assert_eq!(bar.timestamp, chunk[0].timestamp);
+            assert!(approx_eq(bar.open, chunk[0].open));
+            assert!(approx_eq(bar.close, chunk[chunk.len() - 1].close));
+            let high = chunk.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+            let low = chunk.iter().map(|c| c.low).fold(f64::MAX, f64::min);
+            let volume: f64 = chunk.iter().map(|c| c.volume).sum();
+            assert!(approx_eq(bar.high, high) && approx_eq(bar.low, low));
+            assert!(approx_eq(bar.volume, volume));
+        }
+    }
+
+    #[test]
+    fn test_resample_months_and_gaps() {
+        // Daily candles over 2024-01-15 .. 2024-04-23, with a week missing
+        let mut daily = minutes(0, 100);
+        for (i, candle) in daily.iter_mut().enumerate() {
+            candle.timestamp = 1_705_276_800_000 + i64::try_from(i).unwrap() * DAY;
+        }
+        daily.drain(40..47);
+
+        let monthly = Resampler::new(Timeframe::Months(1))
+            .unwrap()
+            .resample(&daily);
+        let starts: Vec<i64> = monthly.iter().map(|b| b.timestamp).collect();
+        assert_eq!(
+            starts,
+            [
+                1_704_067_200_000,
+                1_706_745_600_000,
+                1_709_251_200_000,
+                1_711_929_600_000
+            ]
+        );
+        assert!(approx_eq(monthly[0].open, daily[0].open));
+    }
+
+    #[test]
+    fn test_push_and_update() {
+        let candles = minutes(0, 70);
+        let mut resampler = Resampler::new(Timeframe::Hours(1)).unwrap();
+
+        assert!(resampler.push(candles[0]).unwrap().opened);
+        let update = resampler.push(candles[1]).unwrap();
+        assert!(!update.opened && update.closed.is_none());
+
+        // Revising the live candle replaces it in the bar
+        let mut revised = candles[1];
+        revised.high = 200.0;
+        revised.close = 150.0;
+        let update = resampler.update(revised).unwrap();
+        assert!(approx_eq(update.current.high, 200.0));
+        assert!(approx_eq(update.current.close, 150.0));
+        assert!(approx_eq(
+            update.current.volume,
+            candles[0].volume + candles[1].volume
+        ));
+        let update = resampler.update(candles[1]).unwrap();
+        assert_eq!(update.current, merge(candles[0], &candles[1]));
+
+        for &candle in &candles[2..60] {
+            resampler.push(candle);
+        }
+        let update = resampler.push(candles[60]).unwrap();
+        assert!(update.opened);
+        assert_eq!(update.closed.unwrap().timestamp, 0);
+
+        // A late candle from the closed hour is dropped
+        assert!(resampler.push(candles[59]).is_none());
+        assert_eq!(resampler.current().unwrap().timestamp, HOUR);
+    }
+
+    #[test]
+    fn test_mtf_batch_has_no_lookahead() {
+        let candles = minutes(0, 600);
+        let hourly = Resampler::new(Timeframe::Hours(1)).unwrap();
+        let bars = hourly.resample(&candles);
+        let closes: Vec<f64> = bars.iter().map(|b| b.close).collect();
+        let hourly_rsi = Rsi::new(3).unwrap().calculate(&closes).unwrap();
+
+        let mapped = MtfBatch::new(rsi_close(), hourly.clone())
+            .calculate(&candles)
+            .unwrap();
+        assert_eq!(mapped.len(), candles.len());
+        for (i, value) in mapped.iter().enumerate() {
+            // Minute i sees the hour that closed before its own hour opened
+            let expected = (i / 60).checked_sub(1).map_or(f64::NAN, |h| hourly_rsi[h]);
+            assert!(approx_eq(*value, expected), "{i}");
+        }
+
+        // Changing a future candle never changes earlier values
+        let mut altered = candles.clone();
+        altered[479].close = 500.0;
+        let remapped = MtfBatch::new(rsi_close(), hourly)
+            .calculate(&altered)
+            .unwrap();
+        for i in 0..480 {
+            assert!(approx_eq(mapped[i], remapped[i]), "{i}");
+        }
+        assert!(!approx_eq(mapped[480], remapped[480]));
+    }
+
+    #[test]
+    fn test_mtf_stream_matches_batch() {
+        // From 2024-03-05 21:30 UTC, across Globex's 22:00-23:00 UTC break
+        let candles = minutes(1_709_683_200_000 - 150 * MINUTE, 900);
+        let four_hours =
+            Resampler::with_session(Timeframe::Hours(4), Session::cme_globex()).unwrap();
+
+        let batch = MtfBatch::new(
+            Sourced::new(Macd::new(3, 6, 2).unwrap(), PriceSource::Close),
+            four_hours.clone(),
+        );
+        let expected = batch.calculate(&candles).unwrap();
+
+        let mut stream = Mtf::new(
+            Sourced::new(MacdStream::new(3, 6, 2).unwrap(), PriceSource::Close),
+            four_hours,
+        );
+        let mut reference = Sourced::new(MacdStream::new(3, 6, 2).unwrap(), PriceSource::Close);
+        let mut forming_bar = None;
+        for (candle, expected) in candles.iter().zip(&expected) {
+            let forming = stream.next(*candle);
+            let closed = stream.closed().map_or(f64::NAN, |o| o.histogram);
+            assert!(approx_eq(closed, expected.histogram));
+
+            // The forming value is the stream's value on the partial bar
+            let Some(&bar) = stream.current_bar() else {
+                assert!(forming.is_none());
+                continue;
+            };
+            let reference_value = if forming_bar == Some(bar.timestamp) {
+                reference.update(bar)
+            } else {
+                forming_bar = Some(bar.timestamp);
+                reference.next(bar)
+            };
+            assert_eq!(
+                forming.map(|o| o.histogram.to_bits()),
+                reference_value.map(|o| o.histogram.to_bits())
+            );
+        }
+    }
+
+    #[test]
+    fn test_mtf_stream_update_and_peek() {
+        let candles = minutes(0, 300);
+        let hourly = Resampler::new(Timeframe::Hours(1)).unwrap();
+        let rsi = || Sourced::new(RsiStream::new(2).unwrap(), PriceSource::Close);
+
+        let mut live = Mtf::new(rsi(), hourly.clone());
+        let mut reference = Mtf::new(rsi(), hourly);
+        for &candle in &candles {
+            let mut spike = candle;
+            spike.close += 3.0;
+            spike.high += 3.0;
+
+            let peeked = live.peek(spike);
+            assert_eq!(live.next(spike), peeked);
+            let amended = live.update(candle);
+            let expected = reference.next(candle);
+            match (amended, expected) {
+                (Some(a), Some(b)) => assert!(approx_eq(a, b)),
+                (None, None) => {}
+                other => panic!("readiness mismatch: {other:?}"),
+            }
+            assert_eq!(live.current_bar(), reference.current_bar());
+        }
+    }
+
+    #[test]
+    fn test_daily_pivots_on_minutes() {
+        // Two days of minutes: day two sees the pivots of day one
+        let candles = minutes(0, 2 * 1440);
+        let daily = Resampler::new(Timeframe::Days(1)).unwrap();
+        let bars = daily.resample(&candles);
+        let pivots = PivotPoints::new(PivotPointsVariant::Standard);
+        let per_day: Vec<_> = bars
+            .iter()
+            .map(|b| pivots.calculate_single(b.high, b.low, b.close))
+            .collect();
+
+        let timestamps: Vec<i64> = candles.iter().map(|c| c.timestamp).collect();
+        let mapped = daily.align(&timestamps, &per_day);
+        assert!(mapped[1439].pivot.is_nan());
+        assert_eq!(mapped[1440], per_day[0]);
+        assert_eq!(mapped[2879], per_day[0]);
+    }
+}
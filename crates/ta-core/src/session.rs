@@ -0,0 +1,988 @@
+//! Exchange session calendars.
+//!
+//! A [`Session`] defines when a market trades: local open and close times in
+//! a [`TimeZone`], which may differ by weekday, the weekdays it trades on,
+//! holidays and early closes. Anything that resets per session (such as
+//! [`SessionVwap`] or a [`Resampler`]) maps each timestamp to its trading
+//! date with [`Session::trading_date`].
+//!
+//! Sessions that close before they open span midnight and are labelled with
+//! the date they close on, so CME Globex's Monday session opens on Sunday at
+//! 17:00 Chicago time.
+//!
+//! Time zones use POSIX TZ rules (a standard offset plus an optional yearly
+//! daylight-saving rule), so they work offline and in WASM. The main exchange
+//! zones are built in ([`TimeZone::named`]); any other zone can be given as a
+//! POSIX TZ string. Built-in zones carry their rule changes back to a fixed
+//! date ([`TimeZone::valid_from`]; 1987 for the US zones, 1996 for the
+//! European ones). Earlier timestamps are outside every session: streams skip
+//! them and batch calculators reject them with `InvalidData`. Rules given as
+//! POSIX strings apply to all years.
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::SessionVwap;
+//! use ta_core::session::Session;
+//! use ta_core::{Indicator, OHLCV};
+//!
+//! let nyse = Session::nyse().with_holidays(&[(2024, 7, 4)]);
+//!
+//! // 2024-03-11 13:30 UTC is 09:30 in New York (daylight time)
+//! assert_eq!(nyse.trading_date(1_710_163_800_000), Some(19_793));
+//! // 2024-03-11 13:29 UTC is before the open
+//! assert_eq!(nyse.trading_date(1_710_163_740_000), None);
+//!
+//! // VWAP resets at each New York open; pre-market candles are skipped
+//! let vwap = SessionVwap::with_session(nyse);
+//! let candles = [
+//!     OHLCV::new(1_710_163_740_000, 10.0, 10.0, 10.0, 10.0, 1.0),
+//!     OHLCV::new(1_710_163_800_000, 12.0, 12.0, 12.0, 12.0, 1.0),
+//! ];
+//! let values = vwap.calculate(&candles).unwrap();
+//! assert!(values[0].is_nan());
+//! assert_eq!(values[1], 12.0);
+//! ```
+//!
+//! [`SessionVwap`]: crate::indicators::SessionVwap
+//! [`Resampler`]: crate::resample::Resampler
+
+use std::str::FromStr;
+
+use crate::resample::{civil_from_days, days_from_civil};
+use crate::types::{IndicatorError, IndicatorResult};
+
+#[cfg(feature = "serde")]
+use crate::snapshot::ensure;
+
+const MINUTE: i64 = 60_000;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+/// Built-in zones as POSIX TZ rules, oldest first, each with the UTC date
+/// (year, month, day) it took effect. The first date is as far back as the
+/// zone is covered; `None` for zones that never changed.
+type Era = (Option<(i64, i64, i64)>, &'static str);
+
+const ZONES: &[(&str, &[Era])] = &[
+    ("UTC", &[(None, "UTC0")]),
+    ("America/New_York", US_EASTERN),
+    (
+        "America/Chicago",
+        &[
+            (Some((1987, 1, 1)), "CST6CDT,M4.1.0,M10.5.0"),
+            (Some((2007, 1, 1)), "CST6CDT,M3.2.0,M11.1.0"),
+        ],
+    ),
+    (
+        "America/Los_Angeles",
+        &[
+            (Some((1987, 1, 1)), "PST8PDT,M4.1.0,M10.5.0"),
+            (Some((2007, 1, 1)), "PST8PDT,M3.2.0,M11.1.0"),
+        ],
+    ),
+    ("America/Toronto", US_EASTERN),
+    // No daylight time since February 2019
+    ("America/Sao_Paulo", &[(Some((2019, 3, 1)), "<-03>3")]),
+    (
+        "Europe/London",
+        &[(Some((1996, 1, 1)), "GMT0BST,M3.5.0/1,M10.5.0")],
+    ),
+    ("Europe/Berlin", CENTRAL_EUROPE),
+    ("Europe/Paris", CENTRAL_EUROPE),
+    ("Europe/Amsterdam", CENTRAL_EUROPE),
+    ("Europe/Zurich", CENTRAL_EUROPE),
+    ("Europe/Moscow", &[(Some((2014, 10, 26)), "MSK-3")]),
+    ("Asia/Dubai", &[(Some((1920, 1, 1)), "<+04>-4")]),
+    ("Asia/Kolkata", &[(Some((1946, 1, 1)), "IST-5:30")]),
+    ("Asia/Singapore", &[(Some((1982, 1, 1)), "<+08>-8")]),
+    ("Asia/Hong_Kong", &[(Some((1980, 1, 1)), "HKT-8")]),
+    ("Asia/Shanghai", &[(Some((1992, 1, 1)), "CST-8")]),
+    ("Asia/Seoul", &[(Some((1989, 1, 1)), "KST-9")]),
+    ("Asia/Tokyo", &[(Some((1952, 1, 1)), "JST-9")]),
+    (
+        "Australia/Sydney",
+        &[(Some((2008, 1, 1)), "AEST-10AEDT,M10.1.0,M4.1.0/3")],
+    ),
+    (
+        "Pacific/Auckland",
+        &[
+            (Some((1990, 1, 1)), "NZST-12NZDT,M10.1.0,M3.3.0/3"),
+            (Some((2007, 7, 1)), "NZST-12NZDT,M9.5.0,M4.1.0/3"),
+        ],
+    ),
+];
+
+const US_EASTERN: &[Era] = &[
+    (Some((1987, 1, 1)), "EST5EDT,M4.1.0,M10.5.0"),
+    (Some((2007, 1, 1)), "EST5EDT,M3.2.0,M11.1.0"),
+];
+
+const CENTRAL_EUROPE: &[Era] = &[(Some((1996, 1, 1)), "CET-1CEST,M3.5.0,M10.5.0/3")];
+
+/// Weekday of a day count since the Unix epoch, 0 = Sunday.
+fn weekday_from_sunday(days: i64) -> i64 {
+    (days + 4).rem_euclid(7)
+}
+
+/// A yearly transition: weekday `weekday` (0 = Sunday) of week `week` (1-4,
+/// or 5 for the last) of `month`, at `time` ms local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Transition {
+    month: i64,
+    week: i64,
+    weekday: i64,
+    time: i64,
+}
+
+impl Transition {
+    /// Local time of this transition in `year`, in ms since the epoch.
+    fn local_ms(self, year: i64) -> i64 {
+        let first = days_from_civil(year, self.month, 1);
+        let mut day =
+            first + (self.weekday - weekday_from_sunday(first)).rem_euclid(7) + (self.week - 1) * 7;
+        if self.week == 5 {
+            let next = days_from_civil(year + self.month / 12, self.month % 12 + 1, 1);
+            while day >= next {
+                day -= 7;
+            }
+        }
+        day * DAY + self.time
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Dst {
+    offset: i64,
+    start: Transition,
+    end: Transition,
+}
+
+/// A standard UTC offset and an optional yearly daylight-saving rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Rule {
+    // Offsets are ms east of UTC
+    std_offset: i64,
+    dst: Option<Dst>,
+}
+
+impl Rule {
+    fn offset_at(self, timestamp: i64) -> i64 {
+        let Some(dst) = self.dst else {
+            return self.std_offset;
+        };
+
+        let (year, _) = civil_from_days((timestamp + self.std_offset).div_euclid(DAY));
+        // Transitions happen at local time on the clock in effect before them
+        let start = dst.start.local_ms(year) - self.std_offset;
+        let end = dst.end.local_ms(year) - dst.offset;
+        let in_dst = if start < end {
+            timestamp >= start && timestamp < end
+        } else {
+            // Southern hemisphere: daylight time spans the new year
+            timestamp >= start || timestamp < end
+        };
+
+        if in_dst {
+            dst.offset
+        } else {
+            self.std_offset
+        }
+    }
+
+    /// Checks the offsets and transitions against the ranges POSIX rules
+    /// allow.
+    #[cfg(feature = "serde")]
+    fn check_state(self) -> Result<(), String> {
+        let within_week = |ms: i64| ms.abs() <= 7 * DAY;
+        ensure(within_week(self.std_offset), "UTC offset out of range")?;
+        let Some(dst) = self.dst else {
+            return Ok(());
+        };
+        ensure(within_week(dst.offset), "daylight offset out of range")?;
+        for t in [dst.start, dst.end] {
+            ensure(
+                (1..=12).contains(&t.month)
+                    && (1..=5).contains(&t.week)
+                    && (0..=6).contains(&t.weekday)
+                    && within_week(t.time),
+                "daylight-saving transition out of range",
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A time zone: a standard UTC offset and an optional daylight-saving rule,
+/// with the rule it replaced for built-in zones whose rules changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeZone {
+    rule: Rule,
+    // (timestamp `rule` took effect, rule before it)
+    previous: Option<(i64, Rule)>,
+    // Earliest timestamp the rules are known for
+    valid_from: Option<i64>,
+}
+
+impl TimeZone {
+    /// UTC.
+    #[must_use]
+    pub const fn utc() -> Self {
+        Self::fixed(0)
+    }
+
+    /// A zone `offset_ms` east of UTC all year.
+    #[must_use]
+    pub const fn fixed(offset_ms: i64) -> Self {
+        Self {
+            rule: Rule {
+                std_offset: offset_ms,
+                dst: None,
+            },
+            previous: None,
+            valid_from: None,
+        }
+    }
+
+    /// A built-in zone by IANA name, e.g. `"America/New_York"`, with its
+    /// rules back to [`valid_from`](Self::valid_from).
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the zone is not built in.
+    pub fn named(name: &str) -> IndicatorResult<Self> {
+        let (_, eras) = ZONES
+            .iter()
+            .find(|(zone, _)| *zone == name)
+            .ok_or_else(|| {
+                IndicatorError::InvalidParameter(format!("unknown time zone '{name}'"))
+            })?;
+        let since =
+            |date: Option<(i64, i64, i64)>| date.map(|(y, m, d)| days_from_civil(y, m, d) * DAY);
+
+        let (first, current) = (eras[0], eras[eras.len() - 1]);
+        let mut tz = Self::posix(current.1)?;
+        tz.valid_from = since(first.0);
+        if let [(_, older), (changed, _)] = eras[..] {
+            tz.previous = since(changed).zip(Some(Self::posix(older)?.rule));
+        }
+        Ok(tz)
+    }
+
+    /// A zone from a POSIX TZ string, e.g. `"EST5EDT,M3.2.0,M11.1.0"`.
+    /// Offsets are hours west of UTC, as POSIX has them; daylight-saving
+    /// rules must use the `Mm.w.d[/time]` form. The rules apply to all years.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if the string can't be parsed.
+    pub fn posix(rule: &str) -> IndicatorResult<Self> {
+        let invalid = || IndicatorError::InvalidParameter(format!("invalid TZ rule '{rule}'"));
+        let mut parser = Parser(rule);
+
+        parser.name().ok_or_else(invalid)?;
+        let std_offset = -parser.offset().ok_or_else(invalid)?;
+        if parser.0.is_empty() {
+            return Ok(Self::fixed(std_offset));
+        }
+
+        parser.name().ok_or_else(invalid)?;
+        let offset = if parser.0.starts_with(',') {
+            std_offset + HOUR
+        } else {
+            -parser.offset().ok_or_else(invalid)?
+        };
+        let start = parser.transition().ok_or_else(invalid)?;
+        let end = parser.transition().ok_or_else(invalid)?;
+        if !parser.0.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            rule: Rule {
+                std_offset,
+                dst: Some(Dst { offset, start, end }),
+            },
+            previous: None,
+            valid_from: None,
+        })
+    }
+
+    /// Earliest timestamp the zone's rules are known for, or `None` if they
+    /// hold for all time. Built-in zones only carry their rules back so far;
+    /// [`Session`]s treat earlier timestamps as outside every session.
+    #[must_use]
+    pub const fn valid_from(&self) -> Option<i64> {
+        self.valid_from
+    }
+
+    /// Whether `timestamp` is at or after [`valid_from`](Self::valid_from).
+    #[must_use]
+    pub fn covers(&self, timestamp: i64) -> bool {
+        self.valid_from.is_none_or(|from| timestamp >= from)
+    }
+
+    /// UTC offset in effect at `timestamp`, in ms east of UTC. Before
+    /// [`valid_from`](Self::valid_from) this is the earliest known rule's.
+    #[must_use]
+    pub fn offset_at(&self, timestamp: i64) -> i64 {
+        let rule = match self.previous {
+            Some((changed, rule)) if timestamp < changed => rule,
+            _ => self.rule,
+        };
+        rule.offset_at(timestamp)
+    }
+
+    /// Local wall-clock time at `timestamp`, in ms since the epoch.
+    #[must_use]
+    pub fn to_local(&self, timestamp: i64) -> i64 {
+        timestamp + self.offset_at(timestamp)
+    }
+
+    /// Timestamp of a local wall-clock time. Times skipped by a
+    /// daylight-saving change resolve to the instant an hour later.
+    #[must_use]
+    pub fn from_local(&self, local: i64) -> i64 {
+        let guess = local - self.rule.std_offset;
+        local - self.offset_at(guess)
+    }
+
+    /// Checks the rules of a restored zone.
+    #[cfg(feature = "serde")]
+    fn check_state(&self) -> Result<(), String> {
+        self.rule.check_state()?;
+        let Some((changed, rule)) = self.previous else {
+            return Ok(());
+        };
+        rule.check_state()?;
+        ensure(
+            self.valid_from.is_some_and(|from| from <= changed),
+            "rule change must follow the start of the zone's rules",
+        )
+    }
+}
+
+/// Parses a built-in zone name, falling back to a POSIX TZ string.
+impl FromStr for TimeZone {
+    type Err = IndicatorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::named(s).or_else(|_| Self::posix(s))
+    }
+}
+
+/// Cursor over a POSIX TZ string.
+struct Parser<'a>(&'a str);
+
+impl Parser<'_> {
+    /// Zone abbreviation: `<...>` or at least three letters.
+    fn name(&mut self) -> Option<()> {
+        if let Some(rest) = self.0.strip_prefix('<') {
+            let end = rest.find('>')?;
+            self.0 = &rest[end + 1..];
+            return Some(());
+        }
+        let end = self
+            .0
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(self.0.len());
+        if end < 3 {
+            return None;
+        }
+        self.0 = &self.0[end..];
+        Some(())
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in ms.
+    fn offset(&mut self) -> Option<i64> {
+        let sign = match self.0.as_bytes().first()? {
+            b'-' => -1,
+            b'+' => 1,
+            _ => return self.clock(),
+        };
+        self.0 = &self.0[1..];
+        Some(sign * self.clock()?)
+    }
+
+    /// `hh[:mm[:ss]]` in ms.
+    fn clock(&mut self) -> Option<i64> {
+        let mut total = 0;
+        for unit in [HOUR, MINUTE, 1000] {
+            let end = self
+                .0
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(self.0.len());
+            let value: i64 = self.0[..end].parse().ok()?;
+            total += value * unit;
+            self.0 = &self.0[end..];
+            match self.0.strip_prefix(':') {
+                Some(rest) if unit != 1000 => self.0 = rest,
+                _ => break,
+            }
+        }
+        Some(total)
+    }
+
+    /// `,Mm.w.d[/time]`.
+    fn transition(&mut self) -> Option<Transition> {
+        self.0 = self.0.strip_prefix(",M")?;
+        let mut number = |max: i64| -> Option<i64> {
+            let end = self
+                .0
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(self.0.len());
+            let value: i64 = self.0[..end].parse().ok()?;
+            self.0 = self.0[end..].strip_prefix('.').unwrap_or(&self.0[end..]);
+            (value <= max).then_some(value)
+        };
+        let month = number(12).filter(|m| *m >= 1)?;
+        let week = number(5).filter(|w| *w >= 1)?;
+        let weekday = number(6)?;
+        let time = match self.0.strip_prefix('/') {
+            Some(rest) => {
+                self.0 = rest;
+                self.offset()?
+            }
+            None => 2 * HOUR,
+        };
+        Some(Transition {
+            month,
+            week,
+            weekday,
+            time,
+        })
+    }
+}
+
+/// Day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Monday to Friday.
+    pub const WEEKDAYS: [Self; 5] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+    ];
+
+    /// Every day of the week.
+    pub const ALL: [Self; 7] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+        Self::Saturday,
+        Self::Sunday,
+    ];
+
+    /// Weekday of a day count since the Unix epoch.
+    #[must_use]
+    pub const fn from_days(days: i64) -> Self {
+        match (days + 3).rem_euclid(7) {
+            0 => Self::Monday,
+            1 => Self::Tuesday,
+            2 => Self::Wednesday,
+            3 => Self::Thursday,
+            4 => Self::Friday,
+            5 => Self::Saturday,
+            _ => Self::Sunday,
+        }
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A trading session schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Session {
+    tz: TimeZone,
+    // Local (open, close) times of day in ms per weekday of the trading
+    // date, Monday first; `close` is in (0, 24h] and at or before `open` for
+    // sessions spanning midnight
+    hours: [(i64, i64); 7],
+    // Bit per `Weekday` of the trading dates
+    days: u8,
+    // Sorted trading dates (days since the epoch) without a session
+    holidays: Vec<i64>,
+    // Trading dates closing early, sorted, with their local close time
+    early_closes: Vec<(i64, i64)>,
+}
+
+/// Local time of day in ms of (hour, minute), `None` past 24:00.
+fn time_of_day((hour, minute): (u32, u32)) -> Option<i64> {
+    let ms = i64::from(hour) * HOUR + i64::from(minute) * MINUTE;
+    (minute < 60 && ms <= DAY).then_some(ms)
+}
+
+/// Session hours in ms from (hour, minute) open and close times.
+fn session_hours(open: (u32, u32), close: (u32, u32)) -> IndicatorResult<(i64, i64)> {
+    match (time_of_day(open), time_of_day(close)) {
+        (Some(open), Some(close)) if open < DAY && close > 0 => Ok((open, close)),
+        _ => Err(IndicatorError::InvalidParameter(
+            "session times must be between 00:00 and 24:00".to_string(),
+        )),
+    }
+}
+
+impl Session {
+    /// A session from `open` to `close` local time in `tz` as (hour, minute),
+    /// trading Monday to Friday. `close` at or before `open` spans midnight;
+    /// `(24, 0)` closes at midnight.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if a time is out of range.
+    pub fn new(tz: TimeZone, open: (u32, u32), close: (u32, u32)) -> IndicatorResult<Self> {
+        Ok(Self {
+            tz,
+            hours: [session_hours(open, close)?; 7],
+            days: Weekday::WEEKDAYS.iter().fold(0, |days, d| days | d.bit()),
+            holidays: Vec::new(),
+            early_closes: Vec::new(),
+        })
+    }
+
+    /// Calendar days in UTC, every day of the week.
+    #[must_use]
+    pub const fn utc() -> Self {
+        Self {
+            tz: TimeZone::utc(),
+            hours: [(0, DAY); 7],
+            days: 0x7f,
+            holidays: Vec::new(),
+            early_closes: Vec::new(),
+        }
+    }
+
+    /// NYSE/Nasdaq regular hours: 09:30-16:00 New York time.
+    #[must_use]
+    pub fn nyse() -> Self {
+        Self::preset("America/New_York", (9, 30), (16, 0))
+    }
+
+    /// CME Globex: 17:00-16:00 Chicago time, Sunday evening to Friday.
+    #[must_use]
+    pub fn cme_globex() -> Self {
+        Self::preset("America/Chicago", (17, 0), (16, 0))
+    }
+
+    /// London Stock Exchange: 08:00-16:30 London time.
+    #[must_use]
+    pub fn lse() -> Self {
+        Self::preset("Europe/London", (8, 0), (16, 30))
+    }
+
+    /// Xetra: 09:00-17:30 Frankfurt time.
+    #[must_use]
+    pub fn xetra() -> Self {
+        Self::preset("Europe/Berlin", (9, 0), (17, 30))
+    }
+
+    /// Tokyo Stock Exchange: 09:00-15:30 Tokyo time.
+    #[must_use]
+    pub fn tse() -> Self {
+        Self::preset("Asia/Tokyo", (9, 0), (15, 30))
+    }
+
+    /// Hong Kong Exchange: 09:30-16:00 Hong Kong time.
+    #[must_use]
+    pub fn hkex() -> Self {
+        Self::preset("Asia/Hong_Kong", (9, 30), (16, 0))
+    }
+
+    fn preset(zone: &str, open: (u32, u32), close: (u32, u32)) -> Self {
+        let tz = TimeZone::named(zone).expect("built-in zone");
+        Self::new(tz, open, close).expect("valid preset")
+    }
+
+    /// Trade on `days` instead of Monday to Friday. Days are trading dates:
+    /// for sessions spanning midnight, the day the session closes.
+    #[must_use]
+    pub fn with_trading_days(mut self, days: &[Weekday]) -> Self {
+        self.days = days.iter().fold(0, |mask, d| mask | d.bit());
+        self
+    }
+
+    /// Trade `days` from `open` to `close` instead of the hours given to
+    /// [`new`](Self::new), adding them to the trading days. As there, days
+    /// are trading dates and `close` at or before `open` spans midnight, so
+    /// a Sunday-evening open belongs to Monday.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if a time is out of range.
+    pub fn with_hours(
+        mut self,
+        days: &[Weekday],
+        open: (u32, u32),
+        close: (u32, u32),
+    ) -> IndicatorResult<Self> {
+        let hours = session_hours(open, close)?;
+        for &day in days {
+            self.hours[day as usize] = hours;
+            self.days |= day.bit();
+        }
+        Ok(self)
+    }
+
+    /// Add holidays as (year, month, day) trading dates.
+    #[must_use]
+    pub fn with_holidays(mut self, dates: &[(i32, u32, u32)]) -> Self {
+        self.holidays
+            .extend(dates.iter().map(|&date| civil_days(date)));
+        self.holidays.sort_unstable();
+        self.holidays.dedup();
+        self
+    }
+
+    /// Close early at `close` local time on (year, month, day) trading dates,
+    /// e.g. 13:00 on the day after Thanksgiving. A close later than the
+    /// day's regular close has no effect.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if `close` is out of range.
+    pub fn with_early_closes(
+        mut self,
+        dates: &[(i32, u32, u32)],
+        close: (u32, u32),
+    ) -> IndicatorResult<Self> {
+        let close = time_of_day(close).filter(|&ms| ms > 0).ok_or_else(|| {
+            IndicatorError::InvalidParameter(
+                "session times must be between 00:00 and 24:00".to_string(),
+            )
+        })?;
+        let dates: Vec<i64> = dates.iter().map(|&date| civil_days(date)).collect();
+        self.early_closes.retain(|(date, _)| !dates.contains(date));
+        self.early_closes
+            .extend(dates.into_iter().map(|date| (date, close)));
+        self.early_closes.sort_unstable();
+        self.early_closes.dedup_by_key(|(date, _)| *date);
+        Ok(self)
+    }
+
+    /// Returns the session's time zone.
+    #[must_use]
+    pub const fn time_zone(&self) -> &TimeZone {
+        &self.tz
+    }
+
+    /// Local start and end of the session of trading date `date`, in ms
+    /// since the epoch.
+    fn bounds(&self, date: i64) -> (i64, i64) {
+        let (open, close) = self.hours[Weekday::from_days(date) as usize];
+        let start = if open < close {
+            date * DAY + open
+        } else {
+            (date - 1) * DAY + open
+        };
+        let close = match self.early_closes.binary_search_by_key(&date, |&(d, _)| d) {
+            Ok(i) => close.min(self.early_closes[i].1),
+            Err(_) => close,
+        };
+        (start, date * DAY + close)
+    }
+
+    /// Trading date (days since the epoch) of the session `timestamp` falls
+    /// in, or `None` if the market is closed or `timestamp` is before the
+    /// time zone's [`valid_from`](TimeZone::valid_from).
+    #[must_use]
+    pub fn trading_date(&self, timestamp: i64) -> Option<i64> {
+        if !self.tz.covers(timestamp) {
+            return None;
+        }
+        let local = self.tz.to_local(timestamp);
+        let day = local.div_euclid(DAY);
+
+        // Sessions spanning midnight belong to the next day
+        [day, day + 1].into_iter().find(|&date| {
+            let (start, end) = self.bounds(date);
+            let trades = self.days & Weekday::from_days(date).bit() != 0;
+            trades && (start..end).contains(&local) && self.holidays.binary_search(&date).is_err()
+        })
+    }
+
+    /// Whether the market is open at `timestamp`.
+    #[must_use]
+    pub fn is_open(&self, timestamp: i64) -> bool {
+        self.trading_date(timestamp).is_some()
+    }
+
+    /// Timestamp at which the session of trading date `date` opens.
+    #[must_use]
+    pub fn open_time(&self, date: i64) -> i64 {
+        self.tz.from_local(self.bounds(date).0)
+    }
+
+    /// Checks that the candle at `index` is within the time zone's rules,
+    /// for batch calculators that reject rather than skip earlier ones.
+    pub(crate) fn check_timestamp(&self, index: usize, timestamp: i64) -> IndicatorResult<()> {
+        if self.tz.covers(timestamp) {
+            Ok(())
+        } else {
+            Err(IndicatorError::InvalidData {
+                index,
+                reason: "timestamp is before the session time zone's rules".to_string(),
+            })
+        }
+    }
+
+    /// Checks the zone, hours, days, holidays and early closes of a restored
+    /// session.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_state(&self) -> Result<(), String> {
+        self.tz.check_state()?;
+        let valid = |time: i64| time > 0 && time <= DAY;
+        ensure(
+            self.hours
+                .iter()
+                .all(|&(open, close)| (0..DAY).contains(&open) && valid(close)),
+            "session times must be between 00:00 and 24:00",
+        )?;
+        ensure(self.days <= 0x7f, "trading days must be weekdays")?;
+        ensure(
+            self.holidays.windows(2).all(|w| w[0] < w[1]),
+            "holidays must be sorted",
+        )?;
+        ensure(
+            self.early_closes.windows(2).all(|w| w[0].0 < w[1].0)
+                && self.early_closes.iter().all(|&(_, close)| valid(close)),
+            "early closes must be sorted and between 00:00 and 24:00",
+        )
+    }
+}
+
+/// Days since the epoch of a (year, month, day) date.
+fn civil_days((y, m, d): (i32, u32, u32)) -> i64 {
+    days_from_civil(i64::from(y), i64::from(m), i64::from(d))
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Timestamp of a UTC date and time.
+    fn utc(y: i64, m: i64, d: i64, hour: i64, minute: i64) -> i64 {
+        days_from_civil(y, m, d) * DAY + hour * HOUR + minute * MINUTE
+    }
+
+    #[test]
+    fn test_dst_transitions() {
+        let ny = TimeZone::named("America/New_York").unwrap();
+        // 2024: DST from March 10 07:00 UTC to November 3 06:00 UTC
+        assert_eq!(ny.offset_at(utc(2024, 3, 10, 6, 59)), -5 * HOUR);
+        assert_eq!(ny.offset_at(utc(2024, 3, 10, 7, 0)), -4 * HOUR);
+        assert_eq!(ny.offset_at(utc(2024, 11, 3, 5, 59)), -4 * HOUR);
+        assert_eq!(ny.offset_at(utc(2024, 11, 3, 6, 0)), -5 * HOUR);
+
+        // Europe switches at 01:00 UTC on the last Sundays of March and October
+        let berlin: TimeZone = "Europe/Berlin".parse().unwrap();
+        assert_eq!(berlin.offset_at(utc(2024, 3, 31, 0, 59)), HOUR);
+        assert_eq!(berlin.offset_at(utc(2024, 3, 31, 1, 0)), 2 * HOUR);
+        assert_eq!(berlin.offset_at(utc(2024, 10, 27, 1, 0)), HOUR);
+        let london = TimeZone::named("Europe/London").unwrap();
+        assert_eq!(london.offset_at(utc(2024, 7, 1, 12, 0)), HOUR);
+        assert_eq!(london.offset_at(utc(2024, 12, 1, 12, 0)), 0);
+
+        // Southern hemisphere: daylight time over the new year
+        let sydney = TimeZone::named("Australia/Sydney").unwrap();
+        assert_eq!(sydney.offset_at(utc(2024, 1, 15, 0, 0)), 11 * HOUR);
+        assert_eq!(sydney.offset_at(utc(2024, 6, 15, 0, 0)), 10 * HOUR);
+        assert_eq!(sydney.offset_at(utc(2024, 4, 6, 15, 59)), 11 * HOUR);
+        assert_eq!(sydney.offset_at(utc(2024, 4, 6, 16, 0)), 10 * HOUR);
+
+        let kolkata = TimeZone::named("Asia/Kolkata").unwrap();
+        assert_eq!(
+            kolkata.offset_at(utc(2024, 6, 1, 0, 0)),
+            5 * HOUR + 30 * MINUTE
+        );
+    }
+
+    #[test]
+    fn test_dated_rules() {
+        let ny = TimeZone::named("America/New_York").unwrap();
+        // 2006: DST from the first Sunday of April to the last of October
+        assert_eq!(ny.offset_at(utc(2006, 3, 15, 12, 0)), -5 * HOUR);
+        assert_eq!(ny.offset_at(utc(2006, 4, 2, 6, 59)), -5 * HOUR);
+        assert_eq!(ny.offset_at(utc(2006, 4, 2, 7, 0)), -4 * HOUR);
+        assert_eq!(ny.offset_at(utc(2006, 10, 29, 5, 59)), -4 * HOUR);
+        assert_eq!(ny.offset_at(utc(2006, 11, 1, 12, 0)), -5 * HOUR);
+        // 2007 onwards: the second Sunday of March
+        assert_eq!(ny.offset_at(utc(2007, 3, 15, 12, 0)), -4 * HOUR);
+
+        // New Zealand moved to September-April in the 2007 season
+        let auckland = TimeZone::named("Pacific/Auckland").unwrap();
+        assert_eq!(auckland.offset_at(utc(2007, 3, 20, 0, 0)), 12 * HOUR);
+        assert_eq!(auckland.offset_at(utc(2007, 10, 1, 0, 0)), 13 * HOUR);
+        assert_eq!(auckland.offset_at(utc(2008, 4, 1, 0, 0)), 13 * HOUR);
+
+        // Timestamps before a zone's rules fall outside every session
+        assert_eq!(ny.valid_from(), Some(utc(1987, 1, 1, 0, 0)));
+        assert!(!ny.covers(utc(1986, 12, 31, 23, 59)));
+        assert_eq!(TimeZone::utc().valid_from(), None);
+        assert_eq!(
+            TimeZone::posix("EST5EDT,M3.2.0,M11.1.0")
+                .unwrap()
+                .valid_from(),
+            None
+        );
+        let nyse = Session::nyse();
+        assert!(nyse.is_open(utc(1987, 1, 2, 15, 0)));
+        assert!(!nyse.is_open(utc(1986, 12, 31, 15, 0)));
+        assert_eq!(
+            nyse.check_timestamp(3, utc(1986, 12, 31, 15, 0)),
+            Err(IndicatorError::InvalidData {
+                index: 3,
+                reason: "timestamp is before the session time zone's rules".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_posix_parsing() {
+        assert_eq!(TimeZone::posix("UTC0"), Ok(TimeZone::utc()));
+        assert_eq!(TimeZone::posix("<+08>-8"), Ok(TimeZone::fixed(8 * HOUR)));
+        assert_eq!(
+            TimeZone::posix("EST5EDT,M3.2.0/2:00:00,M11.1.0/02"),
+            TimeZone::posix("EST5EDT,M3.2.0,M11.1.0")
+        );
+        // Explicit daylight offset
+        let half = TimeZone::posix("LHST-10:30LHDT-11,M10.1.0,M4.1.0").unwrap();
+        assert_eq!(half.offset_at(utc(2024, 1, 1, 0, 0)), 11 * HOUR);
+
+        for bad in [
+            "",
+            "EST",
+            "E5",
+            "EST5EDT,M13.1.0,M11.1.0",
+            "EST5EDT,M3.2.0",
+            "Mars/Olympus",
+        ] {
+            assert!(bad.parse::<TimeZone>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_local_round_trip() {
+        let chicago = TimeZone::named("America/Chicago").unwrap();
+        for ts in [utc(2024, 1, 2, 23, 0), utc(2024, 7, 2, 22, 0)] {
+            assert_eq!(chicago.from_local(chicago.to_local(ts)), ts);
+        }
+    }
+
+    #[test]
+    fn test_nyse_session() {
+        let nyse = Session::nyse();
+        // Winter: 09:30 EST = 14:30 UTC; summer: 13:30 UTC
+        let jan = days_from_civil(2024, 1, 3);
+        assert_eq!(nyse.trading_date(utc(2024, 1, 3, 14, 29)), None);
+        assert_eq!(nyse.trading_date(utc(2024, 1, 3, 14, 30)), Some(jan));
+        assert_eq!(nyse.trading_date(utc(2024, 1, 3, 20, 59)), Some(jan));
+        assert_eq!(nyse.trading_date(utc(2024, 1, 3, 21, 0)), None);
+        assert!(nyse.is_open(utc(2024, 7, 3, 13, 30)));
+        assert_eq!(nyse.open_time(jan), utc(2024, 1, 3, 14, 30));
+
+        // Weekends and holidays
+        assert!(!nyse.is_open(utc(2024, 1, 6, 15, 0)));
+        let closed = nyse.with_holidays(&[(2024, 7, 4)]);
+        assert!(!closed.is_open(utc(2024, 7, 4, 15, 0)));
+        assert!(closed.is_open(utc(2024, 7, 5, 15, 0)));
+    }
+
+    #[test]
+    fn test_weekly_hours_and_early_closes() {
+        // Shorter Fridays, early close the day after Thanksgiving
+        let session = Session::nyse()
+            .with_hours(&[Weekday::Friday], (9, 30), (13, 0))
+            .unwrap()
+            .with_early_closes(&[(2024, 11, 29), (2024, 11, 27)], (13, 0))
+            .unwrap();
+        let wednesday = days_from_civil(2024, 11, 27);
+        assert!(session.is_open(utc(2024, 11, 27, 17, 59)));
+        assert!(!session.is_open(utc(2024, 11, 27, 18, 0)));
+        assert!(session.is_open(utc(2024, 11, 26, 20, 59)));
+        assert_eq!(session.open_time(wednesday), utc(2024, 11, 27, 14, 30));
+        assert!(!session.is_open(utc(2024, 12, 6, 18, 0)));
+        assert!(session.is_open(utc(2024, 12, 5, 20, 0)));
+
+        // A later call replaces a date's early close; one after the regular
+        // close changes nothing
+        let session = session
+            .with_early_closes(&[(2024, 11, 27)], (17, 0))
+            .unwrap();
+        assert!(session.is_open(utc(2024, 11, 27, 20, 59)));
+        assert!(!session.is_open(utc(2024, 11, 27, 21, 0)));
+
+        // Adding hours for a weekday makes it trade: a Sunday session that
+        // opens on Saturday evening
+        let weekend = Session::utc()
+            .with_trading_days(&Weekday::WEEKDAYS)
+            .with_hours(&[Weekday::Sunday], (20, 0), (4, 0))
+            .unwrap();
+        let sunday = days_from_civil(2024, 1, 7);
+        assert_eq!(weekend.trading_date(utc(2024, 1, 6, 21, 0)), Some(sunday));
+        assert_eq!(weekend.trading_date(utc(2024, 1, 7, 3, 59)), Some(sunday));
+        assert_eq!(weekend.trading_date(utc(2024, 1, 7, 4, 0)), None);
+        assert_eq!(weekend.open_time(sunday), utc(2024, 1, 6, 20, 0));
+        assert_eq!(
+            weekend.trading_date(utc(2024, 1, 8, 0, 0)),
+            Some(sunday + 1)
+        );
+
+        assert!(Session::utc()
+            .with_hours(&[Weekday::Monday], (9, 60), (16, 0))
+            .is_err());
+        assert!(Session::utc()
+            .with_early_closes(&[(2024, 1, 2)], (0, 0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_overnight_session() {
+        let globex = Session::cme_globex();
+        // Sunday 17:00 CST opens Monday's session
+        let monday = days_from_civil(2024, 1, 8);
+        assert_eq!(globex.trading_date(utc(2024, 1, 7, 23, 0)), Some(monday));
+        assert_eq!(globex.open_time(monday), utc(2024, 1, 7, 23, 0));
+        assert_eq!(globex.trading_date(utc(2024, 1, 8, 21, 59)), Some(monday));
+        // Daily maintenance break 16:00-17:00 CT
+        assert_eq!(globex.trading_date(utc(2024, 1, 8, 22, 30)), None);
+        assert_eq!(
+            globex.trading_date(utc(2024, 1, 8, 23, 0)),
+            Some(monday + 1)
+        );
+        // Friday evening opens Saturday's session, which doesn't trade
+        assert_eq!(globex.trading_date(utc(2024, 1, 12, 23, 0)), None);
+        // Summer: the open moves to 22:00 UTC
+        assert!(globex.is_open(utc(2024, 7, 8, 22, 0)));
+
+        let utc_days = Session::utc();
+        assert_eq!(
+            utc_days.trading_date(utc(2024, 1, 6, 0, 0)),
+            Some(days_from_civil(2024, 1, 6))
+        );
+        assert_eq!(utc_days.trading_date(-1), Some(-1));
+        assert!(Session::new(TimeZone::utc(), (25, 0), (16, 0)).is_err());
+        assert!(Session::new(TimeZone::utc(), (9, 0), (0, 0)).is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! Snapshot/restore of streaming indicator state.
+//!
+//! Every `*Stream` type implements [`Snapshot`] when the `serde` feature is
+//! enabled. The feature is opt-in, not part of the default `wasm` feature,
+//! since it adds the serde and bincode dependencies; the npm package is
+//! built with it. A snapshot captures the complete internal state, so a restored
+//! stream produces bit-identical output to the original - workers can persist
+//! their streams on shutdown instead of re-running `init()` over history.
+//!
+//! Two forms are supported:
+//! - **serde**: [`Snapshot::snapshot`] returns a [`StreamSnapshot`] that can be
+//!   written with any serde format and read back with [`Snapshot::restore`]
+//! - **binary**: [`Snapshot::to_bytes`] / [`Snapshot::from_bytes`] use a compact
+//!   little-endian encoding (floats stored as raw IEEE 754 bits)
+//!
+//! Both forms carry [`SNAPSHOT_VERSION`] and the stream type name, and restoring
+//! rejects snapshots from another format version or another stream type.
+//! Restored state is also checked with [`Snapshot::validate`], so a corrupt or
+//! hand-edited snapshot is rejected instead of panicking on the next bar.
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::RsiStream;
+//! use ta_core::{Snapshot, StreamingIndicator};
+//!
+//! let mut rsi = RsiStream::new(14).unwrap();
+//! rsi.init(&[44.0, 44.5, 43.8, 44.2, 45.1, 45.6, 45.2, 46.0]).unwrap();
+//!
+//! let bytes = rsi.to_bytes().unwrap();
+//! let mut restored = RsiStream::from_bytes(&bytes).unwrap();
+//!
+//! assert_eq!(rsi.next(46.4), restored.next(46.4));
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::indicators::{
+    AdxStream, AnchoredVwapStream, AtrStream, BBandsStream, CvdOhlcvStream, CvdStream,
+    DonchianStream, EmaStream, FrvpStream, HmaStream, IchimokuStream, KeltnerStream, LinRegStream,
+    MacdStream, MfiStream, PsarStream, RollingVwapStream, RsiStream, SessionVolumeProfileStream,
+    SessionVwapStream, SmaStream, SqueezeStream, StochRsiStream, StochStream, SuperTrendStream,
+    WmaStream,
+};
+use crate::types::{IndicatorError, IndicatorResult};
+
+/// Current snapshot format version.
+///
+/// Bumped whenever the state layout of any stream changes.
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// Versioned envelope around a stream's state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamSnapshot<T> {
+    /// Snapshot format version ([`SNAPSHOT_VERSION`] when written)
+    pub version: u16,
+    /// Stream type name (e.g. `"MacdStream"`)
+    pub kind: String,
+    /// Complete internal state of the stream
+    pub state: T,
+}
+
+/// Save and restore the complete state of a streaming indicator.
+pub trait Snapshot: Serialize + DeserializeOwned {
+    /// Stream type name recorded in every snapshot.
+    const KIND: &'static str;
+
+    /// Check the invariants of the state (buffer sizes, ring positions,
+    /// counters, deque indices) that `next`, `update` and `peek` rely on.
+    ///
+    /// # Errors
+    /// Returns `InvalidSnapshot` naming the first invariant that fails.
+    fn validate(&self) -> IndicatorResult<()>;
+
+    /// Capture the current state in a versioned envelope for serde serialization.
+    fn snapshot(&self) -> StreamSnapshot<&Self> {
+        StreamSnapshot {
+            version: SNAPSHOT_VERSION,
+            kind: Self::KIND.to_string(),
+            state: self,
+        }
+    }
+
+    /// Rebuild a stream from a snapshot produced by [`snapshot`](Self::snapshot).
+    ///
+    /// # Errors
+    /// Returns `InvalidSnapshot` if the version or stream type does not match,
+    /// or the state fails [`validate`](Self::validate).
+    fn restore(snapshot: StreamSnapshot<Self>) -> IndicatorResult<Self> {
+        check_header::<Self>(snapshot.version, &snapshot.kind)?;
+        snapshot.state.validate()?;
+        Ok(snapshot.state)
+    }
+
+    /// Encode the current state in the compact binary form.
+    ///
+    /// # Errors
+    /// Returns `InvalidSnapshot` if the state cannot be encoded.
+    fn to_bytes(&self) -> IndicatorResult<Vec<u8>> {
+        bincode::serialize(&self.snapshot()).map_err(invalid)
+    }
+
+    /// Rebuild a stream from bytes produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    /// Returns `InvalidSnapshot` if the bytes are truncated or corrupt, the
+    /// version or stream type does not match, or the state fails
+    /// [`validate`](Self::validate).
+    fn from_bytes(bytes: &[u8]) -> IndicatorResult<Self> {
+        // Read the header first so a mismatch is reported as such rather than
+        // as a decoding failure of the state
+        let (version, kind): (u16, String) = bincode::deserialize(bytes).map_err(invalid)?;
+        check_header::<Self>(version, &kind)?;
+
+        let snapshot: StreamSnapshot<Self> = bincode::deserialize(bytes).map_err(invalid)?;
+        snapshot.state.validate()?;
+        Ok(snapshot.state)
+    }
+}
+
+fn check_header<S: Snapshot>(version: u16, kind: &str) -> IndicatorResult<()> {
+    if version != SNAPSHOT_VERSION {
+        return Err(IndicatorError::InvalidSnapshot(format!(
+            "unsupported version {version} (expected {SNAPSHOT_VERSION})"
+        )));
+    }
+    if kind != S::KIND {
+        return Err(IndicatorError::InvalidSnapshot(format!(
+            "snapshot is for {kind}, not {}",
+            S::KIND
+        )));
+    }
+    Ok(())
+}
+
+/// Fails with `what` unless `ok`. Building block of the streams'
+/// `check_state`, whose messages become [`Snapshot::validate`] errors.
+pub(crate) fn ensure(ok: bool, what: &str) -> Result<(), String> {
+    if ok {
+        Ok(())
+    } else {
+        Err(what.to_string())
+    }
+}
+
+/// Checks a ring buffer of `len` slots written at `head` for a window of
+/// `period` values.
+pub(crate) fn check_ring(period: usize, len: usize, head: usize) -> Result<(), String> {
+    ensure(period > 0, "period must be greater than 0")?;
+    ensure(len == period, "buffer length must equal the period")?;
+    ensure(head < period, "buffer head must be less than the period")
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn invalid(err: bincode::Error) -> IndicatorError {
+    IndicatorError::InvalidSnapshot(err.to_string())
+}
+
+macro_rules! impl_snapshot {
+    // Streams whose state any values of the right types make valid
+    (stateless: $($stream:ident),* $(,)?) => {
+        $(
+            impl Snapshot for $stream {
+                const KIND: &'static str = stringify!($stream);
+
+                fn validate(&self) -> IndicatorResult<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+    ($($stream:ident),* $(,)?) => {
+        $(
+            impl Snapshot for $stream {
+                const KIND: &'static str = stringify!($stream);
+
+                fn validate(&self) -> IndicatorResult<()> {
+                    self.check_state().map_err(|what| {
+                        IndicatorError::InvalidSnapshot(format!("corrupt {} state: {what}", Self::KIND))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot!(
+    AdxStream,
+    AtrStream,
+    BBandsStream,
+    DonchianStream,
+    EmaStream,
+    FrvpStream,
+    HmaStream,
+    IchimokuStream,
+    KeltnerStream,
+    LinRegStream,
+    MacdStream,
+    MfiStream,
+    PsarStream,
+    RollingVwapStream,
+    RsiStream,
+    SessionVolumeProfileStream,
+    SessionVwapStream,
+    SmaStream,
+    SqueezeStream,
+    StochRsiStream,
+    StochStream,
+    SuperTrendStream,
+    WmaStream,
+);
+
+impl_snapshot!(stateless: AnchoredVwapStream, CvdOhlcvStream, CvdStream);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        KeltnerBand, KeltnerMa, ProfilePeriod, StochType, SuperTrendAtr, SuperTrendSource,
+    };
+    use crate::traits::StreamingIndicator;
+    use crate::types::OHLCV;
+    use std::fmt::Debug;
+
+    const DAY_MS: i64 = 86_400_000;
+
+    fn candles(n: i32) -> Vec<OHLCV> {
+        (0..n)
+            .map(|i| {
+                let x = f64::from(i);
+                let close = 100.0 + (x * 0.37).sin() * 5.0 + x * 0.05;
+                let open = close - (x * 0.91).cos();
+                let high = open.max(close) + 0.5 + (x * 0.13).sin().abs();
+                let low = open.min(close) - 0.5 - (x * 0.29).cos().abs();
+                let volume = 1000.0 + (x * 0.53).sin() * 400.0;
+                // Four candles per day so session boundaries are crossed
+                OHLCV::new(i64::from(i) * DAY_MS / 4, open, high, low, close, volume)
+            })
+            .collect()
+    }
+
+    /// Snapshot a stream mid-history, then check the restored copy matches the
+    /// original on every following `next` and `update`, down to the state bytes.
+    fn assert_round_trip<S, I, O>(mut stream: S, data: &[I])
+    where
+        S: Snapshot + StreamingIndicator<I, O>,
+        I: Copy,
+        O: Debug,
+    {
+        let (history, live) = data.split_at(data.len() / 2);
+        stream.init(history).unwrap();
+
+        let bytes = stream.to_bytes().unwrap();
+        let mut restored = S::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.to_bytes().unwrap(), bytes);
+        assert_eq!(restored.is_ready(), stream.is_ready());
+
+        for &value in live {
+            // Debug formatting of f64 round-trips exactly
+            let expected = format!("{:?}", stream.next(value));
+            assert_eq!(format!("{:?}", restored.next(value)), expected);
+            let expected = format!("{:?}", stream.update(value));
+            assert_eq!(format!("{:?}", restored.update(value)), expected);
+        }
+        assert_eq!(restored.to_bytes().unwrap(), stream.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_price_streams() {
+        let bars = candles(120);
+        let closes: Vec<f64> = bars.iter().map(|c| c.close).collect();
+
+        assert_round_trip(SmaStream::new(10).unwrap(), &closes);
+        assert_round_trip(EmaStream::new(10).unwrap(), &closes);
+        assert_round_trip(WmaStream::new(10).unwrap(), &closes);
+        assert_round_trip(RsiStream::new(14).unwrap(), &closes);
+        assert_round_trip(HmaStream::new(9).unwrap(), &closes);
+        assert_round_trip(MacdStream::new(12, 26, 9).unwrap(), &closes);
+        assert_round_trip(BBandsStream::new(20, 2.0).unwrap(), &closes);
+        assert_round_trip(LinRegStream::new(14, 2.0).unwrap(), &closes);
+        assert_round_trip(StochRsiStream::new(14, 14, 3, 3).unwrap(), &closes);
+        assert_round_trip(CvdStream::new(), &closes);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_bar_streams() {
+        let bars = candles(120);
+        let hl: Vec<(f64, f64)> = bars.iter().map(|c| (c.high, c.low)).collect();
+        let hlc: Vec<(f64, f64, f64)> = bars.iter().map(|c| (c.high, c.low, c.close)).collect();
+        let hlcv: Vec<(f64, f64, f64, f64)> = bars
+            .iter()
+            .map(|c| (c.high, c.low, c.close, c.volume))
+            .collect();
+
+        assert_round_trip(AtrStream::new(14).unwrap(), &hlc);
+        assert_round_trip(AdxStream::new(14).unwrap(), &hlc);
+        assert_round_trip(StochStream::new(14, 3, StochType::Fast).unwrap(), &hlc);
+        assert_round_trip(
+            StochStream::new_with_slowing(14, 3, 3, StochType::Slow).unwrap(),
+            &hlc,
+        );
+        assert_round_trip(IchimokuStream::new(9, 26, 52).unwrap(), &hlc);
+        assert_round_trip(DonchianStream::new(20).unwrap(), &hl);
+        assert_round_trip(PsarStream::new(0.02, 0.02, 0.2).unwrap(), &hl);
+        assert_round_trip(KeltnerStream::new(20, 10, 2.0).unwrap(), &hlc);
+        assert_round_trip(
+            KeltnerStream::with_options(20, 10, 1.5, KeltnerMa::Sma, KeltnerBand::Range).unwrap(),
+            &hlc,
+        );
+        assert_round_trip(SqueezeStream::new(20, 2.0, 1.5).unwrap(), &hlc);
+        assert_round_trip(SuperTrendStream::new(10, 3.0).unwrap(), &hlc);
+        assert_round_trip(
+            SuperTrendStream::with_options(7, 2.0, SuperTrendSource::Close, SuperTrendAtr::Sma)
+                .unwrap(),
+            &hlc,
+        );
+        assert_round_trip(MfiStream::new(14).unwrap(), &hlcv);
+        assert_round_trip(CvdOhlcvStream::new(), &hlcv);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_candle_streams() {
+        let bars = candles(120);
+
+        assert_round_trip(SessionVwapStream::new(), &bars);
+        assert_round_trip(RollingVwapStream::new(20).unwrap(), &bars);
+        assert_round_trip(AnchoredVwapStream::with_anchor(bars[10].timestamp), &bars);
+        assert_round_trip(FrvpStream::new(24).unwrap(), &bars);
+        assert_round_trip(
+            SessionVolumeProfileStream::new(ProfilePeriod::default(), 24).unwrap(),
+            &bars,
+        );
+    }
+
+    #[test]
+    fn test_snapshot_restore_from_envelope() {
+        let mut sma = SmaStream::new(3).unwrap();
+        sma.init(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+
+        let snapshot = sma.snapshot();
+        assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        assert_eq!(snapshot.kind, "SmaStream");
+
+        let owned = StreamSnapshot {
+            version: snapshot.version,
+            kind: snapshot.kind,
+            state: sma.clone(),
+        };
+        let mut restored = SmaStream::restore(owned).unwrap();
+        assert_eq!(restored.next(5.0), sma.next(5.0));
+    }
+
+    #[test]
+    fn test_snapshot_rejects_mismatches() {
+        let mut ema = EmaStream::new(5).unwrap();
+        ema.init(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let bytes = ema.to_bytes().unwrap();
+
+        // Wrong stream type
+        assert!(matches!(
+            SmaStream::from_bytes(&bytes),
+            Err(IndicatorError::InvalidSnapshot(_))
+        ));
+
+        // Truncated state
+        assert!(matches!(
+            EmaStream::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(IndicatorError::InvalidSnapshot(_))
+        ));
+
+        // Unknown format version (stored little-endian at the start)
+        let mut future = bytes.clone();
+        future[0] = future[0].wrapping_add(1);
+        let err = EmaStream::from_bytes(&future).unwrap_err();
+        assert!(err.to_string().contains("unsupported version"));
+
+        let envelope = StreamSnapshot {
+            version: SNAPSHOT_VERSION,
+            kind: "WmaStream".to_string(),
+            state: ema,
+        };
+        assert!(EmaStream::restore(envelope).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_rejects_corrupt_state() {
+        let mut sma = SmaStream::new(3).unwrap();
+        sma.init(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        let mut bytes = sma.to_bytes().unwrap();
+
+        // Ring head past the buffer; it is followed by five 8-byte fields
+        let head = bytes.len() - 48;
+        bytes[head..head + 8].copy_from_slice(&200u64.to_le_bytes());
+        let err = SmaStream::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(err, IndicatorError::InvalidSnapshot(_)));
+        assert!(err.to_string().contains("buffer head"));
+
+        // Deque entries ahead of the stream's bar index, its last field
+        let mut donchian = DonchianStream::new(3).unwrap();
+        donchian
+            .init(&[(2.0, 1.0), (3.0, 2.0), (4.0, 3.0)])
+            .unwrap();
+        let mut bytes = donchian.to_bytes().unwrap();
+        let index = bytes.len() - 8;
+        bytes[index..].copy_from_slice(&1u64.to_le_bytes());
+        assert!(matches!(
+            DonchianStream::from_bytes(&bytes),
+            Err(IndicatorError::InvalidSnapshot(_))
+        ));
+    }
+}
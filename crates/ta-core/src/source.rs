@@ -0,0 +1,381 @@
+//! Price source selection for single-series indicators fed with OHLCV.
+//!
+//! Single-series indicators (SMA, RSI, MACD, ...) take plain `f64` prices.
+//! [`Sourced`] wraps any of them so it accepts [`OHLCV`] candles instead,
+//! deriving each price with a [`PriceSource`].
+//!
+//! # Example
+//! ```
+//! use ta_core::indicators::{Rsi, RsiStream};
+//! use ta_core::{Indicator, PriceSource, Sourced, StreamingIndicator, OHLCV};
+//!
+//! let candles: Vec<OHLCV> = (0..30)
+//!     .map(|i| {
+//!         let c = 100.0 + f64::from(i % 7);
+//!         OHLCV::new(i64::from(i), c - 0.5, c + 1.0, c - 1.0, c, 1000.0)
+//!     })
+//!     .collect();
+//!
+//! // RSI of typical price, batch and streaming
+//! let rsi = Sourced::new(Rsi::new(14).unwrap(), PriceSource::Hlc3);
+//! let batch = rsi.calculate(&candles).unwrap();
+//!
+//! let mut stream = Sourced::new(RsiStream::new(14).unwrap(), PriceSource::Hlc3);
+//! let streamed = stream.init(&candles).unwrap();
+//! assert!((batch[29] - streamed[29]).abs() < 1e-10);
+//! ```
+
+use std::str::FromStr;
+
+use crate::traits::{Indicator, StreamingIndicator};
+use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+
+/// Which price to take from each candle.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PriceSource {
+    /// Opening price
+    Open,
+    /// Highest price
+    High,
+    /// Lowest price
+    Low,
+    /// Closing price (default)
+    #[default]
+    Close,
+    /// Median price: (high + low) / 2
+    Hl2,
+    /// Typical price: (high + low + close) / 3
+    Hlc3,
+    /// Average price: (open + high + low + close) / 4
+    Ohlc4,
+    /// Weighted close: (high + low + 2 × close) / 4
+    Hlcc4,
+    /// Custom weights, normalized by their sum
+    Weighted {
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+    },
+}
+
+impl PriceSource {
+    /// Creates a custom weighted source.
+    ///
+    /// # Errors
+    /// Returns `InvalidParameter` if any weight is negative or not finite, or
+    /// all weights are zero.
+    pub fn weighted(open: f64, high: f64, low: f64, close: f64) -> IndicatorResult<Self> {
+        let weights = [open, high, low, close];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(IndicatorError::InvalidParameter(
+                "weights must be non-negative finite numbers".to_string(),
+            ));
+        }
+        if weights.iter().sum::<f64>() <= 0.0 {
+            return Err(IndicatorError::InvalidParameter(
+                "at least one weight must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self::Weighted {
+            open,
+            high,
+            low,
+            close,
+        })
+    }
+
+    /// Derives the price from a candle.
+    #[inline]
+    #[must_use]
+    pub fn price(&self, candle: &OHLCV) -> f64 {
+        match *self {
+            Self::Open => candle.open,
+            Self::High => candle.high,
+            Self::Low => candle.low,
+            Self::Close => candle.close,
+            Self::Hl2 => candle.median_price(),
+            Self::Hlc3 => candle.typical_price(),
+            Self::Ohlc4 => (candle.open + candle.high + candle.low + candle.close) / 4.0,
+            Self::Hlcc4 => (candle.high + candle.low + 2.0 * candle.close) / 4.0,
+            Self::Weighted {
+                open,
+                high,
+                low,
+                close,
+            } => {
+                (candle.open * open + candle.high * high + candle.low * low + candle.close * close)
+                    / (open + high + low + close)
+            }
+        }
+    }
+}
+
+impl FromStr for PriceSource {
+    type Err = IndicatorError;
+
+    /// Parses `open`, `high`, `low`, `close`, `hl2`, `hlc3`, `ohlc4` or `hlcc4`
+    /// (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "open" => Ok(Self::Open),
+            "high" => Ok(Self::High),
+            "low" => Ok(Self::Low),
+            "close" => Ok(Self::Close),
+            "hl2" => Ok(Self::Hl2),
+            "hlc3" => Ok(Self::Hlc3),
+            "ohlc4" => Ok(Self::Ohlc4),
+            "hlcc4" => Ok(Self::Hlcc4),
+            _ => Err(IndicatorError::InvalidParameter(format!(
+                "unknown price source '{s}'"
+            ))),
+        }
+    }
+}
+
+/// Single-series indicator fed with candles through a [`PriceSource`].
+///
+/// Works with any batch calculator taking `&[f64]` and any stream taking
+/// `f64`, e.g. `Sourced::new(Macd::new(12, 26, 9)?, PriceSource::Ohlc4)`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sourced<T> {
+    inner: T,
+    source: PriceSource,
+}
+
+impl<T> Sourced<T> {
+    /// Wraps `inner` so it reads `source` from each candle.
+    #[must_use]
+    pub const fn new(inner: T, source: PriceSource) -> Self {
+        Self { inner, source }
+    }
+
+    /// Returns the price source.
+    #[must_use]
+    pub const fn source(&self) -> PriceSource {
+        self.source
+    }
+
+    /// Returns the wrapped indicator.
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    fn prices(&self, candles: &[OHLCV]) -> Vec<f64> {
+        candles.iter().map(|c| self.source.price(c)).collect()
+    }
+}
+
+impl<T, O> Indicator<&[OHLCV], O> for Sourced<T>
+where
+    T: for<'a> Indicator<&'a [f64], O>,
+{
+    fn calculate(&self, data: &[OHLCV]) -> IndicatorResult<O> {
+        self.inner.calculate(&self.prices(data))
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+impl<T, O> StreamingIndicator<OHLCV, O> for Sourced<T>
+where
+    T: StreamingIndicator<f64, O>,
+{
+    fn init(&mut self, data: &[OHLCV]) -> IndicatorResult<Vec<O>> {
+        let prices = self.prices(data);
+        self.inner.init(&prices)
+    }
+
+    #[inline]
+    fn next(&mut self, candle: OHLCV) -> Option<O> {
+        self.inner.next(self.source.price(&candle))
+    }
+
+    fn update(&mut self, candle: OHLCV) -> Option<O> {
+        self.inner.update(self.source.price(&candle))
+    }
+
+    fn peek(&self, candle: OHLCV) -> Option<O> {
+        self.inner.peek(self.source.price(&candle))
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        BBands, BBandsStream, Ema, EmaStream, Hma, HmaStream, LinReg, LinRegStream, Macd,
+        MacdStream, Rsi, RsiStream, Sma, SmaStream, StochRsi, StochRsiStream, Wma, WmaStream,
+    };
+
+    fn candles(n: i32) -> Vec<OHLCV> {
+        (0..n)
+            .map(|i| {
+                let x = f64::from(i);
+                let close = 100.0 + (x * 0.37).sin() * 5.0;
+                let open = close - (x * 0.91).cos();
+                OHLCV::new(
+                    i64::from(i),
+                    open,
+                    open.max(close) + 0.8,
+                    open.min(close) - 0.6,
+                    close,
+                    1000.0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_price_source_values() {
+        let c = OHLCV::new(0, 10.0, 14.0, 8.0, 12.0, 100.0);
+        let cases = [
+            (PriceSource::Open, 10.0),
+            (PriceSource::High, 14.0),
+            (PriceSource::Low, 8.0),
+            (PriceSource::Close, 12.0),
+            (PriceSource::Hl2, 11.0),
+            (PriceSource::Hlc3, 34.0 / 3.0),
+            (PriceSource::Ohlc4, 11.0),
+            (PriceSource::Hlcc4, 11.5),
+            (PriceSource::weighted(0.0, 1.0, 1.0, 2.0).unwrap(), 11.5),
+        ];
+        for (source, expected) in cases {
+            assert!((source.price(&c) - expected).abs() < 1e-12, "{source:?}");
+        }
+
+        assert!(PriceSource::weighted(0.0, 0.0, 0.0, 0.0).is_err());
+        assert!(PriceSource::weighted(-1.0, 1.0, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_price_source_from_str() {
+        assert_eq!("hlc3".parse::<PriceSource>(), Ok(PriceSource::Hlc3));
+        assert_eq!("OHLC4".parse::<PriceSource>(), Ok(PriceSource::Ohlc4));
+        assert_eq!("close".parse::<PriceSource>(), Ok(PriceSource::Close));
+        assert!("vwap".parse::<PriceSource>().is_err());
+        assert_eq!(PriceSource::default(), PriceSource::Close);
+    }
+
+    /// Checks a sourced batch and stream against the plain indicator run on
+    /// pre-extracted prices.
+    fn assert_sourced<B, S, O>(batch: B, stream: impl Fn() -> S, source: PriceSource)
+    where
+        B: for<'a> Indicator<&'a [f64], Vec<O>>,
+        S: StreamingIndicator<f64, O>,
+        O: std::fmt::Debug,
+    {
+        let data = candles(80);
+        let prices: Vec<f64> = data.iter().map(|c| source.price(c)).collect();
+
+        let expected = batch.calculate(&prices).unwrap();
+        let sourced = Sourced::new(batch, source).calculate(&data).unwrap();
+        assert_eq!(format!("{sourced:?}"), format!("{expected:?}"));
+
+        let expected = stream().init(&prices).unwrap();
+        let mut sourced = Sourced::new(stream(), source);
+        let actual = sourced.init(&data[..79]).unwrap();
+        assert_eq!(format!("{actual:?}"), format!("{:?}", &expected[..79]));
+        assert_eq!(
+            format!("{:?}", sourced.next(data[79])),
+            format!("{:?}", Some(&expected[79]))
+        );
+    }
+
+    #[test]
+    fn test_sourced_single_series_indicators() {
+        let source = PriceSource::Hlc3;
+        assert_sourced(
+            Sma::new(10).unwrap(),
+            || SmaStream::new(10).unwrap(),
+            source,
+        );
+        assert_sourced(
+            Ema::new(10).unwrap(),
+            || EmaStream::new(10).unwrap(),
+            source,
+        );
+        assert_sourced(
+            Wma::new(10).unwrap(),
+            || WmaStream::new(10).unwrap(),
+            source,
+        );
+        assert_sourced(
+            Rsi::new(14).unwrap(),
+            || RsiStream::new(14).unwrap(),
+            source,
+        );
+        assert_sourced(Hma::new(9).unwrap(), || HmaStream::new(9).unwrap(), source);
+        assert_sourced(
+            BBands::new(20, 2.0).unwrap(),
+            || BBandsStream::new(20, 2.0).unwrap(),
+            PriceSource::Ohlc4,
+        );
+        assert_sourced(
+            LinReg::new(14, 2.0).unwrap(),
+            || LinRegStream::new(14, 2.0).unwrap(),
+            PriceSource::Hl2,
+        );
+    }
+
+    #[test]
+    fn test_sourced_macd_and_stoch_rsi() {
+        let data = candles(80);
+        let prices: Vec<f64> = data.iter().map(|c| c.open).collect();
+
+        let expected = Macd::new(12, 26, 9).unwrap().calculate(&prices).unwrap();
+        let sourced = Sourced::new(Macd::new(12, 26, 9).unwrap(), PriceSource::Open);
+        assert_eq!(
+            format!("{:?}", sourced.calculate(&data).unwrap()),
+            format!("{expected:?}")
+        );
+
+        let mut reference = MacdStream::new(12, 26, 9).unwrap();
+        let mut stream = Sourced::new(MacdStream::new(12, 26, 9).unwrap(), PriceSource::Open);
+        for (candle, &price) in data.iter().zip(&prices) {
+            assert_eq!(
+                format!("{:?}", stream.next(*candle)),
+                format!("{:?}", reference.next(price))
+            );
+        }
+
+        let expected = StochRsi::new(14, 14, 3, 3)
+            .unwrap()
+            .calculate(&prices)
+            .unwrap();
+        let sourced = Sourced::new(StochRsi::new(14, 14, 3, 3).unwrap(), PriceSource::Open);
+        assert_eq!(
+            format!("{:?}", sourced.calculate(&data).unwrap()),
+            format!("{expected:?}")
+        );
+
+        let mut reference = StochRsiStream::new(14, 14, 3, 3).unwrap();
+        let mut stream = Sourced::new(
+            StochRsiStream::new(14, 14, 3, 3).unwrap(),
+            PriceSource::Open,
+        );
+        for (candle, &price) in data.iter().zip(&prices) {
+            assert_eq!(
+                format!("{:?}", stream.next(*candle)),
+                format!("{:?}", reference.next(price))
+            );
+        }
+    }
+}
@@ -3,8 +3,13 @@
 //! All indicators implement two modes:
 //! - **Batch mode** via [`Indicator`]: For historical data calculation
 //! - **Streaming mode** via [`StreamingIndicator`]: For O(1) real-time updates
+//!
+//! [`DynIndicator`] wraps the streaming mode behind a uniform, object-safe interface
+//! over OHLCV candles for indicators configured at runtime.
 
-use crate::types::IndicatorResult;
+use crate::nan::{NanGuard, NanGuardBatch, NanPolicy};
+use crate::pipe::{Pipe, PipeBatch};
+use crate::types::{IndicatorResult, OHLCV};
 
 /// Trait for batch/historical indicator calculations.
 ///
@@ -20,6 +25,42 @@ pub trait Indicator<Input, Output> {
     /// Returns a vector of indicator values aligned with the input data.
     /// Early values may be `NaN` if insufficient data exists for calculation.
     fn calculate(&self, data: Input) -> IndicatorResult<Output>;
+
+    /// Number of leading outputs without a complete value.
+    ///
+    /// The first output with every component valid is at index `lookback()`;
+    /// everything before it is `NaN` (or partly `NaN` for multi-output
+    /// indicators whose components warm up at different rates).
+    fn lookback(&self) -> usize;
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    ///
+    /// Always `lookback() + 1`; use it to size history requests.
+    fn warmup_len(&self) -> usize {
+        self.lookback() + 1
+    }
+
+    /// Feed this indicator's output into `next`, skipping its `NaN` warmup.
+    ///
+    /// See [`PipeBatch`].
+    #[must_use]
+    fn pipe<B>(self, next: B) -> PipeBatch<Self, B>
+    where
+        Self: Sized,
+    {
+        PipeBatch::new(self, next)
+    }
+
+    /// Apply `policy` to missing (`NaN`) input bars.
+    ///
+    /// See [`NanGuardBatch`].
+    #[must_use]
+    fn with_nan_policy(self, policy: NanPolicy) -> NanGuardBatch<Self>
+    where
+        Self: Sized,
+    {
+        NanGuardBatch::new(self, policy)
+    }
 }
 
 /// Trait for streaming/real-time indicator calculations.
@@ -51,9 +92,158 @@ pub trait StreamingIndicator<Input, Output> {
     /// - `None` if insufficient data has been accumulated
     fn next(&mut self, value: Input) -> Option<Output>;
 
+    /// Replace the most recent input with `value` and return the corrected
+    /// indicator value.
+    ///
+    /// Use this for a live candle that is still forming: call [`next`](Self::next)
+    /// when a new bar opens, then `update` on every tick until it closes.
+    /// Calling `update` before any value has been processed behaves like `next`.
+    ///
+    /// This must run in O(1) time complexity.
+    fn update(&mut self, value: Input) -> Option<Output>;
+
+    /// Return the value [`next`](Self::next) would produce for `value`,
+    /// without changing any state.
+    ///
+    /// Use this for what-if evaluation, e.g. previewing the indicator at a
+    /// hypothetical price before an order is placed.
+    fn peek(&self, value: Input) -> Option<Output>;
+
     /// Reset the indicator to its initial state.
     fn reset(&mut self);
 
     /// Returns `true` if the indicator has been initialized with enough data.
     fn is_ready(&self) -> bool;
+
+    /// Number of leading inputs that produce no complete value.
+    ///
+    /// The first value with every component valid is returned for the input
+    /// at index `lookback()`. Matches [`Indicator::lookback`] of the batch
+    /// counterpart.
+    fn lookback(&self) -> usize;
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    ///
+    /// Always `lookback() + 1`; use it to size history requests.
+    fn warmup_len(&self) -> usize {
+        self.lookback() + 1
+    }
+
+    /// Feed this indicator's output into `next` on every tick.
+    ///
+    /// See [`Pipe`].
+    #[must_use]
+    fn pipe<B>(self, next: B) -> Pipe<Self, B>
+    where
+        Self: Sized,
+    {
+        Pipe::new(self, next)
+    }
+
+    /// Apply `policy` to missing (`NaN`) input bars.
+    ///
+    /// See [`NanGuard`].
+    #[must_use]
+    fn with_nan_policy(self, policy: NanPolicy) -> NanGuard<Self, Input>
+    where
+        Self: Sized,
+    {
+        NanGuard::new(self, policy)
+    }
+}
+
+impl<Input, Output, S> StreamingIndicator<Input, Output> for Box<S>
+where
+    S: StreamingIndicator<Input, Output> + ?Sized,
+{
+    fn init(&mut self, data: &[Input]) -> IndicatorResult<Vec<Output>> {
+        (**self).init(data)
+    }
+
+    fn next(&mut self, value: Input) -> Option<Output> {
+        (**self).next(value)
+    }
+
+    fn update(&mut self, value: Input) -> Option<Output> {
+        (**self).update(value)
+    }
+
+    fn peek(&self, value: Input) -> Option<Output> {
+        (**self).peek(value)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+
+    fn is_ready(&self) -> bool {
+        (**self).is_ready()
+    }
+
+    fn lookback(&self) -> usize {
+        (**self).lookback()
+    }
+}
+
+/// Indicator output type with a placeholder for bars that have no value.
+///
+/// Used to fill warmup positions when results are aligned with the input.
+pub trait IndicatorOutput {
+    /// The "no value yet" output (all components `NaN`).
+    fn nan() -> Self;
+}
+
+impl IndicatorOutput for f64 {
+    fn nan() -> Self {
+        f64::NAN
+    }
+}
+
+/// Object-safe indicator interface over [`OHLCV`] candles.
+///
+/// [`Indicator`] and [`StreamingIndicator`] are generic over each indicator's
+/// own input and output types, so differently-shaped indicators cannot share a
+/// collection. `DynIndicator` erases those types: every implementor consumes
+/// candles and produces a fixed set of named `f64` columns, with `NaN` where
+/// the indicator has no value yet.
+///
+/// Instances are usually built by name with [`registry::create`](crate::registry::create).
+pub trait DynIndicator {
+    /// Registry name of the indicator (e.g. `"macd"`).
+    fn name(&self) -> &'static str;
+
+    /// Names of the output columns, in the order values are returned.
+    fn columns(&self) -> &'static [&'static str];
+
+    /// Reset and process historical candles.
+    ///
+    /// Returns one vector per column, each aligned with `candles`.
+    ///
+    /// # Errors
+    /// Returns an error if the candles cannot be processed.
+    fn init(&mut self, candles: &[OHLCV]) -> IndicatorResult<Vec<Vec<f64>>>;
+
+    /// Process a new candle and return one value per column.
+    fn next(&mut self, candle: OHLCV) -> Vec<f64>;
+
+    /// Replace the most recent candle and return the corrected values.
+    fn update(&mut self, candle: OHLCV) -> Vec<f64>;
+
+    /// Return the values `next` would produce for `candle` without
+    /// changing any state.
+    fn peek(&self, candle: &OHLCV) -> Vec<f64>;
+
+    /// Reset the indicator to its initial state.
+    fn reset(&mut self);
+
+    /// Returns `true` if the indicator has been initialized with enough data.
+    fn is_ready(&self) -> bool;
+
+    /// Number of leading candles before every column has a value.
+    fn lookback(&self) -> usize;
+
+    /// Minimum number of candles needed for every column to have a value.
+    fn warmup_len(&self) -> usize {
+        self.lookback() + 1
+    }
 }
@@ -4,6 +4,7 @@
 ///
 /// All price values use `f64` for precision as required by institutional-grade calculations.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OHLCV {
     /// Unix timestamp in milliseconds (UTC)
     pub timestamp: i64,
@@ -53,11 +54,169 @@ impl OHLCV {
     }
 }
 
+/// Borrowed struct-of-arrays view over OHLCV data.
+///
+/// Every OHLCV indicator has a `calculate_columns()` method next to its
+/// `Indicator` impl, so data already held column-wise (e.g. in a dataframe)
+/// can be passed without building a `Vec<OHLCV>` or a tuple per indicator. All six
+/// columns are checked to have the same length on construction; fill
+/// columns an indicator doesn't read (e.g. `open` for ATR) with anything of
+/// the right length.
+///
+/// # Example
+/// ```
+/// use ta_core::indicators::Atr;
+/// use ta_core::OhlcvColumns;
+///
+/// let time = [0, 60_000, 120_000];
+/// let open = [10.0, 11.0, 12.0];
+/// let high = [11.0, 12.0, 13.0];
+/// let low = [9.0, 10.0, 11.0];
+/// let close = [10.5, 11.5, 12.5];
+/// let volume = [100.0, 120.0, 90.0];
+///
+/// let columns = OhlcvColumns::new(&time, &open, &high, &low, &close, &volume).unwrap();
+/// let atr = Atr::new(2).unwrap().calculate_columns(&columns).unwrap();
+/// assert_eq!(atr.len(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OhlcvColumns<'a> {
+    timestamp: &'a [i64],
+    open: &'a [f64],
+    high: &'a [f64],
+    low: &'a [f64],
+    close: &'a [f64],
+    volume: &'a [f64],
+}
+
+impl<'a> OhlcvColumns<'a> {
+    /// Creates a columnar view over timestamps and OHLCV prices.
+    ///
+    /// # Errors
+    /// Returns `LengthMismatch` if the columns differ in length.
+    pub fn new(
+        timestamp: &'a [i64],
+        open: &'a [f64],
+        high: &'a [f64],
+        low: &'a [f64],
+        close: &'a [f64],
+        volume: &'a [f64],
+    ) -> IndicatorResult<Self> {
+        check_lengths(&[
+            ("timestamps", timestamp.len()),
+            ("opens", open.len()),
+            ("highs", high.len()),
+            ("lows", low.len()),
+            ("closes", close.len()),
+            ("volumes", volume.len()),
+        ])?;
+        Ok(Self {
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        })
+    }
+
+    /// Number of candles.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.timestamp.len()
+    }
+
+    /// Returns true if there are no candles.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.timestamp.is_empty()
+    }
+
+    /// Timestamp column (Unix milliseconds, UTC).
+    #[must_use]
+    pub const fn timestamp(&self) -> &'a [i64] {
+        self.timestamp
+    }
+
+    /// Open column.
+    #[must_use]
+    pub const fn open(&self) -> &'a [f64] {
+        self.open
+    }
+
+    /// High column.
+    #[must_use]
+    pub const fn high(&self) -> &'a [f64] {
+        self.high
+    }
+
+    /// Low column.
+    #[must_use]
+    pub const fn low(&self) -> &'a [f64] {
+        self.low
+    }
+
+    /// Close column.
+    #[must_use]
+    pub const fn close(&self) -> &'a [f64] {
+        self.close
+    }
+
+    /// Volume column.
+    #[must_use]
+    pub const fn volume(&self) -> &'a [f64] {
+        self.volume
+    }
+
+    /// Candle at `index`, or `None` if out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<OHLCV> {
+        (index < self.len()).then(|| self.candle(index))
+    }
+
+    /// Iterates the rows as candles.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = OHLCV> + Clone + 'a {
+        let columns = *self;
+        (0..self.len()).map(move |i| columns.candle(i))
+    }
+
+    /// View over the candles in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds.
+    #[must_use]
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Self {
+        Self {
+            timestamp: &self.timestamp[range.clone()],
+            open: &self.open[range.clone()],
+            high: &self.high[range.clone()],
+            low: &self.low[range.clone()],
+            close: &self.close[range.clone()],
+            volume: &self.volume[range],
+        }
+    }
+
+    fn candle(&self, i: usize) -> OHLCV {
+        OHLCV::new(
+            self.timestamp[i],
+            self.open[i],
+            self.high[i],
+            self.low[i],
+            self.close[i],
+            self.volume[i],
+        )
+    }
+}
+
 /// Result type alias for indicator calculations.
 pub type IndicatorResult<T> = Result<T, IndicatorError>;
 
 /// Errors that can occur during indicator calculations.
+///
+/// New variants may be added in minor releases, so matches need a wildcard
+/// arm; [`code`](Self::code) is the stable way to tell variants apart.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum IndicatorError {
     /// Not enough data points to calculate the indicator
     InsufficientData { required: usize, provided: usize },
@@ -65,6 +224,63 @@ pub enum IndicatorError {
     InvalidParameter(String),
     /// Indicator has not been properly initialized
     NotInitialized,
+    /// Snapshot bytes are corrupt, from another format version, or for a different stream
+    InvalidSnapshot(String),
+    /// Input data failed validation in strict mode (see [`crate::validate`])
+    InvalidData { index: usize, reason: String },
+    /// Input arrays that must be parallel differ in length: array `name` has
+    /// `len` elements where `expected_name` has `expected`
+    LengthMismatch {
+        name: &'static str,
+        len: usize,
+        expected_name: &'static str,
+        expected: usize,
+    },
+    /// Input value at `index` is infinite or `NaN` where a finite number is required
+    NonFiniteInput { index: usize },
+    /// Parameter `name` is outside `min..=max`
+    OutOfRange {
+        name: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl IndicatorError {
+    /// Stable machine-readable code identifying the variant, e.g.
+    /// `"LENGTH_MISMATCH"`. Codes never change between releases, unlike the
+    /// `Display` messages.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::InsufficientData { .. } => "INSUFFICIENT_DATA",
+            Self::InvalidParameter(_) => "INVALID_PARAMETER",
+            Self::NotInitialized => "NOT_INITIALIZED",
+            Self::InvalidSnapshot(_) => "INVALID_SNAPSHOT",
+            Self::InvalidData { .. } => "INVALID_DATA",
+            Self::LengthMismatch { .. } => "LENGTH_MISMATCH",
+            Self::NonFiniteInput { .. } => "NON_FINITE_INPUT",
+            Self::OutOfRange { .. } => "OUT_OF_RANGE",
+        }
+    }
+}
+
+/// Checks that parallel input arrays, given as `(name, length)`, all have
+/// the length of the first one.
+pub(crate) fn check_lengths(arrays: &[(&'static str, usize)]) -> IndicatorResult<()> {
+    let Some(&(expected_name, expected)) = arrays.first() else {
+        return Ok(());
+    };
+    match arrays.iter().find(|&&(_, len)| len != expected) {
+        Some(&(name, len)) => Err(IndicatorError::LengthMismatch {
+            name,
+            len,
+            expected_name,
+            expected,
+        }),
+        None => Ok(()),
+    }
 }
 
 impl std::fmt::Display for IndicatorError {
@@ -78,8 +294,114 @@ impl std::fmt::Display for IndicatorError {
             }
             Self::InvalidParameter(msg) => write!(f, "Invalid parameter: {msg}"),
             Self::NotInitialized => write!(f, "Indicator not initialized"),
+            Self::InvalidSnapshot(msg) => write!(f, "Invalid snapshot: {msg}"),
+            Self::InvalidData { index, reason } => {
+                write!(f, "Invalid data at bar {index}: {reason}")
+            }
+            Self::LengthMismatch {
+                name,
+                len,
+                expected_name,
+                expected,
+            } => write!(
+                f,
+                "Length mismatch: {name} has {len} elements, {expected_name} has {expected}"
+            ),
+            Self::NonFiniteInput { index } => write!(f, "Non-finite input at index {index}"),
+            Self::OutOfRange {
+                name,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "Out of range: {name} must be between {min} and {max}, got {value}"
+            ),
         }
     }
 }
 
 impl std::error::Error for IndicatorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_and_messages() {
+        let err = check_lengths(&[("highs", 3), ("lows", 3), ("closes", 2)]).unwrap_err();
+        assert_eq!(err.code(), "LENGTH_MISMATCH");
+        assert_eq!(
+            err.to_string(),
+            "Length mismatch: closes has 2 elements, highs has 3"
+        );
+        assert!(check_lengths(&[("highs", 3), ("lows", 3)]).is_ok());
+
+        let err = IndicatorError::OutOfRange {
+            name: "value_area_percent",
+            value: 1.5,
+            min: 0.0,
+            max: 1.0,
+        };
+        assert_eq!(err.code(), "OUT_OF_RANGE");
+        assert_eq!(
+            err.to_string(),
+            "Out of range: value_area_percent must be between 0 and 1, got 1.5"
+        );
+        assert_eq!(
+            IndicatorError::NonFiniteInput { index: 4 }.code(),
+            "NON_FINITE_INPUT"
+        );
+    }
+
+    #[test]
+    fn test_ohlcv_columns_length_mismatch() {
+        let result = OhlcvColumns::new(
+            &[0, 1],
+            &[1.0, 2.0],
+            &[1.0, 2.0],
+            &[1.0],
+            &[1.0, 2.0],
+            &[1.0, 2.0],
+        );
+        assert_eq!(
+            result,
+            Err(IndicatorError::LengthMismatch {
+                name: "lows",
+                len: 1,
+                expected_name: "timestamps",
+                expected: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_ohlcv_columns_rows() {
+        let timestamps = [0, 60_000, 120_000];
+        let opens = [10.0, 11.0, 12.0];
+        let highs = [11.0, 12.0, 13.0];
+        let lows = [9.0, 10.0, 11.0];
+        let closes = [10.5, 11.5, 12.5];
+        let volumes = [100.0, 120.0, 90.0];
+        let columns =
+            OhlcvColumns::new(&timestamps, &opens, &highs, &lows, &closes, &volumes).unwrap();
+
+        assert_eq!(columns.len(), 3);
+        assert!(!columns.is_empty());
+        assert_eq!(
+            columns.get(1),
+            Some(OHLCV::new(60_000, 11.0, 12.0, 10.0, 11.5, 120.0))
+        );
+        assert_eq!(columns.get(3), None);
+
+        let rows: Vec<OHLCV> = columns.iter().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].timestamp, 120_000);
+
+        let tail = columns.slice(1..3);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail.close(), &[11.5, 12.5]);
+        assert_eq!(tail.get(0), columns.get(1));
+        assert!(columns.slice(3..3).is_empty());
+    }
+}
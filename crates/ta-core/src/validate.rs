@@ -0,0 +1,496 @@
+//! OHLCV data validation and repair.
+//!
+//! Exchange feeds occasionally deliver impossible candles (high below low,
+//! a close outside the bar's range, negative volume) or a broken sequence
+//! (duplicate, out-of-order or missing timestamps). Indicators take their
+//! input at face value, so such bars skew `Frvp` profiles, `SessionVwap`
+//! sessions and `Atr` ranges without any error.
+//!
+//! A [`Validator`] reports every anomaly per bar in a [`ValidationReport`],
+//! rejects bad data outright in strict mode ([`Validator::check`]), or fixes
+//! it with the repair operations: [`sort_dedupe`], [`clamp`] and
+//! [`fill_gaps`] (all three at once with [`Validator::repair`]).
+//!
+//! Bars with `NaN` fields are not anomalies here: they are missing values,
+//! handled by a [`NanPolicy`](crate::NanPolicy).
+//!
+//! # Example
+//! ```
+//! use ta_core::validate::{Anomaly, Validator};
+//! use ta_core::OHLCV;
+//!
+//! let candles = [
+//!     OHLCV::new(0, 10.0, 11.0, 9.0, 10.5, 100.0),
+//!     OHLCV::new(60_000, 10.5, 9.0, 11.0, 10.0, 100.0), // high < low
+//!     OHLCV::new(180_000, 10.0, 10.5, 9.5, 10.2, 80.0), // 120000 missing
+//! ];
+//!
+//! let validator = Validator::new().with_interval(60_000);
+//! let report = validator.validate(&candles);
+//! assert_eq!(report.bar(1).collect::<Vec<_>>(), [&Anomaly::HighBelowLow]);
+//! assert_eq!(report.bar(2).collect::<Vec<_>>(), [&Anomaly::Gap { missing: 1 }]);
+//! assert!(validator.check(&candles).is_err());
+//!
+//! let repaired = validator.repair(&candles);
+//! assert_eq!(repaired.len(), 4);
+//! assert!(validator.validate(&repaired).is_clean());
+//! ```
+
+use crate::types::{IndicatorError, IndicatorResult, OHLCV};
+
+/// A problem with a single bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anomaly {
+    /// High is below low
+    HighBelowLow,
+    /// Open is outside the low-high range
+    OpenOutOfRange,
+    /// Close is outside the low-high range
+    CloseOutOfRange,
+    /// Volume is negative
+    NegativeVolume,
+    /// Same timestamp as the previous bar
+    DuplicateTimestamp,
+    /// Timestamp earlier than the previous bar's
+    OutOfOrder,
+    /// Bars missing between the previous bar and this one (needs an interval)
+    Gap {
+        /// Number of missing bars
+        missing: usize,
+    },
+}
+
+impl std::fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HighBelowLow => write!(f, "high is below low"),
+            Self::OpenOutOfRange => write!(f, "open is outside the low-high range"),
+            Self::CloseOutOfRange => write!(f, "close is outside the low-high range"),
+            Self::NegativeVolume => write!(f, "volume is negative"),
+            Self::DuplicateTimestamp => write!(f, "timestamp duplicates the previous bar"),
+            Self::OutOfOrder => write!(f, "timestamp is earlier than the previous bar"),
+            Self::Gap { missing } => write!(f, "{missing} bar(s) missing before this one"),
+        }
+    }
+}
+
+/// An anomaly found at a given bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarAnomaly {
+    /// Index of the bar in the validated slice
+    pub index: usize,
+    /// Timestamp of the bar
+    pub timestamp: i64,
+    /// What is wrong with it
+    pub anomaly: Anomaly,
+}
+
+/// Every anomaly found in a series, in bar order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Anomalies ordered by bar index; a bar may have several
+    pub anomalies: Vec<BarAnomaly>,
+}
+
+impl ValidationReport {
+    /// Returns true if no anomaly was found.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+
+    /// Anomalies of the bar at `index`.
+    pub fn bar(&self, index: usize) -> impl Iterator<Item = &Anomaly> {
+        self.anomalies
+            .iter()
+            .filter(move |a| a.index == index)
+            .map(|a| &a.anomaly)
+    }
+
+    /// Indices of the bars with at least one anomaly, ascending.
+    #[must_use]
+    pub fn bad_bars(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = self.anomalies.iter().map(|a| a.index).collect();
+        indices.dedup();
+        indices
+    }
+}
+
+/// Checks OHLCV series for anomalies.
+///
+/// Gaps are only detected with an expected bar interval.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Validator {
+    interval: Option<i64>,
+}
+
+impl Validator {
+    /// Creates a validator checking bar contents and timestamp order.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { interval: None }
+    }
+
+    /// Also reports gaps, expecting one bar every `interval_ms` milliseconds.
+    #[must_use]
+    pub const fn with_interval(self, interval_ms: i64) -> Self {
+        Self {
+            interval: if interval_ms > 0 {
+                Some(interval_ms)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Expected bar interval in milliseconds, if set.
+    #[must_use]
+    pub const fn interval(&self) -> Option<i64> {
+        self.interval
+    }
+
+    /// Reports every anomaly in `candles`.
+    #[must_use]
+    pub fn validate(&self, candles: &[OHLCV]) -> ValidationReport {
+        let mut anomalies = Vec::new();
+        let mut prev: Option<i64> = None;
+
+        for (index, candle) in candles.iter().enumerate() {
+            let mut push = |anomaly| {
+                anomalies.push(BarAnomaly {
+                    index,
+                    timestamp: candle.timestamp,
+                    anomaly,
+                });
+            };
+
+            if candle.high < candle.low {
+                push(Anomaly::HighBelowLow);
+            } else {
+                if candle.open < candle.low || candle.open > candle.high {
+                    push(Anomaly::OpenOutOfRange);
+                }
+                if candle.close < candle.low || candle.close > candle.high {
+                    push(Anomaly::CloseOutOfRange);
+                }
+            }
+            if candle.volume < 0.0 {
+                push(Anomaly::NegativeVolume);
+            }
+
+            if let Some(prev) = prev {
+                let delta = candle.timestamp.saturating_sub(prev);
+                if delta == 0 {
+                    push(Anomaly::DuplicateTimestamp);
+                } else if delta < 0 {
+                    push(Anomaly::OutOfOrder);
+                } else if let Some(interval) = self.interval {
+                    let missing = missing_bars(delta, interval);
+                    if missing > 0 {
+                        push(Anomaly::Gap { missing });
+                    }
+                }
+            }
+            // Out-of-order bars don't move the reference point back
+            prev = Some(prev.map_or(candle.timestamp, |p| p.max(candle.timestamp)));
+        }
+
+        ValidationReport { anomalies }
+    }
+
+    /// Strict mode: accepts `candles` only if they have no anomaly.
+    ///
+    /// # Errors
+    /// Returns `InvalidData` describing the first anomaly found.
+    pub fn check(&self, candles: &[OHLCV]) -> IndicatorResult<()> {
+        match self.validate(candles).anomalies.first() {
+            None => Ok(()),
+            Some(bad) => Err(IndicatorError::InvalidData {
+                index: bad.index,
+                reason: bad.anomaly.to_string(),
+            }),
+        }
+    }
+
+    /// Returns a repaired copy of `candles`: sorted and deduplicated by
+    /// timestamp, clamped, and gap-filled when an interval is set (gaps of
+    /// more than [`MAX_GAP_FILL`] bars stay open).
+    #[must_use]
+    pub fn repair(&self, candles: &[OHLCV]) -> Vec<OHLCV> {
+        let mut repaired = candles.to_vec();
+        sort_dedupe(&mut repaired);
+        clamp(&mut repaired);
+        match self.interval {
+            Some(interval) => fill_gaps(&repaired, interval),
+            None => repaired,
+        }
+    }
+}
+
+/// Sorts candles by timestamp and removes duplicates, keeping the last
+/// candle received for each timestamp (the most recent revision).
+pub fn sort_dedupe(candles: &mut Vec<OHLCV>) {
+    // Stable: candles sharing a timestamp stay in arrival order
+    candles.sort_by_key(|c| c.timestamp);
+    candles.reverse();
+    candles.dedup_by_key(|c| c.timestamp);
+    candles.reverse();
+}
+
+/// Makes every candle self-consistent: swaps high and low when inverted,
+/// clamps open and close into the low-high range, and zeroes negative
+/// volume. `NaN` fields are missing values and are left alone, as is the
+/// open and close of a bar whose high or low is `NaN`.
+pub fn clamp(candles: &mut [OHLCV]) {
+    for candle in candles {
+        if candle.high < candle.low {
+            std::mem::swap(&mut candle.high, &mut candle.low);
+        }
+        // False if either bound is NaN, where `f64::clamp` would panic
+        if candle.low <= candle.high {
+            candle.open = candle.open.clamp(candle.low, candle.high);
+            candle.close = candle.close.clamp(candle.low, candle.high);
+        }
+        if candle.volume < 0.0 {
+            candle.volume = 0.0;
+        }
+    }
+}
+
+/// Most bars [`fill_gaps`] inserts into a single gap. Longer gaps, such as
+/// a corrupt timestamp far in the future, are left open rather than filled
+/// with millions of bars.
+pub const MAX_GAP_FILL: usize = 10_000;
+
+/// Bars missing between two timestamps `delta` ms apart, expecting one
+/// every `interval` ms: the multiples of `interval` strictly inside `delta`.
+fn missing_bars(delta: i64, interval: i64) -> usize {
+    if delta <= 0 || interval <= 0 {
+        return 0;
+    }
+    usize::try_from((delta - 1) / interval).unwrap_or(0)
+}
+
+/// Inserts flat bars into gaps of a sorted series: one every `interval_ms`
+/// after the bar before the gap, at its close with zero volume. Gaps of
+/// more than [`MAX_GAP_FILL`] bars are left open.
+#[must_use]
+pub fn fill_gaps(candles: &[OHLCV], interval_ms: i64) -> Vec<OHLCV> {
+    let mut filled = Vec::with_capacity(candles.len());
+    for (i, &candle) in candles.iter().enumerate() {
+        if let Some(prev) = i.checked_sub(1).map(|p| candles[p]) {
+            let missing =
+                missing_bars(candle.timestamp.saturating_sub(prev.timestamp), interval_ms);
+            if missing <= MAX_GAP_FILL {
+                let mut timestamp = prev.timestamp;
+                for _ in 0..missing {
+                    timestamp += interval_ms;
+                    filled.push(OHLCV::new(
+                        timestamp, prev.close, prev.close, prev.close, prev.close, 0.0,
+                    ));
+                }
+            }
+        }
+        filled.push(candle);
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: i64, open: f64, high: f64, low: f64, close: f64) -> OHLCV {
+        OHLCV::new(timestamp, open, high, low, close, 100.0)
+    }
+
+    #[test]
+    fn test_clean_series() {
+        let candles: Vec<OHLCV> = (0..10)
+            .map(|i| candle(i * 60_000, 10.0, 11.0, 9.0, 10.5))
+            .collect();
+        let report = Validator::new().with_interval(60_000).validate(&candles);
+        assert!(report.is_clean());
+        assert!(Validator::new().check(&candles).is_ok());
+    }
+
+    #[test]
+    fn test_bar_anomalies() {
+        let candles = [
+            candle(0, 12.0, 11.0, 9.0, 8.0),
+            candle(1, 10.0, 9.0, 11.0, 10.0),
+            OHLCV::new(2, 10.0, 11.0, 9.0, 10.0, -5.0),
+        ];
+        let report = Validator::new().validate(&candles);
+        assert_eq!(
+            report.bar(0).collect::<Vec<_>>(),
+            [&Anomaly::OpenOutOfRange, &Anomaly::CloseOutOfRange]
+        );
+        // Range checks are meaningless on an inverted bar
+        assert_eq!(report.bar(1).collect::<Vec<_>>(), [&Anomaly::HighBelowLow]);
+        assert_eq!(
+            report.bar(2).collect::<Vec<_>>(),
+            [&Anomaly::NegativeVolume]
+        );
+        assert_eq!(report.bad_bars(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_sequence_anomalies() {
+        let candles = [
+            candle(0, 10.0, 11.0, 9.0, 10.0),
+            candle(60_000, 10.0, 11.0, 9.0, 10.0),
+            candle(60_000, 10.0, 11.0, 9.0, 10.0),
+            candle(30_000, 10.0, 11.0, 9.0, 10.0),
+            candle(240_000, 10.0, 11.0, 9.0, 10.0),
+        ];
+
+        let report = Validator::new().validate(&candles);
+        assert_eq!(
+            report.anomalies,
+            [
+                BarAnomaly {
+                    index: 2,
+                    timestamp: 60_000,
+                    anomaly: Anomaly::DuplicateTimestamp
+                },
+                BarAnomaly {
+                    index: 3,
+                    timestamp: 30_000,
+                    anomaly: Anomaly::OutOfOrder
+                },
+            ]
+        );
+
+        // The gap is measured from the latest timestamp seen, 60000
+        let report = Validator::new().with_interval(60_000).validate(&candles);
+        assert_eq!(
+            report.bar(4).collect::<Vec<_>>(),
+            [&Anomaly::Gap { missing: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_check_reports_first_anomaly() {
+        let candles = [
+            candle(0, 10.0, 11.0, 9.0, 10.0),
+            candle(0, 10.0, 11.0, 9.0, 12.0),
+        ];
+        let err = Validator::new().check(&candles).unwrap_err();
+        assert_eq!(
+            err,
+            IndicatorError::InvalidData {
+                index: 1,
+                reason: "close is outside the low-high range".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_sort_dedupe_keeps_last_revision() {
+        let mut candles = vec![
+            candle(120_000, 10.0, 11.0, 9.0, 10.0),
+            candle(0, 10.0, 11.0, 9.0, 10.0),
+            candle(60_000, 10.0, 11.0, 9.0, 10.0),
+            candle(60_000, 10.0, 11.0, 9.0, 10.8),
+        ];
+        sort_dedupe(&mut candles);
+        let timestamps: Vec<i64> = candles.iter().map(|c| c.timestamp).collect();
+        assert_eq!(timestamps, [0, 60_000, 120_000]);
+        assert_eq!(candles[1].close.to_bits(), 10.8_f64.to_bits());
+    }
+
+    #[test]
+    fn test_clamp() {
+        let mut candles = [
+            candle(0, 12.0, 11.0, 9.0, 8.0),
+            OHLCV::new(1, 10.0, 9.0, 11.0, 10.0, -5.0),
+        ];
+        clamp(&mut candles);
+        assert_eq!(candles[0], candle(0, 11.0, 11.0, 9.0, 9.0));
+        assert_eq!(candles[1], OHLCV::new(1, 10.0, 11.0, 9.0, 10.0, 0.0));
+        assert!(Validator::new().validate(&candles).is_clean());
+    }
+
+    #[test]
+    fn test_fill_gaps() {
+        let candles = [
+            candle(0, 10.0, 11.0, 9.0, 10.5),
+            candle(180_000, 10.0, 11.0, 9.0, 10.0),
+            candle(240_000, 10.0, 11.0, 9.0, 10.0),
+        ];
+        let filled = fill_gaps(&candles, 60_000);
+        let timestamps: Vec<i64> = filled.iter().map(|c| c.timestamp).collect();
+        assert_eq!(timestamps, [0, 60_000, 120_000, 180_000, 240_000]);
+        assert_eq!(filled[1], OHLCV::new(60_000, 10.5, 10.5, 10.5, 10.5, 0.0));
+        assert_eq!(filled[2], OHLCV::new(120_000, 10.5, 10.5, 10.5, 10.5, 0.0));
+    }
+
+    #[test]
+    fn test_fill_gaps_matches_reported_gaps() {
+        // Deltas that aren't multiples of the interval, and a runaway one
+        let candles = [
+            candle(0, 10.0, 11.0, 9.0, 10.0),
+            candle(90_000, 10.0, 11.0, 9.0, 10.0),
+            candle(210_000, 10.0, 11.0, 9.0, 10.0),
+            candle(1_000_000_000_000, 10.0, 11.0, 9.0, 10.0),
+        ];
+        let validator = Validator::new().with_interval(60_000);
+        let report = validator.validate(&candles);
+        assert_eq!(
+            report.bar(1).collect::<Vec<_>>(),
+            [&Anomaly::Gap { missing: 1 }]
+        );
+        assert_eq!(
+            report.bar(2).collect::<Vec<_>>(),
+            [&Anomaly::Gap { missing: 1 }]
+        );
+
+        let filled = fill_gaps(&candles, 60_000);
+        let timestamps: Vec<i64> = filled.iter().map(|c| c.timestamp).collect();
+        assert_eq!(
+            timestamps,
+            [0, 60_000, 90_000, 150_000, 210_000, 1_000_000_000_000]
+        );
+        // The only gaps left open are the ones too long to fill
+        let report = validator.validate(&filled);
+        assert_eq!(report.bad_bars(), [5]);
+    }
+
+    #[test]
+    fn test_repair_keeps_nan_bars() {
+        let candles = [
+            candle(0, 10.0, f64::NAN, 9.0, 10.0),
+            candle(60_000, f64::NAN, 11.0, 9.0, 12.0),
+            candle(120_000, 10.0, 11.0, f64::NAN, f64::NAN),
+        ];
+        // Only the close above the high counts; NaN fields are missing values
+        let validator = Validator::new().with_interval(60_000);
+        assert_eq!(validator.validate(&candles).bad_bars(), [1]);
+
+        let repaired = validator.repair(&candles);
+        assert_eq!(repaired.len(), 3);
+        assert!(repaired[0].high.is_nan());
+        assert_eq!(repaired[0].open.to_bits(), 10.0_f64.to_bits());
+        assert!(repaired[1].open.is_nan());
+        assert_eq!(repaired[1].close.to_bits(), 11.0_f64.to_bits());
+        assert!(repaired[2].low.is_nan() && repaired[2].close.is_nan());
+    }
+
+    #[test]
+    fn test_repair() {
+        let candles = [
+            candle(120_000, 10.0, 11.0, 9.0, 10.0),
+            candle(0, 10.0, 9.0, 11.0, 10.0),
+            candle(0, 10.0, 11.0, 9.0, 12.0),
+            candle(300_000, 10.0, 11.0, 9.0, 10.0),
+        ];
+        let validator = Validator::new().with_interval(60_000);
+        assert!(!validator.validate(&candles).is_clean());
+
+        let repaired = validator.repair(&candles);
+        assert!(validator.check(&repaired).is_ok());
+        assert_eq!(repaired.len(), 6);
+        assert_eq!(repaired[0], candle(0, 10.0, 11.0, 9.0, 11.0));
+    }
+}
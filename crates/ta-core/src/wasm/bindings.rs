@@ -2,20 +2,39 @@
 //!
 //! Provides both batch (stateless) functions and streaming (stateful) classes.
 
+use std::cell::RefCell;
+
+use wasm_bindgen::convert::TryFromJsValue;
 use wasm_bindgen::prelude::*;
 
+use crate::bars::{Bar, BarAggregator, BarUpdate, Classification, Quote, Side, Trade};
+use crate::charts::{
+    BarBuilder, HeikinAshi, HeikinAshiStream, RangeBars, Renko, TickBars, VolumeBars,
+};
+use crate::corporate_actions::{AdjustMode, Adjuster, CorporateAction};
 use crate::indicators::{
     Adx, AdxBar, AdxOutput, AdxStream, AnchoredVwap, AnchoredVwapStream, Atr, AtrBar, AtrStream,
-    BBands, BBandsOutput, BBandsStream, Cvd, CvdBar, CvdOhlcv, CvdOhlcvStream, CvdStream, Ema,
-    EmaStream, Frvp, FrvpOutput, FrvpStream, Hma, HmaStream, Ichimoku, IchimokuBar, IchimokuOutput,
-    IchimokuStream, LinReg, LinRegOutput, LinRegStream, Macd, MacdOutput, MacdStream, Mfi, MfiBar,
-    MfiStream, PivotPoints, PivotPointsOutput, PivotPointsVariant, RollingVwap, RollingVwapStream,
-    Rsi, RsiStream, SessionVwap, SessionVwapStream, Sma, SmaStream, Stoch, StochBar, StochOutput,
-    StochRsi, StochRsiOutput, StochRsiStream, StochStream, StochType, VolumeProfileRow, Wma,
-    WmaStream,
+    BBands, BBandsOutput, BBandsStream, Cvd, CvdBar, CvdOhlcv, CvdOhlcvStream, CvdStream, DeltaBar,
+    DeltaUpdate, DevelopingProfile, Donchian, DonchianBar, DonchianOutput, DonchianStream, Ema,
+    EmaStream, FootprintBar, FootprintLevel, FootprintStream, FootprintUpdate, Frvp, FrvpOutput,
+    FrvpStream, Hma, HmaStream, Ichimoku, IchimokuBar, IchimokuOutput, IchimokuStream, Keltner,
+    KeltnerBand, KeltnerBar, KeltnerMa, KeltnerOutput, KeltnerStream, LinReg, LinRegOutput,
+    LinRegStream, Macd, MacdOutput, MacdStream, Mfi, MfiBar, MfiStream, PivotPoints,
+    PivotPointsOutput, PivotPointsVariant, ProfilePeriod, Psar, PsarBar, PsarOutput, PsarStream,
+    RollingVwap, RollingVwapStream, Rsi, RsiStream, SessionProfile, SessionVolumeProfile,
+    SessionVolumeProfileStream, SessionVwap, SessionVwapStream, Sma, SmaStream, Squeeze,
+    SqueezeBar, SqueezeOutput, SqueezeStream, Stoch, StochBar, StochOutput, StochRsi,
+    StochRsiOutput, StochRsiStream, StochStream, StochType, SuperTrend, SuperTrendAtr,
+    SuperTrendBar, SuperTrendOutput, SuperTrendSource, SuperTrendStream, TradeCvdStream,
+    VolumeProfileRow, Wma, WmaStream,
 };
-use crate::traits::{Indicator, StreamingIndicator};
-use crate::types::OHLCV;
+use crate::resample::{ResampleUpdate, Resampler, Timeframe};
+use crate::session::{Session, TimeZone, Weekday};
+#[cfg(feature = "serde")]
+use crate::snapshot::Snapshot;
+use crate::traits::{Indicator, IndicatorOutput, StreamingIndicator};
+use crate::types::{check_lengths, IndicatorError, OhlcvColumns, OHLCV};
+use crate::validate::{Anomaly, Validator};
 
 // ============================================================================
 // Initialization
@@ -28,6 +47,101 @@ pub fn init() {
     console_error_panic_hook::set_once();
 }
 
+// ============================================================================
+// Errors
+// ============================================================================
+
+thread_local! {
+    /// Error classes registered from JS, keyed by error code.
+    static ERROR_CLASSES: RefCell<Option<js_sys::Object>> = const { RefCell::new(None) };
+}
+
+/// Register the JS error classes to throw, as an object mapping each error
+/// code (e.g. `LENGTH_MISMATCH`) to its class.
+///
+/// Errors thrown afterwards get the class prototype, so `instanceof` works.
+/// Called once by the TypeScript wrapper on load.
+#[wasm_bindgen(js_name = "registerErrorClasses")]
+pub fn register_error_classes(classes: js_sys::Object) {
+    ERROR_CLASSES.with(|c| *c.borrow_mut() = Some(classes));
+}
+
+/// Convert an `IndicatorError` into a JS `Error` carrying its `code` and the
+/// variant's fields as properties.
+fn js_error(err: IndicatorError) -> JsError {
+    let code = err.code();
+    let error = JsError::new(&err.to_string());
+    let (name, fields) = error_fields(err);
+
+    let obj = JsValue::from(error.clone());
+    let props = [("name", name.into()), ("code", code.into())];
+    for (key, value) in props.into_iter().chain(fields) {
+        // Setting a property on a fresh Error object cannot fail.
+        let _ = js_sys::Reflect::set(&obj, &key.into(), &value);
+    }
+    ERROR_CLASSES.with(|c| {
+        let Some(classes) = c.borrow().clone() else {
+            return;
+        };
+        let prototype = js_sys::Reflect::get(&classes, &code.into())
+            .and_then(|class| js_sys::Reflect::get(&class, &"prototype".into()));
+        if let Ok(prototype) = prototype {
+            if let Some(prototype) = prototype.dyn_ref::<js_sys::Object>() {
+                js_sys::Object::set_prototype_of(obj.unchecked_ref::<js_sys::Object>(), prototype);
+            }
+        }
+    });
+    error
+}
+
+/// JS class name and properties for each `IndicatorError` variant.
+fn error_fields(err: IndicatorError) -> (&'static str, Vec<(&'static str, JsValue)>) {
+    match err {
+        IndicatorError::InsufficientData { required, provided } => (
+            "InsufficientDataError",
+            vec![("required", required.into()), ("provided", provided.into())],
+        ),
+        IndicatorError::InvalidParameter(_) => ("InvalidParameterError", Vec::new()),
+        IndicatorError::NotInitialized => ("NotInitializedError", Vec::new()),
+        IndicatorError::InvalidSnapshot(_) => ("InvalidSnapshotError", Vec::new()),
+        IndicatorError::InvalidData { index, reason } => (
+            "InvalidDataError",
+            vec![("index", index.into()), ("reason", reason.into())],
+        ),
+        IndicatorError::LengthMismatch {
+            name,
+            len,
+            expected_name,
+            expected,
+        } => (
+            "LengthMismatchError",
+            vec![
+                ("array", name.into()),
+                ("length", len.into()),
+                ("expectedArray", expected_name.into()),
+                ("expectedLength", expected.into()),
+            ],
+        ),
+        IndicatorError::NonFiniteInput { index } => {
+            ("NonFiniteInputError", vec![("index", index.into())])
+        }
+        IndicatorError::OutOfRange {
+            name,
+            value,
+            min,
+            max,
+        } => (
+            "OutOfRangeError",
+            vec![
+                ("param", name.into()),
+                ("value", value.into()),
+                ("min", min.into()),
+                ("max", max.into()),
+            ],
+        ),
+    }
+}
+
 // ============================================================================
 // Batch Functions (Stateless)
 // ============================================================================
@@ -37,10 +151,8 @@ pub fn init() {
 /// Returns Float64Array with NaN for insufficient data points.
 #[wasm_bindgen(js_name = "sma")]
 pub fn sma_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
-    let indicator = Sma::new(period).map_err(|e| JsError::new(&e.to_string()))?;
-    indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))
+    let indicator = Sma::new(period).map_err(js_error)?;
+    indicator.calculate(data).map_err(js_error)
 }
 
 /// Calculate EMA for an array of prices.
@@ -48,10 +160,8 @@ pub fn sma_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
 /// Returns Float64Array with NaN for insufficient data points.
 #[wasm_bindgen(js_name = "ema")]
 pub fn ema_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
-    let indicator = Ema::new(period).map_err(|e| JsError::new(&e.to_string()))?;
-    indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))
+    let indicator = Ema::new(period).map_err(js_error)?;
+    indicator.calculate(data).map_err(js_error)
 }
 
 /// Calculate RSI for an array of prices.
@@ -59,10 +169,8 @@ pub fn ema_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
 /// Returns Float64Array with NaN for insufficient data points.
 #[wasm_bindgen(js_name = "rsi")]
 pub fn rsi_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
-    let indicator = Rsi::new(period).map_err(|e| JsError::new(&e.to_string()))?;
-    indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))
+    let indicator = Rsi::new(period).map_err(js_error)?;
+    indicator.calculate(data).map_err(js_error)
 }
 
 /// Calculate WMA for an array of prices.
@@ -70,10 +178,8 @@ pub fn rsi_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
 /// Returns Float64Array with NaN for insufficient data points.
 #[wasm_bindgen(js_name = "wma")]
 pub fn wma_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
-    let indicator = Wma::new(period).map_err(|e| JsError::new(&e.to_string()))?;
-    indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))
+    let indicator = Wma::new(period).map_err(js_error)?;
+    indicator.calculate(data).map_err(js_error)
 }
 
 /// MACD output returned from JavaScript.
@@ -125,11 +231,8 @@ pub fn macd_batch(
     slow_period: usize,
     signal_period: usize,
 ) -> Result<JsValue, JsError> {
-    let indicator = Macd::new(fast_period, slow_period, signal_period)
-        .map_err(|e| JsError::new(&e.to_string()))?;
-    let results = indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator = Macd::new(fast_period, slow_period, signal_period).map_err(js_error)?;
+    let results = indicator.calculate(data).map_err(js_error)?;
 
     // Convert to separate arrays for JS
     let macd_line: Vec<f64> = results.iter().map(|r| r.macd).collect();
@@ -165,10 +268,8 @@ pub fn macd_batch(
 /// Returns an object with `upper`, `middle`, `lower`, `percentB`, and `bandwidth` arrays.
 #[wasm_bindgen(js_name = "bbands")]
 pub fn bbands_batch(data: &[f64], period: usize, k: f64) -> Result<JsValue, JsError> {
-    let indicator = BBands::new(period, k).map_err(|e| JsError::new(&e.to_string()))?;
-    let results = indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator = BBands::new(period, k).map_err(js_error)?;
+    let results = indicator.calculate(data).map_err(js_error)?;
 
     // Convert to separate arrays for JS
     let upper: Vec<f64> = results.iter().map(|r| r.upper).collect();
@@ -222,10 +323,10 @@ pub fn atr_batch(
     closes: &[f64],
     period: usize,
 ) -> Result<Vec<f64>, JsError> {
-    let indicator = Atr::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator = Atr::new(period).map_err(js_error)?;
     indicator
-        .calculate(&(&highs, &lows, &closes))
-        .map_err(|e| JsError::new(&e.to_string()))
+        .calculate(&(highs, lows, closes))
+        .map_err(js_error)
 }
 
 // ============================================================================
@@ -243,16 +344,14 @@ impl WasmSmaStream {
     /// Create a new streaming SMA calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmSmaStream, JsError> {
-        let inner = SmaStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = SmaStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data. Returns array of SMA values.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<Vec<f64>, JsError> {
-        self.inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(data).map_err(js_error)
     }
 
     /// Process next value. Returns SMA or NaN if not ready.
@@ -260,6 +359,35 @@ impl WasmSmaStream {
         self.inner.next(value).unwrap_or(f64::NAN)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.inner.update(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> f64 {
+        self.inner.peek(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|&value| self.inner.peek(value).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Feed this stream's output into `next`, another single-value stream or
+    /// pipe. Both are consumed; each tick then flows through the chain in WASM.
+    pub fn pipe(self, next: &JsValue) -> Result<WasmPipeStream, JsError> {
+        WasmPipeStream::chain(Box::new(self.inner), next)
+    }
+
     /// Reset the calculator to initial state.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -271,6 +399,17 @@ impl WasmSmaStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -278,6 +417,24 @@ impl WasmSmaStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "SmaStream")]
+impl WasmSmaStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSmaStream, JsError> {
+        let inner = SmaStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 /// Streaming EMA calculator for real-time O(1) updates.
 #[wasm_bindgen(js_name = "EmaStream")]
 pub struct WasmEmaStream {
@@ -289,16 +446,14 @@ impl WasmEmaStream {
     /// Create a new streaming EMA calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmEmaStream, JsError> {
-        let inner = EmaStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = EmaStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data. Returns array of EMA values.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<Vec<f64>, JsError> {
-        self.inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(data).map_err(js_error)
     }
 
     /// Process next value. Returns EMA or NaN if not ready.
@@ -306,6 +461,35 @@ impl WasmEmaStream {
         self.inner.next(value).unwrap_or(f64::NAN)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.inner.update(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> f64 {
+        self.inner.peek(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|&value| self.inner.peek(value).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Feed this stream's output into `next`, another single-value stream or
+    /// pipe. Both are consumed; each tick then flows through the chain in WASM.
+    pub fn pipe(self, next: &JsValue) -> Result<WasmPipeStream, JsError> {
+        WasmPipeStream::chain(Box::new(self.inner), next)
+    }
+
     /// Get current EMA value without consuming a new value.
     pub fn current(&self) -> f64 {
         self.inner.current().unwrap_or(f64::NAN)
@@ -322,6 +506,17 @@ impl WasmEmaStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -335,6 +530,24 @@ impl WasmEmaStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "EmaStream")]
+impl WasmEmaStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmEmaStream, JsError> {
+        let inner = EmaStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 /// Streaming RSI calculator for real-time O(1) updates.
 #[wasm_bindgen(js_name = "RsiStream")]
 pub struct WasmRsiStream {
@@ -346,16 +559,14 @@ impl WasmRsiStream {
     /// Create a new streaming RSI calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmRsiStream, JsError> {
-        let inner = RsiStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = RsiStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data. Returns array of RSI values.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<Vec<f64>, JsError> {
-        self.inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(data).map_err(js_error)
     }
 
     /// Process next value. Returns RSI or NaN if not ready.
@@ -363,6 +574,35 @@ impl WasmRsiStream {
         self.inner.next(value).unwrap_or(f64::NAN)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.inner.update(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> f64 {
+        self.inner.peek(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|&value| self.inner.peek(value).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Feed this stream's output into `next`, another single-value stream or
+    /// pipe. Both are consumed; each tick then flows through the chain in WASM.
+    pub fn pipe(self, next: &JsValue) -> Result<WasmPipeStream, JsError> {
+        WasmPipeStream::chain(Box::new(self.inner), next)
+    }
+
     /// Get current RSI value without consuming a new value.
     pub fn current(&self) -> f64 {
         self.inner.current().unwrap_or(f64::NAN)
@@ -379,6 +619,17 @@ impl WasmRsiStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -386,6 +637,24 @@ impl WasmRsiStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "RsiStream")]
+impl WasmRsiStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmRsiStream, JsError> {
+        let inner = RsiStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 /// Streaming WMA calculator for real-time O(1) updates.
 #[wasm_bindgen(js_name = "WmaStream")]
 pub struct WasmWmaStream {
@@ -397,16 +666,14 @@ impl WasmWmaStream {
     /// Create a new streaming WMA calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmWmaStream, JsError> {
-        let inner = WmaStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = WmaStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data. Returns array of WMA values.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<Vec<f64>, JsError> {
-        self.inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(data).map_err(js_error)
     }
 
     /// Process next value. Returns WMA or NaN if not ready.
@@ -414,6 +681,35 @@ impl WasmWmaStream {
         self.inner.next(value).unwrap_or(f64::NAN)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.inner.update(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> f64 {
+        self.inner.peek(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|&value| self.inner.peek(value).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Feed this stream's output into `next`, another single-value stream or
+    /// pipe. Both are consumed; each tick then flows through the chain in WASM.
+    pub fn pipe(self, next: &JsValue) -> Result<WasmPipeStream, JsError> {
+        WasmPipeStream::chain(Box::new(self.inner), next)
+    }
+
     /// Reset the calculator to initial state.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -425,6 +721,17 @@ impl WasmWmaStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -432,6 +739,24 @@ impl WasmWmaStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "WmaStream")]
+impl WasmWmaStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmWmaStream, JsError> {
+        let inner = WmaStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 /// Streaming MACD calculator for real-time O(1) updates.
 #[wasm_bindgen(js_name = "MacdStream")]
 pub struct WasmMacdStream {
@@ -447,18 +772,14 @@ impl WasmMacdStream {
         slow_period: usize,
         signal_period: usize,
     ) -> Result<WasmMacdStream, JsError> {
-        let inner = MacdStream::new(fast_period, slow_period, signal_period)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = MacdStream::new(fast_period, slow_period, signal_period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data. Returns array of MACD outputs as JS object.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<JsValue, JsError> {
-        let results = self
-            .inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(data).map_err(js_error)?;
 
         // Convert to separate arrays for JS
         let macd_line: Vec<f64> = results.iter().map(|r| r.macd).collect();
@@ -493,6 +814,31 @@ impl WasmMacdStream {
         self.inner.next(value).map(WasmMacdOutput::from)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> Option<WasmMacdOutput> {
+        self.inner.update(value).map(WasmMacdOutput::from)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> Option<WasmMacdOutput> {
+        self.inner.peek(value).map(WasmMacdOutput::from)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<WasmMacdOutput> {
+        values
+            .iter()
+            .map(|&value| {
+                WasmMacdOutput::from(self.inner.peek(value).unwrap_or_else(MacdOutput::nan))
+            })
+            .collect()
+    }
+
     /// Reset the calculator to initial state.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -504,6 +850,17 @@ impl WasmMacdStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the fast period.
     #[wasm_bindgen(getter, js_name = "fastPeriod")]
     pub fn fast_period(&self) -> usize {
@@ -523,6 +880,24 @@ impl WasmMacdStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "MacdStream")]
+impl WasmMacdStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmMacdStream, JsError> {
+        let inner = MacdStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 /// Bollinger Bands output for streaming mode.
 #[wasm_bindgen]
 pub struct WasmBBandsOutput {
@@ -589,17 +964,14 @@ impl WasmBBandsStream {
     /// Create a new streaming Bollinger Bands calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize, k: f64) -> Result<WasmBBandsStream, JsError> {
-        let inner = BBandsStream::new(period, k).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = BBandsStream::new(period, k).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data. Returns object with arrays.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<JsValue, JsError> {
-        let results = self
-            .inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(data).map_err(js_error)?;
 
         // Convert to separate arrays for JS
         let upper: Vec<f64> = results.iter().map(|r| r.upper).collect();
@@ -648,6 +1020,31 @@ impl WasmBBandsStream {
         self.inner.next(value).map(WasmBBandsOutput::from)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> Option<WasmBBandsOutput> {
+        self.inner.update(value).map(WasmBBandsOutput::from)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> Option<WasmBBandsOutput> {
+        self.inner.peek(value).map(WasmBBandsOutput::from)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<WasmBBandsOutput> {
+        values
+            .iter()
+            .map(|&value| {
+                WasmBBandsOutput::from(self.inner.peek(value).unwrap_or_else(BBandsOutput::nan))
+            })
+            .collect()
+    }
+
     /// Reset the calculator to initial state.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -659,6 +1056,17 @@ impl WasmBBandsStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -672,6 +1080,24 @@ impl WasmBBandsStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "BBandsStream")]
+impl WasmBBandsStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmBBandsStream, JsError> {
+        let inner = BBandsStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 /// Streaming ATR calculator for real-time O(1) updates.
 #[wasm_bindgen(js_name = "AtrStream")]
 pub struct WasmAtrStream {
@@ -683,7 +1109,7 @@ impl WasmAtrStream {
     /// Create a new streaming ATR calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmAtrStream, JsError> {
-        let inner = AtrStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = AtrStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -697,11 +1123,12 @@ impl WasmAtrStream {
         lows: &[f64],
         closes: &[f64],
     ) -> Result<Vec<f64>, JsError> {
-        if highs.len() != lows.len() || lows.len() != closes.len() {
-            return Err(JsError::new(
-                "highs, lows, and closes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
 
         let bars: Vec<AtrBar> = highs
             .iter()
@@ -710,9 +1137,7 @@ impl WasmAtrStream {
             .map(|((&h, &l), &c)| (h, l, c))
             .collect();
 
-        self.inner
-            .init(&bars)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(&bars).map_err(js_error)
     }
 
     /// Process next bar. Takes high, low, close.
@@ -721,6 +1146,18 @@ impl WasmAtrStream {
         self.inner.next((high, low, close)).unwrap_or(f64::NAN)
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
+        self.inner.update((high, low, close)).unwrap_or(f64::NAN)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> f64 {
+        self.inner.peek((high, low, close)).unwrap_or(f64::NAN)
+    }
+
     /// Get current ATR value without consuming a new bar.
     pub fn current(&self) -> f64 {
         self.inner.current().unwrap_or(f64::NAN)
@@ -737,6 +1174,17 @@ impl WasmAtrStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -744,6 +1192,24 @@ impl WasmAtrStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "AtrStream")]
+impl WasmAtrStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmAtrStream, JsError> {
+        let inner = AtrStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // Stochastic Oscillator
 // ============================================================================
@@ -790,11 +1256,10 @@ pub fn stoch_fast_batch(
     k_period: usize,
     d_period: usize,
 ) -> Result<JsValue, JsError> {
-    let indicator = Stoch::new(k_period, d_period, StochType::Fast)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator = Stoch::new(k_period, d_period, StochType::Fast).map_err(js_error)?;
     let results = indicator
-        .calculate(&(&highs, &lows, &closes))
-        .map_err(|e| JsError::new(&e.to_string()))?;
+        .calculate(&(highs, lows, closes))
+        .map_err(js_error)?;
 
     // Convert to separate arrays for JS
     let k_line: Vec<f64> = results.iter().map(|r| r.k).collect();
@@ -829,11 +1294,11 @@ pub fn stoch_slow_batch(
     d_period: usize,
     slowing: usize,
 ) -> Result<JsValue, JsError> {
-    let indicator = Stoch::new_with_slowing(k_period, d_period, slowing, StochType::Slow)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator =
+        Stoch::new_with_slowing(k_period, d_period, slowing, StochType::Slow).map_err(js_error)?;
     let results = indicator
-        .calculate(&(&highs, &lows, &closes))
-        .map_err(|e| JsError::new(&e.to_string()))?;
+        .calculate(&(highs, lows, closes))
+        .map_err(js_error)?;
 
     // Convert to separate arrays for JS
     let k_line: Vec<f64> = results.iter().map(|r| r.k).collect();
@@ -867,8 +1332,7 @@ impl WasmStochFastStream {
     /// Create a new streaming Fast Stochastic calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(k_period: usize, d_period: usize) -> Result<WasmStochFastStream, JsError> {
-        let inner = StochStream::new(k_period, d_period, StochType::Fast)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = StochStream::new(k_period, d_period, StochType::Fast).map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -881,11 +1345,12 @@ impl WasmStochFastStream {
         lows: &[f64],
         closes: &[f64],
     ) -> Result<JsValue, JsError> {
-        if highs.len() != lows.len() || highs.len() != closes.len() {
-            return Err(JsError::new(
-                "highs, lows, and closes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
 
         let bars: Vec<StochBar> = highs
             .iter()
@@ -894,10 +1359,7 @@ impl WasmStochFastStream {
             .map(|((&h, &l), &c)| (h, l, c))
             .collect();
 
-        let results = self
-            .inner
-            .init(&bars)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(&bars).map_err(js_error)?;
 
         // Convert to separate arrays for JS
         let k_line: Vec<f64> = results.iter().map(|r| r.k).collect();
@@ -928,6 +1390,22 @@ impl WasmStochFastStream {
             .map(WasmStochOutput::from)
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<WasmStochOutput> {
+        self.inner
+            .update((high, low, close))
+            .map(WasmStochOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> Option<WasmStochOutput> {
+        self.inner
+            .peek((high, low, close))
+            .map(WasmStochOutput::from)
+    }
+
     /// Reset the calculator to initial state.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -939,6 +1417,17 @@ impl WasmStochFastStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the K period.
     #[wasm_bindgen(getter, js_name = "kPeriod")]
     pub fn k_period(&self) -> usize {
@@ -952,6 +1441,29 @@ impl WasmStochFastStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "StochFastStream")]
+impl WasmStochFastStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmStochFastStream, JsError> {
+        let inner = StochStream::from_bytes(bytes).map_err(js_error)?;
+        if inner.stoch_type() != StochType::Fast {
+            return Err(js_error(IndicatorError::InvalidSnapshot(
+                "not a fast stochastic stream".to_string(),
+            )));
+        }
+        Ok(Self { inner })
+    }
+}
+
 /// Streaming Slow Stochastic calculator for real-time O(1) updates.
 #[wasm_bindgen(js_name = "StochSlowStream")]
 pub struct WasmStochSlowStream {
@@ -968,7 +1480,7 @@ impl WasmStochSlowStream {
         slowing: usize,
     ) -> Result<WasmStochSlowStream, JsError> {
         let inner = StochStream::new_with_slowing(k_period, d_period, slowing, StochType::Slow)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+            .map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -981,11 +1493,12 @@ impl WasmStochSlowStream {
         lows: &[f64],
         closes: &[f64],
     ) -> Result<JsValue, JsError> {
-        if highs.len() != lows.len() || highs.len() != closes.len() {
-            return Err(JsError::new(
-                "highs, lows, and closes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
 
         let bars: Vec<StochBar> = highs
             .iter()
@@ -994,10 +1507,7 @@ impl WasmStochSlowStream {
             .map(|((&h, &l), &c)| (h, l, c))
             .collect();
 
-        let results = self
-            .inner
-            .init(&bars)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(&bars).map_err(js_error)?;
 
         // Convert to separate arrays for JS
         let k_line: Vec<f64> = results.iter().map(|r| r.k).collect();
@@ -1028,6 +1538,22 @@ impl WasmStochSlowStream {
             .map(WasmStochOutput::from)
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<WasmStochOutput> {
+        self.inner
+            .update((high, low, close))
+            .map(WasmStochOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> Option<WasmStochOutput> {
+        self.inner
+            .peek((high, low, close))
+            .map(WasmStochOutput::from)
+    }
+
     /// Reset the calculator to initial state.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -1039,6 +1565,17 @@ impl WasmStochSlowStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the K period.
     #[wasm_bindgen(getter, js_name = "kPeriod")]
     pub fn k_period(&self) -> usize {
@@ -1058,6 +1595,29 @@ impl WasmStochSlowStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "StochSlowStream")]
+impl WasmStochSlowStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmStochSlowStream, JsError> {
+        let inner = StochStream::from_bytes(bytes).map_err(js_error)?;
+        if inner.stoch_type() != StochType::Slow {
+            return Err(js_error(IndicatorError::InvalidSnapshot(
+                "not a slow stochastic stream".to_string(),
+            )));
+        }
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // Stochastic RSI
 // ============================================================================
@@ -1104,11 +1664,9 @@ pub fn stoch_rsi_batch(
     k_smooth: usize,
     d_period: usize,
 ) -> Result<JsValue, JsError> {
-    let indicator = StochRsi::new(rsi_period, stoch_period, k_smooth, d_period)
-        .map_err(|e| JsError::new(&e.to_string()))?;
-    let results = indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator =
+        StochRsi::new(rsi_period, stoch_period, k_smooth, d_period).map_err(js_error)?;
+    let results = indicator.calculate(data).map_err(js_error)?;
 
     // Convert to separate arrays for JS
     let k_line: Vec<f64> = results.iter().map(|r| r.k).collect();
@@ -1147,8 +1705,8 @@ impl WasmStochRsiStream {
         k_smooth: usize,
         d_period: usize,
     ) -> Result<WasmStochRsiStream, JsError> {
-        let inner = StochRsiStream::new(rsi_period, stoch_period, k_smooth, d_period)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let inner =
+            StochRsiStream::new(rsi_period, stoch_period, k_smooth, d_period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -1156,10 +1714,7 @@ impl WasmStochRsiStream {
     /// Returns an object with `k` and `d` arrays.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<JsValue, JsError> {
-        let results = self
-            .inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(data).map_err(js_error)?;
 
         // Convert to separate arrays for JS
         let k_line: Vec<f64> = results.iter().map(|r| r.k).collect();
@@ -1187,6 +1742,31 @@ impl WasmStochRsiStream {
         self.inner.next(value).map(WasmStochRsiOutput::from)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> Option<WasmStochRsiOutput> {
+        self.inner.update(value).map(WasmStochRsiOutput::from)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> Option<WasmStochRsiOutput> {
+        self.inner.peek(value).map(WasmStochRsiOutput::from)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<WasmStochRsiOutput> {
+        values
+            .iter()
+            .map(|&value| {
+                WasmStochRsiOutput::from(self.inner.peek(value).unwrap_or_else(StochRsiOutput::nan))
+            })
+            .collect()
+    }
+
     /// Reset the calculator to initial state.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -1198,6 +1778,17 @@ impl WasmStochRsiStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the RSI period.
     #[wasm_bindgen(getter, js_name = "rsiPeriod")]
     pub fn rsi_period(&self) -> usize {
@@ -1223,6 +1814,24 @@ impl WasmStochRsiStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "StochRsiStream")]
+impl WasmStochRsiStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmStochRsiStream, JsError> {
+        let inner = StochRsiStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // Cumulative Volume Delta (CVD)
 // ============================================================================
@@ -1236,8 +1845,7 @@ impl WasmStochRsiStream {
 #[wasm_bindgen(js_name = "cvd")]
 pub fn cvd_batch(deltas: &[f64]) -> Result<Vec<f64>, JsError> {
     let cvd = Cvd::new();
-    cvd.calculate(deltas)
-        .map_err(|e| JsError::new(&e.to_string()))
+    cvd.calculate(deltas).map_err(js_error)
 }
 
 /// Calculate CVD from OHLCV data using volume approximation.
@@ -1256,11 +1864,13 @@ pub fn cvd_ohlcv_batch(
     closes: &[f64],
     volumes: &[f64],
 ) -> Result<Vec<f64>, JsError> {
-    if highs.len() != lows.len() || highs.len() != closes.len() || highs.len() != volumes.len() {
-        return Err(JsError::new(
-            "highs, lows, closes, and volumes must have the same length",
-        ));
-    }
+    check_lengths(&[
+        ("highs", highs.len()),
+        ("lows", lows.len()),
+        ("closes", closes.len()),
+        ("volumes", volumes.len()),
+    ])
+    .map_err(js_error)?;
 
     let bars: Vec<CvdBar> = highs
         .iter()
@@ -1271,8 +1881,7 @@ pub fn cvd_ohlcv_batch(
         .collect();
 
     let cvd = CvdOhlcv::new();
-    cvd.calculate(&bars)
-        .map_err(|e| JsError::new(&e.to_string()))
+    cvd.calculate(bars.as_slice()).map_err(js_error)
 }
 
 /// Streaming CVD calculator for pre-computed delta values.
@@ -1295,9 +1904,7 @@ impl WasmCvdStream {
     /// Returns array of CVD values.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, deltas: &[f64]) -> Result<Vec<f64>, JsError> {
-        self.inner
-            .init(deltas)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(deltas).map_err(js_error)
     }
 
     /// Process next delta value. Returns CVD value or undefined if NaN input.
@@ -1305,6 +1912,35 @@ impl WasmCvdStream {
         self.inner.next(delta)
     }
 
+    /// Replace the most recent delta with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, delta: f64) -> Option<f64> {
+        self.inner.update(delta)
+    }
+
+    /// Preview the output `next` would return for this delta, without
+    /// changing any state.
+    pub fn peek(&self, delta: f64) -> Option<f64> {
+        self.inner.peek(delta)
+    }
+
+    /// Preview `next` for each candidate delta independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, deltas: &[f64]) -> Vec<f64> {
+        deltas
+            .iter()
+            .map(|&delta| self.inner.peek(delta).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Feed this stream's output into `next`, another single-value stream or
+    /// pipe. Both are consumed; each tick then flows through the chain in WASM.
+    pub fn pipe(self, next: &JsValue) -> Result<WasmPipeStream, JsError> {
+        WasmPipeStream::chain(Box::new(self.inner), next)
+    }
+
     /// Get current CVD value without consuming a new delta.
     pub fn current(&self) -> Option<f64> {
         self.inner.current()
@@ -1320,6 +1956,35 @@ impl WasmCvdStream {
     pub fn is_ready(&self) -> bool {
         self.inner.is_ready()
     }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "CvdStream")]
+impl WasmCvdStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmCvdStream, JsError> {
+        let inner = CvdStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
 }
 
 /// Streaming CVD calculator for OHLCV data.
@@ -1349,12 +2014,13 @@ impl WasmCvdOhlcvStream {
         closes: &[f64],
         volumes: &[f64],
     ) -> Result<Vec<f64>, JsError> {
-        if highs.len() != lows.len() || highs.len() != closes.len() || highs.len() != volumes.len()
-        {
-            return Err(JsError::new(
-                "highs, lows, closes, and volumes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+            ("volumes", volumes.len()),
+        ])
+        .map_err(js_error)?;
 
         let bars: Vec<CvdBar> = highs
             .iter()
@@ -1364,9 +2030,7 @@ impl WasmCvdOhlcvStream {
             .map(|(((&h, &l), &c), &v)| (h, l, c, v))
             .collect();
 
-        self.inner
-            .init(&bars)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(&bars).map_err(js_error)
     }
 
     /// Process next bar. Takes high, low, close, volume.
@@ -1375,6 +2039,18 @@ impl WasmCvdOhlcvStream {
         self.inner.next((high, low, close, volume))
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> Option<f64> {
+        self.inner.update((high, low, close, volume))
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64, volume: f64) -> Option<f64> {
+        self.inner.peek((high, low, close, volume))
+    }
+
     /// Get current CVD value without consuming a new bar.
     pub fn current(&self) -> Option<f64> {
         self.inner.current()
@@ -1390,6 +2066,35 @@ impl WasmCvdOhlcvStream {
     pub fn is_ready(&self) -> bool {
         self.inner.is_ready()
     }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "CvdOhlcvStream")]
+impl WasmCvdOhlcvStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmCvdOhlcvStream, JsError> {
+        let inner = CvdOhlcvStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
 }
 
 // ============================================================================
@@ -1405,15 +2110,15 @@ fn arrays_to_ohlcv(
     closes: &[f64],
     volumes: &[f64],
 ) -> Result<Vec<OHLCV>, JsError> {
-    let len = timestamps.len();
-    if opens.len() != len
-        || highs.len() != len
-        || lows.len() != len
-        || closes.len() != len
-        || volumes.len() != len
-    {
-        return Err(JsError::new("All OHLCV arrays must have the same length"));
-    }
+    check_lengths(&[
+        ("timestamps", timestamps.len()),
+        ("opens", opens.len()),
+        ("highs", highs.len()),
+        ("lows", lows.len()),
+        ("closes", closes.len()),
+        ("volumes", volumes.len()),
+    ])
+    .map_err(js_error)?;
 
     Ok(timestamps
         .iter()
@@ -1426,6 +2131,11 @@ fn arrays_to_ohlcv(
         .collect())
 }
 
+/// Helper function to convert JS timestamps (Unix ms as f64) to i64.
+fn to_millis(timestamps: &[f64]) -> Vec<i64> {
+    timestamps.iter().map(|&t| t as i64).collect()
+}
+
 /// Calculate Session VWAP (resets daily at UTC midnight).
 ///
 /// Takes OHLCV arrays and returns VWAP values.
@@ -1438,10 +2148,32 @@ pub fn session_vwap_batch(
     closes: &[f64],
     volumes: &[f64],
 ) -> Result<Vec<f64>, JsError> {
-    let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+    let timestamps = to_millis(timestamps);
+    let columns =
+        OhlcvColumns::new(&timestamps, opens, highs, lows, closes, volumes).map_err(js_error)?;
     let vwap = SessionVwap::new();
-    vwap.calculate(&candles)
-        .map_err(|e| JsError::new(&e.to_string()))
+    vwap.calculate_columns(&columns).map_err(js_error)
+}
+
+/// Calculate Session VWAP resetting at each open of an exchange `session`.
+///
+/// Candles outside the session are NaN.
+#[wasm_bindgen(js_name = "sessionVwapWithSession")]
+pub fn session_vwap_with_session(
+    timestamps: &[f64],
+    opens: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[f64],
+    session: &WasmSession,
+) -> Result<Vec<f64>, JsError> {
+    let timestamps = to_millis(timestamps);
+    let columns =
+        OhlcvColumns::new(&timestamps, opens, highs, lows, closes, volumes).map_err(js_error)?;
+    SessionVwap::with_session(session.inner.clone())
+        .calculate_columns(&columns)
+        .map_err(js_error)
 }
 
 /// Calculate Rolling VWAP with a sliding window.
@@ -1457,10 +2189,11 @@ pub fn rolling_vwap_batch(
     volumes: &[f64],
     period: usize,
 ) -> Result<Vec<f64>, JsError> {
-    let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
-    let vwap = RollingVwap::new(period).map_err(|e| JsError::new(&e.to_string()))?;
-    vwap.calculate(&candles)
-        .map_err(|e| JsError::new(&e.to_string()))
+    let timestamps = to_millis(timestamps);
+    let columns =
+        OhlcvColumns::new(&timestamps, opens, highs, lows, closes, volumes).map_err(js_error)?;
+    let vwap = RollingVwap::new(period).map_err(js_error)?;
+    vwap.calculate_columns(&columns).map_err(js_error)
 }
 
 /// Calculate Anchored VWAP starting from a specific index.
@@ -1476,10 +2209,11 @@ pub fn anchored_vwap_batch(
     volumes: &[f64],
     anchor_index: usize,
 ) -> Result<Vec<f64>, JsError> {
-    let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+    let timestamps = to_millis(timestamps);
+    let columns =
+        OhlcvColumns::new(&timestamps, opens, highs, lows, closes, volumes).map_err(js_error)?;
     let vwap = AnchoredVwap::new(anchor_index);
-    vwap.calculate(&candles)
-        .map_err(|e| JsError::new(&e.to_string()))
+    vwap.calculate_columns(&columns).map_err(js_error)
 }
 
 /// Calculate Anchored VWAP starting from a specific timestamp.
@@ -1495,11 +2229,15 @@ pub fn anchored_vwap_from_timestamp_batch(
     volumes: &[f64],
     anchor_timestamp: f64,
 ) -> Result<Vec<f64>, JsError> {
-    let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
-    let vwap = AnchoredVwap::from_timestamp(&candles, anchor_timestamp as i64)
+    let timestamps = to_millis(timestamps);
+    let columns =
+        OhlcvColumns::new(&timestamps, opens, highs, lows, closes, volumes).map_err(js_error)?;
+    let anchor_index = timestamps
+        .iter()
+        .position(|&t| t >= anchor_timestamp as i64)
         .ok_or_else(|| JsError::new("No candle found at or after anchor timestamp"))?;
-    vwap.calculate(&candles)
-        .map_err(|e| JsError::new(&e.to_string()))
+    let vwap = AnchoredVwap::new(anchor_index);
+    vwap.calculate_columns(&columns).map_err(js_error)
 }
 
 /// Streaming Session VWAP calculator (resets daily at UTC midnight).
@@ -1518,6 +2256,15 @@ impl WasmSessionVwapStream {
         }
     }
 
+    /// Create a streaming Session VWAP calculator resetting at each open of
+    /// an exchange `session`.
+    #[wasm_bindgen(js_name = "withSession")]
+    pub fn with_session(session: &WasmSession) -> WasmSessionVwapStream {
+        Self {
+            inner: SessionVwapStream::with_session(session.inner.clone()),
+        }
+    }
+
     /// Initialize with historical OHLCV data.
     /// Returns array of VWAP values.
     #[wasm_bindgen(js_name = "init")]
@@ -1531,9 +2278,7 @@ impl WasmSessionVwapStream {
         volumes: &[f64],
     ) -> Result<Vec<f64>, JsError> {
         let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
-        self.inner
-            .init(&candles)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(&candles).map_err(js_error)
     }
 
     /// Process next candle. Returns VWAP value.
@@ -1550,6 +2295,36 @@ impl WasmSessionVwapStream {
         self.inner.next(candle)
     }
 
+    /// Replace the most recent candle with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<f64> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.update(candle)
+    }
+
+    /// Preview the output `next` would return for this candle, without
+    /// changing any state.
+    pub fn peek(
+        &self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<f64> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.peek(candle)
+    }
+
     /// Get current VWAP value without consuming a new candle.
     pub fn current(&self) -> Option<f64> {
         self.inner.current()
@@ -1577,6 +2352,35 @@ impl WasmSessionVwapStream {
     pub fn is_ready(&self) -> bool {
         self.inner.is_ready()
     }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "SessionVwapStream")]
+impl WasmSessionVwapStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSessionVwapStream, JsError> {
+        let inner = SessionVwapStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
 }
 
 /// Streaming Rolling VWAP calculator with sliding window.
@@ -1590,7 +2394,7 @@ impl WasmRollingVwapStream {
     /// Create a new streaming Rolling VWAP calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmRollingVwapStream, JsError> {
-        let inner = RollingVwapStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = RollingVwapStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -1607,9 +2411,7 @@ impl WasmRollingVwapStream {
         volumes: &[f64],
     ) -> Result<Vec<f64>, JsError> {
         let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
-        self.inner
-            .init(&candles)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(&candles).map_err(js_error)
     }
 
     /// Process next candle. Returns VWAP value or NaN if not ready.
@@ -1626,6 +2428,36 @@ impl WasmRollingVwapStream {
         self.inner.next(candle).unwrap_or(f64::NAN)
     }
 
+    /// Replace the most recent candle with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> f64 {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.update(candle).unwrap_or(f64::NAN)
+    }
+
+    /// Preview the output `next` would return for this candle, without
+    /// changing any state.
+    pub fn peek(
+        &self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> f64 {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.peek(candle).unwrap_or(f64::NAN)
+    }
+
     /// Get current VWAP value without consuming a new candle.
     pub fn current(&self) -> f64 {
         self.inner.current().unwrap_or(f64::NAN)
@@ -1642,6 +2474,17 @@ impl WasmRollingVwapStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -1649,6 +2492,24 @@ impl WasmRollingVwapStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "RollingVwapStream")]
+impl WasmRollingVwapStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmRollingVwapStream, JsError> {
+        let inner = RollingVwapStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 /// Streaming Anchored VWAP calculator.
 #[wasm_bindgen(js_name = "AnchoredVwapStream")]
 pub struct WasmAnchoredVwapStream {
@@ -1699,9 +2560,7 @@ impl WasmAnchoredVwapStream {
         volumes: &[f64],
     ) -> Result<Vec<f64>, JsError> {
         let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
-        self.inner
-            .init(&candles)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(&candles).map_err(js_error)
     }
 
     /// Process next candle. Returns VWAP value or undefined if before anchor.
@@ -1718,6 +2577,36 @@ impl WasmAnchoredVwapStream {
         self.inner.next(candle)
     }
 
+    /// Replace the most recent candle with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<f64> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.update(candle)
+    }
+
+    /// Preview the output `next` would return for this candle, without
+    /// changing any state.
+    pub fn peek(
+        &self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<f64> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.peek(candle)
+    }
+
     /// Get current VWAP value without consuming a new candle.
     pub fn current(&self) -> Option<f64> {
         self.inner.current()
@@ -1751,6 +2640,35 @@ impl WasmAnchoredVwapStream {
     pub fn is_ready(&self) -> bool {
         self.inner.is_ready()
     }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "AnchoredVwapStream")]
+impl WasmAnchoredVwapStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmAnchoredVwapStream, JsError> {
+        let inner = AnchoredVwapStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
 }
 
 // ============================================================================
@@ -1879,9 +2797,7 @@ pub fn pivot_points_batch(
 ) -> Result<JsValue, JsError> {
     let pp_variant = parse_pivot_variant(variant)?;
     let pp = PivotPoints::new(pp_variant);
-    let results: Vec<PivotPointsOutput> = pp
-        .calculate((highs, lows, closes))
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let results: Vec<PivotPointsOutput> = pp.calculate((highs, lows, closes)).map_err(js_error)?;
 
     // Convert to separate arrays for JS
     let pivots: Vec<f64> = results.iter().map(|r| r.pivot).collect();
@@ -2128,12 +3044,13 @@ pub fn frvp_batch(
     num_bins: Option<usize>,
     value_area_percent: Option<f64>,
 ) -> Result<WasmFrvpOutput, JsError> {
-    let len = highs.len();
-    if lows.len() != len || closes.len() != len || volumes.len() != len {
-        return Err(JsError::new(
-            "highs, lows, closes, and volumes must have the same length",
-        ));
-    }
+    check_lengths(&[
+        ("highs", highs.len()),
+        ("lows", lows.len()),
+        ("closes", closes.len()),
+        ("volumes", volumes.len()),
+    ])
+    .map_err(js_error)?;
 
     // Build OHLCV from arrays (using dummy timestamp and open)
     let candles: Vec<OHLCV> = highs
@@ -2148,12 +3065,9 @@ pub fn frvp_batch(
     let num_bins = num_bins.unwrap_or(100);
     let value_area_percent = value_area_percent.unwrap_or(0.70);
 
-    let frvp = Frvp::with_value_area(num_bins, value_area_percent)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let frvp = Frvp::with_value_area(num_bins, value_area_percent).map_err(js_error)?;
 
-    let result = frvp
-        .calculate(&candles)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let result = frvp.calculate(candles.as_slice()).map_err(js_error)?;
 
     Ok(WasmFrvpOutput::from(result))
 }
@@ -2179,7 +3093,7 @@ impl WasmFrvpStream {
             Some(pct) => FrvpStream::with_value_area(num_bins, pct),
             None => FrvpStream::new(num_bins),
         }
-        .map_err(|e| JsError::new(&e.to_string()))?;
+        .map_err(js_error)?;
 
         Ok(Self { inner })
     }
@@ -2199,12 +3113,13 @@ impl WasmFrvpStream {
         closes: &[f64],
         volumes: &[f64],
     ) -> Result<Option<WasmFrvpOutput>, JsError> {
-        let len = highs.len();
-        if lows.len() != len || closes.len() != len || volumes.len() != len {
-            return Err(JsError::new(
-                "highs, lows, closes, and volumes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+            ("volumes", volumes.len()),
+        ])
+        .map_err(js_error)?;
 
         let candles: Vec<OHLCV> = highs
             .iter()
@@ -2215,10 +3130,7 @@ impl WasmFrvpStream {
             .map(|(i, (((&h, &l), &c), &v))| OHLCV::new(i as i64, l, h, l, c, v))
             .collect();
 
-        let results = self
-            .inner
-            .init(&candles)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(&candles).map_err(js_error)?;
 
         Ok(results.into_iter().next().map(WasmFrvpOutput::from))
     }
@@ -2235,6 +3147,26 @@ impl WasmFrvpStream {
         self.inner.next(candle).map(WasmFrvpOutput::from)
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(
+        &mut self,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<WasmFrvpOutput> {
+        let candle = OHLCV::new(0, low, high, low, close, volume);
+        self.inner.update(candle).map(WasmFrvpOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64, volume: f64) -> Option<WasmFrvpOutput> {
+        let candle = OHLCV::new(0, low, high, low, close, volume);
+        self.inner.peek(candle).map(WasmFrvpOutput::from)
+    }
+
     /// Reset the calculator and clear all candles.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -2246,6 +3178,17 @@ impl WasmFrvpStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the number of price bins.
     #[wasm_bindgen(getter, js_name = "numBins")]
     pub fn num_bins(&self) -> usize {
@@ -2264,6 +3207,24 @@ impl WasmFrvpStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "FrvpStream")]
+impl WasmFrvpStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmFrvpStream, JsError> {
+        let inner = FrvpStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // MFI (Money Flow Index)
 // ============================================================================
@@ -2279,10 +3240,10 @@ pub fn mfi_batch(
     volumes: &[f64],
     period: usize,
 ) -> Result<Vec<f64>, JsError> {
-    let indicator = Mfi::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator = Mfi::new(period).map_err(js_error)?;
     indicator
-        .calculate(&(&highs, &lows, &closes, &volumes))
-        .map_err(|e| JsError::new(&e.to_string()))
+        .calculate(&(highs, lows, closes, volumes))
+        .map_err(js_error)
 }
 
 /// Streaming MFI calculator for real-time O(1) updates.
@@ -2296,7 +3257,7 @@ impl WasmMfiStream {
     /// Create a new streaming MFI calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmMfiStream, JsError> {
-        let inner = MfiStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = MfiStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -2309,12 +3270,13 @@ impl WasmMfiStream {
         closes: &[f64],
         volumes: &[f64],
     ) -> Result<Vec<f64>, JsError> {
-        if highs.len() != lows.len() || lows.len() != closes.len() || closes.len() != volumes.len()
-        {
-            return Err(JsError::new(
-                "highs, lows, closes, and volumes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+            ("volumes", volumes.len()),
+        ])
+        .map_err(js_error)?;
 
         let bars: Vec<MfiBar> = highs
             .iter()
@@ -2324,9 +3286,7 @@ impl WasmMfiStream {
             .map(|(((&h, &l), &c), &v)| (h, l, c, v))
             .collect();
 
-        self.inner
-            .init(&bars)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(&bars).map_err(js_error)
     }
 
     /// Process next bar.
@@ -2334,6 +3294,18 @@ impl WasmMfiStream {
         self.inner.next((high, low, close, volume))
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> Option<f64> {
+        self.inner.update((high, low, close, volume))
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64, volume: f64) -> Option<f64> {
+        self.inner.peek((high, low, close, volume))
+    }
+
     /// Get current MFI value.
     pub fn current(&self) -> Option<f64> {
         self.inner.current()
@@ -2350,6 +3322,17 @@ impl WasmMfiStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -2357,6 +3340,24 @@ impl WasmMfiStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "MfiStream")]
+impl WasmMfiStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmMfiStream, JsError> {
+        let inner = MfiStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // HMA (Hull Moving Average)
 // ============================================================================
@@ -2366,10 +3367,8 @@ impl WasmMfiStream {
 /// Returns Float64Array with NaN for insufficient data points.
 #[wasm_bindgen(js_name = "hma")]
 pub fn hma_batch(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
-    let indicator = Hma::new(period).map_err(|e| JsError::new(&e.to_string()))?;
-    indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))
+    let indicator = Hma::new(period).map_err(js_error)?;
+    indicator.calculate(data).map_err(js_error)
 }
 
 /// Streaming HMA calculator for real-time updates.
@@ -2383,16 +3382,14 @@ impl WasmHmaStream {
     /// Create a new streaming HMA calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmHmaStream, JsError> {
-        let inner = HmaStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = HmaStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<Vec<f64>, JsError> {
-        self.inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))
+        self.inner.init(data).map_err(js_error)
     }
 
     /// Process next value.
@@ -2400,6 +3397,35 @@ impl WasmHmaStream {
         self.inner.next(value)
     }
 
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.inner.update(value)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> Option<f64> {
+        self.inner.peek(value)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|&value| self.inner.peek(value).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Feed this stream's output into `next`, another single-value stream or
+    /// pipe. Both are consumed; each tick then flows through the chain in WASM.
+    pub fn pipe(self, next: &JsValue) -> Result<WasmPipeStream, JsError> {
+        WasmPipeStream::chain(Box::new(self.inner), next)
+    }
+
     /// Reset the calculator.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -2411,6 +3437,17 @@ impl WasmHmaStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -2430,6 +3467,24 @@ impl WasmHmaStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "HmaStream")]
+impl WasmHmaStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmHmaStream, JsError> {
+        let inner = HmaStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // Ichimoku Cloud
 // ============================================================================
@@ -2501,11 +3556,11 @@ pub fn ichimoku_batch(
     kijun_period: usize,
     senkou_b_period: usize,
 ) -> Result<JsValue, JsError> {
-    let indicator = Ichimoku::new(tenkan_period, kijun_period, senkou_b_period)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator =
+        Ichimoku::new(tenkan_period, kijun_period, senkou_b_period).map_err(js_error)?;
     let results = indicator
-        .calculate(&(&highs, &lows, &closes))
-        .map_err(|e| JsError::new(&e.to_string()))?;
+        .calculate(&(highs, lows, closes))
+        .map_err(js_error)?;
 
     let tenkan: Vec<f64> = results.iter().map(|r| r.tenkan_sen).collect();
     let kijun: Vec<f64> = results.iter().map(|r| r.kijun_sen).collect();
@@ -2568,7 +3623,7 @@ impl WasmIchimokuStream {
             kijun_period.unwrap_or(26),
             senkou_b_period.unwrap_or(52),
         )
-        .map_err(|e| JsError::new(&e.to_string()))?;
+        .map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -2580,11 +3635,12 @@ impl WasmIchimokuStream {
         lows: &[f64],
         closes: &[f64],
     ) -> Result<JsValue, JsError> {
-        if highs.len() != lows.len() || lows.len() != closes.len() {
-            return Err(JsError::new(
-                "highs, lows, and closes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
 
         let bars: Vec<IchimokuBar> = highs
             .iter()
@@ -2593,10 +3649,7 @@ impl WasmIchimokuStream {
             .map(|((&h, &l), &c)| (h, l, c))
             .collect();
 
-        let results = self
-            .inner
-            .init(&bars)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(&bars).map_err(js_error)?;
 
         let tenkan: Vec<f64> = results.iter().map(|r| r.tenkan_sen).collect();
         let kijun: Vec<f64> = results.iter().map(|r| r.kijun_sen).collect();
@@ -2646,6 +3699,22 @@ impl WasmIchimokuStream {
             .map(WasmIchimokuOutput::from)
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<WasmIchimokuOutput> {
+        self.inner
+            .update((high, low, close))
+            .map(WasmIchimokuOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> Option<WasmIchimokuOutput> {
+        self.inner
+            .peek((high, low, close))
+            .map(WasmIchimokuOutput::from)
+    }
+
     /// Reset the calculator.
     pub fn reset(&mut self) {
         self.inner.reset();
@@ -2657,6 +3726,17 @@ impl WasmIchimokuStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the Tenkan-sen period.
     #[wasm_bindgen(getter, js_name = "tenkanPeriod")]
     pub fn tenkan_period(&self) -> usize {
@@ -2676,6 +3756,24 @@ impl WasmIchimokuStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "IchimokuStream")]
+impl WasmIchimokuStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmIchimokuStream, JsError> {
+        let inner = IchimokuStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // ADX (Average Directional Index)
 // ============================================================================
@@ -2729,10 +3827,10 @@ pub fn adx_batch(
     closes: &[f64],
     period: usize,
 ) -> Result<JsValue, JsError> {
-    let indicator = Adx::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator = Adx::new(period).map_err(js_error)?;
     let results = indicator
-        .calculate(&(&highs, &lows, &closes))
-        .map_err(|e| JsError::new(&e.to_string()))?;
+        .calculate(&(highs, lows, closes))
+        .map_err(js_error)?;
 
     let adx_vals: Vec<f64> = results.iter().map(|r| r.adx).collect();
     let plus_di: Vec<f64> = results.iter().map(|r| r.plus_di).collect();
@@ -2772,7 +3870,7 @@ impl WasmAdxStream {
     /// Create a new streaming ADX calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize) -> Result<WasmAdxStream, JsError> {
-        let inner = AdxStream::new(period).map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = AdxStream::new(period).map_err(js_error)?;
         Ok(Self { inner })
     }
 
@@ -2784,11 +3882,12 @@ impl WasmAdxStream {
         lows: &[f64],
         closes: &[f64],
     ) -> Result<JsValue, JsError> {
-        if highs.len() != lows.len() || lows.len() != closes.len() {
-            return Err(JsError::new(
-                "highs, lows, and closes must have the same length",
-            ));
-        }
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
 
         let bars: Vec<AdxBar> = highs
             .iter()
@@ -2797,10 +3896,7 @@ impl WasmAdxStream {
             .map(|((&h, &l), &c)| (h, l, c))
             .collect();
 
-        let results = self
-            .inner
-            .init(&bars)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(&bars).map_err(js_error)?;
 
         let adx_vals: Vec<f64> = results.iter().map(|r| r.adx).collect();
         let plus_di: Vec<f64> = results.iter().map(|r| r.plus_di).collect();
@@ -2834,6 +3930,20 @@ impl WasmAdxStream {
         self.inner.next((high, low, close)).map(WasmAdxOutput::from)
     }
 
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<WasmAdxOutput> {
+        self.inner
+            .update((high, low, close))
+            .map(WasmAdxOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> Option<WasmAdxOutput> {
+        self.inner.peek((high, low, close)).map(WasmAdxOutput::from)
+    }
+
     /// Get current values.
     pub fn current(&self) -> Option<WasmAdxOutput> {
         self.inner.current().map(WasmAdxOutput::from)
@@ -2850,6 +3960,17 @@ impl WasmAdxStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -2857,6 +3978,24 @@ impl WasmAdxStream {
     }
 }
 
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "AdxStream")]
+impl WasmAdxStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmAdxStream, JsError> {
+        let inner = AdxStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
 // ============================================================================
 // Linear Regression Channels
 // ============================================================================
@@ -2933,11 +4072,8 @@ pub fn linreg_batch(
     period: usize,
     num_std_dev: Option<f64>,
 ) -> Result<JsValue, JsError> {
-    let indicator = LinReg::new(period, num_std_dev.unwrap_or(2.0))
-        .map_err(|e| JsError::new(&e.to_string()))?;
-    let results = indicator
-        .calculate(data)
-        .map_err(|e| JsError::new(&e.to_string()))?;
+    let indicator = LinReg::new(period, num_std_dev.unwrap_or(2.0)).map_err(js_error)?;
+    let results = indicator.calculate(data).map_err(js_error)?;
 
     let values: Vec<f64> = results.iter().map(|r| r.value).collect();
     let upper: Vec<f64> = results.iter().map(|r| r.upper).collect();
@@ -2998,18 +4134,14 @@ impl WasmLinRegStream {
     /// Create a new streaming Linear Regression calculator.
     #[wasm_bindgen(constructor)]
     pub fn new(period: usize, num_std_dev: Option<f64>) -> Result<WasmLinRegStream, JsError> {
-        let inner = LinRegStream::new(period, num_std_dev.unwrap_or(2.0))
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let inner = LinRegStream::new(period, num_std_dev.unwrap_or(2.0)).map_err(js_error)?;
         Ok(Self { inner })
     }
 
     /// Initialize with historical data.
     #[wasm_bindgen(js_name = "init")]
     pub fn init_history(&mut self, data: &[f64]) -> Result<JsValue, JsError> {
-        let results = self
-            .inner
-            .init(data)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+        let results = self.inner.init(data).map_err(js_error)?;
 
         let values: Vec<f64> = results.iter().map(|r| r.value).collect();
         let upper: Vec<f64> = results.iter().map(|r| r.upper).collect();
@@ -3064,8 +4196,33 @@ impl WasmLinRegStream {
         self.inner.next(value).map(WasmLinRegOutput::from)
     }
 
-    /// Reset the calculator.
-    pub fn reset(&mut self) {
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> Option<WasmLinRegOutput> {
+        self.inner.update(value).map(WasmLinRegOutput::from)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> Option<WasmLinRegOutput> {
+        self.inner.peek(value).map(WasmLinRegOutput::from)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<WasmLinRegOutput> {
+        values
+            .iter()
+            .map(|&value| {
+                WasmLinRegOutput::from(self.inner.peek(value).unwrap_or_else(LinRegOutput::nan))
+            })
+            .collect()
+    }
+
+    /// Reset the calculator.
+    pub fn reset(&mut self) {
         self.inner.reset();
     }
 
@@ -3075,6 +4232,17 @@ impl WasmLinRegStream {
         self.inner.is_ready()
     }
 
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
     /// Get the period.
     #[wasm_bindgen(getter)]
     pub fn period(&self) -> usize {
@@ -3087,3 +4255,3316 @@ impl WasmLinRegStream {
         self.inner.num_std_dev()
     }
 }
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "LinRegStream")]
+impl WasmLinRegStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmLinRegStream, JsError> {
+        let inner = LinRegStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
+// ============================================================================
+// Keltner Channels
+// ============================================================================
+
+/// Helper to convert a middle-line average name to enum
+fn parse_keltner_ma(ma: &str) -> Result<KeltnerMa, JsError> {
+    match ma.to_lowercase().as_str() {
+        "ema" => Ok(KeltnerMa::Ema),
+        "sma" => Ok(KeltnerMa::Sma),
+        _ => Err(JsError::new(&format!(
+            "Invalid Keltner average: '{ma}'. Use 'ema' or 'sma'"
+        ))),
+    }
+}
+
+/// Helper to convert a band width name to enum
+fn parse_keltner_band(band: &str) -> Result<KeltnerBand, JsError> {
+    match band.to_lowercase().as_str() {
+        "atr" => Ok(KeltnerBand::Atr),
+        "range" => Ok(KeltnerBand::Range),
+        _ => Err(JsError::new(&format!(
+            "Invalid Keltner band: '{band}'. Use 'atr' or 'range'"
+        ))),
+    }
+}
+
+fn keltner_to_js(results: &[KeltnerOutput]) -> Result<JsValue, JsError> {
+    arrays_to_js(&[
+        ("upper", results.iter().map(|r| r.upper).collect()),
+        ("middle", results.iter().map(|r| r.middle).collect()),
+        ("lower", results.iter().map(|r| r.lower).collect()),
+        ("width", results.iter().map(|r| r.width).collect()),
+    ])
+}
+
+/// Keltner Channels output for WASM.
+#[wasm_bindgen]
+pub struct WasmKeltnerOutput {
+    upper_val: f64,
+    middle_val: f64,
+    lower_val: f64,
+    width_val: f64,
+}
+
+#[wasm_bindgen]
+impl WasmKeltnerOutput {
+    /// Upper band value
+    #[wasm_bindgen(getter)]
+    pub fn upper(&self) -> f64 {
+        self.upper_val
+    }
+
+    /// Middle line value
+    #[wasm_bindgen(getter)]
+    pub fn middle(&self) -> f64 {
+        self.middle_val
+    }
+
+    /// Lower band value
+    #[wasm_bindgen(getter)]
+    pub fn lower(&self) -> f64 {
+        self.lower_val
+    }
+
+    /// Width: (upper - lower) / middle
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> f64 {
+        self.width_val
+    }
+}
+
+impl From<KeltnerOutput> for WasmKeltnerOutput {
+    fn from(o: KeltnerOutput) -> Self {
+        Self {
+            upper_val: o.upper,
+            middle_val: o.middle,
+            lower_val: o.lower,
+            width_val: o.width,
+        }
+    }
+}
+
+/// Streaming Keltner Channels calculator.
+#[wasm_bindgen(js_name = "KeltnerStream")]
+pub struct WasmKeltnerStream {
+    inner: KeltnerStream,
+}
+
+#[wasm_bindgen(js_class = "KeltnerStream")]
+impl WasmKeltnerStream {
+    /// Create a new streaming Keltner calculator. `ma` is 'ema' (default)
+    /// or 'sma'; `band` is 'atr' (default) or 'range'.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        period: usize,
+        atr_period: usize,
+        multiplier: f64,
+        ma: Option<String>,
+        band: Option<String>,
+    ) -> Result<WasmKeltnerStream, JsError> {
+        let ma = ma.map_or(Ok(KeltnerMa::Ema), |ma| parse_keltner_ma(&ma))?;
+        let band = band.map_or(Ok(KeltnerBand::Atr), |band| parse_keltner_band(&band))?;
+        let inner = KeltnerStream::with_options(period, atr_period, multiplier, ma, band)
+            .map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Initialize with historical data. Returns object with arrays.
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init_history(
+        &mut self,
+        highs: &[f64],
+        lows: &[f64],
+        closes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
+
+        let bars: Vec<KeltnerBar> = highs
+            .iter()
+            .zip(lows.iter())
+            .zip(closes.iter())
+            .map(|((&h, &l), &c)| (h, l, c))
+            .collect();
+
+        let results = self.inner.init(&bars).map_err(js_error)?;
+        keltner_to_js(&results)
+    }
+
+    /// Process next bar.
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<WasmKeltnerOutput> {
+        self.inner
+            .next((high, low, close))
+            .map(WasmKeltnerOutput::from)
+    }
+
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<WasmKeltnerOutput> {
+        self.inner
+            .update((high, low, close))
+            .map(WasmKeltnerOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> Option<WasmKeltnerOutput> {
+        self.inner
+            .peek((high, low, close))
+            .map(WasmKeltnerOutput::from)
+    }
+
+    /// Reset the calculator.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Check if ready.
+    #[wasm_bindgen(js_name = "isReady")]
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
+    /// Get the middle line period.
+    #[wasm_bindgen(getter)]
+    pub fn period(&self) -> usize {
+        self.inner.period()
+    }
+
+    /// Get the ATR (or range) period.
+    #[wasm_bindgen(getter, js_name = "atrPeriod")]
+    pub fn atr_period(&self) -> usize {
+        self.inner.atr_period()
+    }
+
+    /// Get the band multiplier.
+    #[wasm_bindgen(getter)]
+    pub fn multiplier(&self) -> f64 {
+        self.inner.multiplier()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "KeltnerStream")]
+impl WasmKeltnerStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmKeltnerStream, JsError> {
+        let inner = KeltnerStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
+// ============================================================================
+// TTM Squeeze
+// ============================================================================
+
+/// Squeeze results as `squeezeOn` (Uint8Array of 0/1), `squeezeBars`
+/// (Uint32Array), and `momentum` (Float64Array).
+fn squeeze_to_js(results: &[SqueezeOutput]) -> Result<JsValue, JsError> {
+    let squeeze_on: Vec<u8> = results.iter().map(|r| u8::from(r.squeeze_on)).collect();
+    let squeeze_bars: Vec<u32> = results
+        .iter()
+        .map(|r| u32::try_from(r.squeeze_bars).unwrap_or(u32::MAX))
+        .collect();
+    let momentum: Vec<f64> = results.iter().map(|r| r.momentum).collect();
+
+    let obj = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &obj,
+        &JsValue::from_str("squeezeOn"),
+        &js_sys::Uint8Array::from(&squeeze_on[..]).into(),
+    )
+    .map_err(|_| JsError::new("Failed to set squeezeOn"))?;
+    js_sys::Reflect::set(
+        &obj,
+        &JsValue::from_str("squeezeBars"),
+        &js_sys::Uint32Array::from(&squeeze_bars[..]).into(),
+    )
+    .map_err(|_| JsError::new("Failed to set squeezeBars"))?;
+    js_sys::Reflect::set(
+        &obj,
+        &JsValue::from_str("momentum"),
+        &js_sys::Float64Array::from(&momentum[..]).into(),
+    )
+    .map_err(|_| JsError::new("Failed to set momentum"))?;
+
+    Ok(obj.into())
+}
+
+/// TTM Squeeze output for WASM.
+#[wasm_bindgen]
+pub struct WasmSqueezeOutput {
+    squeeze_on_val: bool,
+    squeeze_bars_val: usize,
+    momentum_val: f64,
+}
+
+#[wasm_bindgen]
+impl WasmSqueezeOutput {
+    /// Whether the Bollinger Bands are inside the Keltner Channels
+    #[wasm_bindgen(getter, js_name = "squeezeOn")]
+    pub fn squeeze_on(&self) -> bool {
+        self.squeeze_on_val
+    }
+
+    /// Consecutive squeeze-on bars including this one (0 when off)
+    #[wasm_bindgen(getter, js_name = "squeezeBars")]
+    pub fn squeeze_bars(&self) -> usize {
+        self.squeeze_bars_val
+    }
+
+    /// Momentum histogram value (NaN until the regression window is full)
+    #[wasm_bindgen(getter)]
+    pub fn momentum(&self) -> f64 {
+        self.momentum_val
+    }
+}
+
+impl From<SqueezeOutput> for WasmSqueezeOutput {
+    fn from(o: SqueezeOutput) -> Self {
+        Self {
+            squeeze_on_val: o.squeeze_on,
+            squeeze_bars_val: o.squeeze_bars,
+            momentum_val: o.momentum,
+        }
+    }
+}
+
+/// Streaming TTM Squeeze calculator.
+#[wasm_bindgen(js_name = "SqueezeStream")]
+pub struct WasmSqueezeStream {
+    inner: SqueezeStream,
+}
+
+#[wasm_bindgen(js_class = "SqueezeStream")]
+impl WasmSqueezeStream {
+    /// Create a new streaming Squeeze calculator.
+    #[wasm_bindgen(constructor)]
+    pub fn new(period: usize, bb_mult: f64, kc_mult: f64) -> Result<WasmSqueezeStream, JsError> {
+        let inner = SqueezeStream::new(period, bb_mult, kc_mult).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Initialize with historical data. Returns object with arrays.
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init_history(
+        &mut self,
+        highs: &[f64],
+        lows: &[f64],
+        closes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
+
+        let bars: Vec<SqueezeBar> = highs
+            .iter()
+            .zip(lows.iter())
+            .zip(closes.iter())
+            .map(|((&h, &l), &c)| (h, l, c))
+            .collect();
+
+        let results = self.inner.init(&bars).map_err(js_error)?;
+        squeeze_to_js(&results)
+    }
+
+    /// Process next bar.
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<WasmSqueezeOutput> {
+        self.inner
+            .next((high, low, close))
+            .map(WasmSqueezeOutput::from)
+    }
+
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<WasmSqueezeOutput> {
+        self.inner
+            .update((high, low, close))
+            .map(WasmSqueezeOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> Option<WasmSqueezeOutput> {
+        self.inner
+            .peek((high, low, close))
+            .map(WasmSqueezeOutput::from)
+    }
+
+    /// Reset the calculator.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Check if ready.
+    #[wasm_bindgen(js_name = "isReady")]
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
+    /// Get the period.
+    #[wasm_bindgen(getter)]
+    pub fn period(&self) -> usize {
+        self.inner.period()
+    }
+
+    /// Get the Bollinger Bands multiplier.
+    #[wasm_bindgen(getter, js_name = "bbMult")]
+    pub fn bb_mult(&self) -> f64 {
+        self.inner.bb_mult()
+    }
+
+    /// Get the Keltner Channels multiplier.
+    #[wasm_bindgen(getter, js_name = "kcMult")]
+    pub fn kc_mult(&self) -> f64 {
+        self.inner.kc_mult()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "SqueezeStream")]
+impl WasmSqueezeStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSqueezeStream, JsError> {
+        let inner = SqueezeStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
+// ============================================================================
+// Donchian Channels
+// ============================================================================
+
+/// Donchian results as `upper`, `lower`, `middle`, `width` (Float64Array) and
+/// `barsSinceHigh`, `barsSinceLow` (Uint32Array).
+fn donchian_to_js(results: &[DonchianOutput]) -> Result<JsValue, JsError> {
+    let obj = arrays_to_js(&[
+        ("upper", results.iter().map(|r| r.upper).collect()),
+        ("lower", results.iter().map(|r| r.lower).collect()),
+        ("middle", results.iter().map(|r| r.middle).collect()),
+        ("width", results.iter().map(|r| r.width).collect()),
+    ])?;
+
+    let count = |f: fn(&DonchianOutput) -> usize| -> Vec<u32> {
+        results
+            .iter()
+            .map(|r| u32::try_from(f(r)).unwrap_or(u32::MAX))
+            .collect()
+    };
+    let counts = [
+        ("barsSinceHigh", count(|r| r.bars_since_high)),
+        ("barsSinceLow", count(|r| r.bars_since_low)),
+    ];
+    for (key, values) in counts {
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str(key),
+            &js_sys::Uint32Array::from(&values[..]).into(),
+        )
+        .map_err(|_| JsError::new(&format!("Failed to set {key} property")))?;
+    }
+
+    Ok(obj)
+}
+
+/// Donchian Channels output for WASM.
+#[wasm_bindgen]
+pub struct WasmDonchianOutput {
+    upper_val: f64,
+    lower_val: f64,
+    middle_val: f64,
+    width_val: f64,
+    bars_since_high_val: usize,
+    bars_since_low_val: usize,
+}
+
+#[wasm_bindgen]
+impl WasmDonchianOutput {
+    /// Highest high over the period
+    #[wasm_bindgen(getter)]
+    pub fn upper(&self) -> f64 {
+        self.upper_val
+    }
+
+    /// Lowest low over the period
+    #[wasm_bindgen(getter)]
+    pub fn lower(&self) -> f64 {
+        self.lower_val
+    }
+
+    /// Midpoint of upper and lower
+    #[wasm_bindgen(getter)]
+    pub fn middle(&self) -> f64 {
+        self.middle_val
+    }
+
+    /// Width: (upper - lower) / middle
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> f64 {
+        self.width_val
+    }
+
+    /// Bars since the highest high (0 = this bar)
+    #[wasm_bindgen(getter, js_name = "barsSinceHigh")]
+    pub fn bars_since_high(&self) -> usize {
+        self.bars_since_high_val
+    }
+
+    /// Bars since the lowest low (0 = this bar)
+    #[wasm_bindgen(getter, js_name = "barsSinceLow")]
+    pub fn bars_since_low(&self) -> usize {
+        self.bars_since_low_val
+    }
+}
+
+impl From<DonchianOutput> for WasmDonchianOutput {
+    fn from(o: DonchianOutput) -> Self {
+        Self {
+            upper_val: o.upper,
+            lower_val: o.lower,
+            middle_val: o.middle,
+            width_val: o.width,
+            bars_since_high_val: o.bars_since_high,
+            bars_since_low_val: o.bars_since_low,
+        }
+    }
+}
+
+/// Streaming Donchian Channels calculator.
+#[wasm_bindgen(js_name = "DonchianStream")]
+pub struct WasmDonchianStream {
+    inner: DonchianStream,
+}
+
+#[wasm_bindgen(js_class = "DonchianStream")]
+impl WasmDonchianStream {
+    /// Create a new streaming Donchian Channels calculator.
+    #[wasm_bindgen(constructor)]
+    pub fn new(period: usize) -> Result<WasmDonchianStream, JsError> {
+        let inner = DonchianStream::new(period).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Initialize with historical data. Returns object with arrays.
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init_history(&mut self, highs: &[f64], lows: &[f64]) -> Result<JsValue, JsError> {
+        check_lengths(&[("highs", highs.len()), ("lows", lows.len())]).map_err(js_error)?;
+
+        let bars: Vec<DonchianBar> = highs
+            .iter()
+            .zip(lows.iter())
+            .map(|(&h, &l)| (h, l))
+            .collect();
+
+        let results = self.inner.init(&bars).map_err(js_error)?;
+        donchian_to_js(&results)
+    }
+
+    /// Process next bar.
+    pub fn next(&mut self, high: f64, low: f64) -> Option<WasmDonchianOutput> {
+        self.inner.next((high, low)).map(WasmDonchianOutput::from)
+    }
+
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64) -> Option<WasmDonchianOutput> {
+        self.inner.update((high, low)).map(WasmDonchianOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64) -> Option<WasmDonchianOutput> {
+        self.inner.peek((high, low)).map(WasmDonchianOutput::from)
+    }
+
+    /// Reset the calculator.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Check if ready.
+    #[wasm_bindgen(js_name = "isReady")]
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
+    /// Get the period.
+    #[wasm_bindgen(getter)]
+    pub fn period(&self) -> usize {
+        self.inner.period()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "DonchianStream")]
+impl WasmDonchianStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmDonchianStream, JsError> {
+        let inner = DonchianStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
+// ============================================================================
+// Supertrend
+// ============================================================================
+
+/// Helper to convert a Supertrend source name to enum
+fn parse_supertrend_source(source: &str) -> Result<SuperTrendSource, JsError> {
+    match source.to_lowercase().as_str() {
+        "hl2" => Ok(SuperTrendSource::Hl2),
+        "hlc3" => Ok(SuperTrendSource::Hlc3),
+        "close" => Ok(SuperTrendSource::Close),
+        _ => Err(JsError::new(&format!(
+            "Invalid Supertrend source: '{source}'. Use 'hl2', 'hlc3' or 'close'"
+        ))),
+    }
+}
+
+/// Helper to convert an ATR smoothing name to enum
+fn parse_supertrend_atr(atr: &str) -> Result<SuperTrendAtr, JsError> {
+    match atr.to_lowercase().as_str() {
+        "wilder" => Ok(SuperTrendAtr::Wilder),
+        "sma" => Ok(SuperTrendAtr::Sma),
+        _ => Err(JsError::new(&format!(
+            "Invalid Supertrend ATR smoothing: '{atr}'. Use 'wilder' or 'sma'"
+        ))),
+    }
+}
+
+/// Supertrend results as `value` (Float64Array), `direction` (Int8Array of
+/// 1/-1, 0 during warmup), and `flip` (Uint8Array of 0/1).
+fn supertrend_to_js(results: &[SuperTrendOutput]) -> Result<JsValue, JsError> {
+    let value: Vec<f64> = results.iter().map(|r| r.value).collect();
+    let direction: Vec<i8> = results.iter().map(|r| r.direction).collect();
+    let flip: Vec<u8> = results.iter().map(|r| u8::from(r.flip)).collect();
+
+    let obj = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &obj,
+        &JsValue::from_str("value"),
+        &js_sys::Float64Array::from(&value[..]).into(),
+    )
+    .map_err(|_| JsError::new("Failed to set value"))?;
+    js_sys::Reflect::set(
+        &obj,
+        &JsValue::from_str("direction"),
+        &js_sys::Int8Array::from(&direction[..]).into(),
+    )
+    .map_err(|_| JsError::new("Failed to set direction"))?;
+    js_sys::Reflect::set(
+        &obj,
+        &JsValue::from_str("flip"),
+        &js_sys::Uint8Array::from(&flip[..]).into(),
+    )
+    .map_err(|_| JsError::new("Failed to set flip"))?;
+
+    Ok(obj.into())
+}
+
+/// Supertrend output for WASM.
+#[wasm_bindgen]
+pub struct WasmSuperTrendOutput {
+    value_val: f64,
+    direction_val: i8,
+    flip_val: bool,
+}
+
+#[wasm_bindgen]
+impl WasmSuperTrendOutput {
+    /// Supertrend line (lower band in an uptrend, upper band in a downtrend)
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> f64 {
+        self.value_val
+    }
+
+    /// Trend direction: 1 = up, -1 = down
+    #[wasm_bindgen(getter)]
+    pub fn direction(&self) -> i8 {
+        self.direction_val
+    }
+
+    /// Whether the trend flipped on this bar
+    #[wasm_bindgen(getter)]
+    pub fn flip(&self) -> bool {
+        self.flip_val
+    }
+}
+
+impl From<SuperTrendOutput> for WasmSuperTrendOutput {
+    fn from(o: SuperTrendOutput) -> Self {
+        Self {
+            value_val: o.value,
+            direction_val: o.direction,
+            flip_val: o.flip,
+        }
+    }
+}
+
+/// Streaming Supertrend calculator.
+#[wasm_bindgen(js_name = "SuperTrendStream")]
+pub struct WasmSuperTrendStream {
+    inner: SuperTrendStream,
+}
+
+#[wasm_bindgen(js_class = "SuperTrendStream")]
+impl WasmSuperTrendStream {
+    /// Create a new streaming Supertrend calculator. `source` is 'hl2'
+    /// (default), 'hlc3' or 'close'; `atr` is 'wilder' (default) or 'sma'.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        atr_period: usize,
+        multiplier: f64,
+        source: Option<String>,
+        atr: Option<String>,
+    ) -> Result<WasmSuperTrendStream, JsError> {
+        let source = source.map_or(Ok(SuperTrendSource::Hl2), |source| {
+            parse_supertrend_source(&source)
+        })?;
+        let atr = atr.map_or(Ok(SuperTrendAtr::Wilder), |atr| parse_supertrend_atr(&atr))?;
+        let inner = SuperTrendStream::with_options(atr_period, multiplier, source, atr)
+            .map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Initialize with historical data. Returns object with arrays.
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init_history(
+        &mut self,
+        highs: &[f64],
+        lows: &[f64],
+        closes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        check_lengths(&[
+            ("highs", highs.len()),
+            ("lows", lows.len()),
+            ("closes", closes.len()),
+        ])
+        .map_err(js_error)?;
+
+        let bars: Vec<SuperTrendBar> = highs
+            .iter()
+            .zip(lows.iter())
+            .zip(closes.iter())
+            .map(|((&h, &l), &c)| (h, l, c))
+            .collect();
+
+        let results = self.inner.init(&bars).map_err(js_error)?;
+        supertrend_to_js(&results)
+    }
+
+    /// Process next bar.
+    pub fn next(&mut self, high: f64, low: f64, close: f64) -> Option<WasmSuperTrendOutput> {
+        self.inner
+            .next((high, low, close))
+            .map(WasmSuperTrendOutput::from)
+    }
+
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<WasmSuperTrendOutput> {
+        self.inner
+            .update((high, low, close))
+            .map(WasmSuperTrendOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64, close: f64) -> Option<WasmSuperTrendOutput> {
+        self.inner
+            .peek((high, low, close))
+            .map(WasmSuperTrendOutput::from)
+    }
+
+    /// Reset the calculator.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Check if ready.
+    #[wasm_bindgen(js_name = "isReady")]
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
+    /// Get the ATR period.
+    #[wasm_bindgen(getter, js_name = "atrPeriod")]
+    pub fn atr_period(&self) -> usize {
+        self.inner.atr_period()
+    }
+
+    /// Get the ATR multiplier.
+    #[wasm_bindgen(getter)]
+    pub fn multiplier(&self) -> f64 {
+        self.inner.multiplier()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "SuperTrendStream")]
+impl WasmSuperTrendStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSuperTrendStream, JsError> {
+        let inner = SuperTrendStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
+// ============================================================================
+// Parabolic SAR
+// ============================================================================
+
+/// Parabolic SAR results as `sar`, `af`, `ep` (Float64Array) and
+/// `direction` (Int8Array of 1/-1, 0 during warmup).
+fn psar_to_js(results: &[PsarOutput]) -> Result<JsValue, JsError> {
+    let obj = arrays_to_js(&[
+        ("sar", results.iter().map(|r| r.sar).collect()),
+        ("af", results.iter().map(|r| r.af).collect()),
+        ("ep", results.iter().map(|r| r.ep).collect()),
+    ])?;
+
+    let direction: Vec<i8> = results.iter().map(|r| r.direction).collect();
+    js_sys::Reflect::set(
+        &obj,
+        &JsValue::from_str("direction"),
+        &js_sys::Int8Array::from(&direction[..]).into(),
+    )
+    .map_err(|_| JsError::new("Failed to set direction"))?;
+
+    Ok(obj)
+}
+
+/// Parabolic SAR output for WASM.
+#[wasm_bindgen]
+pub struct WasmPsarOutput {
+    sar_val: f64,
+    direction_val: i8,
+    af_val: f64,
+    ep_val: f64,
+}
+
+#[wasm_bindgen]
+impl WasmPsarOutput {
+    /// Stop level for this bar
+    #[wasm_bindgen(getter)]
+    pub fn sar(&self) -> f64 {
+        self.sar_val
+    }
+
+    /// Trend direction: 1 = long, -1 = short
+    #[wasm_bindgen(getter)]
+    pub fn direction(&self) -> i8 {
+        self.direction_val
+    }
+
+    /// Acceleration factor applied to the next SAR
+    #[wasm_bindgen(getter)]
+    pub fn af(&self) -> f64 {
+        self.af_val
+    }
+
+    /// Extreme point of the current trend
+    #[wasm_bindgen(getter)]
+    pub fn ep(&self) -> f64 {
+        self.ep_val
+    }
+}
+
+impl From<PsarOutput> for WasmPsarOutput {
+    fn from(o: PsarOutput) -> Self {
+        Self {
+            sar_val: o.sar,
+            direction_val: o.direction,
+            af_val: o.af,
+            ep_val: o.ep,
+        }
+    }
+}
+
+/// Streaming Parabolic SAR calculator.
+#[wasm_bindgen(js_name = "PsarStream")]
+pub struct WasmPsarStream {
+    inner: PsarStream,
+}
+
+#[wasm_bindgen(js_class = "PsarStream")]
+impl WasmPsarStream {
+    /// Create a new streaming Parabolic SAR calculator.
+    #[wasm_bindgen(constructor)]
+    pub fn new(start: f64, increment: f64, maximum: f64) -> Result<WasmPsarStream, JsError> {
+        let inner = PsarStream::new(start, increment, maximum).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Initialize with historical data. Returns object with arrays.
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init_history(&mut self, highs: &[f64], lows: &[f64]) -> Result<JsValue, JsError> {
+        check_lengths(&[("highs", highs.len()), ("lows", lows.len())]).map_err(js_error)?;
+
+        let bars: Vec<PsarBar> = highs
+            .iter()
+            .zip(lows.iter())
+            .map(|(&h, &l)| (h, l))
+            .collect();
+
+        let results = self.inner.init(&bars).map_err(js_error)?;
+        psar_to_js(&results)
+    }
+
+    /// Process next bar.
+    pub fn next(&mut self, high: f64, low: f64) -> Option<WasmPsarOutput> {
+        self.inner.next((high, low)).map(WasmPsarOutput::from)
+    }
+
+    /// Replace the most recent bar with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, high: f64, low: f64) -> Option<WasmPsarOutput> {
+        self.inner.update((high, low)).map(WasmPsarOutput::from)
+    }
+
+    /// Preview the output `next` would return for this bar, without
+    /// changing any state.
+    pub fn peek(&self, high: f64, low: f64) -> Option<WasmPsarOutput> {
+        self.inner.peek((high, low)).map(WasmPsarOutput::from)
+    }
+
+    /// Reset the calculator.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Check if ready.
+    #[wasm_bindgen(js_name = "isReady")]
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+
+    /// Get the initial acceleration factor.
+    #[wasm_bindgen(getter)]
+    pub fn start(&self) -> f64 {
+        self.inner.start()
+    }
+
+    /// Get the acceleration factor increment.
+    #[wasm_bindgen(getter)]
+    pub fn increment(&self) -> f64 {
+        self.inner.increment()
+    }
+
+    /// Get the maximum acceleration factor.
+    #[wasm_bindgen(getter)]
+    pub fn maximum(&self) -> f64 {
+        self.inner.maximum()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "PsarStream")]
+impl WasmPsarStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output. The restored stream
+    /// continues exactly where the original left off.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmPsarStream, JsError> {
+        let inner = PsarStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
+// ============================================================================
+// Indicator Chaining
+// ============================================================================
+
+type ScalarStream = Box<dyn StreamingIndicator<f64, f64>>;
+
+/// Unwrap a JS single-value stream (or pipe) into its Rust stream.
+///
+/// Takes ownership: the JS object can no longer be used afterwards.
+fn scalar_stream(value: &JsValue) -> Result<ScalarStream, JsError> {
+    if let Some(stream) = WasmSmaStream::try_from_js_value_ref(value) {
+        return Ok(Box::new(stream.inner));
+    }
+    if let Some(stream) = WasmEmaStream::try_from_js_value_ref(value) {
+        return Ok(Box::new(stream.inner));
+    }
+    if let Some(stream) = WasmWmaStream::try_from_js_value_ref(value) {
+        return Ok(Box::new(stream.inner));
+    }
+    if let Some(stream) = WasmRsiStream::try_from_js_value_ref(value) {
+        return Ok(Box::new(stream.inner));
+    }
+    if let Some(stream) = WasmHmaStream::try_from_js_value_ref(value) {
+        return Ok(Box::new(stream.inner));
+    }
+    if let Some(stream) = WasmCvdStream::try_from_js_value_ref(value) {
+        return Ok(Box::new(stream.inner));
+    }
+    if let Some(pipe) = WasmPipeStream::try_from_js_value_ref(value) {
+        return Ok(pipe.inner);
+    }
+    Err(JsError::new(
+        "pipe() expects an SmaStream, EmaStream, WmaStream, RsiStream, HmaStream, CvdStream or PipeStream",
+    ))
+}
+
+/// Chain of single-value streams, e.g. `rsi.stream(14).pipe(sma.stream(9))`.
+///
+/// Each stage only receives values the previous stage produced, so warmups
+/// add up instead of propagating NaN.
+#[wasm_bindgen(js_name = "PipeStream")]
+pub struct WasmPipeStream {
+    inner: ScalarStream,
+}
+
+impl WasmPipeStream {
+    fn chain(first: ScalarStream, next: &JsValue) -> Result<Self, JsError> {
+        let second = scalar_stream(next)?;
+        Ok(Self {
+            inner: Box::new(first.pipe(second)),
+        })
+    }
+}
+
+#[wasm_bindgen(js_class = "PipeStream")]
+impl WasmPipeStream {
+    /// Initialize with historical data. Returns the output of the last stage.
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init_history(&mut self, data: &[f64]) -> Result<Vec<f64>, JsError> {
+        self.inner.init(data).map_err(js_error)
+    }
+
+    /// Process next value. Returns the last stage's output or NaN if not ready.
+    pub fn next(&mut self, value: f64) -> f64 {
+        self.inner.next(value).unwrap_or(f64::NAN)
+    }
+
+    /// Replace the most recent value with a revised one (live candle update).
+    /// Returns the corrected output, like `next`.
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.inner.update(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview the output `next` would return for this value, without
+    /// changing any state.
+    pub fn peek(&self, value: f64) -> f64 {
+        self.inner.peek(value).unwrap_or(f64::NAN)
+    }
+
+    /// Preview `next` for each candidate value independently, e.g. to draw
+    /// the indicator across a ladder of hypothetical prices. `NaN` marks
+    /// candidates that would not produce a value.
+    #[wasm_bindgen(js_name = "peekMany")]
+    pub fn peek_many(&self, values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|&value| self.inner.peek(value).unwrap_or(f64::NAN))
+            .collect()
+    }
+
+    /// Append another stage to the chain.
+    pub fn pipe(self, next: &JsValue) -> Result<WasmPipeStream, JsError> {
+        Self::chain(self.inner, next)
+    }
+
+    /// Reset every stage to initial state.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Check if the last stage has enough data to produce values.
+    #[wasm_bindgen(js_name = "isReady")]
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Number of leading inputs that produce no complete value.
+    pub fn lookback(&self) -> usize {
+        self.inner.lookback()
+    }
+
+    /// Minimum number of inputs needed to produce the first complete value.
+    #[wasm_bindgen(js_name = "warmupLen")]
+    pub fn warmup_len(&self) -> usize {
+        self.inner.warmup_len()
+    }
+}
+
+// ============================================================================
+// Bar Aggregation
+// ============================================================================
+
+/// Time bar built from trades, returned to JavaScript.
+#[wasm_bindgen(js_name = "Bar")]
+pub struct WasmBar {
+    inner: Bar,
+}
+
+#[wasm_bindgen(js_class = "Bar")]
+impl WasmBar {
+    /// Bar open time (Unix milliseconds, UTC)
+    #[wasm_bindgen(getter)]
+    pub fn timestamp(&self) -> f64 {
+        self.inner.candle.timestamp as f64
+    }
+
+    /// Opening price
+    #[wasm_bindgen(getter)]
+    pub fn open(&self) -> f64 {
+        self.inner.candle.open
+    }
+
+    /// Highest price
+    #[wasm_bindgen(getter)]
+    pub fn high(&self) -> f64 {
+        self.inner.candle.high
+    }
+
+    /// Lowest price
+    #[wasm_bindgen(getter)]
+    pub fn low(&self) -> f64 {
+        self.inner.candle.low
+    }
+
+    /// Closing price
+    #[wasm_bindgen(getter)]
+    pub fn close(&self) -> f64 {
+        self.inner.candle.close
+    }
+
+    /// Total traded volume
+    #[wasm_bindgen(getter)]
+    pub fn volume(&self) -> f64 {
+        self.inner.candle.volume
+    }
+
+    /// Volume of buyer-initiated trades
+    #[wasm_bindgen(getter, js_name = "buyVolume")]
+    pub fn buy_volume(&self) -> f64 {
+        self.inner.buy_volume
+    }
+
+    /// Volume of seller-initiated trades
+    #[wasm_bindgen(getter, js_name = "sellVolume")]
+    pub fn sell_volume(&self) -> f64 {
+        self.inner.sell_volume
+    }
+
+    /// Buy volume minus sell volume
+    #[wasm_bindgen(getter)]
+    pub fn delta(&self) -> f64 {
+        self.inner.delta()
+    }
+
+    /// Number of trades in the bar
+    #[wasm_bindgen(getter, js_name = "tradeCount")]
+    pub fn trade_count(&self) -> usize {
+        self.inner.trade_count
+    }
+}
+
+impl From<Bar> for WasmBar {
+    fn from(inner: Bar) -> Self {
+        Self { inner }
+    }
+}
+
+/// Result of pushing a trade into a `BarAggregator`.
+#[wasm_bindgen(js_name = "BarUpdate")]
+pub struct WasmBarUpdate {
+    inner: BarUpdate,
+}
+
+#[wasm_bindgen(js_class = "BarUpdate")]
+impl WasmBarUpdate {
+    /// The bar this trade closed, if it started a new interval
+    #[wasm_bindgen(getter)]
+    pub fn closed(&self) -> Option<WasmBar> {
+        self.inner.closed.map(WasmBar::from)
+    }
+
+    /// The forming bar including this trade
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> WasmBar {
+        self.inner.current.into()
+    }
+
+    /// Whether this trade opened the forming bar: feed it to streams with
+    /// `next()` if so, `update()` otherwise
+    #[wasm_bindgen(getter)]
+    pub fn opened(&self) -> bool {
+        self.inner.opened
+    }
+}
+
+/// Trade side from its JS encoding: positive for buy, negative for sell.
+fn side_from_sign(side: Option<i8>) -> Option<Side> {
+    match side? {
+        s if s > 0 => Some(Side::Buy),
+        s if s < 0 => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+/// Aggregates raw trades into UTC-aligned time bars.
+#[wasm_bindgen(js_name = "BarAggregator")]
+pub struct WasmBarAggregator {
+    inner: BarAggregator,
+}
+
+#[wasm_bindgen(js_class = "BarAggregator")]
+impl WasmBarAggregator {
+    /// Create an aggregator for bars of `intervalMs` milliseconds. Bars open
+    /// on UTC-aligned boundaries shifted by `offsetMs` (default 0).
+    #[wasm_bindgen(constructor)]
+    pub fn new(interval_ms: f64, offset_ms: Option<f64>) -> Result<WasmBarAggregator, JsError> {
+        let inner = BarAggregator::with_offset(interval_ms as i64, offset_ms.unwrap_or(0.0) as i64)
+            .map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Add a trade. `side` is 1 for buy, -1 for sell, omitted if unknown.
+    /// Returns undefined if the trade was dropped (late or invalid).
+    pub fn push(
+        &mut self,
+        timestamp: f64,
+        price: f64,
+        size: f64,
+        side: Option<i8>,
+    ) -> Option<WasmBarUpdate> {
+        let trade = Trade {
+            timestamp: timestamp as i64,
+            price,
+            size,
+            side: side_from_sign(side),
+        };
+        self.inner.push(trade).map(|inner| WasmBarUpdate { inner })
+    }
+
+    /// Add many trades at once; `sides` may be omitted. Returns the bars they
+    /// closed; the last bar stays open (see `current`).
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        prices: &[f64],
+        sizes: &[f64],
+        sides: Option<Vec<i8>>,
+    ) -> Result<Vec<WasmBar>, JsError> {
+        let sides = sides.unwrap_or_default();
+        check_lengths(&[
+            ("timestamps", timestamps.len()),
+            ("prices", prices.len()),
+            ("sizes", sizes.len()),
+        ])
+        .map_err(js_error)?;
+        if !sides.is_empty() {
+            check_lengths(&[("timestamps", timestamps.len()), ("sides", sides.len())])
+                .map_err(js_error)?;
+        }
+
+        let mut closed = Vec::new();
+        for i in 0..timestamps.len() {
+            let trade = Trade {
+                timestamp: timestamps[i] as i64,
+                price: prices[i],
+                size: sizes[i],
+                side: side_from_sign(sides.get(i).copied()),
+            };
+            if let Some(bar) = self.inner.push(trade).and_then(|update| update.closed) {
+                closed.push(bar.into());
+            }
+        }
+        Ok(closed)
+    }
+
+    /// Close the forming bar if its interval has ended by `now`. Call from a
+    /// timer to get bar-close events when no trades arrive.
+    pub fn advance(&mut self, now: f64) -> Option<WasmBar> {
+        self.inner.advance(now as i64).map(WasmBar::from)
+    }
+
+    /// Close the forming bar regardless of time.
+    pub fn flush(&mut self) -> Option<WasmBar> {
+        self.inner.flush().map(WasmBar::from)
+    }
+
+    /// The forming bar, if any.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> Option<WasmBar> {
+        self.inner.current().copied().map(WasmBar::from)
+    }
+
+    /// Bar interval in milliseconds.
+    #[wasm_bindgen(getter)]
+    pub fn interval(&self) -> f64 {
+        self.inner.interval() as f64
+    }
+
+    /// Drop the forming bar and forget all history.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+// ============================================================================
+// Trade CVD
+// ============================================================================
+
+/// Trade classification from its JS name: `"aggressor"`, `"tick"` or
+/// `"leeReady"`.
+fn parse_classification(rule: &str) -> Result<Classification, JsError> {
+    match rule {
+        "aggressor" => Ok(Classification::Aggressor),
+        "tick" => Ok(Classification::TickRule),
+        "leeReady" => Ok(Classification::LeeReady),
+        other => Err(js_error(IndicatorError::InvalidParameter(format!(
+            "unknown trade classification '{other}'. Use 'aggressor', 'tick' or 'leeReady'"
+        )))),
+    }
+}
+
+/// Trades from JS columns, each with the quote prevailing at it. `sides`
+/// may be empty; `quotes`, if not empty, holds a bid and an ask per trade,
+/// interleaved.
+fn trades_from_js(
+    timestamps: &[f64],
+    prices: &[f64],
+    sizes: &[f64],
+    sides: Option<Vec<i8>>,
+    quotes: Option<Vec<f64>>,
+) -> Result<Vec<(Trade, Option<Quote>)>, JsError> {
+    let sides = sides.unwrap_or_default();
+    let quotes = quotes.unwrap_or_default();
+    check_lengths(&[
+        ("timestamps", timestamps.len()),
+        ("prices", prices.len()),
+        ("sizes", sizes.len()),
+    ])
+    .map_err(js_error)?;
+    if !sides.is_empty() {
+        check_lengths(&[("timestamps", timestamps.len()), ("sides", sides.len())])
+            .map_err(js_error)?;
+    }
+    if !quotes.is_empty() {
+        // A bid and an ask per trade
+        check_lengths(&[
+            ("timestamps", 2 * timestamps.len()),
+            ("quotes", quotes.len()),
+        ])
+        .map_err(js_error)?;
+    }
+
+    Ok((0..timestamps.len())
+        .map(|i| {
+            let trade = Trade {
+                timestamp: timestamps[i] as i64,
+                price: prices[i],
+                size: sizes[i],
+                side: side_from_sign(sides.get(i).copied()),
+            };
+            let quote = match quotes.get(2 * i..2 * i + 2) {
+                Some(&[bid, ask]) => Some(Quote::new(bid, ask)),
+                _ => None,
+            };
+            (trade, quote)
+        })
+        .collect())
+}
+
+/// Time bar of signed trades with its cumulative volume delta candle.
+#[wasm_bindgen(js_name = "DeltaBar")]
+pub struct WasmDeltaBar {
+    inner: DeltaBar,
+}
+
+#[wasm_bindgen(js_class = "DeltaBar")]
+impl WasmDeltaBar {
+    /// Price bar; buy and sell volumes follow the classification
+    #[wasm_bindgen(getter)]
+    pub fn bar(&self) -> WasmBar {
+        self.inner.bar.into()
+    }
+
+    /// CVD candle `{ time, open, high, low, close, volume }`: open is the
+    /// previous bar's close, volume the bar's volume
+    #[wasm_bindgen(getter)]
+    pub fn cvd(&self) -> JsValue {
+        candle_to_js(&self.inner.cvd)
+    }
+}
+
+impl From<DeltaBar> for WasmDeltaBar {
+    fn from(inner: DeltaBar) -> Self {
+        Self { inner }
+    }
+}
+
+/// Result of pushing a trade into a `TradeCvdStream`.
+#[wasm_bindgen(js_name = "DeltaUpdate")]
+pub struct WasmDeltaUpdate {
+    inner: DeltaUpdate,
+}
+
+#[wasm_bindgen(js_class = "DeltaUpdate")]
+impl WasmDeltaUpdate {
+    /// The bar this trade closed, if it started a new interval
+    #[wasm_bindgen(getter)]
+    pub fn closed(&self) -> Option<WasmDeltaBar> {
+        self.inner.closed.map(WasmDeltaBar::from)
+    }
+
+    /// The forming bar including this trade
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> WasmDeltaBar {
+        self.inner.current.into()
+    }
+
+    /// Whether this trade opened the forming bar
+    #[wasm_bindgen(getter)]
+    pub fn opened(&self) -> bool {
+        self.inner.opened
+    }
+}
+
+/// Cumulative volume delta from trades, aggregated into UTC-aligned time
+/// bars with a CVD candle per bar.
+#[wasm_bindgen(js_name = "TradeCvdStream")]
+pub struct WasmTradeCvdStream {
+    inner: TradeCvdStream,
+}
+
+#[wasm_bindgen(js_class = "TradeCvdStream")]
+impl WasmTradeCvdStream {
+    /// Create a stream for bars of `intervalMs` milliseconds, shifted by
+    /// `offsetMs` (default 0). `rule` signs trades: `"aggressor"` (the
+    /// reported side, default), `"tick"` (tick rule) or `"leeReady"` (quote
+    /// midpoint, then tick rule; set quotes with `setQuote()`).
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        interval_ms: f64,
+        rule: Option<String>,
+        offset_ms: Option<f64>,
+    ) -> Result<WasmTradeCvdStream, JsError> {
+        let inner = TradeCvdStream::with_offset(
+            interval_ms as i64,
+            offset_ms.unwrap_or(0.0) as i64,
+            match rule {
+                Some(rule) => parse_classification(&rule)?,
+                None => Classification::Aggressor,
+            },
+        )
+        .map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Set the quote prevailing from now on (used by `"leeReady"`).
+    #[wasm_bindgen(js_name = "setQuote")]
+    pub fn set_quote(&mut self, bid: f64, ask: f64) {
+        self.inner.set_quote(Quote::new(bid, ask));
+    }
+
+    /// Add a trade. `side` is 1 for buy, -1 for sell, omitted if unknown.
+    /// Returns undefined if the trade was dropped (late or invalid).
+    pub fn push(
+        &mut self,
+        timestamp: f64,
+        price: f64,
+        size: f64,
+        side: Option<i8>,
+    ) -> Option<WasmDeltaUpdate> {
+        let trade = Trade {
+            timestamp: timestamp as i64,
+            price,
+            size,
+            side: side_from_sign(side),
+        };
+        self.inner
+            .push(trade)
+            .map(|inner| WasmDeltaUpdate { inner })
+    }
+
+    /// Add many trades at once; `sides` may be omitted, and `quotes` holds
+    /// the bid and ask prevailing at each trade, interleaved (for
+    /// `"leeReady"`). Returns the bars they closed; the last bar stays open.
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        prices: &[f64],
+        sizes: &[f64],
+        sides: Option<Vec<i8>>,
+        quotes: Option<Vec<f64>>,
+    ) -> Result<Vec<WasmDeltaBar>, JsError> {
+        let mut closed = Vec::new();
+        for (trade, quote) in trades_from_js(timestamps, prices, sizes, sides, quotes)? {
+            if let Some(quote) = quote {
+                self.inner.set_quote(quote);
+            }
+            if let Some(bar) = self.inner.push(trade).and_then(|update| update.closed) {
+                closed.push(bar.into());
+            }
+        }
+        Ok(closed)
+    }
+
+    /// Close the forming bar if its interval has ended by `now`.
+    pub fn advance(&mut self, now: f64) -> Option<WasmDeltaBar> {
+        self.inner.advance(now as i64).map(WasmDeltaBar::from)
+    }
+
+    /// Close the forming bar regardless of time.
+    pub fn flush(&mut self) -> Option<WasmDeltaBar> {
+        self.inner.flush().map(WasmDeltaBar::from)
+    }
+
+    /// The forming bar, if any.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> Option<WasmDeltaBar> {
+        self.inner.current().map(WasmDeltaBar::from)
+    }
+
+    /// CVD after the last accepted trade.
+    #[wasm_bindgen(getter)]
+    pub fn cumulative(&self) -> f64 {
+        self.inner.cumulative()
+    }
+
+    /// Drop the forming bar, the classifier state and the CVD.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+// ============================================================================
+// Footprint
+// ============================================================================
+
+/// Values per level in `FootprintBar.levels`: price, buy, sell and total
+/// volume.
+const FOOTPRINT_LEVEL_STRIDE: usize = 4;
+
+/// A level as a plain `{ price, buyVolume, sellVolume, volume }` object.
+fn footprint_level_to_js(level: &FootprintLevel) -> JsValue {
+    let obj = js_sys::Object::new();
+    for (key, value) in [
+        ("price", level.price),
+        ("buyVolume", level.buy_volume),
+        ("sellVolume", level.sell_volume),
+        ("volume", level.volume),
+    ] {
+        let _ = js_sys::Reflect::set(&obj, &JsValue::from_str(key), &JsValue::from_f64(value));
+    }
+    obj.into()
+}
+
+/// Trade side in its JS encoding: 1 for buy, -1 for sell.
+const fn side_to_sign(side: Side) -> i8 {
+    match side {
+        Side::Buy => 1,
+        Side::Sell => -1,
+    }
+}
+
+/// Time bar with buy and sell volume at every price tick.
+///
+/// Levels come as flat typed arrays for rendering: a footprint of `n` levels
+/// is `levels` of length `4 * n`.
+#[wasm_bindgen(js_name = "FootprintBar")]
+pub struct WasmFootprintBar {
+    inner: FootprintBar,
+}
+
+#[wasm_bindgen(js_class = "FootprintBar")]
+impl WasmFootprintBar {
+    /// Price bar; buy and sell volumes follow the classification
+    #[wasm_bindgen(getter)]
+    pub fn bar(&self) -> WasmBar {
+        self.inner.bar.into()
+    }
+
+    /// Distance between adjacent levels
+    #[wasm_bindgen(getter, js_name = "tickSize")]
+    pub fn tick_size(&self) -> f64 {
+        self.inner.tick_size
+    }
+
+    /// Number of levels, one per tick from the bar's low to its high
+    #[wasm_bindgen(getter, js_name = "levelCount")]
+    pub fn level_count(&self) -> usize {
+        self.inner.levels.len()
+    }
+
+    /// Levels ascending by price as `[price, buyVolume, sellVolume, volume]`
+    /// quadruples, flattened
+    #[wasm_bindgen(getter)]
+    pub fn levels(&self) -> Vec<f64> {
+        let mut flat = Vec::with_capacity(self.inner.levels.len() * FOOTPRINT_LEVEL_STRIDE);
+        for level in &self.inner.levels {
+            flat.extend([
+                level.price,
+                level.buy_volume,
+                level.sell_volume,
+                level.volume,
+            ]);
+        }
+        flat
+    }
+
+    /// Buy volume minus sell volume of the bar
+    #[wasm_bindgen(getter)]
+    pub fn delta(&self) -> f64 {
+        self.inner.delta()
+    }
+
+    /// Price of the level with the highest volume
+    #[wasm_bindgen(getter)]
+    pub fn poc(&self) -> Option<f64> {
+        self.inner.poc().map(|level| level.price)
+    }
+
+    /// Diagonal imbalance flags per level: 1 if buying at the level is at
+    /// least `ratio` times the selling one tick below, plus 2 if selling is
+    /// at least `ratio` times the buying one tick above.
+    pub fn imbalances(&self, ratio: f64) -> Vec<u8> {
+        let mut flags = vec![0; self.inner.levels.len()];
+        let levels = &self.inner.levels;
+        for imbalance in self.inner.imbalances(ratio) {
+            let idx = levels.partition_point(|level| level.price < imbalance.price);
+            flags[idx] |= match imbalance.side {
+                Side::Buy => 1,
+                Side::Sell => 2,
+            };
+        }
+        flags
+    }
+
+    /// Runs of at least `minLevels` (default 3) consecutive levels
+    /// imbalanced to the same side, as `[side, low, high, levels]`
+    /// quadruples, flattened; side is 1 for buy, -1 for sell.
+    #[wasm_bindgen(js_name = "stackedImbalances")]
+    pub fn stacked_imbalances(&self, ratio: f64, min_levels: Option<usize>) -> Vec<f64> {
+        let mut flat = Vec::new();
+        for stack in self
+            .inner
+            .stacked_imbalances(ratio, min_levels.unwrap_or(3))
+        {
+            flat.extend([
+                f64::from(side_to_sign(stack.side)),
+                stack.low,
+                stack.high,
+                stack.levels as f64,
+            ]);
+        }
+        flat
+    }
+}
+
+impl From<FootprintBar> for WasmFootprintBar {
+    fn from(inner: FootprintBar) -> Self {
+        Self { inner }
+    }
+}
+
+/// Result of pushing a trade into a `FootprintStream`.
+#[wasm_bindgen(js_name = "FootprintUpdate")]
+pub struct WasmFootprintUpdate {
+    inner: FootprintUpdate,
+}
+
+#[wasm_bindgen(js_class = "FootprintUpdate")]
+impl WasmFootprintUpdate {
+    /// The bar this trade closed, if it started a new interval
+    #[wasm_bindgen(getter)]
+    pub fn closed(&self) -> Option<WasmFootprintBar> {
+        self.inner.closed.clone().map(WasmFootprintBar::from)
+    }
+
+    /// The forming bar's candle including this trade
+    #[wasm_bindgen(getter)]
+    pub fn bar(&self) -> WasmBar {
+        self.inner.bar.into()
+    }
+
+    /// The level this trade added to, as `{ price, buyVolume, sellVolume,
+    /// volume }`
+    #[wasm_bindgen(getter)]
+    pub fn level(&self) -> JsValue {
+        footprint_level_to_js(&self.inner.level)
+    }
+
+    /// Whether this trade opened the forming bar
+    #[wasm_bindgen(getter)]
+    pub fn opened(&self) -> bool {
+        self.inner.opened
+    }
+}
+
+/// Footprint from trades: buy and sell volume at every price tick of
+/// UTC-aligned time bars.
+#[wasm_bindgen(js_name = "FootprintStream")]
+pub struct WasmFootprintStream {
+    inner: FootprintStream,
+}
+
+#[wasm_bindgen(js_class = "FootprintStream")]
+impl WasmFootprintStream {
+    /// Create a stream for bars of `intervalMs` milliseconds with levels
+    /// `tickSize` apart, shifted by `offsetMs` (default 0). `rule` signs
+    /// trades as for `TradeCvdStream`: `"aggressor"` (default), `"tick"` or
+    /// `"leeReady"`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        interval_ms: f64,
+        tick_size: f64,
+        rule: Option<String>,
+        offset_ms: Option<f64>,
+    ) -> Result<WasmFootprintStream, JsError> {
+        let inner = FootprintStream::with_offset(
+            interval_ms as i64,
+            offset_ms.unwrap_or(0.0) as i64,
+            tick_size,
+            match rule {
+                Some(rule) => parse_classification(&rule)?,
+                None => Classification::Aggressor,
+            },
+        )
+        .map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Distance between adjacent levels.
+    #[wasm_bindgen(getter, js_name = "tickSize")]
+    pub fn tick_size(&self) -> f64 {
+        self.inner.tick_size()
+    }
+
+    /// Set the quote prevailing from now on (used by `"leeReady"`).
+    #[wasm_bindgen(js_name = "setQuote")]
+    pub fn set_quote(&mut self, bid: f64, ask: f64) {
+        self.inner.set_quote(Quote::new(bid, ask));
+    }
+
+    /// Add a trade. `side` is 1 for buy, -1 for sell, omitted if unknown.
+    /// Returns undefined if the trade was dropped (late or invalid).
+    pub fn push(
+        &mut self,
+        timestamp: f64,
+        price: f64,
+        size: f64,
+        side: Option<i8>,
+    ) -> Option<WasmFootprintUpdate> {
+        let trade = Trade {
+            timestamp: timestamp as i64,
+            price,
+            size,
+            side: side_from_sign(side),
+        };
+        self.inner
+            .push(trade)
+            .map(|inner| WasmFootprintUpdate { inner })
+    }
+
+    /// Add many trades at once; `sides` may be omitted, and `quotes` holds
+    /// the bid and ask prevailing at each trade, interleaved (for
+    /// `"leeReady"`). Returns the bars they closed; the last bar stays open.
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        prices: &[f64],
+        sizes: &[f64],
+        sides: Option<Vec<i8>>,
+        quotes: Option<Vec<f64>>,
+    ) -> Result<Vec<WasmFootprintBar>, JsError> {
+        let mut closed = Vec::new();
+        for (trade, quote) in trades_from_js(timestamps, prices, sizes, sides, quotes)? {
+            if let Some(quote) = quote {
+                self.inner.set_quote(quote);
+            }
+            if let Some(bar) = self.inner.push(trade).and_then(|update| update.closed) {
+                closed.push(bar.into());
+            }
+        }
+        Ok(closed)
+    }
+
+    /// Close the forming bar if its interval has ended by `now`.
+    pub fn advance(&mut self, now: f64) -> Option<WasmFootprintBar> {
+        self.inner.advance(now as i64).map(WasmFootprintBar::from)
+    }
+
+    /// Close the forming bar regardless of time.
+    pub fn flush(&mut self) -> Option<WasmFootprintBar> {
+        self.inner.flush().map(WasmFootprintBar::from)
+    }
+
+    /// The forming bar, if any.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> Option<WasmFootprintBar> {
+        self.inner.current().map(WasmFootprintBar::from)
+    }
+
+    /// Drop the forming bar and the classifier state.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+// ============================================================================
+// Resampling
+// ============================================================================
+
+/// Resampler from its JS arguments: a timeframe string like `"15m"`, `"4h"`,
+/// `"1d"`, `"1w"` or `"1M"`, and the session calendar (UTC days if none).
+fn resampler_from_js(timeframe: &str, session: Option<&WasmSession>) -> Result<Resampler, JsError> {
+    let session = session.map_or_else(Session::utc, |s| s.inner.clone());
+    timeframe
+        .parse::<Timeframe>()
+        .and_then(|tf| Resampler::with_session(tf, session))
+        .map_err(js_error)
+}
+
+/// A candle as a plain `{ time, open, high, low, close, volume }` object.
+fn candle_to_js(candle: &OHLCV) -> JsValue {
+    let obj = js_sys::Object::new();
+    for (key, value) in [
+        ("time", candle.timestamp as f64),
+        ("open", candle.open),
+        ("high", candle.high),
+        ("low", candle.low),
+        ("close", candle.close),
+        ("volume", candle.volume),
+    ] {
+        let _ = js_sys::Reflect::set(&obj, &JsValue::from_str(key), &JsValue::from_f64(value));
+    }
+    obj.into()
+}
+
+/// Candles as an object with `time`, `open`, `high`, `low`, `close` and
+/// `volume` arrays.
+fn candles_to_js(candles: &[OHLCV]) -> Result<JsValue, JsError> {
+    let obj = js_sys::Object::new();
+    for (key, values) in [
+        (
+            "time",
+            candles
+                .iter()
+                .map(|c| c.timestamp as f64)
+                .collect::<Vec<_>>(),
+        ),
+        ("open", candles.iter().map(|c| c.open).collect()),
+        ("high", candles.iter().map(|c| c.high).collect()),
+        ("low", candles.iter().map(|c| c.low).collect()),
+        ("close", candles.iter().map(|c| c.close).collect()),
+        ("volume", candles.iter().map(|c| c.volume).collect()),
+    ] {
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str(key),
+            &js_sys::Float64Array::from(&values[..]).into(),
+        )
+        .map_err(|_| JsError::new(&format!("Failed to set {key} property")))?;
+    }
+    Ok(obj.into())
+}
+
+/// Map higher-timeframe values back onto lower-timeframe timestamps.
+///
+/// `values` holds one value per bar of `resample()` over the same timestamps
+/// and timeframe, on UTC days. Each timestamp gets the value of the last bar
+/// that closed before it, so there is no lookahead; the first bar gets NaN.
+#[wasm_bindgen(js_name = "alignTimeframe")]
+pub fn align_timeframe(
+    timestamps: &[f64],
+    values: &[f64],
+    timeframe: &str,
+) -> Result<Vec<f64>, JsError> {
+    Ok(align_with(
+        timestamps,
+        values,
+        &resampler_from_js(timeframe, None)?,
+    ))
+}
+
+/// `alignTimeframe` for bars resampled over the trading dates of `session`.
+#[wasm_bindgen(js_name = "alignTimeframeWithSession")]
+pub fn align_timeframe_with_session(
+    timestamps: &[f64],
+    values: &[f64],
+    timeframe: &str,
+    session: &WasmSession,
+) -> Result<Vec<f64>, JsError> {
+    Ok(align_with(
+        timestamps,
+        values,
+        &resampler_from_js(timeframe, Some(session))?,
+    ))
+}
+
+fn align_with(timestamps: &[f64], values: &[f64], resampler: &Resampler) -> Vec<f64> {
+    let timestamps: Vec<i64> = timestamps.iter().map(|&t| t as i64).collect();
+    resampler.align(&timestamps, values)
+}
+
+/// Result of pushing a candle into a `Resampler`.
+#[wasm_bindgen(js_name = "ResampleUpdate")]
+pub struct WasmResampleUpdate {
+    inner: ResampleUpdate,
+}
+
+#[wasm_bindgen(js_class = "ResampleUpdate")]
+impl WasmResampleUpdate {
+    /// The bar this candle closed, if it opened a new one
+    #[wasm_bindgen(getter)]
+    pub fn closed(&self) -> JsValue {
+        self.inner
+            .closed
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// The forming bar including this candle
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> JsValue {
+        candle_to_js(&self.inner.current)
+    }
+
+    /// Whether this candle opened the forming bar: feed it to streams with
+    /// `next()` if so, `update()` otherwise
+    #[wasm_bindgen(getter)]
+    pub fn opened(&self) -> bool {
+        self.inner.opened
+    }
+}
+
+/// Aggregates lower-timeframe candles into higher-timeframe bars, e.g. to run
+/// a 1h stream on live 1m candles.
+#[wasm_bindgen(js_name = "Resampler")]
+pub struct WasmResampler {
+    inner: Resampler,
+}
+
+#[wasm_bindgen(js_class = "Resampler")]
+impl WasmResampler {
+    /// Create a resampler for `timeframe` (`"15m"`, `"4h"`, `"1d"`, `"1w"`,
+    /// `"1M"`) over UTC days.
+    #[wasm_bindgen(constructor)]
+    pub fn new(timeframe: &str) -> Result<WasmResampler, JsError> {
+        Ok(Self {
+            inner: resampler_from_js(timeframe, None)?,
+        })
+    }
+
+    /// Create a resampler over the trading dates of `session`: intraday bars
+    /// start at each session open and candles outside its sessions are dropped.
+    #[wasm_bindgen(js_name = "withSession")]
+    pub fn with_session(timeframe: &str, session: &WasmSession) -> Result<WasmResampler, JsError> {
+        Ok(Self {
+            inner: resampler_from_js(timeframe, Some(session))?,
+        })
+    }
+
+    /// Add a candle. Returns undefined if it belongs to a bar that has closed.
+    pub fn push(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<WasmResampleUpdate> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner
+            .push(candle)
+            .map(|inner| WasmResampleUpdate { inner })
+    }
+
+    /// Add many candles at once. Returns the bars they closed as an object
+    /// with `time`, `open`, `high`, `low`, `close` and `volume` arrays; the
+    /// last bar stays open (see `current`).
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        opens: &[f64],
+        highs: &[f64],
+        lows: &[f64],
+        closes: &[f64],
+        volumes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+        let closed: Vec<OHLCV> = candles
+            .into_iter()
+            .filter_map(|candle| self.inner.push(candle)?.closed)
+            .collect();
+        candles_to_js(&closed)
+    }
+
+    /// Replace the most recent candle with a revised one.
+    pub fn update(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<WasmResampleUpdate> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner
+            .update(candle)
+            .map(|inner| WasmResampleUpdate { inner })
+    }
+
+    /// Close the forming bar regardless of time.
+    pub fn flush(&mut self) -> JsValue {
+        self.inner
+            .flush()
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// The forming bar, if any.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> JsValue {
+        self.inner
+            .current()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// Drop the forming bar.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+// ============================================================================
+// Alternative Bar Types
+// ============================================================================
+
+/// Bars as an array of `{ time, open, high, low, close, volume }` objects.
+fn bars_to_array(bars: &[OHLCV]) -> js_sys::Array {
+    bars.iter().map(candle_to_js).collect()
+}
+
+/// Feed trade arrays into a trade bar builder; returns the closed bars.
+fn push_trades<B: BarBuilder<Trade>>(
+    builder: &mut B,
+    timestamps: &[f64],
+    prices: &[f64],
+    sizes: &[f64],
+) -> Result<JsValue, JsError> {
+    check_lengths(&[
+        ("timestamps", timestamps.len()),
+        ("prices", prices.len()),
+        ("sizes", sizes.len()),
+    ])
+    .map_err(js_error)?;
+
+    let mut closed = Vec::new();
+    for i in 0..timestamps.len() {
+        closed.extend(builder.push(Trade::new(timestamps[i] as i64, prices[i], sizes[i])));
+    }
+    candles_to_js(&closed)
+}
+
+/// Calculate Heikin-Ashi candles.
+///
+/// Returns an object with `time`, `open`, `high`, `low`, `close` and `volume`
+/// arrays.
+#[wasm_bindgen(js_name = "heikinAshi")]
+pub fn heikin_ashi_batch(
+    timestamps: &[f64],
+    opens: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[f64],
+) -> Result<JsValue, JsError> {
+    let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+    let ha = HeikinAshi::new().calculate(&candles).map_err(js_error)?;
+    candles_to_js(&ha)
+}
+
+/// Streaming Heikin-Ashi candles.
+#[wasm_bindgen(js_name = "HeikinAshiStream")]
+pub struct WasmHeikinAshiStream {
+    inner: HeikinAshiStream,
+}
+
+#[wasm_bindgen(js_class = "HeikinAshiStream")]
+impl WasmHeikinAshiStream {
+    /// Create a new streaming Heikin-Ashi transformer.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmHeikinAshiStream {
+        Self {
+            inner: HeikinAshiStream::new(),
+        }
+    }
+
+    /// Process the next candle; returns its Heikin-Ashi candle.
+    pub fn next(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> JsValue {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner
+            .next(candle)
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// Replace the most recent candle with a revised one.
+    pub fn update(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> JsValue {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner
+            .update(candle)
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// Preview the Heikin-Ashi candle of the next candle without changing state.
+    pub fn peek(
+        &self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> JsValue {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner
+            .peek(candle)
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// Reset the transformer state.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl Default for WasmHeikinAshiStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renko bricks built from candle closes.
+#[wasm_bindgen(js_name = "RenkoBars")]
+pub struct WasmRenkoBars {
+    inner: Renko,
+}
+
+#[wasm_bindgen(js_class = "RenkoBars")]
+impl WasmRenkoBars {
+    /// Create Renko with bricks of `boxSize` price units.
+    #[wasm_bindgen(constructor)]
+    pub fn new(box_size: f64) -> Result<WasmRenkoBars, JsError> {
+        let inner = Renko::new(box_size).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Create Renko with bricks sized by the ATR over `period` candles.
+    pub fn atr(period: usize) -> Result<WasmRenkoBars, JsError> {
+        let inner = Renko::atr(period).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Add a candle; returns the bricks it completed.
+    pub fn push(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> js_sys::Array {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        bars_to_array(&self.inner.push(candle))
+    }
+
+    /// Add many candles at once; returns the bricks they completed as an
+    /// object with `time`, `open`, `high`, `low`, `close` and `volume` arrays.
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        opens: &[f64],
+        highs: &[f64],
+        lows: &[f64],
+        closes: &[f64],
+        volumes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+        let bricks: Vec<OHLCV> = candles
+            .into_iter()
+            .flat_map(|candle| self.inner.push(candle))
+            .collect();
+        candles_to_js(&bricks)
+    }
+
+    /// Drop all bricks and state.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Range bars built from trades.
+#[wasm_bindgen(js_name = "RangeBars")]
+pub struct WasmRangeBars {
+    inner: RangeBars,
+}
+
+#[wasm_bindgen(js_class = "RangeBars")]
+impl WasmRangeBars {
+    /// Create range bars spanning `range` price units.
+    #[wasm_bindgen(constructor)]
+    pub fn new(range: f64) -> Result<WasmRangeBars, JsError> {
+        let inner = RangeBars::new(range).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Add a trade; returns the bars it closed.
+    pub fn push(&mut self, timestamp: f64, price: f64, size: f64) -> js_sys::Array {
+        bars_to_array(&self.inner.push(Trade::new(timestamp as i64, price, size)))
+    }
+
+    /// Add many trades at once; returns the bars they closed as an object
+    /// with `time`, `open`, `high`, `low`, `close` and `volume` arrays.
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        prices: &[f64],
+        sizes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        push_trades(&mut self.inner, timestamps, prices, sizes)
+    }
+
+    /// The forming bar, if any.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> JsValue {
+        self.inner
+            .current()
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// Drop the forming bar.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Volume bars built from trades.
+#[wasm_bindgen(js_name = "VolumeBars")]
+pub struct WasmVolumeBars {
+    inner: VolumeBars,
+}
+
+#[wasm_bindgen(js_class = "VolumeBars")]
+impl WasmVolumeBars {
+    /// Create bars of `volume` traded units.
+    #[wasm_bindgen(constructor)]
+    pub fn new(volume: f64) -> Result<WasmVolumeBars, JsError> {
+        let inner = VolumeBars::new(volume).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Add a trade; returns the bars it closed.
+    pub fn push(&mut self, timestamp: f64, price: f64, size: f64) -> js_sys::Array {
+        bars_to_array(&self.inner.push(Trade::new(timestamp as i64, price, size)))
+    }
+
+    /// Add many trades at once; returns the bars they closed as an object
+    /// with `time`, `open`, `high`, `low`, `close` and `volume` arrays.
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        prices: &[f64],
+        sizes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        push_trades(&mut self.inner, timestamps, prices, sizes)
+    }
+
+    /// The forming bar, if any.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> JsValue {
+        self.inner
+            .current()
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// Drop the forming bar.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Tick bars built from trades.
+#[wasm_bindgen(js_name = "TickBars")]
+pub struct WasmTickBars {
+    inner: TickBars,
+}
+
+#[wasm_bindgen(js_class = "TickBars")]
+impl WasmTickBars {
+    /// Create bars of `ticks` trades.
+    #[wasm_bindgen(constructor)]
+    pub fn new(ticks: usize) -> Result<WasmTickBars, JsError> {
+        let inner = TickBars::new(ticks).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Add a trade; returns the bars it closed.
+    pub fn push(&mut self, timestamp: f64, price: f64, size: f64) -> js_sys::Array {
+        bars_to_array(&self.inner.push(Trade::new(timestamp as i64, price, size)))
+    }
+
+    /// Add many trades at once; returns the bars they closed as an object
+    /// with `time`, `open`, `high`, `low`, `close` and `volume` arrays.
+    #[wasm_bindgen(js_name = "pushMany")]
+    pub fn push_many(
+        &mut self,
+        timestamps: &[f64],
+        prices: &[f64],
+        sizes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        push_trades(&mut self.inner, timestamps, prices, sizes)
+    }
+
+    /// The forming bar, if any.
+    #[wasm_bindgen(getter)]
+    pub fn current(&self) -> JsValue {
+        self.inner
+            .current()
+            .as_ref()
+            .map_or(JsValue::UNDEFINED, candle_to_js)
+    }
+
+    /// Drop the forming bar.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+// ============================================================================
+// Session Calendars
+// ============================================================================
+
+/// Parse a local time of day like `"09:30"`.
+fn parse_time_of_day(time: &str) -> Result<(u32, u32), JsError> {
+    time.split_once(':')
+        .and_then(|(h, m)| Some((h.parse().ok()?, m.parse().ok()?)))
+        .ok_or_else(|| JsError::new(&format!("Invalid time of day: '{time}'. Use 'HH:MM'")))
+}
+
+/// Parse a date like `"2024-07-04"`.
+fn parse_date(date: &str) -> Result<(i32, u32, u32), JsError> {
+    let parsed = match date.split('-').collect::<Vec<_>>()[..] {
+        [y, m, d] => y.parse().ok().zip(m.parse().ok()).zip(d.parse().ok()),
+        _ => None,
+    };
+    match parsed {
+        Some(((y, m), d)) if (1..=12).contains(&m) && (1..=31).contains(&d) => Ok((y, m, d)),
+        _ => Err(JsError::new(&format!(
+            "Invalid date: '{date}'. Use 'YYYY-MM-DD'"
+        ))),
+    }
+}
+
+/// Parse weekdays numbered as `Date.getDay()`, 0 = Sunday to 6 = Saturday.
+fn parse_weekdays(days: &[u8]) -> Result<Vec<Weekday>, JsError> {
+    days.iter()
+        .map(|&d| match d {
+            0 => Ok(Weekday::Sunday),
+            1..=6 => Ok(Weekday::ALL[usize::from(d) - 1]),
+            _ => Err(JsError::new("Trading days must be between 0 and 6")),
+        })
+        .collect()
+}
+
+/// Exchange trading session: local open/close times in a time zone, trading
+/// days, per-day hours, holidays and early closes.
+#[wasm_bindgen(js_name = "Session")]
+pub struct WasmSession {
+    inner: Session,
+}
+
+#[wasm_bindgen(js_class = "Session")]
+impl WasmSession {
+    /// Create a session from `open` to `close` (`"HH:MM"`, local time) in
+    /// `timeZone`: a built-in IANA name like `"America/New_York"` or a POSIX
+    /// TZ string. Trades Monday to Friday; `close` at or before `open` spans
+    /// midnight.
+    #[wasm_bindgen(constructor)]
+    pub fn new(time_zone: &str, open: &str, close: &str) -> Result<WasmSession, JsError> {
+        let tz: TimeZone = time_zone.parse().map_err(js_error)?;
+        let inner = Session::new(tz, parse_time_of_day(open)?, parse_time_of_day(close)?)
+            .map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// A built-in session: `"utc"`, `"nyse"`, `"cme"`, `"lse"`, `"xetra"`,
+    /// `"tse"` or `"hkex"`.
+    pub fn preset(name: &str) -> Result<WasmSession, JsError> {
+        let inner = match name.to_lowercase().as_str() {
+            "utc" | "crypto" => Session::utc(),
+            "nyse" | "nasdaq" => Session::nyse(),
+            "cme" | "globex" => Session::cme_globex(),
+            "lse" => Session::lse(),
+            "xetra" => Session::xetra(),
+            "tse" => Session::tse(),
+            "hkex" => Session::hkex(),
+            _ => {
+                return Err(JsError::new(&format!(
+                    "Unknown session: '{name}'. Use 'utc', 'nyse', 'cme', 'lse', 'xetra', 'tse' or 'hkex'"
+                )))
+            }
+        };
+        Ok(Self { inner })
+    }
+
+    /// Set the trading days, 0 = Sunday to 6 = Saturday (as `Date.getDay()`).
+    /// For sessions spanning midnight these are the days the session closes.
+    #[wasm_bindgen(js_name = "setTradingDays")]
+    pub fn set_trading_days(&mut self, days: &[u8]) -> Result<(), JsError> {
+        let days = parse_weekdays(days)?;
+        self.inner = std::mem::take(&mut self.inner).with_trading_days(&days);
+        Ok(())
+    }
+
+    /// Trade `days` (0 = Sunday to 6 = Saturday) from `open` to `close`
+    /// (`"HH:MM"`) instead of the session's regular hours, adding them to the
+    /// trading days, e.g. a shorter Friday.
+    #[wasm_bindgen(js_name = "setHours")]
+    pub fn set_hours(&mut self, days: &[u8], open: &str, close: &str) -> Result<(), JsError> {
+        let days = parse_weekdays(days)?;
+        let (open, close) = (parse_time_of_day(open)?, parse_time_of_day(close)?);
+        self.inner = std::mem::take(&mut self.inner)
+            .with_hours(&days, open, close)
+            .map_err(js_error)?;
+        Ok(())
+    }
+
+    /// Add holidays as `"YYYY-MM-DD"` trading dates.
+    #[wasm_bindgen(js_name = "addHolidays")]
+    pub fn add_holidays(&mut self, dates: Vec<String>) -> Result<(), JsError> {
+        let dates = dates
+            .into_iter()
+            .map(|d| parse_date(&d))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.inner = std::mem::take(&mut self.inner).with_holidays(&dates);
+        Ok(())
+    }
+
+    /// Close early at `close` (`"HH:MM"`) on `"YYYY-MM-DD"` trading dates.
+    #[wasm_bindgen(js_name = "addEarlyCloses")]
+    pub fn add_early_closes(&mut self, dates: Vec<String>, close: &str) -> Result<(), JsError> {
+        let dates = dates
+            .into_iter()
+            .map(|d| parse_date(&d))
+            .collect::<Result<Vec<_>, _>>()?;
+        let close = parse_time_of_day(close)?;
+        self.inner = std::mem::take(&mut self.inner)
+            .with_early_closes(&dates, close)
+            .map_err(js_error)?;
+        Ok(())
+    }
+
+    /// Trading date of the session `timestamp` falls in, as days since the
+    /// Unix epoch, or undefined if the market is closed.
+    #[wasm_bindgen(js_name = "tradingDate")]
+    pub fn trading_date(&self, timestamp: f64) -> Option<f64> {
+        self.inner
+            .trading_date(timestamp as i64)
+            .map(|date| date as f64)
+    }
+
+    /// Whether the market is open at `timestamp`.
+    #[wasm_bindgen(js_name = "isOpen")]
+    pub fn is_open(&self, timestamp: f64) -> bool {
+        self.inner.is_open(timestamp as i64)
+    }
+
+    /// Timestamp at which the session of trading date `date` opens.
+    #[wasm_bindgen(js_name = "openTime")]
+    pub fn open_time(&self, date: f64) -> f64 {
+        self.inner.open_time(date as i64) as f64
+    }
+}
+
+// ============================================================================
+// Session Volume Profile
+// ============================================================================
+
+/// How candles are split into session volume profiles.
+#[wasm_bindgen(js_name = "ProfilePeriod")]
+pub struct WasmProfilePeriod {
+    inner: ProfilePeriod,
+}
+
+#[wasm_bindgen(js_class = "ProfilePeriod")]
+impl WasmProfilePeriod {
+    /// One profile per session of `session`; candles outside it are skipped.
+    pub fn session(session: &WasmSession) -> WasmProfilePeriod {
+        Self {
+            inner: ProfilePeriod::Session(session.inner.clone()),
+        }
+    }
+
+    /// One profile per calendar period of UTC days: a timeframe like `"1d"`,
+    /// `"1w"` or `"1M"`.
+    pub fn calendar(timeframe: &str) -> Result<WasmProfilePeriod, JsError> {
+        let timeframe = timeframe.parse::<Timeframe>().map_err(js_error)?;
+        Ok(Self {
+            inner: ProfilePeriod::calendar(timeframe),
+        })
+    }
+
+    /// One profile per calendar period of the trading dates of `session`,
+    /// e.g. weekly profiles of CME Globex sessions.
+    #[wasm_bindgen(js_name = "calendarWithSession")]
+    pub fn calendar_with_session(
+        timeframe: &str,
+        session: &WasmSession,
+    ) -> Result<WasmProfilePeriod, JsError> {
+        let timeframe = timeframe.parse::<Timeframe>().map_err(js_error)?;
+        Ok(Self {
+            inner: ProfilePeriod::Calendar {
+                timeframe,
+                session: session.inner.clone(),
+            },
+        })
+    }
+}
+
+/// A session profile as a plain object: `start`, `end`, `candles`, `poc`,
+/// `vah`, `val`, `totalVolume`, `pocVolume`, `valueAreaVolume`,
+/// `pocRevisited` (undefined while the POC is naked) and a `histogram` of
+/// `prices`, `volumes`, `lows` and `highs` arrays.
+fn session_profile_to_js(session: &SessionProfile) -> Result<JsValue, JsError> {
+    let profile = &session.profile;
+    let obj = js_sys::Object::new();
+    let histogram = arrays_to_js(&[
+        (
+            "prices",
+            profile.histogram.iter().map(|r| r.price).collect(),
+        ),
+        (
+            "volumes",
+            profile.histogram.iter().map(|r| r.volume).collect(),
+        ),
+        ("lows", profile.histogram.iter().map(|r| r.low).collect()),
+        ("highs", profile.histogram.iter().map(|r| r.high).collect()),
+    ])?;
+    for (key, value) in [
+        ("start", JsValue::from_f64(session.start as f64)),
+        ("end", JsValue::from_f64(session.end as f64)),
+        ("candles", JsValue::from_f64(session.candles as f64)),
+        ("poc", JsValue::from_f64(profile.poc)),
+        ("vah", JsValue::from_f64(profile.vah)),
+        ("val", JsValue::from_f64(profile.val)),
+        ("totalVolume", JsValue::from_f64(profile.total_volume)),
+        ("pocVolume", JsValue::from_f64(profile.poc_volume)),
+        (
+            "valueAreaVolume",
+            JsValue::from_f64(profile.value_area_volume),
+        ),
+        (
+            "pocRevisited",
+            session
+                .poc_revisited
+                .map_or(JsValue::UNDEFINED, |t| JsValue::from_f64(t as f64)),
+        ),
+        ("histogram", histogram),
+    ] {
+        js_sys::Reflect::set(&obj, &JsValue::from_str(key), &value)
+            .map_err(|_| JsError::new(&format!("Failed to set {key} property")))?;
+    }
+    Ok(obj.into())
+}
+
+/// Session profiles as an array of plain objects.
+fn session_profiles_to_js<'a>(
+    sessions: impl Iterator<Item = &'a SessionProfile>,
+) -> Result<js_sys::Array, JsError> {
+    let result = js_sys::Array::new();
+    for session in sessions {
+        result.push(&session_profile_to_js(session)?);
+    }
+    Ok(result)
+}
+
+/// Developing POC/VAH/VAL as `poc`, `vah` and `val` arrays.
+fn developing_to_js(developing: &[DevelopingProfile]) -> Result<JsValue, JsError> {
+    arrays_to_js(&[
+        ("poc", developing.iter().map(|d| d.poc).collect()),
+        ("vah", developing.iter().map(|d| d.vah).collect()),
+        ("val", developing.iter().map(|d| d.val).collect()),
+    ])
+}
+
+/// A developing profile as a plain `{ poc, vah, val }` object.
+fn developing_profile_to_js(developing: DevelopingProfile) -> JsValue {
+    let obj = js_sys::Object::new();
+    for (key, value) in [
+        ("poc", developing.poc),
+        ("vah", developing.vah),
+        ("val", developing.val),
+    ] {
+        let _ = js_sys::Reflect::set(&obj, &JsValue::from_str(key), &JsValue::from_f64(value));
+    }
+    obj.into()
+}
+
+/// Streaming session volume profile: developing POC/VAH/VAL per candle,
+/// completed session profiles and naked POC tracking.
+#[wasm_bindgen(js_name = "SessionVolumeProfileStream")]
+pub struct WasmSessionVolumeProfileStream {
+    inner: SessionVolumeProfileStream,
+}
+
+#[wasm_bindgen(js_class = "SessionVolumeProfileStream")]
+impl WasmSessionVolumeProfileStream {
+    /// Create a stream splitting candles by `period`, with `numBins` price
+    /// bins per profile (default 100) and a value area of
+    /// `valueAreaPercent` (default 0.70).
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        period: &WasmProfilePeriod,
+        num_bins: Option<usize>,
+        value_area_percent: Option<f64>,
+    ) -> Result<WasmSessionVolumeProfileStream, JsError> {
+        let inner = SessionVolumeProfileStream::with_value_area(
+            period.inner.clone(),
+            num_bins.unwrap_or(100),
+            value_area_percent.unwrap_or(0.70),
+        )
+        .map_err(js_error)?;
+        Ok(Self { inner })
+    }
+
+    /// Initialize with historical OHLCV data. Returns `poc`, `vah` and `val`
+    /// arrays, NaN outside sessions.
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init_history(
+        &mut self,
+        timestamps: &[f64],
+        opens: &[f64],
+        highs: &[f64],
+        lows: &[f64],
+        closes: &[f64],
+        volumes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+        let developing = self.inner.init(&candles).map_err(js_error)?;
+        developing_to_js(&developing)
+    }
+
+    /// Process the next candle. Returns the developing `{ poc, vah, val }`,
+    /// or undefined outside sessions.
+    pub fn next(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<JsValue> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.next(candle).map(developing_profile_to_js)
+    }
+
+    /// Replace the most recent candle with a revised one (live candle update).
+    pub fn update(
+        &mut self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<JsValue> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.update(candle).map(developing_profile_to_js)
+    }
+
+    /// Preview the output `next` would return for this candle, without
+    /// changing any state.
+    pub fn peek(
+        &self,
+        timestamp: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<JsValue> {
+        let candle = OHLCV::new(timestamp as i64, open, high, low, close, volume);
+        self.inner.peek(candle).map(developing_profile_to_js)
+    }
+
+    /// Completed session profiles, oldest first.
+    pub fn sessions(&self) -> Result<js_sys::Array, JsError> {
+        session_profiles_to_js(self.inner.sessions().iter())
+    }
+
+    /// Completed sessions whose POC no later candle has traded through.
+    #[wasm_bindgen(js_name = "nakedPocs")]
+    pub fn naked_pocs(&self) -> Result<js_sys::Array, JsError> {
+        session_profiles_to_js(self.inner.naked_pocs())
+    }
+
+    /// Profile of the forming session, if any.
+    #[wasm_bindgen(js_name = "currentSession")]
+    pub fn current_session(&self) -> Result<JsValue, JsError> {
+        self.inner
+            .current_session()
+            .map_or(Ok(JsValue::UNDEFINED), |session| {
+                session_profile_to_js(&session)
+            })
+    }
+
+    /// Reset the calculator and drop all sessions.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen(js_class = "SessionVolumeProfileStream")]
+impl WasmSessionVolumeProfileStream {
+    /// Serialize the complete stream state to a compact binary snapshot.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.inner.to_bytes().map_err(js_error)
+    }
+
+    /// Restore a stream from `toBytes()` output.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmSessionVolumeProfileStream, JsError> {
+        let inner = SessionVolumeProfileStream::from_bytes(bytes).map_err(js_error)?;
+        Ok(Self { inner })
+    }
+}
+
+// ============================================================================
+// Columnar Input
+// ============================================================================
+
+/// Values per row of an interleaved OHLCV buffer: time, open, high, low,
+/// close, volume.
+const OHLCV_STRIDE: usize = 6;
+
+/// Object with one `Float64Array` property per `(name, values)` pair.
+fn arrays_to_js(fields: &[(&str, Vec<f64>)]) -> Result<JsValue, JsError> {
+    let obj = js_sys::Object::new();
+    for (key, values) in fields {
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str(key),
+            &js_sys::Float64Array::from(&values[..]).into(),
+        )
+        .map_err(|_| JsError::new(&format!("Failed to set {key} property")))?;
+    }
+    Ok(obj.into())
+}
+
+/// OHLCV data loaded from a single interleaved buffer.
+///
+/// The buffer holds one row per candle: `[time, open, high, low, close,
+/// volume, time, open, ...]`. It is split into columns once; every method
+/// then runs its indicator over those columns without building candles.
+#[wasm_bindgen(js_name = "OhlcvColumns")]
+pub struct WasmOhlcvColumns {
+    timestamps: Vec<i64>,
+    opens: Vec<f64>,
+    highs: Vec<f64>,
+    lows: Vec<f64>,
+    closes: Vec<f64>,
+    volumes: Vec<f64>,
+}
+
+impl WasmOhlcvColumns {
+    fn columns(&self) -> Result<OhlcvColumns<'_>, JsError> {
+        OhlcvColumns::new(
+            &self.timestamps,
+            &self.opens,
+            &self.highs,
+            &self.lows,
+            &self.closes,
+            &self.volumes,
+        )
+        .map_err(js_error)
+    }
+
+    fn stoch(&self, indicator: &Stoch) -> Result<JsValue, JsError> {
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        arrays_to_js(&[
+            ("k", results.iter().map(|r| r.k).collect()),
+            ("d", results.iter().map(|r| r.d).collect()),
+        ])
+    }
+}
+
+#[wasm_bindgen(js_class = "OhlcvColumns")]
+impl WasmOhlcvColumns {
+    /// Load candles from an interleaved `Float64Array` with 6 values per row.
+    #[wasm_bindgen(constructor)]
+    pub fn new(interleaved: &[f64]) -> Result<WasmOhlcvColumns, JsError> {
+        if !interleaved.len().is_multiple_of(OHLCV_STRIDE) {
+            return Err(JsError::new(&format!(
+                "Interleaved OHLCV length must be a multiple of {OHLCV_STRIDE}, got {}",
+                interleaved.len()
+            )));
+        }
+        let rows = interleaved.chunks_exact(OHLCV_STRIDE);
+        let column = |i: usize| -> Vec<f64> { rows.clone().map(|row| row[i]).collect() };
+        Ok(Self {
+            timestamps: to_millis(&column(0)),
+            opens: column(1),
+            highs: column(2),
+            lows: column(3),
+            closes: column(4),
+            volumes: column(5),
+        })
+    }
+
+    /// Number of candles.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    /// Average True Range.
+    pub fn atr(&self, period: usize) -> Result<Vec<f64>, JsError> {
+        let indicator = Atr::new(period).map_err(js_error)?;
+        indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)
+    }
+
+    /// ADX with `adx`, `plusDi`, and `minusDi` arrays.
+    pub fn adx(&self, period: usize) -> Result<JsValue, JsError> {
+        let indicator = Adx::new(period).map_err(js_error)?;
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        arrays_to_js(&[
+            ("adx", results.iter().map(|r| r.adx).collect()),
+            ("plusDi", results.iter().map(|r| r.plus_di).collect()),
+            ("minusDi", results.iter().map(|r| r.minus_di).collect()),
+        ])
+    }
+
+    /// Fast Stochastic with `k` and `d` arrays.
+    #[wasm_bindgen(js_name = "stochFast")]
+    pub fn stoch_fast(&self, k_period: usize, d_period: usize) -> Result<JsValue, JsError> {
+        let indicator = Stoch::new(k_period, d_period, StochType::Fast).map_err(js_error)?;
+        self.stoch(&indicator)
+    }
+
+    /// Slow Stochastic with `k` and `d` arrays.
+    #[wasm_bindgen(js_name = "stochSlow")]
+    pub fn stoch_slow(
+        &self,
+        k_period: usize,
+        d_period: usize,
+        slowing: usize,
+    ) -> Result<JsValue, JsError> {
+        let indicator = Stoch::new_with_slowing(k_period, d_period, slowing, StochType::Slow)
+            .map_err(js_error)?;
+        self.stoch(&indicator)
+    }
+
+    /// Ichimoku Cloud with an array per component.
+    pub fn ichimoku(
+        &self,
+        tenkan_period: usize,
+        kijun_period: usize,
+        senkou_b_period: usize,
+    ) -> Result<JsValue, JsError> {
+        let indicator =
+            Ichimoku::new(tenkan_period, kijun_period, senkou_b_period).map_err(js_error)?;
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        arrays_to_js(&[
+            ("tenkanSen", results.iter().map(|r| r.tenkan_sen).collect()),
+            ("kijunSen", results.iter().map(|r| r.kijun_sen).collect()),
+            (
+                "senkouSpanA",
+                results.iter().map(|r| r.senkou_span_a).collect(),
+            ),
+            (
+                "senkouSpanB",
+                results.iter().map(|r| r.senkou_span_b).collect(),
+            ),
+            (
+                "chikouSpan",
+                results.iter().map(|r| r.chikou_span).collect(),
+            ),
+        ])
+    }
+
+    /// Keltner Channels with `upper`, `middle`, `lower`, and `width` arrays.
+    /// `ma` is 'ema' (default) or 'sma'; `band` is 'atr' (default) or 'range'.
+    pub fn keltner(
+        &self,
+        period: usize,
+        atr_period: usize,
+        multiplier: f64,
+        ma: Option<String>,
+        band: Option<String>,
+    ) -> Result<JsValue, JsError> {
+        let ma = ma.map_or(Ok(KeltnerMa::Ema), |ma| parse_keltner_ma(&ma))?;
+        let band = band.map_or(Ok(KeltnerBand::Atr), |band| parse_keltner_band(&band))?;
+        let indicator = Keltner::new(period, atr_period, multiplier)
+            .map_err(js_error)?
+            .with_ma(ma)
+            .with_band(band);
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        keltner_to_js(&results)
+    }
+
+    /// TTM Squeeze with `squeezeOn`, `squeezeBars`, and `momentum` arrays.
+    pub fn squeeze(&self, period: usize, bb_mult: f64, kc_mult: f64) -> Result<JsValue, JsError> {
+        let indicator = Squeeze::new(period, bb_mult, kc_mult).map_err(js_error)?;
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        squeeze_to_js(&results)
+    }
+
+    /// Donchian Channels with `upper`, `lower`, `middle`, `width`,
+    /// `barsSinceHigh`, and `barsSinceLow` arrays.
+    pub fn donchian(&self, period: usize) -> Result<JsValue, JsError> {
+        let indicator = Donchian::new(period).map_err(js_error)?;
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        donchian_to_js(&results)
+    }
+
+    /// Supertrend with `value`, `direction`, and `flip` arrays. `source` is
+    /// 'hl2' (default), 'hlc3' or 'close'; `atr` is 'wilder' (default) or 'sma'.
+    pub fn supertrend(
+        &self,
+        atr_period: usize,
+        multiplier: f64,
+        source: Option<String>,
+        atr: Option<String>,
+    ) -> Result<JsValue, JsError> {
+        let source = source.map_or(Ok(SuperTrendSource::Hl2), |source| {
+            parse_supertrend_source(&source)
+        })?;
+        let atr = atr.map_or(Ok(SuperTrendAtr::Wilder), |atr| parse_supertrend_atr(&atr))?;
+        let indicator = SuperTrend::new(atr_period, multiplier)
+            .map_err(js_error)?
+            .with_source(source)
+            .with_atr(atr);
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        supertrend_to_js(&results)
+    }
+
+    /// Parabolic SAR with `sar`, `direction`, `af`, and `ep` arrays.
+    pub fn psar(&self, start: f64, increment: f64, maximum: f64) -> Result<JsValue, JsError> {
+        let indicator = Psar::new(start, increment, maximum).map_err(js_error)?;
+        let results = indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+        psar_to_js(&results)
+    }
+
+    /// Money Flow Index.
+    pub fn mfi(&self, period: usize) -> Result<Vec<f64>, JsError> {
+        let indicator = Mfi::new(period).map_err(js_error)?;
+        indicator
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)
+    }
+
+    /// Pivot points per candle ('standard', 'fibonacci', or 'woodie').
+    #[wasm_bindgen(js_name = "pivotPoints")]
+    pub fn pivot_points(&self, variant: &str) -> Result<JsValue, JsError> {
+        let pp = PivotPoints::new(parse_pivot_variant(variant)?);
+        let results: Vec<PivotPointsOutput> =
+            pp.calculate_columns(&self.columns()?).map_err(js_error)?;
+        arrays_to_js(&[
+            ("pivot", results.iter().map(|r| r.pivot).collect()),
+            ("r1", results.iter().map(|r| r.r1).collect()),
+            ("r2", results.iter().map(|r| r.r2).collect()),
+            ("r3", results.iter().map(|r| r.r3).collect()),
+            ("s1", results.iter().map(|r| r.s1).collect()),
+            ("s2", results.iter().map(|r| r.s2).collect()),
+            ("s3", results.iter().map(|r| r.s3).collect()),
+        ])
+    }
+
+    /// Cumulative volume delta estimated from candle structure.
+    #[wasm_bindgen(js_name = "cvdOhlcv")]
+    pub fn cvd_ohlcv(&self) -> Result<Vec<f64>, JsError> {
+        CvdOhlcv::new()
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)
+    }
+
+    /// Session VWAP, resetting at UTC midnight.
+    #[wasm_bindgen(js_name = "sessionVwap")]
+    pub fn session_vwap(&self) -> Result<Vec<f64>, JsError> {
+        SessionVwap::new()
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)
+    }
+
+    /// Session VWAP resetting at each open of an exchange `session`.
+    #[wasm_bindgen(js_name = "sessionVwapWithSession")]
+    pub fn session_vwap_with_session(&self, session: &WasmSession) -> Result<Vec<f64>, JsError> {
+        SessionVwap::with_session(session.inner.clone())
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)
+    }
+
+    /// Rolling VWAP over `period` candles.
+    #[wasm_bindgen(js_name = "rollingVwap")]
+    pub fn rolling_vwap(&self, period: usize) -> Result<Vec<f64>, JsError> {
+        RollingVwap::new(period)
+            .map_err(js_error)?
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)
+    }
+
+    /// Anchored VWAP starting at candle `anchor_index`.
+    #[wasm_bindgen(js_name = "anchoredVwap")]
+    pub fn anchored_vwap(&self, anchor_index: usize) -> Result<Vec<f64>, JsError> {
+        AnchoredVwap::new(anchor_index)
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)
+    }
+
+    /// Fixed Range Volume Profile over all candles.
+    pub fn frvp(
+        &self,
+        num_bins: Option<usize>,
+        value_area_percent: Option<f64>,
+    ) -> Result<WasmFrvpOutput, JsError> {
+        let frvp =
+            Frvp::with_value_area(num_bins.unwrap_or(100), value_area_percent.unwrap_or(0.70))
+                .map_err(js_error)?;
+        let result = frvp.calculate_columns(&self.columns()?).map_err(js_error)?;
+        Ok(WasmFrvpOutput::from(result))
+    }
+
+    /// Volume profile per session or period of `period`: an object with
+    /// `sessions` (plain session profile objects, oldest first; the last may
+    /// be incomplete) and the developing `poc`, `vah` and `val` arrays.
+    #[wasm_bindgen(js_name = "sessionVolumeProfile")]
+    pub fn session_volume_profile(
+        &self,
+        period: &WasmProfilePeriod,
+        num_bins: Option<usize>,
+        value_area_percent: Option<f64>,
+    ) -> Result<JsValue, JsError> {
+        let profile = SessionVolumeProfile::with_value_area(
+            period.inner.clone(),
+            num_bins.unwrap_or(100),
+            value_area_percent.unwrap_or(0.70),
+        )
+        .map_err(js_error)?;
+        let result = profile
+            .calculate_columns(&self.columns()?)
+            .map_err(js_error)?;
+
+        let obj = developing_to_js(&result.developing)?;
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("sessions"),
+            &session_profiles_to_js(result.sessions.iter())?.into(),
+        )
+        .map_err(|_| JsError::new("Failed to set sessions property"))?;
+        Ok(obj)
+    }
+}
+
+// ============================================================================
+// Data Validation
+// ============================================================================
+
+fn validator_from_js(interval_ms: Option<f64>) -> Validator {
+    interval_ms.map_or_else(Validator::new, |ms| {
+        Validator::new().with_interval(ms as i64)
+    })
+}
+
+/// Report anomalies in OHLCV arrays.
+///
+/// Returns an array of `{ index, time, kind, missing, message }` objects in
+/// bar order. `kind` is one of 'highBelowLow', 'openOutOfRange',
+/// 'closeOutOfRange', 'negativeVolume', 'duplicateTimestamp', 'outOfOrder'
+/// or 'gap' (the number of missing bars is in `missing`, 0 otherwise). Gaps
+/// are only reported when `intervalMs` is given.
+#[wasm_bindgen(js_name = "validateCandles")]
+pub fn validate_candles(
+    timestamps: &[f64],
+    opens: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[f64],
+    interval_ms: Option<f64>,
+) -> Result<js_sys::Array, JsError> {
+    let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+    let report = validator_from_js(interval_ms).validate(&candles);
+
+    let result = js_sys::Array::new();
+    for bad in &report.anomalies {
+        let (kind, missing) = match bad.anomaly {
+            Anomaly::HighBelowLow => ("highBelowLow", 0),
+            Anomaly::OpenOutOfRange => ("openOutOfRange", 0),
+            Anomaly::CloseOutOfRange => ("closeOutOfRange", 0),
+            Anomaly::NegativeVolume => ("negativeVolume", 0),
+            Anomaly::DuplicateTimestamp => ("duplicateTimestamp", 0),
+            Anomaly::OutOfOrder => ("outOfOrder", 0),
+            Anomaly::Gap { missing } => ("gap", missing),
+        };
+        let obj = js_sys::Object::new();
+        for (key, value) in [
+            ("index", JsValue::from_f64(bad.index as f64)),
+            ("time", JsValue::from_f64(bad.timestamp as f64)),
+            ("kind", JsValue::from_str(kind)),
+            ("missing", JsValue::from_f64(missing as f64)),
+            ("message", JsValue::from_str(&bad.anomaly.to_string())),
+        ] {
+            js_sys::Reflect::set(&obj, &JsValue::from_str(key), &value)
+                .map_err(|_| JsError::new(&format!("Failed to set {key} property")))?;
+        }
+        result.push(&obj);
+    }
+    Ok(result)
+}
+
+/// Repair OHLCV arrays: sort and deduplicate by timestamp (keeping the last
+/// candle for each timestamp), clamp open/close into the low-high range,
+/// zero negative volume, and fill gaps with flat bars when `intervalMs` is
+/// given.
+///
+/// Returns an object of `time`, `open`, `high`, `low`, `close` and `volume`
+/// arrays.
+#[wasm_bindgen(js_name = "repairCandles")]
+pub fn repair_candles(
+    timestamps: &[f64],
+    opens: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[f64],
+    interval_ms: Option<f64>,
+) -> Result<JsValue, JsError> {
+    let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+    candles_to_js(&validator_from_js(interval_ms).repair(&candles))
+}
+
+// ============================================================================
+// Corporate Actions
+// ============================================================================
+
+/// Back-adjusts OHLCV arrays for stock splits and cash dividends.
+#[wasm_bindgen(js_name = "Adjuster")]
+pub struct WasmAdjuster {
+    inner: Adjuster,
+}
+
+#[wasm_bindgen(js_class = "Adjuster")]
+impl WasmAdjuster {
+    /// Create an adjuster with no actions. With `totalReturn`, dividends are
+    /// applied too; otherwise splits only.
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new(total_return: Option<bool>) -> WasmAdjuster {
+        let mode = if total_return.unwrap_or(false) {
+            AdjustMode::TotalReturn
+        } else {
+            AdjustMode::PriceOnly
+        };
+        Self {
+            inner: Adjuster::new(mode),
+        }
+    }
+
+    /// Add a split of `ratio` new shares per old share (2 for 2-for-1),
+    /// effective from the candle at `exDate` (ms).
+    pub fn split(&mut self, ex_date: f64, ratio: f64) -> Result<(), JsError> {
+        self.inner
+            .push(CorporateAction::Split {
+                ex_date: ex_date as i64,
+                ratio,
+            })
+            .map_err(js_error)
+    }
+
+    /// Add a cash dividend of `amount` per share going ex at `exDate` (ms).
+    pub fn dividend(&mut self, ex_date: f64, amount: f64) -> Result<(), JsError> {
+        self.inner
+            .push(CorporateAction::Dividend {
+                ex_date: ex_date as i64,
+                amount,
+            })
+            .map_err(js_error)
+    }
+
+    /// Back-adjust OHLCV arrays in ascending time order.
+    ///
+    /// Returns an object of `time`, `open`, `high`, `low`, `close` and
+    /// `volume` arrays.
+    pub fn adjust(
+        &self,
+        timestamps: &[f64],
+        opens: &[f64],
+        highs: &[f64],
+        lows: &[f64],
+        closes: &[f64],
+        volumes: &[f64],
+    ) -> Result<JsValue, JsError> {
+        let candles = arrays_to_ohlcv(timestamps, opens, highs, lows, closes, volumes)?;
+        candles_to_js(&self.inner.adjust(&candles).map_err(js_error)?)
+    }
+}
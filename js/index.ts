@@ -14,6 +14,9 @@
  * // OHLCV indicators accept Candle[] directly
  * const candles: Candle[] = [{ open: 100, high: 102, low: 99, close: 101, volume: 1000 }];
  * const atrValues = atr(candles, 14);
+ *
+ * // Single-series indicators take candles too, reading any price source
+ * const rsiTypical = rsi(candles, 14, { source: 'hlc3' });
  * ```
  *
  * @example Streaming Mode
@@ -21,6 +24,17 @@
  * const rsiStream = rsi.stream(14);
  * rsiStream.init(historicalPrices);
  * const currentRsi = rsiStream.next(newPrice);
+ *
+ * // Revise the still-forming bar without advancing the stream
+ * const revisedRsi = rsiStream.update(revisedPrice);
+ *
+ * // Persist the stream and pick up where it left off after a restart
+ * const saved = rsiStream.toBytes();
+ * const resumed = RsiStream.fromBytes(saved);
+ *
+ * // Chain streams without leaving WASM: SMA(9) of RSI(14)
+ * const smoothedRsi = rsi.stream(14).pipe(sma.stream(9));
+ * const value = smoothedRsi.next(newPrice);
  * ```
  */
 
@@ -57,6 +71,58 @@ export interface BBandsOutput {
   bandwidth: Float64Array;
 }
 
+/** Keltner Channels output */
+export interface KeltnerOutput {
+  upper: Float64Array;
+  middle: Float64Array;
+  lower: Float64Array;
+  /** (upper - lower) / middle */
+  width: Float64Array;
+}
+
+/** Donchian Channels output */
+export interface DonchianOutput {
+  upper: Float64Array;
+  lower: Float64Array;
+  middle: Float64Array;
+  /** (upper - lower) / middle */
+  width: Float64Array;
+  /** Bars since the highest high in the window (0 = current bar) */
+  barsSinceHigh: Uint32Array;
+  /** Bars since the lowest low in the window (0 = current bar) */
+  barsSinceLow: Uint32Array;
+}
+
+/** Supertrend output */
+export interface SuperTrendOutput {
+  /** Lower band in an uptrend, upper band in a downtrend */
+  value: Float64Array;
+  /** 1 = up, -1 = down, 0 during warmup */
+  direction: Int8Array;
+  /** 1 on the bar where the direction flips, else 0 */
+  flip: Uint8Array;
+}
+
+/** Parabolic SAR output */
+export interface PsarOutput {
+  sar: Float64Array;
+  /** 1 = long, -1 = short, 0 during warmup */
+  direction: Int8Array;
+  /** Acceleration factor applied to the next SAR */
+  af: Float64Array;
+  /** Extreme point of the current trend */
+  ep: Float64Array;
+}
+
+/** TTM Squeeze output */
+export interface SqueezeOutput {
+  /** 1 while the Bollinger Bands are inside the Keltner Channels, else 0 */
+  squeezeOn: Uint8Array;
+  /** Consecutive squeeze-on bars including the current one (0 when off) */
+  squeezeBars: Uint32Array;
+  momentum: Float64Array;
+}
+
 /** Stochastic output */
 export interface StochOutput {
   k: Float64Array;
@@ -147,6 +213,79 @@ export function extractOHLCV(candles: Candle[]): {
   return { open, high, low, close, volume, time };
 }
 
+/**
+ * Price derived from each candle by single-series indicators.
+ * Either a named source or custom weights (normalized by their sum).
+ */
+export type PriceSource =
+  | 'open'
+  | 'high'
+  | 'low'
+  | 'close'
+  | 'hl2'
+  | 'hlc3'
+  | 'ohlc4'
+  | 'hlcc4'
+  | { open?: number; high?: number; low?: number; close?: number };
+
+/**
+ * Options for single-series indicators given Candle[] input.
+ */
+export interface SourceOptions {
+  /** Price taken from each candle (default: 'close') */
+  source?: PriceSource;
+}
+
+/**
+ * Derives a single price from a candle. Useful for feeding streams with candles:
+ * `stream.next(sourcePrice(candle, 'hlc3'))`.
+ */
+export function sourcePrice(candle: Candle, source: PriceSource = 'close'): number {
+  switch (source) {
+    case 'open':
+      return candle.open;
+    case 'high':
+      return candle.high;
+    case 'low':
+      return candle.low;
+    case 'close':
+      return candle.close;
+    case 'hl2':
+      return (candle.high + candle.low) / 2;
+    case 'hlc3':
+      return (candle.high + candle.low + candle.close) / 3;
+    case 'ohlc4':
+      return (candle.open + candle.high + candle.low + candle.close) / 4;
+    case 'hlcc4':
+      return (candle.high + candle.low + 2 * candle.close) / 4;
+    default: {
+      const { open = 0, high = 0, low = 0, close = 0 } = source;
+      const sum = open + high + low + close;
+      if (!(sum > 0)) {
+        throw new Error('Price source weights must sum to a positive number');
+      }
+      return (candle.open * open + candle.high * high + candle.low * low + candle.close * close) / sum;
+    }
+  }
+}
+
+/**
+ * Converts prices or candles to a Float64Array in a single pass.
+ * Candles are reduced to one price each via `source`; prices pass through
+ * `toFloat64Array` unchanged.
+ */
+export function toPriceSeries(data: PriceInput | CandleInput, source?: PriceSource): Float64Array {
+  if (!isCandleArray(data)) {
+    return toFloat64Array(data as PriceInput);
+  }
+  const len = data.length;
+  const out = new Float64Array(len);
+  for (let i = 0; i < len; i++) {
+    out[i] = sourcePrice(data[i], source);
+  }
+  return out;
+}
+
 /**
  * Check if input is Candle array
  */
@@ -189,6 +328,7 @@ import {
   cvd as wasmCvd,
   cvdOhlcv as wasmCvdOhlcv,
   sessionVwap as wasmSessionVwap,
+  sessionVwapWithSession as wasmSessionVwapWithSession,
   rollingVwap as wasmRollingVwap,
   anchoredVwap as wasmAnchoredVwap,
   anchoredVwapFromTimestamp as wasmAnchoredVwapFromTimestamp,
@@ -217,18 +357,83 @@ import {
   RollingVwapStream,
   AnchoredVwapStream,
   FrvpStream,
+  SessionVolumeProfileStream,
   MfiStream,
   HmaStream,
   IchimokuStream,
   AdxStream,
   LinRegStream,
+  KeltnerStream,
+  SqueezeStream,
+  DonchianStream,
+  SuperTrendStream,
+  PsarStream,
+  PipeStream,
+  // Bar aggregation
+  BarAggregator,
+  Bar,
+  BarUpdate,
+  // Trade CVD
+  TradeCvdStream,
+  DeltaBar,
+  DeltaUpdate,
+  // Footprint
+  FootprintStream,
+  FootprintBar,
+  FootprintUpdate,
+  // Resampling
+  Resampler,
+  ResampleUpdate,
+  alignTimeframe as wasmAlignTimeframe,
+  alignTimeframeWithSession as wasmAlignTimeframeWithSession,
+  // Alternative bar types
+  heikinAshi as wasmHeikinAshi,
+  HeikinAshiStream,
+  RenkoBars,
+  RangeBars,
+  VolumeBars,
+  TickBars,
+  // Session calendars
+  Session,
+  ProfilePeriod,
+  // Columnar input
+  OhlcvColumns,
+  // Data validation
+  validateCandles as wasmValidateCandles,
+  repairCandles as wasmRepairCandles,
+  // Corporate actions
+  Adjuster,
+  // Errors
+  registerErrorClasses as wasmRegisterErrorClasses,
   // Output types from WASM
   FrvpOutput,
   VolumeProfileRow,
 } from '../pkg/ta_core.js';
 
 // Re-export WASM types
-export { FrvpOutput, VolumeProfileRow };
+export {
+  FrvpOutput,
+  VolumeProfileRow,
+  BarAggregator,
+  Bar,
+  BarUpdate,
+  TradeCvdStream,
+  DeltaBar,
+  DeltaUpdate,
+  FootprintStream,
+  FootprintBar,
+  FootprintUpdate,
+  Resampler,
+  ResampleUpdate,
+  RenkoBars,
+  RangeBars,
+  VolumeBars,
+  TickBars,
+  Session,
+  ProfilePeriod,
+  OhlcvColumns,
+  Adjuster,
+};
 
 // Re-export streaming classes for advanced users
 export {
@@ -248,13 +453,87 @@ export {
   RollingVwapStream,
   AnchoredVwapStream,
   FrvpStream,
+  SessionVolumeProfileStream,
   MfiStream,
   HmaStream,
   IchimokuStream,
   AdxStream,
   LinRegStream,
+  KeltnerStream,
+  SqueezeStream,
+  DonchianStream,
+  SuperTrendStream,
+  PsarStream,
+  PipeStream,
+  HeikinAshiStream,
 };
 
+// =============================================================================
+// Errors
+// =============================================================================
+
+/**
+ * Base class of every error thrown by the WASM core. `code` is stable across
+ * releases (e.g. `'LENGTH_MISMATCH'`); match on it or on the subclass rather
+ * than on `message`.
+ */
+export class TaError extends Error {
+  declare readonly code: string;
+}
+
+/** Not enough data points for the indicator. */
+export class InsufficientDataError extends TaError {
+  declare readonly required: number;
+  declare readonly provided: number;
+}
+
+/** A parameter was rejected (e.g. a period of 0). */
+export class InvalidParameterError extends TaError {}
+
+/** The indicator was used before being initialized. */
+export class NotInitializedError extends TaError {}
+
+/** Snapshot bytes are corrupt, from another format version or for another stream. */
+export class InvalidSnapshotError extends TaError {}
+
+/** A candle failed strict validation. */
+export class InvalidDataError extends TaError {
+  declare readonly index: number;
+  declare readonly reason: string;
+}
+
+/** Parallel input arrays differ in length: `array` has `length` elements where `expectedArray` has `expectedLength`. */
+export class LengthMismatchError extends TaError {
+  declare readonly array: string;
+  declare readonly length: number;
+  declare readonly expectedArray: string;
+  declare readonly expectedLength: number;
+}
+
+/** An input value at `index` is `NaN` or infinite where a finite number is required. */
+export class NonFiniteInputError extends TaError {
+  declare readonly index: number;
+}
+
+/** Parameter `param` is outside `min..max`. */
+export class OutOfRangeError extends TaError {
+  declare readonly param: string;
+  declare readonly value: number;
+  declare readonly min: number;
+  declare readonly max: number;
+}
+
+wasmRegisterErrorClasses({
+  INSUFFICIENT_DATA: InsufficientDataError,
+  INVALID_PARAMETER: InvalidParameterError,
+  NOT_INITIALIZED: NotInitializedError,
+  INVALID_SNAPSHOT: InvalidSnapshotError,
+  INVALID_DATA: InvalidDataError,
+  LENGTH_MISMATCH: LengthMismatchError,
+  NON_FINITE_INPUT: NonFiniteInputError,
+  OUT_OF_RANGE: OutOfRangeError,
+});
+
 // =============================================================================
 // Single-Input Indicators
 // =============================================================================
@@ -264,40 +543,60 @@ type PriceInput = number[] | Float64Array;
 /**
  * Simple Moving Average
  */
-export function sma(data: PriceInput, period: number): Float64Array {
-  return wasmSma(toFloat64Array(data), period);
+export function sma(
+  data: PriceInput | CandleInput,
+  period: number,
+  options: SourceOptions = {}
+): Float64Array {
+  return wasmSma(toPriceSeries(data, options.source), period);
 }
 sma.stream = (period: number) => new SmaStream(period);
 
 /**
  * Exponential Moving Average
  */
-export function ema(data: PriceInput, period: number): Float64Array {
-  return wasmEma(toFloat64Array(data), period);
+export function ema(
+  data: PriceInput | CandleInput,
+  period: number,
+  options: SourceOptions = {}
+): Float64Array {
+  return wasmEma(toPriceSeries(data, options.source), period);
 }
 ema.stream = (period: number) => new EmaStream(period);
 
 /**
  * Weighted Moving Average
  */
-export function wma(data: PriceInput, period: number): Float64Array {
-  return wasmWma(toFloat64Array(data), period);
+export function wma(
+  data: PriceInput | CandleInput,
+  period: number,
+  options: SourceOptions = {}
+): Float64Array {
+  return wasmWma(toPriceSeries(data, options.source), period);
 }
 wma.stream = (period: number) => new WmaStream(period);
 
 /**
  * Relative Strength Index
  */
-export function rsi(data: PriceInput, period: number): Float64Array {
-  return wasmRsi(toFloat64Array(data), period);
+export function rsi(
+  data: PriceInput | CandleInput,
+  period: number,
+  options: SourceOptions = {}
+): Float64Array {
+  return wasmRsi(toPriceSeries(data, options.source), period);
 }
 rsi.stream = (period: number) => new RsiStream(period);
 
 /**
  * Hull Moving Average
  */
-export function hma(data: PriceInput, period: number): Float64Array {
-  return wasmHma(toFloat64Array(data), period);
+export function hma(
+  data: PriceInput | CandleInput,
+  period: number,
+  options: SourceOptions = {}
+): Float64Array {
+  return wasmHma(toPriceSeries(data, options.source), period);
 }
 hma.stream = (period: number) => new HmaStream(period);
 
@@ -309,12 +608,13 @@ hma.stream = (period: number) => new HmaStream(period);
  * Moving Average Convergence Divergence
  */
 export function macd(
-  data: PriceInput,
+  data: PriceInput | CandleInput,
   fastPeriod: number,
   slowPeriod: number,
-  signalPeriod: number
+  signalPeriod: number,
+  options: SourceOptions = {}
 ): MacdOutput {
-  return wasmMacd(toFloat64Array(data), fastPeriod, slowPeriod, signalPeriod);
+  return wasmMacd(toPriceSeries(data, options.source), fastPeriod, slowPeriod, signalPeriod);
 }
 macd.stream = (fastPeriod: number, slowPeriod: number, signalPeriod: number) =>
   new MacdStream(fastPeriod, slowPeriod, signalPeriod);
@@ -322,8 +622,13 @@ macd.stream = (fastPeriod: number, slowPeriod: number, signalPeriod: number) =>
 /**
  * Bollinger Bands
  */
-export function bbands(data: PriceInput, period: number, k: number): BBandsOutput {
-  return wasmBbands(toFloat64Array(data), period, k);
+export function bbands(
+  data: PriceInput | CandleInput,
+  period: number,
+  k: number,
+  options: SourceOptions = {}
+): BBandsOutput {
+  return wasmBbands(toPriceSeries(data, options.source), period, k);
 }
 bbands.stream = (period: number, k: number) => new BBandsStream(period, k);
 
@@ -331,13 +636,14 @@ bbands.stream = (period: number, k: number) => new BBandsStream(period, k);
  * Stochastic RSI
  */
 export function stochRsi(
-  data: PriceInput,
+  data: PriceInput | CandleInput,
   rsiPeriod: number,
   stochPeriod: number,
   kSmooth: number,
-  dPeriod: number
+  dPeriod: number,
+  options: SourceOptions = {}
 ): StochOutput {
-  return wasmStochRsi(toFloat64Array(data), rsiPeriod, stochPeriod, kSmooth, dPeriod);
+  return wasmStochRsi(toPriceSeries(data, options.source), rsiPeriod, stochPeriod, kSmooth, dPeriod);
 }
 stochRsi.stream = (
   rsiPeriod: number,
@@ -358,11 +664,12 @@ cvd.stream = () => new CvdStream();
  * Linear Regression
  */
 export function linreg(
-  data: PriceInput,
+  data: PriceInput | CandleInput,
   period: number,
-  numStdDev: number = 2
+  numStdDev: number = 2,
+  options: SourceOptions = {}
 ): LinRegOutput {
-  return wasmLinreg(toFloat64Array(data), period, numStdDev);
+  return wasmLinreg(toPriceSeries(data, options.source), period, numStdDev);
 }
 linreg.stream = (period: number) => new LinRegStream(period);
 
@@ -583,6 +890,173 @@ export function adx(
 }
 adx.stream = (period: number) => new AdxStream(period);
 
+/** Keltner Channels options. Defaults: EMA(20) middle, 2 × ATR(10) bands. */
+export interface KeltnerOptions {
+  period?: number;
+  atrPeriod?: number;
+  multiplier?: number;
+  /** Middle line average of the typical price */
+  ma?: 'ema' | 'sma';
+  /** Band width: Average True Range, or the high-low range averaged like the middle line */
+  band?: 'atr' | 'range';
+}
+
+/**
+ * Keltner Channels: a moving average of the typical price with bands at a
+ * multiple of ATR (or the average high-low range).
+ *
+ * @example
+ * ```typescript
+ * const kc = keltner(candles, { period: 20, atrPeriod: 10, multiplier: 1.5 });
+ * const bb = bbands(candles, 20, 2);
+ * const squeeze = bb.upper[i] < kc.upper[i] && bb.lower[i] > kc.lower[i];
+ * ```
+ */
+export function keltner(candles: Candle[], options: KeltnerOptions = {}): KeltnerOutput {
+  const columns = ohlcvColumns(candles);
+  try {
+    return columns.keltner(
+      options.period ?? 20,
+      options.atrPeriod ?? 10,
+      options.multiplier ?? 2,
+      options.ma,
+      options.band
+    );
+  } finally {
+    columns.free();
+  }
+}
+keltner.stream = (options: KeltnerOptions = {}) =>
+  new KeltnerStream(
+    options.period ?? 20,
+    options.atrPeriod ?? 10,
+    options.multiplier ?? 2,
+    options.ma,
+    options.band
+  );
+
+/** TTM Squeeze options. Defaults: period 20, BB 2σ, KC 1.5 × ATR. */
+export interface SqueezeOptions {
+  period?: number;
+  bbMult?: number;
+  kcMult?: number;
+}
+
+/**
+ * TTM Squeeze: squeeze-on state while Bollinger Bands sit inside Keltner
+ * Channels, the number of consecutive squeeze bars, and a momentum
+ * histogram (linear regression of close minus the Donchian/SMA midline).
+ *
+ * @example
+ * ```typescript
+ * const { squeezeOn, momentum } = squeeze(candles);
+ * const fired = squeezeOn[i - 1] === 1 && squeezeOn[i] === 0;
+ * const long = fired && momentum[i] > 0;
+ * ```
+ */
+export function squeeze(candles: Candle[], options: SqueezeOptions = {}): SqueezeOutput {
+  const columns = ohlcvColumns(candles);
+  try {
+    return columns.squeeze(options.period ?? 20, options.bbMult ?? 2, options.kcMult ?? 1.5);
+  } finally {
+    columns.free();
+  }
+}
+squeeze.stream = (options: SqueezeOptions = {}) =>
+  new SqueezeStream(options.period ?? 20, options.bbMult ?? 2, options.kcMult ?? 1.5);
+
+/**
+ * Donchian Channels: highest high and lowest low over `period` bars, with
+ * the number of bars since each extreme was set.
+ *
+ * @example
+ * ```typescript
+ * const dc = donchian(candles, 20);
+ * const breakout = candles[i].close > dc.upper[i - 1];
+ * const freshHigh = dc.barsSinceHigh[i] === 0;
+ * ```
+ */
+export function donchian(candles: Candle[], period: number = 20): DonchianOutput {
+  const columns = ohlcvColumns(candles);
+  try {
+    return columns.donchian(period);
+  } finally {
+    columns.free();
+  }
+}
+donchian.stream = (period: number = 20) => new DonchianStream(period);
+
+/** Supertrend options. Defaults: ATR period 10, multiplier 3, hl2 source, Wilder ATR. */
+export interface SuperTrendOptions {
+  atrPeriod?: number;
+  multiplier?: number;
+  /** Price the bands are centered on */
+  source?: 'hl2' | 'hlc3' | 'close';
+  /** True range smoothing */
+  atr?: 'wilder' | 'sma';
+}
+
+/**
+ * Supertrend: an ATR trailing stop that flips sides when price closes
+ * through it, with the trend direction and a flag on flip bars.
+ *
+ * @example
+ * ```typescript
+ * const st = supertrend(candles, { atrPeriod: 10, multiplier: 3 });
+ * const buy = st.flip[i] === 1 && st.direction[i] === 1;
+ * const stop = st.value[i];
+ * ```
+ */
+export function supertrend(candles: Candle[], options: SuperTrendOptions = {}): SuperTrendOutput {
+  const columns = ohlcvColumns(candles);
+  try {
+    return columns.supertrend(
+      options.atrPeriod ?? 10,
+      options.multiplier ?? 3,
+      options.source,
+      options.atr
+    );
+  } finally {
+    columns.free();
+  }
+}
+supertrend.stream = (options: SuperTrendOptions = {}) =>
+  new SuperTrendStream(
+    options.atrPeriod ?? 10,
+    options.multiplier ?? 3,
+    options.source,
+    options.atr
+  );
+
+/** Parabolic SAR options. Defaults: start 0.02, increment 0.02, maximum 0.2. */
+export interface PsarOptions {
+  start?: number;
+  increment?: number;
+  maximum?: number;
+}
+
+/**
+ * Parabolic SAR: Wilder's stop-and-reverse trailing stop, with the trend
+ * direction, acceleration factor and extreme point per bar.
+ *
+ * @example
+ * ```typescript
+ * const { sar, direction } = psar(candles);
+ * const stop = sar[i];
+ * const reversed = direction[i] !== direction[i - 1];
+ * ```
+ */
+export function psar(candles: Candle[], options: PsarOptions = {}): PsarOutput {
+  const columns = ohlcvColumns(candles);
+  try {
+    return columns.psar(options.start ?? 0.02, options.increment ?? 0.02, options.maximum ?? 0.2);
+  } finally {
+    columns.free();
+  }
+}
+psar.stream = (options: PsarOptions = {}) =>
+  new PsarStream(options.start ?? 0.02, options.increment ?? 0.02, options.maximum ?? 0.2);
+
 /**
  * Ichimoku Cloud
  * @overload ichimoku(candles, tenkan?, kijun?, senkou?) - Candle array input
@@ -725,20 +1199,117 @@ export function frvp(
 }
 frvp.stream = (numBins: number = 100) => new FrvpStream(numBins);
 
+// =============================================================================
+// Session Volume Profile
+// =============================================================================
+
+/**
+ * Session volume profile options. Profiles are split by `session`, or by
+ * calendar `timeframe` (`'1d'`, `'1w'`, `'1M'`, ...) over the trading dates of
+ * `session` (UTC days without one); the default is one profile per UTC day.
+ */
+export interface SessionVolumeProfileOptions {
+  session?: Session;
+  timeframe?: string;
+  numBins?: number;
+  valueAreaPercent?: number;
+}
+
+/** Volume profile of one session or calendar period. */
+export interface SessionProfile {
+  /** Open time of the session or period */
+  start: number;
+  /** Time of its last candle */
+  end: number;
+  candles: number;
+  poc: number;
+  vah: number;
+  val: number;
+  totalVolume: number;
+  pocVolume: number;
+  valueAreaVolume: number;
+  /** Time of the first later candle trading through the POC; undefined while the POC is naked */
+  pocRevisited?: number;
+  histogram: { prices: Float64Array; volumes: Float64Array; lows: Float64Array; highs: Float64Array };
+}
+
+/**
+ * Session volume profiles: one profile per session (the last may be
+ * incomplete) and the developing POC/VAH/VAL per candle, NaN outside sessions.
+ */
+export interface SessionVolumeProfileResult {
+  sessions: SessionProfile[];
+  poc: Float64Array;
+  vah: Float64Array;
+  val: Float64Array;
+}
+
+/** The WASM `ProfilePeriod` for `options`. */
+function profilePeriod(options: SessionVolumeProfileOptions): ProfilePeriod {
+  const { session, timeframe } = options;
+  if (session) {
+    return timeframe ? ProfilePeriod.calendarWithSession(timeframe, session) : ProfilePeriod.session(session);
+  }
+  return ProfilePeriod.calendar(timeframe ?? '1d');
+}
+
+/**
+ * Volume profile per trading session or calendar period, with developing
+ * POC/VAH/VAL lines and naked POCs (session POCs no later candle has traded
+ * through).
+ *
+ * @example
+ * ```typescript
+ * const { sessions, poc } = sessionVolumeProfile(candles, { session: Session.preset('nyse') });
+ * const naked = sessions.filter((s) => s.pocRevisited === undefined).map((s) => s.poc);
+ * ```
+ */
+export function sessionVolumeProfile(
+  candles: Candle[],
+  options: SessionVolumeProfileOptions = {}
+): SessionVolumeProfileResult {
+  const period = profilePeriod(options);
+  const columns = ohlcvColumns(candles);
+  try {
+    return columns.sessionVolumeProfile(period, options.numBins ?? 100, options.valueAreaPercent ?? 0.7);
+  } finally {
+    columns.free();
+    period.free();
+  }
+}
+sessionVolumeProfile.stream = (options: SessionVolumeProfileOptions = {}) => {
+  const period = profilePeriod(options);
+  const stream = new SessionVolumeProfileStream(period, options.numBins ?? 100, options.valueAreaPercent ?? 0.7);
+  period.free();
+  return stream;
+};
+
 // =============================================================================
 // VWAP Indicators
 // =============================================================================
 
 type VwapCandleInput = Candle[] | OHLCVInput;
 
+/** Session VWAP options. Without a `session`, VWAP resets at UTC midnight. */
+export interface SessionVwapOptions {
+  session?: Session;
+}
+
 /**
- * Session VWAP (resets daily at UTC midnight)
- * @overload sessionVwap(candles) - Candle array input
+ * Session VWAP (resets daily at UTC midnight, or at each open of `options.session`)
+ * @overload sessionVwap(candles, options?) - Candle array input
  * @overload sessionVwap(timestamps, opens, highs, lows, closes, volumes) - Positional arrays (legacy)
+ *
+ * @example
+ * ```typescript
+ * const nyse = Session.preset('nyse');
+ * nyse.addHolidays(['2024-07-04']);
+ * const vwap = sessionVwap(candles, { session: nyse });
+ * ```
  */
 export function sessionVwap(
   inputOrTimestamps: VwapCandleInput | PriceInput,
-  opens?: PriceInput,
+  opens?: PriceInput | SessionVwapOptions,
   highs?: PriceInput,
   lows?: PriceInput,
   closes?: PriceInput,
@@ -756,14 +1327,23 @@ export function sessionVwap(
       toFloat64Array(volumes!)
     );
   }
-  // New object API: sessionVwap(candles) or sessionVwap({ time, open, high, low, close, volume })
+  // New object API: sessionVwap(candles, options?) or sessionVwap({ time, open, high, low, close, volume }, options?)
   const input = inputOrTimestamps as VwapCandleInput;
+  const { session } = (opens ?? {}) as SessionVwapOptions;
+  const calculate = (
+    t: Float64Array,
+    o: Float64Array,
+    h: Float64Array,
+    l: Float64Array,
+    c: Float64Array,
+    v: Float64Array
+  ) => (session ? wasmSessionVwapWithSession(t, o, h, l, c, v, session) : wasmSessionVwap(t, o, h, l, c, v));
   if (isCandleArray(input)) {
     const { time, open, high, low, close, volume } = extractOHLCV(input);
-    return wasmSessionVwap(time, open, high, low, close, volume);
+    return calculate(time, open, high, low, close, volume);
   }
   const { time, open, high, low, close, volume } = input;
-  return wasmSessionVwap(
+  return calculate(
     toFloat64Array(time!),
     toFloat64Array(open!),
     toFloat64Array(high),
@@ -772,7 +1352,8 @@ export function sessionVwap(
     toFloat64Array(volume!)
   );
 }
-sessionVwap.stream = () => new SessionVwapStream();
+sessionVwap.stream = (session?: Session) =>
+  session ? SessionVwapStream.withSession(session) : new SessionVwapStream();
 
 /**
  * Rolling VWAP (sliding window)
@@ -973,6 +1554,525 @@ export function pivotPointsBatch(
   );
 }
 
+// =============================================================================
+// Bar Aggregation
+// =============================================================================
+
+/**
+ * A single executed trade. `side` is the aggressor side, if the feed reports
+ * it; `bid`/`ask` are the quote prevailing at execution, used by Lee-Ready
+ * classification in `tradeCvd()`.
+ */
+export interface Trade {
+  time: number;
+  price: number;
+  size: number;
+  side?: 'buy' | 'sell';
+  bid?: number;
+  ask?: number;
+}
+
+/**
+ * Aggregate trades into time bars of `intervalMs` milliseconds, aligned to UTC
+ * and shifted by `offsetMs`. The last, possibly incomplete bar is included.
+ *
+ * For live feeds use a `BarAggregator` directly: `push()` reports the bar each
+ * trade closed and the forming bar it updated.
+ *
+ * @example
+ * ```typescript
+ * const candles = aggregateTrades(trades, 60_000);
+ * const vwap = sessionVwap(candles);
+ * ```
+ */
+export function aggregateTrades(trades: Trade[], intervalMs: number, offsetMs = 0): Candle[] {
+  const aggregator = new BarAggregator(intervalMs, offsetMs);
+  const n = trades.length;
+  const times = new Float64Array(n);
+  const prices = new Float64Array(n);
+  const sizes = new Float64Array(n);
+  const sides = new Int8Array(n);
+  for (let i = 0; i < n; i++) {
+    const trade = trades[i];
+    times[i] = trade.time;
+    prices[i] = trade.price;
+    sizes[i] = trade.size;
+    sides[i] = trade.side === 'buy' ? 1 : trade.side === 'sell' ? -1 : 0;
+  }
+
+  const bars = aggregator.pushMany(times, prices, sizes, sides);
+  const last = aggregator.flush();
+  if (last) bars.push(last);
+  aggregator.free();
+
+  return bars.map((bar) => {
+    const candle = {
+      open: bar.open,
+      high: bar.high,
+      low: bar.low,
+      close: bar.close,
+      volume: bar.volume,
+      time: bar.timestamp,
+    };
+    bar.free();
+    return candle;
+  });
+}
+
+// =============================================================================
+// Trade CVD
+// =============================================================================
+
+/**
+ * How `tradeCvd()` signs trades: `'aggressor'` uses the reported side,
+ * `'tick'` the tick rule (uptick buy, downtick sell), `'leeReady'` the quote
+ * midpoint with the tick rule for trades at the midpoint or without a quote.
+ */
+export type TradeClassification = 'aggressor' | 'tick' | 'leeReady';
+
+/** Trade CVD options. Bars are shifted by `offsetMs` from UTC alignment. */
+export interface TradeCvdOptions {
+  classification?: TradeClassification;
+  offsetMs?: number;
+}
+
+/**
+ * CVD candle of one time bar: open is the previous bar's CVD, high/low/close
+ * track the running CVD within the bar. `volume` is the bar's volume, split
+ * into `buyVolume` and `sellVolume` by the classification.
+ */
+export interface CvdCandle extends Candle {
+  time: number;
+  volume: number;
+  buyVolume: number;
+  sellVolume: number;
+}
+
+/**
+ * Trades as the columns `pushMany()` takes; `quotes` holds each trade's bid
+ * and ask, interleaved, when `withQuotes` is set.
+ */
+function tradeColumns(trades: Trade[], withQuotes: boolean) {
+  const n = trades.length;
+  const times = new Float64Array(n);
+  const prices = new Float64Array(n);
+  const sizes = new Float64Array(n);
+  const sides = new Int8Array(n);
+  const quotes = withQuotes ? new Float64Array(2 * n) : undefined;
+  for (let i = 0; i < n; i++) {
+    const trade = trades[i];
+    times[i] = trade.time;
+    prices[i] = trade.price;
+    sizes[i] = trade.size;
+    sides[i] = trade.side === 'buy' ? 1 : trade.side === 'sell' ? -1 : 0;
+    if (quotes) {
+      quotes[2 * i] = trade.bid ?? NaN;
+      quotes[2 * i + 1] = trade.ask ?? NaN;
+    }
+  }
+  return { times, prices, sizes, sides, quotes };
+}
+
+/**
+ * Cumulative volume delta from trades, aggregated into time bars of
+ * `intervalMs` milliseconds with one CVD candle per bar. The last, possibly
+ * incomplete bar is included.
+ *
+ * For live feeds use a `TradeCvdStream` directly.
+ *
+ * @example
+ * ```typescript
+ * const cvdCandles = tradeCvd(trades, 60_000, { classification: 'tick' });
+ * ```
+ */
+export function tradeCvd(trades: Trade[], intervalMs: number, options: TradeCvdOptions = {}): CvdCandle[] {
+  const classification = options.classification ?? 'aggressor';
+  const stream = new TradeCvdStream(intervalMs, classification, options.offsetMs ?? 0);
+  const { times, prices, sizes, sides, quotes } = tradeColumns(trades, classification === 'leeReady');
+
+  const bars = stream.pushMany(times, prices, sizes, sides, quotes);
+  const last = stream.flush();
+  if (last) bars.push(last);
+  stream.free();
+
+  return bars.map((deltaBar) => {
+    const bar = deltaBar.bar;
+    const candle: CvdCandle = { ...deltaBar.cvd, buyVolume: bar.buyVolume, sellVolume: bar.sellVolume };
+    bar.free();
+    deltaBar.free();
+    return candle;
+  });
+}
+
+// =============================================================================
+// Footprint
+// =============================================================================
+
+/**
+ * Footprint options: trade classification and bar offset as for `tradeCvd()`,
+ * plus the diagonal imbalance `imbalanceRatio` (default 3) and the minimum
+ * number of consecutive imbalanced levels `stackLevels` (default 3) that
+ * make a stacked imbalance.
+ */
+export interface FootprintOptions extends TradeCvdOptions {
+  imbalanceRatio?: number;
+  stackLevels?: number;
+}
+
+/** Consecutive levels imbalanced to the same side, from `low` to `high`. */
+export interface StackedImbalance {
+  side: 'buy' | 'sell';
+  low: number;
+  high: number;
+  levels: number;
+}
+
+/**
+ * Footprint of one time bar. `levels` holds one
+ * `[price, buyVolume, sellVolume, volume]` quadruple per tick from the bar's
+ * low to its high, flattened; `imbalances` has one entry per level: 1 for a
+ * buy imbalance, 2 for a sell imbalance, 3 for both.
+ */
+export interface FootprintCandle extends CvdCandle {
+  delta: number;
+  poc: number;
+  tickSize: number;
+  levels: Float64Array;
+  imbalances: Uint8Array;
+  stackedImbalances: StackedImbalance[];
+}
+
+/**
+ * Volume at price per bar from trades: buy and sell volume at every
+ * `tickSize` level of time bars of `intervalMs` milliseconds, with the bar's
+ * delta, POC and diagonal imbalances. The last, possibly incomplete bar is
+ * included.
+ *
+ * For live feeds use a `FootprintStream` directly: `push()` reports the level
+ * each trade updated.
+ *
+ * @example
+ * ```typescript
+ * const bars = footprint(trades, 60_000, 0.25, { imbalanceRatio: 4 });
+ * const stacked = bars.filter((bar) => bar.stackedImbalances.length > 0);
+ * ```
+ */
+export function footprint(
+  trades: Trade[],
+  intervalMs: number,
+  tickSize: number,
+  options: FootprintOptions = {}
+): FootprintCandle[] {
+  const classification = options.classification ?? 'aggressor';
+  const ratio = options.imbalanceRatio ?? 3;
+  const stream = new FootprintStream(intervalMs, tickSize, classification, options.offsetMs ?? 0);
+  const { times, prices, sizes, sides, quotes } = tradeColumns(trades, classification === 'leeReady');
+
+  const bars = stream.pushMany(times, prices, sizes, sides, quotes);
+  const last = stream.flush();
+  if (last) bars.push(last);
+  stream.free();
+
+  return bars.map((footprintBar) => {
+    const bar = footprintBar.bar;
+    const stacks = footprintBar.stackedImbalances(ratio, options.stackLevels ?? 3);
+    const stackedImbalances: StackedImbalance[] = [];
+    for (let i = 0; i < stacks.length; i += 4) {
+      stackedImbalances.push({
+        side: stacks[i] > 0 ? 'buy' : 'sell',
+        low: stacks[i + 1],
+        high: stacks[i + 2],
+        levels: stacks[i + 3],
+      });
+    }
+    const candle: FootprintCandle = {
+      time: bar.timestamp,
+      open: bar.open,
+      high: bar.high,
+      low: bar.low,
+      close: bar.close,
+      volume: bar.volume,
+      buyVolume: bar.buyVolume,
+      sellVolume: bar.sellVolume,
+      delta: footprintBar.delta,
+      poc: footprintBar.poc ?? NaN,
+      tickSize: footprintBar.tickSize,
+      levels: footprintBar.levels,
+      imbalances: footprintBar.imbalances(ratio),
+      stackedImbalances,
+    };
+    bar.free();
+    footprintBar.free();
+    return candle;
+  });
+}
+
+// =============================================================================
+// Resampling
+// =============================================================================
+
+/** Candles from the `{ time, open, high, low, close, volume }` arrays WASM returns. */
+function columnsToCandles(columns: {
+  time: Float64Array;
+  open: Float64Array;
+  high: Float64Array;
+  low: Float64Array;
+  close: Float64Array;
+  volume: Float64Array;
+}): Candle[] {
+  return Array.from(columns.time, (time, i) => ({
+    open: columns.open[i],
+    high: columns.high[i],
+    low: columns.low[i],
+    close: columns.close[i],
+    volume: columns.volume[i],
+    time,
+  }));
+}
+
+/**
+ * Resampling options. Bars group the trading dates of `session` (UTC days by
+ * default): intraday bars start at each session open, and candles outside its
+ * sessions are dropped.
+ */
+export interface ResampleOptions {
+  session?: Session;
+}
+
+/**
+ * Resample candles to a higher timeframe: `'15m'`, `'4h'`, `'1d'`, `'1w'`
+ * (Monday open) or `'1M'` (calendar month). Candles need `time`. The last,
+ * possibly incomplete bar is included.
+ *
+ * For live feeds use a `Resampler` directly: `push()` reports the bar each
+ * candle closed and the forming bar it updated.
+ */
+export function resample(
+  candles: Candle[],
+  timeframe: string,
+  options: ResampleOptions = {}
+): Candle[] {
+  const resampler = options.session
+    ? Resampler.withSession(timeframe, options.session)
+    : new Resampler(timeframe);
+  const { time, open, high, low, close, volume } = extractOHLCV(candles);
+  const closed = resampler.pushMany(time, open, high, low, close, volume);
+  const last = resampler.flush();
+  resampler.free();
+
+  const bars = columnsToCandles(closed);
+  if (last) bars.push(last);
+  return bars;
+}
+
+/**
+ * Run an indicator on a higher timeframe and map its values back onto the
+ * input candles. Each candle gets the value of the last higher-timeframe bar
+ * that closed before it, so there is no lookahead.
+ *
+ * @example
+ * ```typescript
+ * // 1h RSI on 1m candles
+ * const hourlyRsi = mtf(candles, '1h', (bars) => rsi(bars, 14));
+ * ```
+ */
+export function mtf(
+  candles: Candle[],
+  timeframe: string,
+  indicator: (bars: Candle[]) => Float64Array | number[],
+  options: ResampleOptions = {}
+): Float64Array {
+  const values = indicator(resample(candles, timeframe, options));
+  const { time } = extractOHLCV(candles);
+  return options.session
+    ? wasmAlignTimeframeWithSession(time, toFloat64Array(values), timeframe, options.session)
+    : wasmAlignTimeframe(time, toFloat64Array(values), timeframe);
+}
+
+// =============================================================================
+// Alternative Bar Types
+// =============================================================================
+
+/**
+ * Heikin-Ashi candles, one per input candle.
+ */
+export function heikinAshi(candles: Candle[]): Candle[] {
+  const { time, open, high, low, close, volume } = extractOHLCV(candles);
+  return columnsToCandles(wasmHeikinAshi(time, open, high, low, close, volume));
+}
+heikinAshi.stream = () => new HeikinAshiStream();
+
+/**
+ * Renko bricks from candle closes: `boxSize` price units per brick, or
+ * `{ atr: period }` to size bricks by the ATR. Only completed bricks are
+ * returned; a reversal needs two boxes.
+ */
+export function renko(candles: Candle[], boxSize: number | { atr: number }): Candle[] {
+  const builder = typeof boxSize === 'number' ? new RenkoBars(boxSize) : RenkoBars.atr(boxSize.atr);
+  const { time, open, high, low, close, volume } = extractOHLCV(candles);
+  const bricks = columnsToCandles(builder.pushMany(time, open, high, low, close, volume));
+  builder.free();
+  return bricks;
+}
+
+type TradeBarBuilder = RangeBars | VolumeBars | TickBars;
+
+/** Completed bars of a trade bar builder over `trades`. */
+function buildTradeBars(builder: TradeBarBuilder, trades: Trade[]): Candle[] {
+  const n = trades.length;
+  const times = new Float64Array(n);
+  const prices = new Float64Array(n);
+  const sizes = new Float64Array(n);
+  for (let i = 0; i < n; i++) {
+    times[i] = trades[i].time;
+    prices[i] = trades[i].price;
+    sizes[i] = trades[i].size;
+  }
+  const bars = columnsToCandles(builder.pushMany(times, prices, sizes));
+  builder.free();
+  return bars;
+}
+
+/** Bars spanning exactly `range` price units. Only completed bars are returned. */
+export function rangeBars(trades: Trade[], range: number): Candle[] {
+  return buildTradeBars(new RangeBars(range), trades);
+}
+
+/** Bars of at least `volume` traded units. Only completed bars are returned. */
+export function volumeBars(trades: Trade[], volume: number): Candle[] {
+  return buildTradeBars(new VolumeBars(volume), trades);
+}
+
+/** Bars of `ticks` trades. Only completed bars are returned. */
+export function tickBars(trades: Trade[], ticks: number): Candle[] {
+  return buildTradeBars(new TickBars(ticks), trades);
+}
+
+// =============================================================================
+// Columnar Input
+// =============================================================================
+
+/**
+ * Packs candles into one interleaved buffer, six values per candle:
+ * `[time, open, high, low, close, volume, time, ...]`.
+ */
+export function toInterleaved(candles: Candle[]): Float64Array {
+  const buffer = new Float64Array(candles.length * 6);
+  candles.forEach((c, i) => {
+    buffer.set([c.time ?? 0, c.open, c.high, c.low, c.close, c.volume ?? 0], i * 6);
+  });
+  return buffer;
+}
+
+/**
+ * Loads candles, or an interleaved buffer from `toInterleaved()`, into WASM
+ * once so several OHLCV indicators can run over the same data.
+ *
+ * @example
+ * ```typescript
+ * const columns = ohlcvColumns(candles);
+ * const atr = columns.atr(14);
+ * const vwap = columns.rollingVwap(20);
+ * columns.free();
+ * ```
+ */
+export function ohlcvColumns(data: Candle[] | Float64Array): OhlcvColumns {
+  return new OhlcvColumns(data instanceof Float64Array ? data : toInterleaved(data));
+}
+
+// =============================================================================
+// Data Validation
+// =============================================================================
+
+/** A problem found at one candle by `validateCandles()`. */
+export interface CandleAnomaly {
+  /** Index of the candle in the input */
+  index: number;
+  time: number;
+  kind:
+    | 'highBelowLow'
+    | 'openOutOfRange'
+    | 'closeOutOfRange'
+    | 'negativeVolume'
+    | 'duplicateTimestamp'
+    | 'outOfOrder'
+    | 'gap';
+  /** Number of missing candles before this one (`gap` only, 0 otherwise) */
+  missing: number;
+  message: string;
+}
+
+/** Validation options. Gaps are only detected with an `interval` (ms). */
+export interface ValidationOptions {
+  interval?: number;
+}
+
+/**
+ * Reports impossible candles (high below low, open/close outside the range,
+ * negative volume) and broken sequences (duplicate, out-of-order or missing
+ * timestamps), in candle order. An empty array means the data is clean.
+ */
+export function validateCandles(candles: Candle[], options: ValidationOptions = {}): CandleAnomaly[] {
+  const { time, open, high, low, close, volume } = extractOHLCV(candles);
+  return wasmValidateCandles(time, open, high, low, close, volume, options.interval);
+}
+
+/**
+ * Repairs candles: sorts and deduplicates by time (the last candle for a
+ * timestamp wins), clamps open/close into the low-high range, zeroes
+ * negative volume and, with an `interval`, fills gaps with flat candles at
+ * the previous close.
+ */
+export function repairCandles(candles: Candle[], options: ValidationOptions = {}): Candle[] {
+  const { time, open, high, low, close, volume } = extractOHLCV(candles);
+  return columnsToCandles(wasmRepairCandles(time, open, high, low, close, volume, options.interval));
+}
+
+// =============================================================================
+// Corporate Actions
+// =============================================================================
+
+/**
+ * A stock split (`ratio` new shares per old share, 2 for 2-for-1) or cash
+ * dividend. `exDate` is the time (ms) of the first candle in the new basis.
+ */
+export type CorporateAction =
+  | { type: 'split'; exDate: number; ratio: number }
+  | { type: 'dividend'; exDate: number; amount: number };
+
+/** Adjustment options. `'priceOnly'` (default) ignores dividends. */
+export interface AdjustOptions {
+  mode?: 'priceOnly' | 'totalReturn';
+}
+
+/**
+ * Back-adjusts candles (in ascending time order) for splits and dividends so
+ * indicators see no jump at ex-dates. Candles before each ex-date have prices
+ * divided by the split ratio and volume multiplied by it; in `'totalReturn'`
+ * mode prices are also scaled by `1 - amount / previous close` for each
+ * dividend. Candles from the last ex-date on are unchanged.
+ */
+export function adjustCandles(
+  candles: Candle[],
+  actions: CorporateAction[],
+  options: AdjustOptions = {}
+): Candle[] {
+  const adjuster = new Adjuster(options.mode === 'totalReturn');
+  try {
+    for (const action of actions) {
+      if (action.type === 'split') {
+        adjuster.split(action.exDate, action.ratio);
+      } else {
+        adjuster.dividend(action.exDate, action.amount);
+      }
+    }
+    const { time, open, high, low, close, volume } = extractOHLCV(candles);
+    return columnsToCandles(adjuster.adjust(time, open, high, low, close, volume));
+  } finally {
+    adjuster.free();
+  }
+}
+
 // =============================================================================
 // Multi-Indicator Analysis Helper
 // =============================================================================
@@ -13,7 +13,8 @@ echo "🦀 Building WASM..."
 wasm-pack build crates/ta-core \
     --target nodejs \
     --out-dir ../../pkg \
-    --out-name ta_core
+    --out-name ta_core \
+    -- --features serde
 
 echo "📦 Building TypeScript..."
 npx --no-install tsc
@@ -43,6 +43,37 @@ import {
   IchimokuStream,
   AdxStream,
   LinRegStream,
+  PipeStream,
+  BarAggregator,
+  aggregateTrades,
+  tradeCvd,
+  footprint,
+  sessionVolumeProfile,
+  keltner,
+  squeeze,
+  donchian,
+  supertrend,
+  psar,
+  Resampler,
+  Session,
+  resample,
+  mtf,
+  heikinAshi,
+  renko,
+  rangeBars,
+  volumeBars,
+  tickBars,
+  OhlcvColumns,
+  ohlcvColumns,
+  toInterleaved,
+  validateCandles,
+  repairCandles,
+  adjustCandles,
+  sourcePrice,
+  TaError,
+  LengthMismatchError,
+  OutOfRangeError,
+  InvalidParameterError,
 } from "../dist/index.js";
 import {
   sma as ftiSma,
@@ -150,6 +181,23 @@ describe("ta-tools Integration Tests", () => {
         }
       }
     });
+
+    it("should amend the forming bar with update()", () => {
+      const period = 5;
+      const live = new SmaStream(period);
+      const closed = new SmaStream(period);
+
+      live.init(new Float64Array(SAMPLE_PRICES.slice(0, 10)));
+      closed.init(new Float64Array(SAMPLE_PRICES.slice(0, 10)));
+
+      // Bar opens, ticks a few times, then closes at SAMPLE_PRICES[10]
+      live.next(SAMPLE_PRICES[10] + 1);
+      live.update(SAMPLE_PRICES[10] - 2);
+      const amended = live.update(SAMPLE_PRICES[10]);
+
+      assertClose(amended, closed.next(SAMPLE_PRICES[10]), 1e-10);
+      assertClose(live.next(SAMPLE_PRICES[11]), closed.next(SAMPLE_PRICES[11]), 1e-10);
+    });
   });
 
   describe("EMA", () => {
@@ -292,6 +340,664 @@ describe("ta-tools Integration Tests", () => {
     });
   });
 
+  describe("Price source", () => {
+    const candles = SAMPLE_PRICES.map((close, i) => ({
+      open: close - 0.3 + (i % 3) * 0.2,
+      high: close + 0.6,
+      low: close - 0.7,
+      close,
+      volume: 1000,
+    }));
+
+    it("should default to close for Candle[] input", () => {
+      const fromCandles = rsi(candles, 5);
+      const fromPrices = rsi(SAMPLE_PRICES, 5);
+      for (let i = 0; i < fromPrices.length; i++) {
+        assertClose(fromCandles[i], fromPrices[i], 1e-10);
+      }
+    });
+
+    it("should read the selected source", () => {
+      const hlc3 = candles.map((c) => (c.high + c.low + c.close) / 3);
+      const expected = rsi(hlc3, 5);
+      const result = rsi(candles, 5, { source: "hlc3" });
+      for (let i = 0; i < expected.length; i++) {
+        assertClose(result[i], expected[i], 1e-10);
+      }
+
+      const opens = candles.map((c) => c.open);
+      const bands = bbands(candles, 5, 2, { source: "open" });
+      const expectedBands = bbands(opens, 5, 2);
+      assertClose(bands.middle[10], expectedBands.middle[10], 1e-10);
+    });
+
+    it("should support custom weights", () => {
+      const weighted = sma(candles, 3, { source: { high: 1, low: 1, close: 2 } });
+      const hlcc4 = sma(candles, 3, { source: "hlcc4" });
+      for (let i = 0; i < hlcc4.length; i++) {
+        assertClose(weighted[i], hlcc4[i], 1e-10);
+      }
+      expect(() => sourcePrice(candles[0], {})).toThrow();
+    });
+  });
+
+  describe("Warmup metadata", () => {
+    it("lookback() should match the NaN prefix of batch output", () => {
+      const data = new Float64Array(SAMPLE_PRICES);
+      const cases: Array<[{ lookback(): number; warmupLen(): number }, Float64Array]> = [
+        [sma.stream(5), sma(data, 5)],
+        [rsi.stream(5), rsi(data, 5)],
+        [hma.stream(9), hma(data, 9)],
+        [macd.stream(5, 8, 3), macd(data, 5, 8, 3).signal],
+      ];
+      for (const [stream, values] of cases) {
+        const lookback = stream.lookback();
+        expect(stream.warmupLen()).toBe(lookback + 1);
+        expect(values.findIndex((v) => !Number.isNaN(v))).toBe(lookback);
+      }
+    });
+
+    it("should add up along a chain", () => {
+      const chain = rsi.stream(5).pipe(sma.stream(3));
+      expect(chain.lookback()).toBe(7);
+    });
+  });
+
+  describe("Peek", () => {
+    it("peek() should preview next() without changing state", () => {
+      const stream = rsi.stream(5);
+      stream.init(new Float64Array(SAMPLE_PRICES));
+
+      const previewed = stream.peek(SAMPLE_PRICES[0]);
+      expect(stream.peek(SAMPLE_PRICES[0])).toBe(previewed);
+      expect(stream.next(SAMPLE_PRICES[0])).toBeCloseTo(previewed, 10);
+    });
+
+    it("peekMany() should match peek() for each candidate", () => {
+      const stream = macd.stream(5, 8, 3);
+      stream.init(new Float64Array(SAMPLE_PRICES));
+
+      const ladder = new Float64Array([95, 100, 105, 110]);
+      const outputs = stream.peekMany(ladder);
+      expect(outputs).toHaveLength(ladder.length);
+      ladder.forEach((price, i) => {
+        expect(outputs[i].histogram).toBeCloseTo(stream.peek(price)!.histogram, 10);
+      });
+
+      const chain = rsi.stream(5).pipe(sma.stream(3));
+      chain.init(new Float64Array(SAMPLE_PRICES));
+      const values = chain.peekMany(ladder);
+      expect(values[3]).toBeGreaterThan(values[0]);
+    });
+  });
+
+  describe("Bar aggregation", () => {
+    const trades = Array.from({ length: 120 }, (_, i) => ({
+      time: 1_700_000_000_000 + i * 7_000,
+      price: 100 + Math.sin(i * 0.7) * 3,
+      size: 1 + (i % 4),
+      side: (i % 3 === 0 ? "sell" : "buy") as "buy" | "sell",
+    }));
+
+    it("aggregateTrades() should build UTC-aligned candles", () => {
+      const candles = aggregateTrades(trades, 60_000);
+      expect(candles.every((c) => c.time! % 60_000 === 0)).toBe(true);
+      const volume = candles.reduce((sum, c) => sum + c.volume!, 0);
+      expect(volume).toBeCloseTo(trades.reduce((sum, t) => sum + t.size, 0), 8);
+    });
+
+    it("push() should report closed and forming bars", () => {
+      const aggregator = new BarAggregator(60_000);
+      const closed = [];
+      for (const t of trades) {
+        const update = aggregator.push(t.time, t.price, t.size, t.side === "buy" ? 1 : -1)!;
+        if (update.closed) closed.push(update.closed);
+        expect(update.current.close).toBe(t.price);
+      }
+      const batch = aggregateTrades(trades, 60_000);
+      expect(closed).toHaveLength(batch.length - 1);
+      expect(closed[0].high).toBeCloseTo(batch[0].high, 10);
+      expect(closed[0].buyVolume + closed[0].sellVolume).toBeCloseTo(closed[0].volume, 10);
+      expect(aggregator.advance(aggregator.current!.timestamp + 60_000)?.close).toBe(
+        trades[trades.length - 1].price
+      );
+    });
+  });
+
+  describe("Resampling", () => {
+    const candles = Array.from({ length: 300 }, (_, i) => {
+      const close = 100 + Math.sin(i * 0.05) * 4;
+      return {
+        time: 1_700_006_400_000 + i * 60_000,
+        open: close - 0.2,
+        high: close + 0.5,
+        low: close - 0.6,
+        close,
+        volume: 10,
+      };
+    });
+
+    it("resample() should build hour-aligned candles", () => {
+      const hourly = resample(candles, "1h");
+      expect(hourly).toHaveLength(5);
+      expect(hourly.every((c) => c.time! % 3_600_000 === 0)).toBe(true);
+      expect(hourly[0].open).toBe(candles[0].open);
+      expect(hourly[0].close).toBe(candles[59].close);
+      expect(hourly[0].volume).toBeCloseTo(600, 10);
+    });
+
+    it("mtf() should only expose closed higher-timeframe bars", () => {
+      const hourly = resample(candles, "1h");
+      const expected = rsi(hourly, 2);
+      const mapped = mtf(candles, "1h", (bars) => rsi(bars, 2));
+
+      expect(mapped.length).toBe(candles.length);
+      for (let i = 0; i < candles.length; i++) {
+        const hour = Math.floor(i / 60) - 1;
+        const value = hour < 0 ? NaN : expected[hour];
+        if (Number.isNaN(value)) {
+          expect(Number.isNaN(mapped[i])).toBe(true);
+        } else {
+          assertClose(mapped[i], value, 1e-10);
+        }
+      }
+    });
+
+    it("Resampler should drive a stream on the forming bar", () => {
+      const resampler = new Resampler("1h");
+      const stream = rsi.stream(2);
+      let last: number | undefined;
+      for (const c of candles) {
+        const update = resampler.push(c.time, c.open, c.high, c.low, c.close, c.volume)!;
+        last = update.opened ? stream.next(update.current.close) : stream.update(update.current.close);
+      }
+      const expected = rsi(resample(candles, "1h"), 2);
+      assertClose(last!, expected[expected.length - 1], 1e-10);
+    });
+  });
+
+  describe("Alternative bar types", () => {
+    const candles = SAMPLE_PRICES.map((close, i) => ({
+      time: i * 60_000,
+      open: i === 0 ? close : SAMPLE_PRICES[i - 1],
+      high: close + 0.5,
+      low: close - 0.5,
+      close,
+      volume: 10,
+    }));
+    const trades = Array.from({ length: 200 }, (_, i) => ({
+      time: i * 1_000,
+      price: 100 + Math.sin(i * 0.2) * 3,
+      size: 1 + (i % 3),
+    }));
+
+    it("heikinAshi() should match its stream", () => {
+      const batch = heikinAshi(candles);
+      const stream = heikinAshi.stream();
+      expect(batch).toHaveLength(candles.length);
+      for (let i = 0; i < candles.length; i++) {
+        const c = candles[i];
+        const ha = stream.next(c.time, c.open, c.high, c.low, c.close, c.volume);
+        assertClose(ha.close, (c.open + c.high + c.low + c.close) / 4, 1e-10);
+        assertClose(ha.open, batch[i].open, 1e-10);
+      }
+    });
+
+    it("renko() should build whole bricks", () => {
+      const bricks = renko(candles, 0.5);
+      expect(bricks.length).toBeGreaterThan(0);
+      for (const b of bricks) {
+        assertClose(Math.abs(b.close - b.open), 0.5, 1e-9);
+      }
+      expect(() => renko(candles, 0)).toThrow();
+      expect(renko(candles, { atr: 5 }).length).toBeGreaterThan(0);
+    });
+
+    it("trade bars should respect their thresholds", () => {
+      for (const bar of rangeBars(trades, 1)) {
+        assertClose(bar.high - bar.low, 1, 1e-9);
+      }
+      expect(volumeBars(trades, 20).every((b) => b.volume! >= 20)).toBe(true);
+      const ticks = tickBars(trades, 10);
+      expect(ticks).toHaveLength(20);
+      expect(ticks[1].time).toBe(10_000);
+    });
+  });
+
+  describe("Session calendars", () => {
+    // 30-minute candles over Friday 2024-03-08 (EST) and Monday 2024-03-11 (EDT)
+    const candles = [Date.UTC(2024, 2, 8), Date.UTC(2024, 2, 11)].flatMap((day) =>
+      Array.from({ length: 48 }, (_, i) => ({
+        time: day + i * 1_800_000,
+        open: 100 + i,
+        high: 100 + i,
+        low: 100 + i,
+        close: 100 + i,
+        volume: 1,
+      }))
+    );
+
+    it("sessionVwap() should reset at the New York open across DST", () => {
+      const nyse = Session.preset("nyse");
+      const vwap = sessionVwap(candles, { session: nyse });
+
+      expect(Number.isNaN(vwap[28])).toBe(true);
+      assertClose(vwap[29], 129, 1e-10);
+      expect(Number.isNaN(vwap[42])).toBe(true);
+      assertClose(vwap[48 + 27], 127, 1e-10);
+
+      const stream = sessionVwap.stream(nyse);
+      const streamed = candles.map((c) => stream.next(c.time, c.open, c.high, c.low, c.close, c.volume));
+      assertClose(streamed[48 + 30]!, vwap[48 + 30], 1e-10);
+    });
+
+    it("Session should honour holidays and custom hours", () => {
+      const session = new Session("Europe/London", "08:00", "16:30");
+      expect(session.isOpen(Date.UTC(2024, 0, 3, 8, 0))).toBe(true);
+      expect(session.isOpen(Date.UTC(2024, 6, 3, 7, 30))).toBe(true);
+      session.addHolidays(["2024-07-03"]);
+      expect(session.isOpen(Date.UTC(2024, 6, 3, 7, 30))).toBe(false);
+      expect(() => new Session("Mars/Olympus", "08:00", "16:30")).toThrow();
+    });
+  });
+
+  describe("Columnar input (OhlcvColumns)", () => {
+    const candles = Array.from({ length: 120 }, (_, i) => {
+      const close = 100 + Math.sin(i * 0.2) * 3;
+      return {
+        time: 1_700_000_000_000 + i * 60_000,
+        open: close - 0.3,
+        high: close + 0.8,
+        low: close - 0.9,
+        close,
+        volume: 100 + i,
+      };
+    });
+
+    it("should match the per-array functions", () => {
+      const columns = ohlcvColumns(toInterleaved(candles));
+      expect(columns.length).toBe(candles.length);
+
+      const expectedAtr = atr(candles, 14);
+      const actualAtr = columns.atr(14);
+      const expectedVwap = rollingVwap(candles, 20);
+      const actualVwap = columns.rollingVwap(20);
+      for (let i = 0; i < candles.length; i++) {
+        assertClose(actualAtr[i], expectedAtr[i]);
+        assertClose(actualVwap[i], expectedVwap[i]);
+      }
+      columns.free();
+    });
+
+    it("should reject a buffer that is not a whole number of rows", () => {
+      expect(() => new OhlcvColumns(new Float64Array(7))).toThrow();
+    });
+  });
+
+  describe("Data validation", () => {
+    const candles = [
+      { time: 0, open: 10, high: 11, low: 9, close: 10.5, volume: 100 },
+      { time: 60_000, open: 10.5, high: 9, low: 11, close: 10, volume: 100 },
+      { time: 60_000, open: 10, high: 11, low: 9, close: 12, volume: -1 },
+      { time: 240_000, open: 10, high: 11, low: 9, close: 10, volume: 100 },
+    ];
+
+    it("validateCandles() should report anomalies per candle", () => {
+      const anomalies = validateCandles(candles, { interval: 60_000 });
+      expect(anomalies.map((a) => [a.index, a.kind])).toEqual([
+        [1, "highBelowLow"],
+        [2, "closeOutOfRange"],
+        [2, "negativeVolume"],
+        [2, "duplicateTimestamp"],
+        [3, "gap"],
+      ]);
+      expect(anomalies[4].missing).toBe(2);
+    });
+
+    it("repairCandles() should return clean candles", () => {
+      const repaired = repairCandles(candles, { interval: 60_000 });
+      expect(repaired.map((c) => c.time)).toEqual([0, 60_000, 120_000, 180_000, 240_000]);
+      expect(repaired[1].close).toBe(11);
+      expect(repaired[1].volume).toBe(0);
+      expect(validateCandles(repaired, { interval: 60_000 })).toEqual([]);
+    });
+  });
+
+  describe("Trade CVD", () => {
+    const trades = [
+      { time: 1_000, price: 10.0, size: 2, side: "buy" as const, bid: 9.9, ask: 10.0 },
+      { time: 2_000, price: 10.1, size: 5, side: "sell" as const, bid: 10.1, ask: 10.2 },
+      { time: 3_000, price: 10.3, size: 1, side: "buy" as const, bid: 10.2, ask: 10.3 },
+      { time: 61_000, price: 10.2, size: 4, bid: 10.2, ask: 10.3 },
+    ];
+
+    it("tradeCvd() should build CVD candles from reported sides", () => {
+      const candles = tradeCvd(trades, 60_000);
+      expect(candles.length).toBe(2);
+      expect(candles[0]).toEqual({
+        time: 0,
+        open: 0,
+        high: 2,
+        low: -3,
+        close: -2,
+        volume: 8,
+        buyVolume: 3,
+        sellVolume: 5,
+      });
+      // The unsigned trade adds volume but no delta
+      expect([candles[1].open, candles[1].close, candles[1].volume]).toEqual([-2, -2, 4]);
+    });
+
+    it("tradeCvd() should classify with the tick rule and Lee-Ready", () => {
+      const tick = tradeCvd(trades, 60_000, { classification: "tick" });
+      expect(tick.map((c) => c.close)).toEqual([6, 2]);
+
+      const leeReady = tradeCvd(trades, 60_000, { classification: "leeReady" });
+      expect(leeReady.map((c) => c.close)).toEqual([-2, -6]);
+    });
+  });
+
+  describe("Footprint", () => {
+    const trades = [
+      { time: 1_000, price: 100.0, size: 4, side: "buy" as const },
+      { time: 2_000, price: 100.25, size: 1, side: "sell" as const },
+      { time: 3_000, price: 100.5, size: 6, side: "buy" as const },
+      { time: 61_000, price: 100.25, size: 2, side: "sell" as const },
+    ];
+
+    it("footprint() should report volume at every tick of each bar", () => {
+      const bars = footprint(trades, 60_000, 0.25);
+      expect(bars.length).toBe(2);
+      expect(Array.from(bars[0].levels)).toEqual([
+        100, 4, 0, 4,
+        100.25, 0, 1, 1,
+        100.5, 6, 0, 6,
+      ]);
+      expect([bars[0].delta, bars[0].poc, bars[0].volume]).toEqual([9, 100.5, 11]);
+      expect(Array.from(bars[1].levels)).toEqual([100.25, 0, 2, 2]);
+    });
+
+    it("footprint() should flag diagonal and stacked imbalances", () => {
+      const [bar] = footprint(trades, 60_000, 0.25, { stackLevels: 1 });
+      // Buying at 100.00 and 100.50 is at least 3x the selling a tick below
+      expect(Array.from(bar.imbalances)).toEqual([1, 0, 1]);
+      expect(bar.stackedImbalances.map((s) => [s.side, s.low, s.levels])).toEqual([
+        ["buy", 100, 1],
+        ["buy", 100.5, 1],
+      ]);
+      expect(footprint(trades, 60_000, 0.25)[0].stackedImbalances).toEqual([]);
+    });
+  });
+
+  describe("Session volume profile", () => {
+    const DAY = 86_400_000;
+    const candles = [
+      { time: 0, open: 99, high: 101, low: 99, close: 100, volume: 1000 },
+      { time: 3_600_000, open: 100, high: 102, low: 100, close: 101, volume: 500 },
+      { time: DAY, open: 104, high: 107, low: 104, close: 106, volume: 800 },
+      { time: 2 * DAY, open: 106, high: 106, low: 103, close: 104, volume: 300 },
+    ];
+
+    it("sessionVolumeProfile() should build one profile per UTC day", () => {
+      const result = sessionVolumeProfile(candles, { numBins: 20 });
+      expect(result.sessions.map((s) => s.start)).toEqual([0, DAY, 2 * DAY]);
+      expect(result.poc.length).toBe(4);
+
+      // Each session matches a fixed range profile over its candles
+      const first = frvp(candles.slice(0, 2), 20);
+      assertClose(result.sessions[0].poc, first.poc);
+      assertClose(result.poc[1], first.poc);
+
+      // Day 1's POC is never revisited; day 2's is on day 3
+      expect(result.sessions[0].pocRevisited).toBeUndefined();
+      expect(result.sessions[1].pocRevisited).toBe(2 * DAY);
+    });
+
+    it("sessionVolumeProfile() should split by calendar period", () => {
+      const weekly = sessionVolumeProfile(candles, { timeframe: "1w", numBins: 20 });
+      expect(weekly.sessions.length).toBe(1);
+      expect(weekly.sessions[0].candles).toBe(4);
+    });
+  });
+
+  describe("Keltner Channels", () => {
+    const candles = Array.from({ length: 40 }, (_, i) => {
+      const close = 100 + Math.sin(i * 0.7) * 5 + i * 0.2;
+      return { time: i * 60_000, open: close, high: close + 1.5, low: close - 1, close, volume: 100 };
+    });
+
+    it("keltner() should put bands at multiplier × ATR around the EMA", () => {
+      const kc = keltner(candles, { period: 20, atrPeriod: 10, multiplier: 2 });
+      const atrValues = atr(candles, 10);
+      expect(kc.middle.length).toBe(40);
+      expect(Number.isNaN(kc.middle[18])).toBe(true);
+      for (let i = 19; i < 40; i++) {
+        assertClose(kc.upper[i] - kc.middle[i], 2 * atrValues[i]);
+        assertClose(kc.width[i], (kc.upper[i] - kc.lower[i]) / kc.middle[i]);
+      }
+    });
+
+    it("keltner.stream() should match batch", () => {
+      const kc = keltner(candles, { ma: "sma", band: "range" });
+      const stream = keltner.stream({ ma: "sma", band: "range" });
+      const last = candles.map((c) => stream.next(c.high, c.low, c.close)).pop();
+      assertClose(last!.middle, kc.middle[39]);
+      assertClose(last!.lower, kc.lower[39]);
+    });
+  });
+
+  describe("TTM Squeeze", () => {
+    // Quiet range for 40 bars, then a steep breakout
+    const candles = Array.from({ length: 60 }, (_, i) => {
+      const close = i < 40 ? 100 + Math.sin(i * 0.9) * 0.3 : 100 + (i - 39) * 2;
+      return { time: i * 60_000, open: close, high: close + 1, low: close - 1, close, volume: 100 };
+    });
+
+    it("squeeze() should count squeeze bars and release on the breakout", () => {
+      const result = squeeze(candles);
+      expect(result.squeezeOn[18]).toBe(0);
+      expect(result.squeezeOn[19]).toBe(1);
+      expect(result.squeezeBars[39]).toBe(21);
+
+      const released = result.squeezeOn.findIndex((on, i) => i > 39 && on === 0);
+      expect(released).toBeGreaterThan(39);
+      expect(result.squeezeBars[released]).toBe(0);
+      expect(result.momentum[released]).toBeGreaterThan(0);
+    });
+
+    it("squeeze.stream() should match batch", () => {
+      const result = squeeze(candles, { period: 10 });
+      const stream = squeeze.stream({ period: 10 });
+      const last = candles.map((c) => stream.next(c.high, c.low, c.close)).pop()!;
+      expect(last.squeezeOn).toBe(result.squeezeOn[59] === 1);
+      expect(last.squeezeBars).toBe(result.squeezeBars[59]);
+      assertClose(last.momentum, result.momentum[59]);
+    });
+  });
+
+  describe("Donchian Channels", () => {
+    const candles = Array.from({ length: 30 }, (_, i) => {
+      const close = 100 + Math.sin(i * 0.6) * 4;
+      return { time: i * 60_000, open: close, high: close + 1, low: close - 1, close, volume: 100 };
+    });
+
+    it("donchian() should track the window extremes and their age", () => {
+      const dc = donchian(candles, 5);
+      expect(Number.isNaN(dc.upper[3])).toBe(true);
+      for (let i = 4; i < 30; i++) {
+        const window = candles.slice(i - 4, i + 1);
+        assertClose(dc.upper[i], Math.max(...window.map((c) => c.high)));
+        assertClose(dc.lower[i], Math.min(...window.map((c) => c.low)));
+        assertClose(dc.middle[i], (dc.upper[i] + dc.lower[i]) / 2);
+        assertClose(candles[i - dc.barsSinceHigh[i]].high, dc.upper[i]);
+        assertClose(candles[i - dc.barsSinceLow[i]].low, dc.lower[i]);
+      }
+    });
+
+    it("donchian.stream() should match batch", () => {
+      const dc = donchian(candles, 5);
+      const stream = donchian.stream(5);
+      const last = candles.map((c) => stream.next(c.high, c.low)).pop()!;
+      assertClose(last.upper, dc.upper[29]);
+      assertClose(last.width, dc.width[29]);
+      expect(last.barsSinceLow).toBe(dc.barsSinceLow[29]);
+    });
+  });
+
+  describe("SuperTrend", () => {
+    // Steady rally, then a sharp sell-off
+    const candles = Array.from({ length: 60 }, (_, i) => {
+      const close = i < 35 ? 100 + i : 135 - (i - 34) * 3;
+      return { time: i * 60_000, open: close, high: close + 1, low: close - 1, close, volume: 100 };
+    });
+
+    it("supertrend() should trail below the rally and flip on the sell-off", () => {
+      const st = supertrend(candles, { atrPeriod: 10, multiplier: 3 });
+      expect(Number.isNaN(st.value[8])).toBe(true);
+      expect(st.direction[8]).toBe(0);
+
+      const flips = Array.from(st.flip.entries()).filter(([, f]) => f === 1).map(([i]) => i);
+      expect(flips.length).toBe(2);
+      expect(st.direction[flips[0]]).toBe(1);
+      expect(st.direction[flips[1]]).toBe(-1);
+      expect(flips[1]).toBeGreaterThan(34);
+      expect(st.value[34]).toBeLessThan(candles[34].low);
+      expect(st.value[59]).toBeGreaterThan(candles[59].high);
+    });
+
+    it("supertrend.stream() should match batch", () => {
+      const options = { atrPeriod: 7, multiplier: 2, source: "close", atr: "sma" } as const;
+      const st = supertrend(candles, options);
+      const stream = supertrend.stream(options);
+      const last = candles.map((c) => stream.next(c.high, c.low, c.close)).pop()!;
+      assertClose(last.value, st.value[59]);
+      expect(last.direction).toBe(st.direction[59]);
+      expect(last.flip).toBe(st.flip[59] === 1);
+    });
+  });
+
+  describe("Parabolic SAR", () => {
+    // Steady rally, then a sharp sell-off
+    const candles = Array.from({ length: 40 }, (_, i) => {
+      const close = i < 25 ? 100 + i : 124 - (i - 24) * 2;
+      return { time: i * 60_000, open: close, high: close + 1, low: close - 1, close, volume: 100 };
+    });
+
+    it("psar() should trail the rally and reverse on the sell-off", () => {
+      const result = psar(candles);
+      expect(Number.isNaN(result.sar[0])).toBe(true);
+      expect(result.direction[1]).toBe(1);
+      expect(result.sar[24]).toBeLessThan(candles[24].low);
+      assertClose(result.af[24], 0.2);
+
+      const reversal = result.direction.findIndex((d, i) => i > 24 && d === -1);
+      expect(reversal).toBeGreaterThan(24);
+      assertClose(result.sar[reversal], result.ep[reversal - 1]);
+      assertClose(result.af[reversal], 0.02);
+      expect(result.sar[39]).toBeGreaterThan(candles[39].high);
+    });
+
+    it("psar.stream() should match batch", () => {
+      const result = psar(candles, { maximum: 0.1 });
+      const stream = psar.stream({ maximum: 0.1 });
+      const last = candles.map((c) => stream.next(c.high, c.low)).pop()!;
+      assertClose(last.sar, result.sar[39]);
+      assertClose(last.ep, result.ep[39]);
+      expect(last.direction).toBe(result.direction[39]);
+    });
+  });
+
+  describe("Corporate actions", () => {
+    const candles = [
+      { time: 0, open: 200, high: 204, low: 198, close: 202, volume: 1000 },
+      { time: 1, open: 101, high: 103, low: 100, close: 102, volume: 2200 },
+      { time: 2, open: 102, high: 104, low: 101, close: 103, volume: 2100 },
+    ];
+
+    it("adjustCandles() should back-adjust splits", () => {
+      const adjusted = adjustCandles(candles, [{ type: "split", exDate: 1, ratio: 2 }]);
+      expect(adjusted[0].close).toBe(101);
+      expect(adjusted[0].volume).toBe(2000);
+      expect(adjusted[1]).toEqual(candles[1]);
+    });
+
+    it("adjustCandles() should apply dividends only in totalReturn mode", () => {
+      const actions = [{ type: "dividend" as const, exDate: 2, amount: 1.02 }];
+      expect(adjustCandles(candles, actions)[1].close).toBe(102);
+      assertClose(adjustCandles(candles, actions, { mode: "totalReturn" })[1].close, 100.98);
+      expect(() => adjustCandles(candles, [{ type: "split", exDate: 1, ratio: 0 }])).toThrow();
+    });
+  });
+
+  describe("Errors", () => {
+    function thrown(fn: () => unknown): unknown {
+      try {
+        fn();
+      } catch (err) {
+        return err;
+      }
+      throw new Error("expected an error");
+    }
+
+    it("should throw LengthMismatchError with the array names and lengths", () => {
+      const err = thrown(() => frvp([3, 4, 5], [1, 2], [2, 3, 4], [10, 10, 10])) as LengthMismatchError;
+      expect(err).toBeInstanceOf(LengthMismatchError);
+      expect(err).toBeInstanceOf(TaError);
+      expect(err).toBeInstanceOf(Error);
+      expect(err.code).toBe("LENGTH_MISMATCH");
+      expect(err.name).toBe("LengthMismatchError");
+      expect([err.array, err.length, err.expectedArray, err.expectedLength]).toEqual(["lows", 2, "highs", 3]);
+    });
+
+    it("should throw OutOfRangeError with the bounds", () => {
+      const candles = [{ open: 1, high: 2, low: 0.5, close: 1.5, volume: 10 }];
+      const err = thrown(() => frvp(candles, 10, 1.5)) as OutOfRangeError;
+      expect(err).toBeInstanceOf(OutOfRangeError);
+      expect(err.code).toBe("OUT_OF_RANGE");
+      expect([err.param, err.value, err.min, err.max]).toEqual(["value_area_percent", 1.5, 0, 1]);
+    });
+
+    it("should throw InvalidParameterError for a zero period", () => {
+      const err = thrown(() => sma(SAMPLE_PRICES, 0)) as InvalidParameterError;
+      expect(err).toBeInstanceOf(InvalidParameterError);
+      expect(err.code).toBe("INVALID_PARAMETER");
+    });
+  });
+
+  describe("Chaining (pipe)", () => {
+    it("rsi.stream().pipe(sma.stream()) should match SMA over RSI's valid values", () => {
+      const data = new Float64Array(SAMPLE_PRICES);
+      const rsiValues = rsi(data, 5);
+      const firstValid = rsiValues.findIndex((v) => !Number.isNaN(v));
+      const expected = sma(rsiValues.slice(firstValid), 3);
+
+      const chain = rsi.stream(5).pipe(sma.stream(3));
+      expect(chain).toBeInstanceOf(PipeStream);
+      const result = chain.init(data);
+
+      expect(result.length).toBe(data.length);
+      for (let i = 0; i < firstValid; i++) {
+        expect(Number.isNaN(result[i])).toBe(true);
+      }
+      for (let i = 0; i < expected.length; i++) {
+        if (Number.isNaN(expected[i])) {
+          expect(Number.isNaN(result[firstValid + i])).toBe(true);
+        } else {
+          assertClose(result[firstValid + i], expected[i], 1e-10);
+        }
+      }
+    });
+
+    it("should extend a chain and reject non-scalar streams", () => {
+      const chain = sma.stream(3).pipe(ema.stream(3)).pipe(rsi.stream(3));
+      expect(chain).toBeInstanceOf(PipeStream);
+      chain.init(new Float64Array(SAMPLE_PRICES));
+      expect(Number.isNaN(chain.next(50))).toBe(false);
+
+      expect(() => sma.stream(3).pipe(new MacdStream(12, 26, 9))).toThrow();
+    });
+  });
+
   describe("MACD", () => {
     // Use more data for MACD since it needs slow period (26) + signal period (9) = 35 values minimum
     const MACD_PRICES = [
@@ -392,6 +1098,27 @@ describe("ta-tools Integration Tests", () => {
       }
     });
 
+    it("should restore exactly from toBytes()/fromBytes()", () => {
+      const history = new Float64Array(MACD_PRICES.slice(0, 30));
+      const original = new MacdStream(12, 26, 9);
+      original.init(history);
+
+      const bytes = original.toBytes();
+      expect(bytes).toBeInstanceOf(Uint8Array);
+      const restored = MacdStream.fromBytes(bytes);
+
+      for (const price of MACD_PRICES.slice(30)) {
+        const a = original.next(price);
+        const b = restored.next(price);
+        expect(b?.macd).toBe(a?.macd);
+        expect(b?.signal).toBe(a?.signal);
+        expect(b?.histogram).toBe(a?.histogram);
+      }
+
+      // Snapshots are tagged with the stream type
+      expect(() => SmaStream.fromBytes(bytes)).toThrow();
+    });
+
     it("should throw on invalid parameters", () => {
       const data = new Float64Array(MACD_PRICES);
       expect(() => macd(data, 0, 26, 9)).toThrow();